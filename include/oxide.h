@@ -0,0 +1,52 @@
+/*
+ * The C API of the Oxide palettizer (liboxide.so / liboxide.dylib / oxide.dll, built with `cargo build --release --lib`).
+ *
+ * Images and palettes are packed RGB bytes: three bytes per pixel or color, rows from top to bottom.
+ * Images are mapped on the GPU when one is available and on the CPU otherwise, with the default settings.
+ */
+
+#ifndef OXIDE_H
+#define OXIDE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The image is mapped and written to the output. */
+#define OXIDE_OK 0
+/* A pointer that is needed is null. */
+#define OXIDE_NULL_POINTER 1
+/* The width or height is 0 or the image is too large to address. */
+#define OXIDE_INVALID_SIZE 2
+/* There is no color to map the image onto. */
+#define OXIDE_EMPTY_PALETTE 3
+/* Mapping the image failed unexpectedly. */
+#define OXIDE_INTERNAL_ERROR 4
+/* A palette is too large to address. */
+#define OXIDE_INVALID_PALETTE 5
+
+/*
+ * Maps every pixel onto the closest color of a palette and writes the result to output.
+ * pixels and output hold width * height * 3 bytes and may be the same buffer, palette holds palette_len * 3 bytes.
+ */
+int32_t oxide_process_rgb8(const uint8_t *pixels, uint32_t width, uint32_t height,
+                           const uint8_t *palette, size_t palette_len,
+                           uint8_t *output);
+
+/*
+ * Maps every pixel onto the closest color of two palettes, preferring the colors of the biased palette, and writes the result to output.
+ * pixels and output hold width * height * 3 bytes and may be the same buffer, each palette holds its length * 3 bytes and may be null when empty.
+ */
+int32_t oxide_process_rgb8_biased(const uint8_t *pixels, uint32_t width, uint32_t height,
+                                  const uint8_t *biased_palette, size_t biased_len,
+                                  const uint8_t *standard_palette, size_t standard_len,
+                                  uint8_t *output);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* OXIDE_H */
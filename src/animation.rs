@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, Result};
+use std::path::Path;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, DynamicImage, Frame, ImageBuffer, Rgb};
+use rayon::prelude::*;
+use crate::config;
+use crate::processor::color_space::{from_hsv, to_hsv};
+use crate::pipeline::FrameProgress;
+use crate::processor::compute::PaletteMapping;
+use crate::processor::quantize::{quantize_octree, Quantizers};
+
+/// Gets the max width and height of the frames of a morph animation.
+pub fn max_frame_size() -> u32 { 1920 }
+
+/// Gets how long each frame of a morph animation is shown in milliseconds.
+fn frame_delay_ms() -> u32 { 50 }
+
+/// Gets the speed of the gif color quantization, from 1 (best quality) to 30 (fastest).
+fn gif_encoding_speed() -> i32 { 10 }
+
+/// Gets the number of frames it takes the palette of a palette cycle to rotate through every hue.
+fn cycle_frame_count() -> usize { 36 }
+
+/// Gets the max number of colors a gif palette can hold.
+pub fn max_gif_palette_size() -> usize { 256 }
+
+
+
+/// Returns if a given path is a gif by its extension.
+pub fn is_gif(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("gif"))
+}
+
+/// Returns the frames of an animation cross-fading an image from one palette mapping to another.
+/// Both mappings are applied once and every frame blends the two results in the configured spectrum space.
+pub fn get_morph_frames(source_image: &DynamicImage, from_mapping: &PaletteMapping, to_mapping: &PaletteMapping, frame_count: usize) -> Vec<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+    let from_image = from_mapping.process(source_image.clone());
+    let to_image = to_mapping.process(source_image.clone());
+    let (width, height) = from_image.dimensions();
+    let spectrum_space = config::get().spectrum_space;
+
+    (0..frame_count).into_par_iter().map(|frame| {
+        let fraction = frame as f32 / frame_count.saturating_sub(1).max(1) as f32;
+        ImageBuffer::from_fn(width, height, |x, y| {
+            spectrum_space.interpolate(from_image.get_pixel(x, y), to_image.get_pixel(x, y), fraction)
+        })
+    }).collect()
+}
+
+/// Writes the frames of an animation to a looping gif, reduced to 256 colors by the configured quantizer.
+pub fn export_gif(path: &Path, frames: &[ImageBuffer<Rgb<u8>, Vec<u8>>]) -> Result<()> {
+    if config::get().quantizer == Quantizers::Octree { return export_octree_gif(path, frames); }
+
+    let mut encoder = GifEncoder::new_with_speed(BufWriter::new(File::create(path)?), gif_encoding_speed());
+    encoder.set_repeat(Repeat::Infinite).map_err(Error::other)?;
+    encoder.encode_frames(frames.iter().map(|frame| {
+        Frame::from_parts(DynamicImage::ImageRgb8(frame.clone()).to_rgba8(), 0, 0, Delay::from_numer_denom_ms(frame_delay_ms(), 1))
+    })).map_err(Error::other)
+}
+
+/// Writes the frames of an animation to a looping gif that shares a single palette built by an octree quantizer.
+fn export_octree_gif(path: &Path, frames: &[ImageBuffer<Rgb<u8>, Vec<u8>>]) -> Result<()> {
+    let Some(first_frame) = frames.first() else { return Err(Error::other("the animation has no frames")); };
+    let (Ok(width), Ok(height)) = (u16::try_from(first_frame.width()), u16::try_from(first_frame.height())) else {
+        return Err(Error::other("the image is too large for a gif"));
+    };
+
+    let (palette, indexed_frames) = quantize_octree(frames, max_gif_palette_size());
+    let palette: Vec<u8> = palette.iter().flat_map(|color| color.0).collect();
+    let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), width, height, &palette).map_err(Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(Error::other)?;
+    for indexed_pixels in indexed_frames {
+        let mut gif_frame = gif::Frame::from_indexed_pixels(width, height, indexed_pixels, None);
+        // gif delays are in hundredths of a second
+        gif_frame.delay = (frame_delay_ms() / 10) as u16;
+        encoder.write_frame(&gif_frame).map_err(Error::other)?;
+    }
+    Ok(())
+}
+
+/// Writes a looping gif of an image whose palette entries rotate through every hue (classic palette cycling).
+/// Every pixel keeps indexing the same palette entry, so the image must only use colors from the given palette of at most 256 colors.
+pub fn export_palette_cycle(path: &Path, image: &ImageBuffer<Rgb<u8>, Vec<u8>>, palette: &[Rgb<u8>]) -> Result<()> {
+    if palette.len() > max_gif_palette_size() {
+        return Err(Error::other(format!("a gif palette holds up to {} colors but the image uses {}", max_gif_palette_size(), palette.len())));
+    }
+    let (Ok(width), Ok(height)) = (u16::try_from(image.width()), u16::try_from(image.height())) else {
+        return Err(Error::other("the image is too large for a gif"));
+    };
+
+    // indexes every pixel into the palette once, only the palette changes between frames
+    let palette_indices: HashMap<Rgb<u8>, u8> = palette.iter().enumerate().map(|(index, color)| (*color, index as u8)).collect();
+    let indexed_pixels = image.pixels()
+        .map(|pixel| palette_indices.get(pixel).copied().ok_or_else(|| Error::other("the image uses colors outside of its palette")))
+        .collect::<Result<Vec<u8>>>()?;
+
+    let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), width, height, &[]).map_err(Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(Error::other)?;
+    for frame in 0..cycle_frame_count() {
+        let hue_shift = frame as f32 * 360.0 / cycle_frame_count() as f32;
+        let cycled_palette: Vec<u8> = palette.iter().flat_map(|color| {
+            let [hue, saturation, value] = to_hsv(color);
+            from_hsv([(hue + hue_shift) % 360.0, saturation, value]).0
+        }).collect();
+
+        let mut gif_frame = gif::Frame::from_palette_pixels(width, height, indexed_pixels.clone(), cycled_palette, None);
+        // gif delays are in hundredths of a second
+        gif_frame.delay = (frame_delay_ms() / 10) as u16;
+        encoder.write_frame(&gif_frame).map_err(Error::other)?;
+    }
+    Ok(())
+}
+
+/// Maps every frame of a gif onto the palettes while keeping its frame delays, disposal methods, offsets, transparency and loop count.
+/// Only the color tables are mapped, every entry on its own, so every pixel keeps pointing at the same entry and the frames are copied over otherwise untouched.
+/// Mappings that need whole images (pixelation, effects and plugins) can't be applied to color tables, so they are refused.
+/// The progress is reported after each frame.
+pub fn process_gif(source_path: &Path, output_path: &Path, palette_mapping: &PaletteMapping, mut on_progress: impl FnMut(&FrameProgress)) -> Result<()> {
+    if palette_mapping.needs_whole_images() { return Err(Error::other("gifs are mapped through their color tables, which can't be pixelated or run through effects and plugins")); }
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = options.read_info(BufReader::new(File::open(source_path)?)).map_err(Error::other)?;
+
+    let map_color_table = |color_table: &[u8]| -> Vec<u8> {
+        let Some(colors) = ImageBuffer::<Rgb<u8>, Vec<u8>>::from_raw((color_table.len() / 3) as u32, 1, color_table.to_vec()) else { return color_table.to_vec(); };
+        palette_mapping.map_colors(&colors).into_raw()
+    };
+
+    // the encoder is created after the first frame, which is when the loop count has been read
+    let mut encoder: Option<gif::Encoder<BufWriter<File>>> = None;
+    let mut progress = FrameProgress::new(None);
+    while let Some(frame) = decoder.read_next_frame().map_err(Error::other)? {
+        let mut frame = frame.clone();
+        progress.decoded_count += 1;
+        frame.palette = frame.palette.as_deref().map(map_color_table);
+        progress.palettized_count += 1;
+
+        if encoder.is_none() {
+            let global_color_table = decoder.global_palette().map(map_color_table).unwrap_or_default();
+            let mut new_encoder = gif::Encoder::new(BufWriter::new(File::create(output_path)?), decoder.width(), decoder.height(), &global_color_table).map_err(Error::other)?;
+            new_encoder.set_repeat(decoder.repeat()).map_err(Error::other)?;
+            encoder = Some(new_encoder);
+        }
+        if let Some(encoder) = &mut encoder { encoder.write_frame(&frame).map_err(Error::other)?; }
+        progress.encoded_count += 1;
+        on_progress(&progress);
+    }
+
+    if encoder.is_none() { return Err(Error::other("the gif has no frames")); }
+    // every frame has been read, so the job is done
+    progress.frame_count = Some(progress.encoded_count);
+    on_progress(&progress);
+    Ok(())
+}
@@ -1,30 +1,66 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use ratatui::crossterm::event;
-use ratatui::crossterm::event::Event;
-use ratatui::prelude::*;
-use crate::ui::{render_current_page, Instruction};
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent};
+use crate::ui::{render_current_page, render_loading, spectrum_slice_count, start_progress_screen, Instruction};
 use std::io::{Error, Result};
 use std::string::String;
 use std::time::{Duration, Instant};
-use image::{ImageBuffer, Rgb};
+use image::{DynamicImage, ImageBuffer, Rgb};
 use img_parts::ImageEXIF;
 use ratatui::backend::Backend;
 use ratatui::Terminal;
 use crate::processor::*;
+use crate::processor::palette::{get_color_blind_safe_colors, get_color_usage, get_dominant_colors, ColorUsage};
+use crate::processor::analysis::{get_difference_heatmap, ImageAnalysis, ImageDifference, ToneStatistics};
+use crate::processor::color_vision::ColorVisionModes;
+use crate::processor::compute::{adapter_label, Gpu, GpuInformation, PaletteMapping, ThresholdMask};
+use crate::processor::dither::{get_dither_comparison, get_dither_guide, get_dither_mode, get_dither_strength, get_ordered_mask, get_quality, DitherModes};
+use crate::processor::guide::{expand_home, ProcessingGuide, ProcessingStepTypes};
+use crate::processor::memory;
+use crate::processor::blend::get_blended_image;
+use crate::config;
+use crate::export::*;
+use crate::video;
+use crate::video::open_source_image;
+use crate::animation;
+use crate::sequence;
+use crate::live::LiveStream;
+use crate::pipeline::FrameProgress;
+use crate::progress::{report, start_progress_bar, ProgressEvents};
+use crate::cli::Arguments;
+use crate::last_run::LastRun;
+use crate::batch::{BatchEntry, BatchState, BatchStatuses};
+use crate::results::{ResultOutputs, ResultRecord};
+use crate::favorites;
+use crate::shuffle;
+use crate::locale::{tr, trf};
 use img_parts::jpeg::Jpeg;
 use img_parts::png::Png;
 use wgpu::Instance;
 use crate::processor::Processors::DeepestAfrica;
 
+/// Gets the max width and height of the preview kept of the new image.
+fn preview_size() -> u32 { 256 }
+
 /// The list of pages in the application.
 #[derive(Copy, Clone)]
 pub enum Pages {
     Launching,
     SelectingImageSource,
+    Analyzing,
+    ReportingDominantColors,
     SelectingProcessingType,
     Preprocessing,
+    PreprocessingDither,
     Finished,
+    InspectingSpectrum,
+    SelectingMorphTarget,
+    PreprocessingMorphTarget,
+    Live,
+    Settings,
+    BatchProcessing,
+    ChoosingDirectories,
 }
 
 
@@ -37,58 +73,169 @@ pub struct App {
     pub source_directory: PathBuf,
     /// The list of paths to images in the source directory.
     pub source_image_paths: Vec<PathBuf>,
+    /// The single image being edited instead of the images in the source directory.
+    single_image_path: Option<PathBuf>,
     /// The output directory for edited images.
     pub output_directory: PathBuf,
     /// The current image selection used during selection.
-    current_image_path_selection: usize,
+    pub current_image_path_selection: usize,
     /// The selected image path.
     selected_image_path: Option<PathBuf>,
     /// The current processor selection used during selection.
     pub current_processor_selection: usize,
     /// The selected processor.
     pub selected_processor: Option<Box<dyn EditProcessor>>,
+    /// The processor and inputs of the last run, which pre-select and pre-fill the processor.
+    last_run: Option<LastRun>,
+    /// The starred processors listed above the others, in the order they were starred.
+    pub favorite_processors: Vec<usize>,
+    /// The search filtering the processor list by name and description while searching.
+    pub processor_search: Option<String>,
+    /// The digits typed so far while entering a two-digit processor number.
+    pub processor_number_entry: Option<String>,
+    /// The source and output directories typed on the directories page.
+    pub directory_inputs: (String, String),
+    /// Whether typing on the directories page goes to the output directory instead of the source directory.
+    pub is_editing_output_directory: bool,
+    /// The latest page change or status message, shown first in plain output for screen readers.
+    pub announcement: Option<String>,
+    /// The page name and status message the announcement was last updated from.
+    announced_state: (String, Option<String>),
     /// The new image for editing.
     pub new_image: Option<ImageBuffer<Rgb<u8>, Vec<u8>>>,
     /// The time it took to process the image
     pub processing_time: Duration,
+    /// The path the new image was saved to.
+    pub output_path: Option<PathBuf>,
+    /// How much of the new image each palette color covers, from the most to the least used.
+    pub color_usage: Vec<ColorUsage>,
+    /// A downscaled copy of the new image shown on the finished page.
+    pub preview: Option<ImageBuffer<Rgb<u8>, Vec<u8>>>,
+    /// The color vision type the preview is simulated for.
+    pub color_vision_mode: ColorVisionModes,
+    /// The guide asking how the error of mapping single images onto the palettes is spread out and how strongly, once a run's processor is set up.
+    /// It starts with the configured mode and strength and keeps them for the next runs.
+    pub dither_guide: ProcessingGuide,
+    /// How perceptually different the new image is from the source image.
+    pub image_difference: Option<ImageDifference>,
+    /// The exposure and tonal statistics of the source image.
+    pub source_tones: Option<ToneStatistics>,
+    /// The exposure and tonal statistics of the new image.
+    pub new_tones: Option<ToneStatistics>,
+    /// The result of the last action taken on the finished page.
+    pub status_message: Option<String>,
+    /// The color statistics of the selected image shown on the analyzing page.
+    pub image_analysis: Option<ImageAnalysis>,
+    /// The dominant colors of the selected image shown on the dominant colors page, from the most to the least used.
+    pub dominant_colors: Vec<ColorUsage>,
+    /// The spectrum of the finished run shown on the spectrum inspection page.
+    pub spectrum: Vec<Rgb<u8>>,
+    /// The blue plane of the spectrum currently shown on the spectrum inspection page.
+    pub spectrum_slice: usize,
+    /// Whether every frame of the numbered image sequence the selected image belongs to is processed with one palette.
+    pub sequence_mode: bool,
+    /// What was adjusted during the finished run to stay within the memory budgets (e.g. tiling or capped palettes).
+    pub memory_adjustments: Vec<String>,
+    /// The final progress of the animation or video job of the finished run.
+    pub frame_progress: Option<FrameProgress>,
+    /// The webcam or video stream palettized on the live page.
+    live_stream: Option<LiveStream>,
+    /// The latest palettized frame of the live stream.
+    pub live_frame: Option<ImageBuffer<Rgb<u8>, Vec<u8>>>,
+    /// The current selection of the processor the finished image is morphed into.
+    pub morph_target_selection: usize,
+    /// The processor the finished image is morphed into.
+    pub morph_target: Option<Box<dyn EditProcessor>>,
+    /// The processing backend and adapters shown on the settings page.
+    pub gpu_information: Option<GpuInformation>,
+    /// Whether images are processed without the terminal interface, reporting progress as text instead.
+    is_headless: bool,
+    /// The source images of the batch run applying the selected processor to every image.
+    pub batch: Vec<BatchEntry>,
+    /// The batch run into the output directory that was interrupted before it finished, which can be resumed.
+    pub interrupted_batch: Option<BatchState>,
+    /// Where the result records of batch runs are written to, if anywhere.
+    result_output: Option<ResultOutputs>,
 }
 impl App {
-    /// Returns a new application state container.
-    pub fn new() -> App {
+    /// Returns a new application state container for the given command line arguments.
+    pub fn new(arguments: &Arguments) -> App {
         let working_directory = std::env::current_dir().expect("Could not get local working directory."); // the binary/run location
-        let source_directory = working_directory.join("source"); // where the source images are
-        let output_directory = working_directory.join("output"); // where the output images are
-        fs::create_dir_all(&source_directory).expect("Could not create source image directory.");
+        // a single image is read from and saved next to wherever it is
+        let single_image_directory = arguments.image_path.as_ref().map(|image_path| image_path.parent().unwrap_or(&working_directory).to_path_buf());
+        let source_directory = single_image_directory.clone().or(arguments.source_directory.clone()).unwrap_or(working_directory.join("source")); // where the source images are
+        let output_directory = arguments.output_directory.clone().or(single_image_directory).unwrap_or(working_directory.join("output")); // where the output images are
+        if arguments.image_path.is_none() { fs::create_dir_all(&source_directory).expect("Could not create source image directory."); }
         fs::create_dir_all(&output_directory).expect("Could not create output image directory.");
 
         let mut app = App {
             current_page: Pages::Launching,
             source_directory: source_directory,
             source_image_paths: Vec::new(),
+            single_image_path: arguments.image_path.clone(),
             output_directory: output_directory,
             current_image_path_selection: 0,
             selected_image_path: None,
             current_processor_selection: 0,
             selected_processor: None,
+            last_run: LastRun::load(),
+            favorite_processors: favorites::load(),
+            processor_search: None,
+            processor_number_entry: None,
+            directory_inputs: ("".to_string(), "".to_string()),
+            is_editing_output_directory: false,
+            announcement: None,
+            announced_state: ("".to_string(), None),
             new_image: None,
             processing_time: Duration::new(0, 0),
+            output_path: None,
+            color_usage: Vec::new(),
+            preview: None,
+            color_vision_mode: ColorVisionModes::Normal,
+            dither_guide: get_dither_guide(),
+            image_difference: None,
+            source_tones: None,
+            new_tones: None,
+            status_message: None,
+            image_analysis: None,
+            dominant_colors: Vec::new(),
+            spectrum: Vec::new(),
+            spectrum_slice: 0,
+            sequence_mode: false,
+            memory_adjustments: Vec::new(),
+            frame_progress: None,
+            live_stream: None,
+            live_frame: None,
+            morph_target_selection: 0,
+            morph_target: None,
+            gpu_information: None,
+            is_headless: false,
+            batch: Vec::new(),
+            interrupted_batch: None,
+            result_output: arguments.result_output.clone(),
         };
 
         app.source_image_paths = app.collect_source_image_paths();
+        app.interrupted_batch = BatchState::load(&app.output_directory);
         app.update_selected_image_path();
+        app.current_processor_selection = app.last_run.as_ref().map(|last_run| last_run.processor_selection).unwrap_or(0);
+        if app.single_image_path.is_some() { app.current_page = Pages::SelectingProcessingType; }
         app
     }
 
     /// Collects source image paths
     fn collect_source_image_paths(&mut self) -> Vec<PathBuf> {
+        if let Some(single_image_path) = &self.single_image_path { return vec![single_image_path.clone()]; }
+
         let mut source_image_paths: Vec<PathBuf> = fs::read_dir(&self.source_directory).expect("Could not read source image directory.")
             .filter_map(|e| e.ok())
             .map(|e| e.path())
             .filter(|p| {
                 p.extension()
                     .and_then(|s| s.to_str())
-                    .map(|s| matches!(s.to_lowercase().as_str(), "png" | "jpg" | "jpeg"))
+                    .map(|s| matches!(s.to_lowercase().as_str(), "png" | "jpg" | "jpeg" | "gif"))
                     .unwrap_or(false)
+                    || video::is_video(p)
             })
             .collect();
         source_image_paths.sort();
@@ -100,9 +247,19 @@ impl App {
         match self.current_page {
             Pages::Launching => "Launching".to_string(),
             Pages::SelectingImageSource => "Selecting Image Source".to_string(),
+            Pages::Analyzing => "Analyzing".to_string(),
+            Pages::ReportingDominantColors => "Dominant Colors".to_string(),
             Pages::SelectingProcessingType => "Selecting Processing Type".to_string(),
             Pages::Preprocessing => "Preprocessing".to_string(),
+            Pages::PreprocessingDither => "Preprocessing Dither".to_string(),
             Pages::Finished => "Finished".to_string(),
+            Pages::InspectingSpectrum => "Inspecting Spectrum".to_string(),
+            Pages::SelectingMorphTarget => "Selecting Morph Target".to_string(),
+            Pages::PreprocessingMorphTarget => "Preprocessing Morph Target".to_string(),
+            Pages::Live => "Live".to_string(),
+            Pages::Settings => "Settings".to_string(),
+            Pages::BatchProcessing => "Batch Processing".to_string(),
+            Pages::ChoosingDirectories => "Choosing Directories".to_string(),
         }
     }
 
@@ -116,6 +273,12 @@ impl App {
         }
     }
 
+    /// Returns the frames of the sequence the selected image belongs to if sequence mode is on.
+    pub fn selected_sequence_frames(&self) -> Vec<PathBuf> {
+        if !self.sequence_mode || self.source_image_paths.is_empty() { return Vec::new(); }
+        sequence::get_sequence_frames(&self.source_image_paths[self.current_image_path_selection])
+    }
+
     /// Selects the next image path in the source list.
     pub fn select_next_source_image_path(&mut self) {
         if self.current_image_path_selection >= self.source_image_paths.len() - 1 {
@@ -138,56 +301,915 @@ impl App {
 
     /// Returns the selected image filename.
     pub fn print_selected_image_filename(&self) -> String {
-        if self.source_image_paths.is_empty() { return "Error: No images to edit".to_string() }
+        if self.source_image_paths.is_empty() { return tr("Error: No images to edit") }
 
         self.source_image_paths[self.current_image_path_selection].clone().file_name().unwrap().to_string_lossy().to_string()
     }
 
+    /// Returns the processors in the order they are listed, with the favorites first and only the search matches while searching.
+    pub fn processor_order(&self) -> Vec<usize> {
+        let search = self.processor_search.clone().unwrap_or_default().to_lowercase();
+        let matches_search = |selection: &usize| {
+            let processor = Processors::get_processor(*selection);
+            processor.name().to_lowercase().contains(&search) || processor.description().to_lowercase().contains(&search)
+                || processor.category().is_some_and(|category| category.to_lowercase().contains(&search))
+        };
+        let others = (0..Processors::number_of_processors()).filter(|selection| !self.favorite_processors.contains(selection));
+        self.favorite_processors.iter().copied().chain(others).filter(matches_search).collect()
+    }
+
+    /// Updates the processor search with a key event and selects the first match.
+    fn update_processor_search(&mut self, key: KeyEvent) {
+        let Some(search) = &self.processor_search else { return; };
+        self.processor_search = Some(term_tools::keyboard(search, key, false));
+        if let Some(first_match) = self.processor_order().first() { self.current_processor_selection = *first_match; }
+    }
+
+    /// Starts editing the source and output directories, beginning with the current ones.
+    fn start_choosing_directories(&mut self) {
+        self.directory_inputs = (self.source_directory.to_string_lossy().to_string(), self.output_directory.to_string_lossy().to_string());
+        self.is_editing_output_directory = false;
+        self.status_message = None;
+        self.current_page = Pages::ChoosingDirectories;
+    }
+
+    /// Modifies the directory being edited on the directories page from a key event.
+    fn update_directory_input(&mut self, key: KeyEvent) {
+        let input = if self.is_editing_output_directory { &mut self.directory_inputs.1 } else { &mut self.directory_inputs.0 };
+        *input = term_tools::keyboard(input, key, false);
+    }
+
+    /// Switches to the typed source and output directories and rescans the source images, or sets a status message if they cannot be used.
+    fn apply_directory_inputs(&mut self) {
+        let source_directory = expand_home(&self.directory_inputs.0);
+        let output_directory = expand_home(&self.directory_inputs.1);
+        if !source_directory.is_dir() {
+            self.status_message = Some(trf("Not a directory: {}", &[source_directory.to_string_lossy().to_string()]));
+            return;
+        }
+        if let Err(e) = fs::create_dir_all(&output_directory) {
+            self.status_message = Some(trf("Could not create {}: {}", &[output_directory.to_string_lossy().to_string(), e.to_string()]));
+            return;
+        }
+
+        self.source_directory = source_directory;
+        self.output_directory = output_directory;
+        self.reset();
+    }
+
+    /// Selects the processor with a given number in the processor list, counting from 1.
+    fn select_processor_number(&mut self, number: usize) {
+        if (1..=Processors::number_of_processors()).contains(&number) { self.current_processor_selection = number - 1; }
+    }
+
     /// Selects the next processor in the list.
     pub fn select_next_processor(&mut self) {
-        if self.current_processor_selection >= Processors::number_of_processors() - 1 {
-            self.current_processor_selection = 0;
-        } else {
-            self.current_processor_selection += 1;
-        }
+        let order = self.processor_order();
+        if order.is_empty() { return; }
+        let position = order.iter().position(|selection| *selection == self.current_processor_selection).unwrap_or(0);
+        self.current_processor_selection = order[(position + 1) % order.len()];
 
         self.update_selected_image_path();
     }
 
     /// Selects the previous processor in the list.
     pub fn select_previous_processor(&mut self) {
-        if self.current_processor_selection == 0 {
-            self.current_processor_selection = Processors::number_of_processors() - 1;
-        } else {
-            self.current_processor_selection -= 1;
-        }
+        let order = self.processor_order();
+        if order.is_empty() { return; }
+        let position = order.iter().position(|selection| *selection == self.current_processor_selection).unwrap_or(0);
+        self.current_processor_selection = order[(position + order.len() - 1) % order.len()];
 
         self.update_selected_image_path();
     }
 
+    /// Stars the selected processor, or unstars it if it already is a favorite.
+    pub fn toggle_favorite_processor(&mut self) {
+        match self.favorite_processors.iter().position(|selection| *selection == self.current_processor_selection) {
+            Some(position) => { self.favorite_processors.remove(position); }
+            None => self.favorite_processors.push(self.current_processor_selection),
+        }
+        let _ = favorites::save(&self.favorite_processors);
+    }
+
     /// Resets the application to the launching page and resets the state.
     pub fn reset(&mut self) {
         self.source_image_paths = self.collect_source_image_paths();
         self.current_page = Pages::SelectingImageSource;
         self.current_image_path_selection = 0;
         self.selected_image_path = None;
-        self.current_processor_selection = 0;
+        // a single image skips straight back to choosing a processor
+        if self.single_image_path.is_some() {
+            self.update_selected_image_path();
+            self.current_page = Pages::SelectingProcessingType;
+        }
+        self.current_processor_selection = self.last_run.as_ref().map(|last_run| last_run.processor_selection).unwrap_or(0);
         self.selected_processor = None;
+        self.processor_search = None;
+        self.processor_number_entry = None;
+        self.batch = Vec::new();
+        self.interrupted_batch = BatchState::load(&self.output_directory);
+        self.output_path = None;
+        self.color_usage = Vec::new();
+        self.preview = None;
+        self.color_vision_mode = ColorVisionModes::Normal;
+        self.image_difference = None;
+        self.source_tones = None;
+        self.new_tones = None;
+        self.status_message = None;
+        self.image_analysis = None;
+        self.dominant_colors = Vec::new();
+        self.spectrum = Vec::new();
+        self.spectrum_slice = 0;
+        self.morph_target_selection = 0;
+        self.morph_target = None;
+        self.memory_adjustments = Vec::new();
+        self.frame_progress = None;
+        self.live_stream = None;
+        self.live_frame = None;
     }
 
+    /// Finishes the current step of the selected processor and, once it is ready, processes the selected image, video, gif or sequence with it.
+    fn process_selected_image(&mut self) -> Result<()> {
+        let Some(processor) = &mut self.selected_processor else { return Ok(()); };
+        let processing_timer = Instant::now();
+        // forgets adjustments made outside of runs (e.g. by exports)
+        memory::take_adjustments();
+
+
+        processor.try_finish_current_step();
+        processor.try_populate();
+
+        // remembers the processor and its inputs for the next run
+        if processor.is_ready() {
+            let last_run = LastRun { processor_selection: self.current_processor_selection, inputs: processor.get_step_inputs() };
+            let _ = last_run.save();
+            self.last_run = Some(last_run);
+        }
+
+
+        // draws the reported progress until processing is done, where batch runs keep showing their list instead
+        let _progress_consumer = match (self.is_headless, self.batch.is_empty()) {
+            (true, _) => Some(start_progress_bar()),
+            (false, true) => Some(start_progress_screen()?),
+            (false, false) => None,
+        };
+
+
+
+        // processes every frame of a video with one palette mapping and re-encodes it, where frames only take the ordered dithering modes
+        let source_path = self.selected_image_path.clone().unwrap();
+        let dithering = get_dither_mode(&self.dither_guide).map(|mode| (mode, get_dither_strength(&self.dither_guide)));
+        let frame_mask = dithering.map(|(mode, strength)| get_ordered_mask(mode, strength)).unwrap_or_else(ThresholdMask::none);
+        let mut frame_progress: Option<FrameProgress> = None;
+        if video::is_video(&source_path) {
+            // continues if the processor is not ready
+            if !processor.is_ready() { return Ok(()); }
+
+            let reference_frame = match open_source_image(&source_path) {
+                Ok(reference_frame) => reference_frame,
+                Err(e) => {
+                    self.status_message = Some(trf("Could not open source video: {}", &[e.to_string()]));
+                    return Ok(());
+                }
+            };
+            report(ProgressEvents::LoadingColors);
+            let palette_mapping = processor.get_palette_mapping(&reference_frame);
+
+            let output_path = get_output_path(&self.output_directory, &source_path, &processor.get_descriptor(Processors::get_processor(self.current_processor_selection).name()), self.sequence_mode);
+
+            let video_result = video::process_video(&source_path, &output_path, &palette_mapping, &frame_mask, |progress| {
+                report(ProgressEvents::FramesDone(progress.clone()));
+                frame_progress = Some(progress.clone());
+            });
+            match video_result {
+                // did process, the results describe the reference frame
+                Ok(_) => {
+                    let new_image = palette_mapping.process_dithered_on(&Gpu::new(), &reference_frame.to_rgb8(), &frame_mask);
+                    report(ProgressEvents::Saved(output_path.clone()));
+                    self.set_results(new_image, &reference_frame, output_path);
+                    self.frame_progress = frame_progress;
+                    self.processing_time = processing_timer.elapsed();
+                    self.current_page = Pages::Finished;
+                }
+
+                // did not process
+                Err(e) => {
+                    self.status_message = Some(trf("Could not process video: {}", &[e.to_string()]));
+                }
+            }
+            return Ok(());
+        }
+
+
+
+        // processes every frame of a gif while keeping its timing
+        if animation::is_gif(&source_path) {
+            // continues if the processor is not ready
+            if !processor.is_ready() { return Ok(()); }
+
+            let source_image = match open_source_image(&source_path) {
+                Ok(source_image) => source_image,
+                Err(e) => {
+                    self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+                    return Ok(());
+                }
+            };
+            report(ProgressEvents::LoadingColors);
+            let palette_mapping = processor.get_palette_mapping(&source_image);
+
+            let output_path = get_output_path(&self.output_directory, &source_path, &processor.get_descriptor(Processors::get_processor(self.current_processor_selection).name()), self.sequence_mode);
+
+            let gif_result = animation::process_gif(&source_path, &output_path, &palette_mapping, |progress| {
+                report(ProgressEvents::FramesDone(progress.clone()));
+                frame_progress = Some(progress.clone());
+            });
+            match gif_result {
+                // did process, the results describe the first frame, mapped color by color like the color tables
+                Ok(_) => {
+                    let new_image = palette_mapping.map_colors(&source_image.to_rgb8());
+                    report(ProgressEvents::Saved(output_path.clone()));
+                    self.set_results(new_image, &source_image, output_path);
+                    self.frame_progress = frame_progress;
+                    self.processing_time = processing_timer.elapsed();
+                    self.current_page = Pages::Finished;
+                }
+
+                // did not process
+                Err(e) => {
+                    self.status_message = Some(trf("Could not process gif: {}", &[e.to_string()]));
+                }
+            }
+            return Ok(());
+        }
 
 
+
+        // processes every frame of a numbered image sequence with one palette mapping
+        let sequence_frames = if self.sequence_mode { sequence::get_sequence_frames(&source_path) } else { Vec::new() };
+        if !sequence_frames.is_empty() {
+            // continues if the processor is not ready
+            if !processor.is_ready() { return Ok(()); }
+
+            let reference_image = match sequence::get_reference_image(&sequence_frames, config::get().sequence_samples) {
+                Ok(reference_image) => reference_image,
+                Err(e) => {
+                    self.status_message = Some(trf("Could not open image sequence: {}", &[e.to_string()]));
+                    return Ok(());
+                }
+            };
+            report(ProgressEvents::LoadingColors);
+            let palette_mapping = processor.get_palette_mapping(&reference_image);
+
+            let output_path = get_output_path(&self.output_directory, &source_path, &processor.get_descriptor(Processors::get_processor(self.current_processor_selection).name()), self.sequence_mode);
+
+            let sequence_result = sequence::process_sequence(&sequence_frames, &output_path, &palette_mapping, &frame_mask, |progress| {
+                report(ProgressEvents::FramesDone(progress.clone()));
+                frame_progress = Some(progress.clone());
+            });
+            match sequence_result.and_then(|_| open_source_image(&source_path).map_err(Error::other)) {
+                // did process, the results describe the selected frame
+                Ok(source_image) => {
+                    let new_image = palette_mapping.process_dithered_on(&Gpu::new(), &source_image.to_rgb8(), &frame_mask);
+                    report(ProgressEvents::Saved(output_path.clone()));
+                    self.set_results(new_image, &source_image, output_path);
+                    self.frame_progress = frame_progress;
+                    self.processing_time = processing_timer.elapsed();
+                    self.current_page = Pages::Finished;
+                }
+
+                // did not process
+                Err(e) => {
+                    self.status_message = Some(trf("Could not process image sequence: {}", &[e.to_string()]));
+                }
+            }
+            return Ok(());
+        }
+
+
+        // processes the image and renders the progress
+        self.new_image = process_source_image(processor.as_ref(), &source_path, dithering);
+
+
+
+        // saves the new image if it is created by try_process()
+        if let Some(new_image) = self.new_image.as_ref() {
+            let output_path = get_output_path(&self.output_directory, &source_path, &processor.get_descriptor(Processors::get_processor(self.current_processor_selection).name()), self.sequence_mode);
+
+
+
+            // saving the new image and working with potential errors
+            match video::save_image(new_image, &output_path) {
+                // did save
+                Ok(_) => {
+                    report(ProgressEvents::Saved(output_path.clone()));
+                    self.output_path = Some(output_path.clone());
+                    self.color_usage = get_color_usage(new_image);
+                    self.preview = Some(get_preview(new_image));
+                    self.new_tones = Some(ToneStatistics::new(new_image));
+                    if let Ok(source_image) = open_source_image(&source_path) {
+                        self.image_difference = ImageDifference::new(&source_image, new_image);
+                        self.source_tones = Some(ToneStatistics::new(&source_image.to_rgb8()));
+
+                        // saving the blended variants and the error heatmap
+                        let mut messages = Vec::new();
+                        match save_blended_variants(&source_image, new_image, &output_path) {
+                            Ok(0) => {}
+                            Ok(variant_count) => messages.push(trf("Saved {} blended variants", &[variant_count.to_string()])),
+                            Err(e) => messages.push(trf("Could not save blended variants: {}", &[e.to_string()])),
+                        }
+                        if config::get().save_heatmap {
+                            match save_difference_heatmap(&source_image, new_image, &output_path) {
+                                Ok(Some(heatmap_path)) => messages.push(trf("Saved difference heatmap to {}", &[heatmap_path.to_string_lossy().to_string()])),
+                                Ok(None) => messages.push(tr("Could not export heatmap: the source and new images differ in size")),
+                                Err(e) => messages.push(trf("Could not export heatmap: {}", &[e.to_string()])),
+                            }
+                        }
+                        self.status_message = (!messages.is_empty()).then(|| messages.join(" "));
+                    }
+
+                    // getting the image type
+                    let image_type = output_path.extension()
+                    .and_then(|s| s.to_str())
+                    .map(|s| s.to_lowercase())
+                    .unwrap_or_default();
+
+                    // injecting the metadata from the source image
+                    match image_type.as_str() {
+                        "jpg" | "jpeg" => {
+                            let source_image = Jpeg::from_bytes(fs::read(source_path.clone())?.into()).unwrap();
+                            let mut new_image = Jpeg::from_bytes(fs::read(output_path.clone())?.into()).unwrap();
+                            new_image.set_exif(source_image.exif().clone());
+                            fs::write(output_path, new_image.encoder().bytes())?;
+                        }
+
+                        "png" => {
+                            let source_image = Png::from_bytes(fs::read(source_path.clone())?.into()).unwrap();
+                            let mut new_image = Png::from_bytes(fs::read(output_path.clone())?.into()).unwrap();
+                            new_image.set_exif(source_image.exif().clone());
+                            fs::write(output_path, new_image.encoder().bytes())?;
+                        }
+
+                        _ => {}
+                    }
+
+
+
+                    // finished
+                    self.memory_adjustments = memory::take_adjustments();
+                    self.processing_time = processing_timer.elapsed();
+                    self.current_page = Pages::Finished;
+                }
+
+                // did not save
+                Err(e) => {
+                    self.status_message = Some(trf("Could not save {}: {}", &[output_path.to_string_lossy().to_string(), e.to_string()]));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Announces a page change or a new status message.
+    fn update_announcement(&mut self) {
+        let state = (self.current_page_name(), self.status_message.clone());
+        if state == self.announced_state { return; }
+
+        if state.1.is_some() && state.1 != self.announced_state.1 { self.announcement = state.1.clone(); }
+        else if state.0 != self.announced_state.0 { self.announcement = Some(trf("Now on page: {}", &[tr(&state.0)])); }
+        self.announced_state = state;
+    }
+
+    /// Processes the selected image with a random processor, filled with random colors if it asks for them.
+    fn shuffle(&mut self) -> Result<()> {
+        let Some(source_image_path) = self.selected_image_path.clone() else { return Ok(()); };
+        let (selection, processor) = shuffle::shuffle_processor(source_image_path, config::get().shuffle_colors);
+        self.current_processor_selection = selection;
+        self.selected_processor = Some(processor);
+        self.processor_search = None;
+        self.current_page = Pages::Preprocessing;
+        self.process_selected_image()
+    }
+
+    /// Builds the palettes of a processor whose steps are filled for the source image at a given index, without mapping or saving the image.
+    /// Returns where the result would be saved and the colors of the palettes, or what went wrong.
+    pub fn plan_headless(&self, image_selection: usize, processor_selection: usize, processor: Box<dyn EditProcessor>) -> std::result::Result<(PathBuf, Vec<Rgb<u8>>), String> {
+        let source_path = &self.source_image_paths[image_selection];
+        let source_image = open_source_image(source_path).map_err(|e| trf("Could not open source image: {}", &[e.to_string()]))?;
+        let palette_mapping = processor.get_palette_mapping(&source_image);
+        let output_path = get_output_path(&self.output_directory, source_path, &processor.get_descriptor(Processors::get_processor(processor_selection).name()), self.sequence_mode);
+        Ok((output_path, palette_mapping.colors()))
+    }
+
+    /// Processes the source image at a given index with a processor whose steps are filled, without the terminal interface.
+    /// Returns the record of where the result is saved or what went wrong.
+    pub fn process_headless(&mut self, image_selection: usize, processor_selection: usize, processor: Box<dyn EditProcessor>) -> ResultRecord {
+        self.is_headless = true;
+        self.current_processor_selection = processor_selection;
+        self.process_image_with(image_selection, processor)
+    }
+
+    /// Processes the source image at a given index with a given processor, returning the record of where the result is saved or what went wrong.
+    fn process_image_with(&mut self, image_selection: usize, processor: Box<dyn EditProcessor>) -> ResultRecord {
+        let processing_timer = Instant::now();
+        let inputs = processor.get_step_inputs();
+        self.current_image_path_selection = image_selection;
+        self.update_selected_image_path();
+        self.selected_processor = Some(processor);
+        self.new_image = None;
+        self.output_path = None;
+        self.color_usage = Vec::new();
+        self.status_message = None;
+        self.memory_adjustments = Vec::new();
+        self.frame_progress = None;
+
+        let result = match self.process_selected_image() {
+            Err(e) => Err(e.to_string()),
+            Ok(_) => match (&self.output_path, &self.status_message) {
+                (Some(output_path), _) => Ok(output_path.clone()),
+                (None, Some(status_message)) => Err(status_message.clone()),
+                (None, None) => Err(tr("Could not save the processed image")),
+            },
+        };
+        ResultRecord {
+            input_path: self.source_image_paths[image_selection].clone(),
+            result,
+            processor: Processors::get_processor(self.current_processor_selection).name(),
+            inputs,
+            color_count: self.color_usage.len(),
+            top_colors: self.color_usage.iter().map(|usage| (usage.color, usage.percent)).collect(),
+            duration: processing_timer.elapsed(),
+            adjustments: self.memory_adjustments.clone(),
+            frames: self.frame_progress.clone(),
+            dry_run_palette: None,
+        }
+    }
+
+    /// Finishes the current step of the selected processor and, once it is ready, processes every source image with its inputs one after another.
+    /// The batch page is redrawn before every image so it lists how far each image is.
+    fn process_batch<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> where Error: From<<B as Backend>::Error> {
+        let Some(processor) = &mut self.selected_processor else { return Ok(()); };
+        processor.try_finish_current_step();
+        processor.try_populate();
+        if !processor.is_ready() { return Ok(()); }
+        let inputs = processor.get_step_inputs();
+
+        // continuing an interrupted run with the same processor and inputs redoes the image it stopped at
+        let interrupted_batch = BatchState::load(&self.output_directory)
+            .filter(|state| state.is_same_run(self.current_processor_selection, &inputs));
+        let interrupted_image_path = interrupted_batch.as_ref().and_then(|state| state.current_image_path.clone());
+        let mut state = BatchState { processor_selection: self.current_processor_selection, inputs: inputs.clone(), current_image_path: None, current_output_path: None };
+
+        self.batch = self.source_image_paths.iter().map(|image_path| BatchEntry::new(image_path.clone())).collect();
+        for image_selection in 0..self.batch.len() {
+            self.batch[image_selection].status = BatchStatuses::Processing;
+            self.current_page = Pages::BatchProcessing;
+            terminal.draw(|frame| render_current_page(frame, self))?;
+
+            // every image gets its own processor filled with the same inputs
+            let processor = Processors::get_processor(self.current_processor_selection).create_edit_with_inputs(self.batch[image_selection].image_path.clone(), &inputs);
+            if let Some(output_path) = self.find_skipped_output(image_selection, processor.as_ref(), interrupted_image_path.as_ref()) {
+                self.batch[image_selection].status = BatchStatuses::Skipped(output_path);
+                continue;
+            }
+            if let Err(e) = self.remove_interrupted_output(image_selection, interrupted_batch.as_ref()) {
+                self.batch[image_selection].status = BatchStatuses::Failed(trf("Could not remove the partly written output: {}", &[e.to_string()]));
+                continue;
+            }
+            state.current_image_path = Some(self.batch[image_selection].image_path.clone());
+            state.current_output_path = Some(self.get_new_output_path(&self.batch[image_selection].image_path, processor.as_ref()));
+            let _ = state.save(&self.output_directory);
+            let record = self.process_image_with(image_selection, processor);
+            if let Some(result_output) = &self.result_output && let Err(e) = result_output.write(&record) {
+                self.status_message = Some(trf("Could not write the result record: {}", &[e.to_string()]));
+            }
+            self.batch[image_selection].status = match record.result {
+                Ok(output_path) => BatchStatuses::Saved(output_path),
+                Err(message) => BatchStatuses::Failed(message),
+            };
+        }
+        let _ = BatchState::clear(&self.output_directory);
+        self.interrupted_batch = None;
+        self.current_page = Pages::BatchProcessing;
+        Ok(())
+    }
+
+    /// Resumes the interrupted batch run with its processor and inputs, skipping the images it already saved.
+    fn resume_batch<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> where Error: From<<B as Backend>::Error> {
+        if self.source_image_paths.is_empty() { return Ok(()); }
+        let Some(state) = self.interrupted_batch.take() else { return Ok(()); };
+
+        self.current_processor_selection = state.processor_selection;
+        self.update_selected_image_path();
+        self.selected_processor = Some(Processors::get_processor(state.processor_selection).create_edit_with_inputs(self.selected_image_path.clone().unwrap(), &state.inputs));
+        self.process_batch(terminal)
+    }
+
+    /// Returns where a processor saves the source image at a given index.
+    fn get_output_path(&self, image_selection: usize, processor: &dyn EditProcessor) -> PathBuf {
+        let processor_name = Processors::get_processor(self.current_processor_selection).name();
+        get_descriptor_path(&self.output_directory, &self.source_image_paths[image_selection], &processor.get_descriptor(processor_name))
+    }
+
+    /// Returns where the source image at a given index is already saved by a processor if batch runs skip it.
+    /// The image an interrupted run stopped at is never skipped, since its output may only be partly written.
+    pub fn find_skipped_output(&self, image_selection: usize, processor: &dyn EditProcessor, interrupted_image_path: Option<&PathBuf>) -> Option<PathBuf> {
+        if interrupted_image_path == Some(&self.source_image_paths[image_selection]) { return None; }
+        if config::get().overwrite || !config::get().skip_existing { return None; }
+        let output_path = self.get_output_path(image_selection, processor);
+        output_path.exists().then_some(output_path)
+    }
+
+    /// Removes the partly written output of the source image at a given index if an interrupted batch run stopped at it, so it is saved under the same name again.
+    /// Only the file the run was writing is removed, so outputs saved before it stay, and image sequences (saved as directories) are left as they are.
+    pub fn remove_interrupted_output(&self, image_selection: usize, interrupted_batch: Option<&BatchState>) -> Result<()> {
+        let Some(interrupted_batch) = interrupted_batch else { return Ok(()); };
+        if interrupted_batch.current_image_path.as_ref() != Some(&self.source_image_paths[image_selection]) { return Ok(()); }
+        let Some(output_path) = &interrupted_batch.current_output_path else { return Ok(()); };
+        if output_path.is_file() { fs::remove_file(output_path)?; }
+        Ok(())
+    }
+
+    /// Returns where a processor saves a source image when it is processed now.
+    pub fn get_new_output_path(&self, source_path: &Path, processor: &dyn EditProcessor) -> PathBuf {
+        get_output_path(&self.output_directory, source_path, &processor.get_descriptor(Processors::get_processor(self.current_processor_selection).name()), self.sequence_mode)
+    }
+
+    /// Analyzes the selected source image for the analyzing page.
+    fn analyze_selected_image(&mut self) {
+        self.update_selected_image_path();
+        let Some(source_path) = &self.selected_image_path else { return; };
+
+        match open_source_image(source_path) {
+            Ok(source_image) => {
+                self.image_analysis = Some(ImageAnalysis::new(&source_image));
+                self.status_message = None;
+            }
+            Err(e) => {
+                self.image_analysis = None;
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+            }
+        }
+    }
+
+    /// Collects the dominant colors of the selected source image for the dominant colors page.
+    fn report_dominant_colors(&mut self) {
+        self.update_selected_image_path();
+        let Some(source_path) = &self.selected_image_path else { return; };
+
+        match open_source_image(source_path) {
+            Ok(source_image) => {
+                self.dominant_colors = get_dominant_colors(&source_image, config::get().dominant_colors);
+                self.status_message = None;
+            }
+            Err(e) => {
+                self.dominant_colors = Vec::new();
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+            }
+        }
+    }
+
+    /// Records the new image of a finished run and its statistics compared to the source image.
+    fn set_results(&mut self, new_image: ImageBuffer<Rgb<u8>, Vec<u8>>, source_image: &DynamicImage, output_path: PathBuf) {
+        self.output_path = Some(output_path);
+        self.color_usage = get_color_usage(&new_image);
+        self.preview = Some(get_preview(&new_image));
+        self.new_tones = Some(ToneStatistics::new(&new_image));
+        self.image_difference = ImageDifference::new(source_image, &new_image);
+        self.source_tones = Some(ToneStatistics::new(&source_image.to_rgb8()));
+        self.new_image = Some(new_image);
+        self.memory_adjustments = memory::take_adjustments();
+        self.status_message = None;
+    }
+
+    /// Collects the spectrum of the finished run for the spectrum inspection page.
+    fn inspect_spectrum(&mut self) {
+        let (Some(processor), Some(source_path)) = (&self.selected_processor, &self.selected_image_path) else { return; };
+
+        match open_source_image(source_path) {
+            Ok(source_image) => {
+                self.spectrum = processor.get_palette_mapping(&source_image).colors();
+                self.spectrum_slice = 0;
+                self.status_message = None;
+            }
+            Err(e) => {
+                self.spectrum = Vec::new();
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+            }
+        }
+    }
+
+    /// Exports the base colors of the finished run as style sheet variables next to the saved image.
+    fn export_palette_variables(&mut self, format: VariableFormats) {
+        let (Some(processor), Some(source_path), Some(output_path)) = (&self.selected_processor, &self.selected_image_path, &self.output_path) else { return; };
+
+        let source_image = match open_source_image(source_path) {
+            Ok(source_image) => source_image,
+            Err(e) => {
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+                return;
+            }
+        };
+
+        let colors = get_color_blind_safe_colors(&processor.get_base_colors(&source_image));
+        let export_path = output_path.with_extension(format.extension());
+        let source_name = source_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        self.status_message = Some(match export_variables(&export_path, &source_name, &colors, config::get().export_spectrum_stops, format) {
+            Ok(report) => trf("Exported palette to {} ({})", &[export_path.to_string_lossy().to_string(), report.summary()]),
+            Err(e) => trf("Could not export palette: {}", &[e.to_string()]),
+        });
+    }
+
+    /// Exports the base colors of the finished run as a GIMP palette next to the saved image.
+    fn export_gimp_palette(&mut self) {
+        let (Some(processor), Some(source_path), Some(output_path)) = (&self.selected_processor, &self.selected_image_path, &self.output_path) else { return; };
+
+        let source_image = match open_source_image(source_path) {
+            Ok(source_image) => source_image,
+            Err(e) => {
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+                return;
+            }
+        };
+
+        let colors = get_color_blind_safe_colors(&processor.get_base_colors(&source_image));
+        let export_path = output_path.with_extension("gpl");
+        let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        self.status_message = Some(match export_gimp_palette(&export_path, &name, &colors, config::get().export_spectrum_stops) {
+            Ok(report) => trf("Exported palette to {} ({})", &[export_path.to_string_lossy().to_string(), report.summary()]),
+            Err(e) => trf("Could not export palette: {}", &[e.to_string()]),
+        });
+    }
+
+    /// Exports the base colors of the finished run as a JSON palette file next to the saved image.
+    fn export_palette_file(&mut self) {
+        let (Some(processor), Some(source_path), Some(output_path)) = (&self.selected_processor, &self.selected_image_path, &self.output_path) else { return; };
+
+        let source_image = match open_source_image(source_path) {
+            Ok(source_image) => source_image,
+            Err(e) => {
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+                return;
+            }
+        };
+
+        let colors = get_color_blind_safe_colors(&processor.get_base_colors(&source_image));
+        let export_path = output_path.with_extension("json");
+        let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        self.status_message = Some(match export_palette_file(&export_path, &name, &colors) {
+            Ok(report) => trf("Exported palette to {} ({})", &[export_path.to_string_lossy().to_string(), report.summary()]),
+            Err(e) => trf("Could not export palette: {}", &[e.to_string()]),
+        });
+    }
+
+    /// Exports terminal color schemes built from the colors of the finished image next to the saved image.
+    fn export_terminal_schemes(&mut self) {
+        let (Some(new_image), Some(output_path)) = (&self.new_image, &self.output_path) else { return; };
+        let Some(scheme) = TerminalScheme::from_image(new_image) else { return; };
+
+        let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        for format in TerminalSchemeFormats::all() {
+            let export_path = output_path.with_extension(format.extension());
+            if let Err(e) = export_terminal_scheme(&export_path, &name, &scheme, format) {
+                self.status_message = Some(trf("Could not export terminal color schemes: {}", &[e.to_string()]));
+                return;
+            }
+        }
+        self.status_message = Some(trf("Exported terminal color schemes to {} ({})", &[output_path.with_extension("*").to_string_lossy().to_string(), scheme.get_contrast_report().summary()]));
+    }
+
+    /// Exports the finished image as ANSI art and plain ASCII art next to the saved image.
+    fn export_text_art(&mut self) {
+        let (Some(new_image), Some(output_path)) = (&self.new_image, &self.output_path) else { return; };
+
+        let (ansi_path, ascii_path) = (output_path.with_extension("ans"), output_path.with_extension("txt"));
+        self.status_message = Some(match export_ansi_art(&ansi_path, new_image).and_then(|_| export_ascii_art(&ascii_path, new_image)) {
+            Ok(_) => trf("Exported ANSI art to {} and ASCII art to {}", &[ansi_path.to_string_lossy().to_string(), ascii_path.to_string_lossy().to_string()]),
+            Err(e) => trf("Could not export text art: {}", &[e.to_string()]),
+        });
+    }
+
+    /// Exports the color mapping of the finished run as a 3D LUT next to the saved image.
+    fn export_lut(&mut self) {
+        let (Some(processor), Some(source_path), Some(output_path)) = (&self.selected_processor, &self.selected_image_path, &self.output_path) else { return; };
+
+        let source_image = match open_source_image(source_path) {
+            Ok(source_image) => source_image,
+            Err(e) => {
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+                return;
+            }
+        };
+
+        let mapped_lattice = get_mapped_lut_lattice(&processor.get_palette_mapping(&source_image));
+        let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let export_path = output_path.with_extension("cube");
+        self.status_message = Some(match export_cube_lut(&export_path, &name, &mapped_lattice) {
+            Ok(_) => trf("Exported LUT to {}", &[export_path.to_string_lossy().to_string()]),
+            Err(e) => trf("Could not export LUT: {}", &[e.to_string()]),
+        });
+    }
+
+    /// Starts palettizing the configured live source with the palettes of the finished run.
+    fn start_live_stream(&mut self) {
+        let (Some(processor), Some(source_path)) = (&self.selected_processor, &self.selected_image_path) else { return; };
+
+        let source_image = match open_source_image(source_path) {
+            Ok(source_image) => source_image,
+            Err(e) => {
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+                return;
+            }
+        };
+
+        let config = config::get();
+        match LiveStream::start(&config.live_source, config.live_format.as_deref(), processor.get_palette_mapping(&source_image)) {
+            Ok(live_stream) => {
+                self.live_stream = Some(live_stream);
+                self.live_frame = None;
+                self.current_page = Pages::Live;
+            }
+            Err(e) => self.status_message = Some(trf("Could not start live mode: {}", &[e.to_string()])),
+        }
+    }
+
+    /// Replaces the live frame with the next palettized frame of the live stream, returning to the finished page when the stream stops.
+    fn update_live_frame(&mut self) {
+        let Some(live_stream) = &mut self.live_stream else { return; };
+
+        match live_stream.next_frame() {
+            Ok(live_frame) => self.live_frame = Some(live_frame),
+            Err(e) => {
+                self.live_stream = None;
+                self.status_message = Some(trf("Live mode stopped: {}", &[e.to_string()]));
+                self.current_page = Pages::Finished;
+            }
+        }
+    }
+
+    /// Exports an animation cross-fading the finished image from its palettes into the palettes of the morph target next to the saved image.
+    fn export_morph(&mut self) {
+        let (Some(processor), Some(morph_target), Some(source_path), Some(output_path)) = (&self.selected_processor, &self.morph_target, &self.selected_image_path, &self.output_path) else { return; };
+
+        let source_image = match open_source_image(source_path) {
+            Ok(source_image) => source_image,
+            Err(e) => {
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+                return;
+            }
+        };
+
+        // the mappings are built from the full image while the frames are scaled down to keep the animation small
+        let from_mapping = processor.get_palette_mapping(&source_image);
+        let to_mapping = morph_target.get_palette_mapping(&source_image);
+        let frame_image = if source_image.width().max(source_image.height()) > animation::max_frame_size() {
+            source_image.thumbnail(animation::max_frame_size(), animation::max_frame_size())
+        } else { source_image };
+        let frames = animation::get_morph_frames(&frame_image, &from_mapping, &to_mapping, config::get().morph_frames);
+
+        let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let target_name = morph_target.get_descriptor(Processors::get_processor(self.morph_target_selection).name());
+        let gif_path = output_path.with_file_name(format!("{} to {}.gif", name, target_name));
+        if let Err(e) = animation::export_gif(&gif_path, &frames) {
+            self.status_message = Some(trf("Could not export morph animation: {}", &[e.to_string()]));
+            return;
+        }
+
+        // the webp copy needs ffmpeg and is skipped without it
+        let webp_path = gif_path.with_extension("webp");
+        self.status_message = Some(match video::convert(&gif_path, &webp_path) {
+            Ok(_) => trf("Exported morph animation to {} and {}", &[gif_path.to_string_lossy().to_string(), webp_path.to_string_lossy().to_string()]),
+            Err(e) => trf("Exported morph animation to {} (no WebP: {})", &[gif_path.to_string_lossy().to_string(), e.to_string()]),
+        });
+    }
+
+    /// Exports a palette cycling gif of the finished image next to the saved image.
+    fn export_palette_cycle(&mut self) {
+        let (Some(new_image), Some(output_path)) = (&self.new_image, &self.output_path) else { return; };
+        if self.color_usage.len() > animation::max_gif_palette_size() {
+            self.status_message = Some(trf("Palette cycling needs {} colors or fewer, the image uses {}", &[animation::max_gif_palette_size().to_string(), self.color_usage.len().to_string()]));
+            return;
+        }
+
+        // the colors of the palettized image are its gif palette
+        let palette: Vec<Rgb<u8>> = self.color_usage.iter().map(|usage| usage.color).collect();
+        let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let export_path = output_path.with_file_name(format!("{} cycle.gif", name));
+        self.status_message = Some(match animation::export_palette_cycle(&export_path, new_image, &palette) {
+            Ok(_) => trf("Exported palette cycle to {}", &[export_path.to_string_lossy().to_string()]),
+            Err(e) => trf("Could not export palette cycle: {}", &[e.to_string()]),
+        });
+    }
+
+    /// Exports a heatmap of where the finished image deviates from the source image next to the saved image.
+    fn export_difference_heatmap(&mut self) {
+        let (Some(new_image), Some(source_path), Some(output_path)) = (&self.new_image, &self.selected_image_path, &self.output_path) else { return; };
+
+        let source_image = match open_source_image(source_path) {
+            Ok(source_image) => source_image,
+            Err(e) => {
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+                return;
+            }
+        };
+        let Some(heatmap) = get_difference_heatmap(&source_image, new_image) else {
+            self.status_message = Some(tr("Could not export heatmap: the source and new images differ in size"));
+            return;
+        };
+
+        let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let export_path = output_path.with_file_name(format!("{} heatmap.png", name));
+        self.status_message = Some(match heatmap.save(&export_path) {
+            Ok(_) => trf("Exported difference heatmap to {}", &[export_path.to_string_lossy().to_string()]),
+            Err(e) => trf("Could not export heatmap: {}", &[e.to_string()]),
+        });
+    }
+
+
+
+    /// Exports a crop of the current image dithered with the palettes of the run under every dithering mode side by side, so the modes can be compared.
+    fn export_dither_comparison(&mut self) {
+        let (Some(processor), Some(source_path), Some(output_path)) = (&self.selected_processor, &self.selected_image_path, &self.output_path) else { return; };
+
+        let source_image = match open_source_image(source_path) {
+            Ok(source_image) => source_image,
+            Err(e) => {
+                self.status_message = Some(trf("Could not open source image: {}", &[e.to_string()]));
+                return;
+            }
+        };
+        let palette_mapping = processor.get_palette_mapping(&source_image);
+        if let PaletteMapping::Effect(_, _) | PaletteMapping::Plugin(_, _) = palette_mapping {
+            self.status_message = Some(tr("Could not export dither comparison: custom shaders and plugins have no palette to dither with"));
+            return;
+        }
+
+        let comparison = get_dither_comparison(&source_image, &palette_mapping, get_dither_strength(&self.dither_guide));
+        let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let export_path = output_path.with_file_name(format!("{} dithering.png", name));
+        let mode_names: Vec<String> = DitherModes::all().iter().map(|mode| tr(&mode.name())).collect();
+        self.status_message = Some(match comparison.save(&export_path) {
+            Ok(_) => trf("Exported dither comparison to {} (from left to right: {})", &[export_path.to_string_lossy().to_string(), mode_names.join(", ")]),
+            Err(e) => trf("Could not export dither comparison: {}", &[e.to_string()]),
+        });
+    }
+
+    /// Changes a setting from the settings page, remembering it in the configuration file and refreshing what the page shows.
+    fn save_setting(&mut self, key: &str, value: &str) {
+        self.status_message = Some(match config::save(key, value) {
+            Ok(()) => trf("Saved {} = {} to {}", &[key.to_string(), value.to_string(), config::CONFIG_FILENAME.to_string()]),
+            Err(e) => trf("Could not save {}: {}", &[key.to_string(), e.to_string()]),
+        });
+        self.gpu_information = Some(GpuInformation::collect());
+    }
+
+    /// Switches processing to the adapter listed after the one in use.
+    fn select_next_adapter(&mut self) {
+        let Some(gpu_information) = &self.gpu_information else { return; };
+        if gpu_information.adapter_labels.is_empty() {
+            self.status_message = Some(tr("No GPU adapters found"));
+            return;
+        }
+
+        let current_label = gpu_information.adapter.as_ref().map(adapter_label);
+        let next_selection = gpu_information.adapter_labels.iter()
+            .position(|label| Some(label) == current_label.as_ref())
+            .map(|selection| (selection + 1) % gpu_information.adapter_labels.len())
+            .unwrap_or(0);
+        let next_label = gpu_information.adapter_labels[next_selection].clone();
+        self.save_setting("gpu_adapter", &next_label);
+    }
+
+    /// Applies the quality preset filled into the dither guide, whose dithering mode the next steps start with, and keeps custom settings as they are.
+    fn apply_selected_quality(&mut self) {
+        let Some(Some(preset)) = get_quality(&self.dither_guide) else { return; };
+        self.save_setting("quality", &preset.name().to_lowercase());
+        // the preset picks the dithering of the run, which keeps its strength
+        let quality = self.dither_guide.get_current_input();
+        self.dither_guide.set_inputs(vec![quality, config::get().dither.setting_name()]);
+    }
+
     /// Runs the application.
     pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> where Error: From<<B as Backend>::Error> {
         // running
         loop {
             // rendering
+            self.update_announcement();
             terminal.draw(|frame| render_current_page(frame, self))?;
 
+            // streaming the next live frame instead of waiting for input
+            if let Pages::Live = self.current_page {
+                self.update_live_frame();
+                if !event::poll(Duration::ZERO)? { continue; }
+            }
 
 
-            // getting input
-            if let Event::Key(key) = event::read()? {
+            // getting input, where resizing clears the screen so the next frame is drawn for the new size without leftovers
+            let input = event::read()?;
+            if let Event::Resize(_, _) = input {
+                terminal.clear()?;
+                continue;
+            }
+            if let Event::Key(key) = input {
                 if key.kind == event::KeyEventKind::Release { continue; }
 
                 match self.current_page {
@@ -212,6 +1234,35 @@ impl App {
                             self.update_selected_image_path();
                             self.current_page = Pages::SelectingProcessingType;
                         }
+                        if key.code == Instruction::analyze_instruction().keybind {
+                            if self.source_image_paths.is_empty() { continue; }
+
+                            terminal.draw(|frame| render_loading(frame, "Analyzing...".to_string()))?;
+                            self.analyze_selected_image();
+                            self.current_page = Pages::Analyzing;
+                        }
+                        if key.code == Instruction::dominant_colors_instruction().keybind {
+                            if self.source_image_paths.is_empty() { continue; }
+
+                            terminal.draw(|frame| render_loading(frame, "Finding dominant colors...".to_string()))?;
+                            self.report_dominant_colors();
+                            self.current_page = Pages::ReportingDominantColors;
+                        }
+                        if key.code == Instruction::sequence_mode_instruction().keybind {
+                            self.sequence_mode = !self.sequence_mode;
+                        }
+                        if key.code == Instruction::resume_batch_instruction().keybind {
+                            self.resume_batch(terminal)?;
+                        }
+                        if key.code == Instruction::directories_instruction().keybind {
+                            self.start_choosing_directories();
+                        }
+                        if key.code == Instruction::settings_instruction().keybind {
+                            terminal.draw(|frame| render_loading(frame, "Looking for GPU adapters...".to_string()))?;
+                            self.status_message = None;
+                            self.gpu_information = Some(GpuInformation::collect());
+                            self.current_page = Pages::Settings;
+                        }
                         if key.code == Instruction::reset_instruction().keybind {
                             self.reset();
                         }
@@ -222,70 +1273,82 @@ impl App {
 
 
 
+                    Pages::Analyzing | Pages::ReportingDominantColors => {
+                        if key.code == Instruction::back_instruction().keybind {
+                            self.current_page = Pages::SelectingImageSource;
+                        }
+                        if key.code == Instruction::quit_instruction().keybind {
+                            break;
+                        }
+                    }
+
+
+
                     Pages::SelectingProcessingType => {
+                        // typing goes to the search while searching, where escape stops searching instead of resetting
+                        if self.processor_search.is_some() {
+                            if key.code == Instruction::back_instruction().keybind {
+                                self.processor_search = None;
+                                continue;
+                            }
+                            if matches!(key.code, KeyCode::Char(_) | KeyCode::Backspace) {
+                                self.update_processor_search(key);
+                                continue;
+                            }
+                        }
+                        else if key.code == Instruction::search_instruction().keybind {
+                            self.processor_search = Some(String::new());
+                            continue;
+                        }
+
+                        // digits jump to a processor by its number, where 0 is the tenth and # starts entering two digits
+                        if let KeyCode::Char(digit @ '0'..='9') = key.code {
+                            match &mut self.processor_number_entry {
+                                Some(entry) => {
+                                    entry.push(digit);
+                                    if entry.len() < 2 { continue; }
+                                    let number = entry.parse().unwrap_or(0);
+                                    self.processor_number_entry = None;
+                                    self.select_processor_number(number);
+                                }
+                                None => self.select_processor_number(if digit == '0' { 10 } else { digit as usize - '0' as usize }),
+                            }
+                            continue;
+                        }
+                        if key.code == Instruction::two_digit_number_instruction().keybind {
+                            self.processor_number_entry = Some(String::new());
+                            continue;
+                        }
+                        if self.processor_number_entry.is_some() && key.code == Instruction::back_instruction().keybind {
+                            self.processor_number_entry = None;
+                            continue;
+                        }
+
                         if key.code == Instruction::select_next().keybind {
                             self.select_next_processor();
                         }
                         if key.code == Instruction::select_previous().keybind {
                             self.select_previous_processor();
                         }
+                        if key.code == Instruction::favorite_instruction().keybind {
+                            self.toggle_favorite_processor();
+                        }
+                        if key.code == Instruction::shuffle_instruction().keybind {
+                            self.shuffle()?;
+                        }
                         if key.code == Instruction::confirm_instruction().keybind {
+                            // cannot continue if the search matches no processor
+                            if self.processor_order().is_empty() { continue; }
+                            self.processor_search = None;
+
                             // from here self.selected_processor is guaranteed to be set
-                            let selected_processor = Processors::get_processor(self.current_processor_selection);
-                            match selected_processor {
-                                Processors::Monochromatic => {
-                                    self.selected_processor = Some(Box::new(MonochromaticEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::MonochromaticWithAccent => {
-                                    self.selected_processor = Some(Box::new(MonochromaticWithAccentEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::AutomaticMonochromatic => {
-                                    self.selected_processor = Some(Box::new(AutomaticMonochromaticEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::AutomaticMonochromaticWithAccent => {
-                                    self.selected_processor = Some(Box::new(AutomaticMonochromaticWithAccentEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::Bichromatic => {
-                                    self.selected_processor = Some(Box::new(BichromaticEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::BichromaticWithAccent => {
-                                    self.selected_processor = Some(Box::new(BichromaticWithAccentEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::Trichromatic => {
-                                    self.selected_processor = Some(Box::new(TrichromaticEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::VolcanicCrater => {
-                                    self.selected_processor = Some(Box::new(VolcanicCraterEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::RedRocks => {
-                                    self.selected_processor = Some(Box::new(RedRocksEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::DeepestAfrica => {
-                                    self.selected_processor = Some(Box::new(DeepestAfricaEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::ArcticWilderness => {
-                                    self.selected_processor = Some(Box::new(ArcticWildernessEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::Iceland => {
-                                    self.selected_processor = Some(Box::new(IcelandEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::EnglishOaks => {
-                                    self.selected_processor = Some(Box::new(EnglishOaksEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::WheatField => {
-                                    self.selected_processor = Some(Box::new(WheatFieldEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::SouthAmericanJungle => {
-                                    self.selected_processor = Some(Box::new(SouthAmericanJungleEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::EuropeanIslands => {
-                                    self.selected_processor = Some(Box::new(EuropeanIslandsEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
-                                Processors::ColorfulIslands => {
-                                    self.selected_processor = Some(Box::new(ColorfulIslandsEdit::new(self.selected_image_path.clone().unwrap())));
-                                }
+                            let mut selected_processor = Processors::get_processor(self.current_processor_selection).create_edit(self.selected_image_path.clone().unwrap());
+                            // pre-fills the inputs of the last run with the same processor
+                            if let Some(last_run) = &self.last_run && last_run.processor_selection == self.current_processor_selection {
+                                selected_processor.set_step_inputs(last_run.inputs.clone());
                             }
-                            
+                            self.selected_processor = Some(selected_processor);
+
                             self.current_page = Pages::Preprocessing;
                         }
                         if key.code == Instruction::reset_instruction().keybind {
@@ -303,109 +1366,264 @@ impl App {
                         if let Some(processor) = &mut self.selected_processor {
                             // trying to finish the current step
                             if key.code == Instruction::confirm_instruction().keybind {
-                                let processing_timer = Instant::now();
-
-
+                                // runs ask for the quality and dithering once the processor is set up
                                 processor.try_finish_current_step();
                                 processor.try_populate();
+                                if processor.is_ready() {
+                                    self.dither_guide.current_step = 0;
+                                    self.current_page = Pages::PreprocessingDither;
+                                }
+                                continue;
+                            }
+                            if key.code == Instruction::batch_instruction().keybind {
+                                self.process_batch(terminal)?;
+                                continue;
+                            }
+
+                            // updating the current guide step input, where only colors are upper-cased so paths and names stay as typed
+                            let capitalize = processor.get_current_step_type() == ProcessingStepTypes::Color;
+                            let new_input = term_tools::keyboard(&processor.get_current_step_input(), key, capitalize);
+                            processor.update_current_step_input(new_input);
+
+                            // trying to reset
+                            if key.code == Instruction::reset_instruction().keybind {
+                                self.reset();
+                            }
+                        }
+
+                        // resets if the processor is None
+                        else { self.reset(); }
+                    }
 
 
 
-                                // Creates a temporary terminal with a concrete backend type
-                                let mut concrete_terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
-                                // processes the image and renders the progress
-                                self.new_image = processor.try_process(&mut concrete_terminal);
+                    Pages::PreprocessingDither => {
+                        // processing with the ready processor once the quality and mode are filled in, and the strength unless it doesn't dither
+                        if key.code == Instruction::confirm_instruction().keybind {
+                            match self.dither_guide.current_step {
+                                0 => if get_quality(&self.dither_guide).is_some() {
+                                    self.apply_selected_quality();
+                                    // except for gifs, where only the color tables are mapped, so there are no pixel positions to dither by
+                                    if self.selected_image_path.as_deref().is_some_and(animation::is_gif) { self.process_selected_image()?; }
+                                    else { self.dither_guide.try_finish_current_step(); }
+                                },
+                                1 => match get_dither_mode(&self.dither_guide) {
+                                    Some(DitherModes::None) => self.process_selected_image()?,
+                                    Some(_) => self.dither_guide.try_finish_current_step(),
+                                    None => {}
+                                },
+                                _ => if self.dither_guide.is_current_input_valid() { self.process_selected_image()?; },
+                            }
+                            continue;
+                        }
 
+                        // trying to go back to the previous step, or to the steps of the processor
+                        if key.code == Instruction::back_instruction().keybind {
+                            if self.dither_guide.current_step > 0 { self.dither_guide.current_step -= 1; }
+                            else { self.current_page = Pages::Preprocessing; }
+                            continue;
+                        }
+
+                        // updating the quality, mode or strength input
+                        let new_input = term_tools::keyboard(&self.dither_guide.get_current_input(), key, true);
+                        self.dither_guide.update_current_input(new_input);
+                    }
 
 
-                                // saves the new image if it is created by try_process()
-                                if let Some(new_image) = self.new_image.as_ref() {
-                                    let source_path = self.selected_image_path.clone().unwrap();
-                                    let output_directory = self.output_directory.clone();
 
-                                    let name = self.selected_image_path.clone().unwrap().file_stem().unwrap().to_string_lossy().to_string();
-                                    let extension = self.selected_image_path.clone().unwrap().extension().unwrap().to_string_lossy().to_string();
-                                    let filename = format!("{} {}.{}",
-                                                           name,
-                                                           processor.get_descriptor(Processors::get_processor(self.current_processor_selection).name()),
-                                                           extension
-                                    );
-                                    let output_path = output_directory.join(filename);
+                    Pages::Finished => {
+                        if key.code == Instruction::export_css_instruction().keybind {
+                            self.export_palette_variables(VariableFormats::Css);
+                        }
+                        if key.code == Instruction::export_scss_instruction().keybind {
+                            self.export_palette_variables(VariableFormats::Scss);
+                        }
+                        if key.code == Instruction::export_gimp_palette_instruction().keybind {
+                            self.export_gimp_palette();
+                        }
+                        if key.code == Instruction::export_palette_file_instruction().keybind {
+                            self.export_palette_file();
+                        }
+                        if key.code == Instruction::export_terminal_schemes_instruction().keybind {
+                            self.export_terminal_schemes();
+                        }
+                        if key.code == Instruction::export_lut_instruction().keybind {
+                            terminal.draw(|frame| render_loading(frame, "Exporting LUT...".to_string()))?;
+                            self.export_lut();
+                        }
+                        if key.code == Instruction::export_text_art_instruction().keybind {
+                            self.export_text_art();
+                        }
+                        if key.code == Instruction::export_heatmap_instruction().keybind {
+                            self.export_difference_heatmap();
+                        }
+                        if key.code == Instruction::dither_comparison_instruction().keybind {
+                            terminal.draw(|frame| render_loading(frame, "Dithering...".to_string()))?;
+                            self.export_dither_comparison();
+                        }
+                        if key.code == Instruction::color_vision_instruction().keybind {
+                            self.color_vision_mode = self.color_vision_mode.next();
+                        }
+                        if key.code == Instruction::palette_cycle_instruction().keybind {
+                            terminal.draw(|frame| render_loading(frame, "Exporting palette cycle...".to_string()))?;
+                            self.export_palette_cycle();
+                        }
+                        if key.code == Instruction::live_instruction().keybind {
+                            terminal.draw(|frame| render_loading(frame, "Starting live mode...".to_string()))?;
+                            self.start_live_stream();
+                        }
+                        if key.code == Instruction::morph_instruction().keybind {
+                            self.morph_target_selection = 0;
+                            self.current_page = Pages::SelectingMorphTarget;
+                        }
+                        if key.code == Instruction::inspect_spectrum_instruction().keybind {
+                            terminal.draw(|frame| render_loading(frame, "Building spectrum...".to_string()))?;
+                            self.inspect_spectrum();
+                            self.current_page = Pages::InspectingSpectrum;
+                        }
+                        if key.code == Instruction::run_again_instruction().keybind {
+                            self.reset();
+                            continue;
+                        }
+                        if key.code == Instruction::quit_instruction().keybind {
+                            break;
+                        }
+                    }
 
 
 
-                                    // saving the new image and working with potential errors
-                                    match new_image.save(&output_path) {
-                                        // did save
-                                        Ok(_) => {
-                                            // getting the image type
-                                            let image_type = output_path.extension()
-                                            .and_then(|s| s.to_str())
-                                            .map(|s| s.to_lowercase())
-                                            .unwrap_or_default();
+                    Pages::InspectingSpectrum => {
+                        if key.code == Instruction::next_slice_instruction().keybind {
+                            self.spectrum_slice = (self.spectrum_slice + 1) % spectrum_slice_count();
+                        }
+                        if key.code == Instruction::previous_slice_instruction().keybind {
+                            self.spectrum_slice = (self.spectrum_slice + spectrum_slice_count() - 1) % spectrum_slice_count();
+                        }
+                        if key.code == Instruction::back_instruction().keybind {
+                            self.current_page = Pages::Finished;
+                        }
+                        if key.code == Instruction::quit_instruction().keybind {
+                            break;
+                        }
+                    }
 
-                                            // injecting the metadata from the source image
-                                            match image_type.as_str() {
-                                                "jpg" | "jpeg" => {
-                                                    let source_image = Jpeg::from_bytes(fs::read(source_path.clone())?.into()).unwrap();
-                                                    let mut new_image = Jpeg::from_bytes(fs::read(output_path.clone())?.into()).unwrap();
-                                                    new_image.set_exif(source_image.exif().clone());
-                                                    fs::write(output_path, new_image.encoder().bytes())?;
-                                                }
 
-                                                "png" => {
-                                                    let source_image = Png::from_bytes(fs::read(source_path.clone())?.into()).unwrap();
-                                                    let mut new_image = Png::from_bytes(fs::read(output_path.clone())?.into()).unwrap();
-                                                    new_image.set_exif(source_image.exif().clone());
-                                                    fs::write(output_path, new_image.encoder().bytes())?;
-                                                }
 
-                                                _ => {}
-                                            }
+                    Pages::Live => {
+                        if key.code == Instruction::color_vision_instruction().keybind {
+                            self.color_vision_mode = self.color_vision_mode.next();
+                        }
+                        if key.code == Instruction::back_instruction().keybind {
+                            self.live_stream = None;
+                            self.current_page = Pages::Finished;
+                        }
+                        if key.code == Instruction::quit_instruction().keybind {
+                            break;
+                        }
+                    }
 
 
 
-                                            // finished
-                                            self.processing_time = processing_timer.elapsed();
-                                            self.current_page = Pages::Finished
-                                        }
+                    Pages::BatchProcessing => {
+                        if key.code == Instruction::reset_instruction().keybind {
+                            self.reset();
+                        }
+                        if key.code == Instruction::quit_instruction().keybind {
+                            break;
+                        }
+                    }
 
-                                        // did not save
-                                        Err(e) => {
-                                            eprintln!("Save error: {:?}", e);
-                                            eprintln!("Output path: {:?}", output_path);
-                                        }
-                                    }
-                                }
-                                // continues if no new image was created (not ready)
-                                else { continue; }
-                            }
 
-                            // updating the current guide step input
-                            let new_input = term_tools::keyboard(&processor.get_current_step_input(), key, true);
-                            processor.update_current_step_input(new_input);
 
-                            // trying to reset
-                            if key.code == Instruction::reset_instruction().keybind {
-                                self.reset();
-                            }
+                    Pages::ChoosingDirectories => {
+                        // letters are typed into the directory, so there is no quit key
+                        if key.code == Instruction::switch_directory_instruction().keybind {
+                            self.is_editing_output_directory = !self.is_editing_output_directory;
+                        }
+                        if matches!(key.code, KeyCode::Char(_) | KeyCode::Backspace) {
+                            self.update_directory_input(key);
+                        }
+                        if key.code == Instruction::confirm_instruction().keybind {
+                            self.apply_directory_inputs();
+                        }
+                        if key.code == Instruction::back_instruction().keybind {
+                            self.status_message = None;
+                            self.current_page = Pages::SelectingImageSource;
                         }
+                    }
 
-                        // resets if the processor is None
-                        else { self.reset(); }
+
+
+                    Pages::Settings => {
+                        if key.code == Instruction::force_cpu_instruction().keybind {
+                            self.save_setting("force_cpu", &(!config::get().force_cpu).to_string());
+                        }
+                        if key.code == Instruction::next_adapter_instruction().keybind {
+                            self.select_next_adapter();
+                        }
+                        if key.code == Instruction::back_instruction().keybind {
+                            self.status_message = None;
+                            self.current_page = Pages::SelectingImageSource;
+                        }
+                        if key.code == Instruction::quit_instruction().keybind {
+                            break;
+                        }
                     }
 
 
 
-                    Pages::Finished => {
-                        if key.code == Instruction::run_again_instruction().keybind {
-                            self.reset();
-                            continue;
+                    Pages::SelectingMorphTarget => {
+                        if key.code == Instruction::select_next().keybind {
+                            self.morph_target_selection = (self.morph_target_selection + 1) % Processors::number_of_processors();
+                        }
+                        if key.code == Instruction::select_previous().keybind {
+                            self.morph_target_selection = (self.morph_target_selection + Processors::number_of_processors() - 1) % Processors::number_of_processors();
+                        }
+                        if key.code == Instruction::confirm_instruction().keybind {
+                            // from here self.morph_target is guaranteed to be set
+                            self.morph_target = Some(Processors::get_processor(self.morph_target_selection).create_edit(self.selected_image_path.clone().unwrap()));
+                            self.current_page = Pages::PreprocessingMorphTarget;
+                        }
+                        if key.code == Instruction::back_instruction().keybind {
+                            self.current_page = Pages::Finished;
                         }
                         if key.code == Instruction::quit_instruction().keybind {
                             break;
                         }
                     }
+
+
+
+                    Pages::PreprocessingMorphTarget => {
+                        // returns to the finished page if the morph target is None
+                        let Some(morph_target) = &mut self.morph_target else {
+                            self.current_page = Pages::Finished;
+                            continue;
+                        };
+
+                        // morphing once the morph target is set up
+                        if key.code == Instruction::confirm_instruction().keybind {
+                            morph_target.try_finish_current_step();
+                            morph_target.try_populate();
+                            if morph_target.is_ready() {
+                                terminal.draw(|frame| render_loading(frame, "Morphing palettes...".to_string()))?;
+                                self.export_morph();
+                                self.current_page = Pages::Finished;
+                                continue;
+                            }
+                        }
+
+                        // trying to go back
+                        if key.code == Instruction::back_instruction().keybind {
+                            self.current_page = Pages::Finished;
+                            continue;
+                        }
+
+                        // updating the current guide step input
+                        let capitalize = morph_target.get_current_step_type() == ProcessingStepTypes::Color;
+                        let new_input = term_tools::keyboard(&morph_target.get_current_step_input(), key, capitalize);
+                        morph_target.update_current_step_input(new_input);
+                    }
                 }
             }
         }
@@ -415,6 +1633,75 @@ impl App {
 
 
 
+/// Returns the path a processed image, gif, video or image sequence (in sequence mode) is saved to, its name followed by the descriptor of the processor.
+/// Image sequences are saved as directories. Existing files are kept unless overwriting is configured.
+fn get_output_path(output_directory: &Path, source_path: &Path, descriptor: &str, sequence_mode: bool) -> PathBuf {
+    let is_sequence = sequence_mode && !video::is_video(source_path) && !animation::is_gif(source_path) && !sequence::get_sequence_frames(source_path).is_empty();
+    let output_path = match is_sequence {
+        true => output_directory.join(format!("{} {}", sequence::get_sequence_name(source_path).unwrap_or_default(), descriptor)),
+        false => get_descriptor_path(output_directory, source_path, descriptor),
+    };
+    if config::get().overwrite { output_path } else { get_available_path(output_path) }
+}
+
+/// Returns the path a processed image, gif or video is saved to when the path is free, its name followed by the descriptor of the processor.
+fn get_descriptor_path(output_directory: &Path, source_path: &Path, descriptor: &str) -> PathBuf {
+    let name = source_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let extension = match (video::is_video(source_path), animation::is_gif(source_path)) {
+        (true, _) => video::output_extension().to_string(),
+        (false, true) => "gif".to_string(),
+        (false, false) => source_path.extension().unwrap_or_default().to_string_lossy().to_string(),
+    };
+    output_directory.join(format!("{} {}.{}", name, descriptor, extension))
+}
+
+/// Returns the given path if it is free, otherwise the first free path with an incrementing suffix (e.g. "name (2).png").
+fn get_available_path(path: PathBuf) -> PathBuf {
+    if !path.exists() { return path; }
+
+    let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let extension = path.extension().map(|extension| format!(".{}", extension.to_string_lossy())).unwrap_or_default();
+    let mut suffix: usize = 2;
+    loop {
+        let candidate = path.with_file_name(format!("{} ({}){}", name, suffix, extension));
+        if !candidate.exists() { return candidate; }
+        suffix += 1;
+    }
+}
+
+/// Saves the configured blends of a processed image over its original next to the saved image (e.g. "name 50%.png") and returns how many were saved.
+fn save_blended_variants(source_image: &DynamicImage, new_image: &ImageBuffer<Rgb<u8>, Vec<u8>>, output_path: &Path) -> image::ImageResult<usize> {
+    let config = config::get();
+    let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let extension = output_path.extension().unwrap_or_default().to_string_lossy().to_string();
+    for opacity in &config.blend_opacities {
+        let variant_path = output_path.with_file_name(format!("{} {}%.{}", name, opacity, extension));
+        let variant_path = if config.overwrite { variant_path } else { get_available_path(variant_path) };
+        video::save_image(&get_blended_image(source_image, new_image, *opacity, config.blend_mode), &variant_path)?;
+    }
+    Ok(config.blend_opacities.len())
+}
+
+/// Saves a heatmap of the per-pixel error between a processed image and its original next to the saved image (e.g. "name heatmap.png") and returns where it was saved,
+/// or None if the images differ in size.
+fn save_difference_heatmap(source_image: &DynamicImage, new_image: &ImageBuffer<Rgb<u8>, Vec<u8>>, output_path: &Path) -> image::ImageResult<Option<PathBuf>> {
+    let Some(heatmap) = get_difference_heatmap(source_image, new_image) else { return Ok(None); };
+    let name = output_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let heatmap_path = output_path.with_file_name(format!("{} heatmap.png", name));
+    let heatmap_path = if config::get().overwrite { heatmap_path } else { get_available_path(heatmap_path) };
+    heatmap.save(&heatmap_path)?;
+    Ok(Some(heatmap_path))
+}
+
+/// Returns a copy of an image scaled down to fit within the preview size.
+fn get_preview(image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let (width, height) = image.dimensions();
+    let scale = (preview_size() as f32 / width.max(height).max(1) as f32).min(1.0);
+    image::imageops::thumbnail(image, ((width as f32 * scale) as u32).max(1), ((height as f32 * scale) as u32).max(1))
+}
+
+
+
 /// Module containing terminal-related tools and utilities.
 pub mod term_tools {
     use ratatui::crossterm::event;
@@ -426,10 +1713,7 @@ pub mod term_tools {
 
         let mut field = field.to_string();
         match input.code {
-            KeyCode::Backspace => {
-                if field.is_empty() { return field; }
-                field.remove(field.len() - 1);
-            }
+            KeyCode::Backspace => { field.pop(); }
             KeyCode::Char(char) => {
                 match char {
                     '0'..='9' => field.push(char),
@@ -449,10 +1733,7 @@ pub mod term_tools {
 
         let mut field = field.to_string();
         match input.code {
-            KeyCode::Backspace => {
-                if field.is_empty() { return field; }
-                field.remove(field.len() - 1);
-            }
+            KeyCode::Backspace => { field.pop(); }
             KeyCode::Char(char) => { field.push(if capitalize { char.to_uppercase().next().unwrap() } else { char } ); }
             _ => {}
         }
@@ -0,0 +1,102 @@
+use std::fs;
+use std::io::Result;
+use std::path::{Path, PathBuf};
+use crate::last_run::LastRun;
+use crate::locale::{tr, trf};
+
+/// The name of the file an unfinished batch run is remembered in, in its output directory.
+pub const BATCH_STATE_FILENAME: &str = "oxide.batch";
+
+
+
+/// The states an image of a batch run goes through.
+pub enum BatchStatuses {
+    /// The image waits for the images before it.
+    Waiting,
+    /// The image is being processed.
+    Processing,
+    /// The image is processed and saved to the given path.
+    Saved(PathBuf),
+    /// The image is skipped because it is already saved to the given path by an earlier run.
+    Skipped(PathBuf),
+    /// The image could not be processed, for the given reason.
+    Failed(String),
+}
+
+
+
+/// An image of a batch run, which processes every source image with the same processor and inputs.
+pub struct BatchEntry {
+    /// The path of the source image.
+    pub image_path: PathBuf,
+    /// How far the image is.
+    pub status: BatchStatuses,
+}
+impl BatchEntry {
+    /// Returns a new entry waiting to be processed.
+    pub fn new(image_path: PathBuf) -> BatchEntry {
+        BatchEntry { image_path, status: BatchStatuses::Waiting }
+    }
+
+    /// Returns if the image is done, whether it is saved, skipped or failed.
+    pub fn is_done(&self) -> bool {
+        matches!(self.status, BatchStatuses::Saved(_) | BatchStatuses::Skipped(_) | BatchStatuses::Failed(_))
+    }
+
+    /// Returns the file name of the image followed by its state.
+    pub fn describe(&self) -> String {
+        let file_name = |path: &PathBuf| path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let status = match &self.status {
+            BatchStatuses::Waiting => tr("waiting"),
+            BatchStatuses::Processing => tr("processing..."),
+            BatchStatuses::Saved(output_path) => trf("saved as {}", &[file_name(output_path)]),
+            BatchStatuses::Skipped(output_path) => trf("skipped, already saved as {}", &[file_name(output_path)]),
+            BatchStatuses::Failed(message) => trf("failed: {}", std::slice::from_ref(message)),
+        };
+        format!("{}: {}", file_name(&self.image_path), status)
+    }
+}
+
+
+
+/// A batch run that has not finished yet, remembered in its output directory so it can be resumed after being interrupted.
+pub struct BatchState {
+    /// The index of the processor in the processor list.
+    pub processor_selection: usize,
+    /// The inputs of every guide step.
+    pub inputs: Vec<String>,
+    /// The source image being processed when the state was last saved, whose output may only be partly written.
+    pub current_image_path: Option<PathBuf>,
+    /// The path the output of the current image is being saved to.
+    pub current_output_path: Option<PathBuf>,
+}
+impl BatchState {
+    /// Loads the unfinished batch run of an output directory, if there is one.
+    /// The processor and inputs are written like the last run, followed by the current image and the path its output is saved to.
+    pub fn load(output_directory: &Path) -> Option<BatchState> {
+        let contents = fs::read_to_string(output_directory.join(BATCH_STATE_FILENAME)).ok()?;
+        let run = LastRun::parse(&contents)?;
+        let get_path = |prefix: &str| contents.lines().find_map(|line| line.strip_prefix(prefix)).map(PathBuf::from);
+        Some(BatchState { processor_selection: run.processor_selection, inputs: run.inputs, current_image_path: get_path("current = "), current_output_path: get_path("output = ") })
+    }
+
+    /// Remembers the batch run in an output directory until it finishes.
+    pub fn save(&self, output_directory: &Path) -> Result<()> {
+        let run = LastRun { processor_selection: self.processor_selection, inputs: self.inputs.clone() };
+        let current = self.current_image_path.as_ref().map(|path| format!("current = {}\n", path.to_string_lossy())).unwrap_or_default();
+        let output = self.current_output_path.as_ref().map(|path| format!("output = {}\n", path.to_string_lossy())).unwrap_or_default();
+        fs::write(output_directory.join(BATCH_STATE_FILENAME), run.format() + current.as_str() + output.as_str())
+    }
+
+    /// Forgets the batch run of an output directory once it is finished.
+    pub fn clear(output_directory: &Path) -> Result<()> {
+        let path = output_directory.join(BATCH_STATE_FILENAME);
+        if path.exists() { fs::remove_file(path)?; }
+        Ok(())
+    }
+
+    /// Returns if the batch run uses a given processor with given inputs, so the run is a continuation of it.
+    pub fn is_same_run(&self, processor_selection: usize, inputs: &[String]) -> bool {
+        self.processor_selection == processor_selection && self.inputs == inputs
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+use std::io::{Error, Result};
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use image::{DynamicImage, Rgb, RgbImage};
+use crate::processor::Processors;
+use crate::processor::guide::ProcessingStepTypes;
+use crate::processor::compute::GpuInformation;
+use crate::video::open_source_image;
+use crate::headless::{exit_with_usage, report_error, ExitCodes};
+use crate::locale::{tr, trf};
+
+/// Gets the width of the generated image benchmarked when no image is given.
+fn generated_image_width() -> u32 { 1920 }
+
+/// Gets the height of the generated image benchmarked when no image is given.
+fn generated_image_height() -> u32 { 1080 }
+
+/// Gets how many times every processor maps the image when no number of runs is given.
+fn default_run_count() -> usize { 3 }
+
+/// Gets the colors the color steps of benchmarked processors are filled with, in order.
+fn bench_colors() -> Vec<String> { vec!["#D94F30".to_string(), "#3A7BD5".to_string(), "#F2C14E".to_string()] }
+
+/// Gets the number the number steps of benchmarked processors are filled with, e.g. the number of colors of Polychromatic.
+fn bench_number() -> String { "3".to_string() }
+
+/// Gets the name the name steps of benchmarked processors are filled with, which is the processor whose palettes Invert and the like map onto.
+fn bench_name() -> String { "Automatic Bichromatic".to_string() }
+
+/// Gets the path of the palette file the file steps of benchmarked processors are filled with, which holds the bench colors.
+fn bench_palette_path() -> PathBuf { std::env::temp_dir().join("oxide bench palette.txt") }
+
+
+
+/// Returns a generated image with smooth gradients covering every hue, for benchmarking without a source image.
+fn generate_image() -> DynamicImage {
+    let (width, height) = (generated_image_width(), generated_image_height());
+    DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+        Rgb([(x * 255 / width) as u8, (y * 255 / height) as u8, ((x + y) * 255 / (width + height)) as u8])
+    }))
+}
+
+/// Returns the colors, numbers, files and names that fill every step of a processor.
+fn bench_inputs(selection: usize) -> (Vec<String>, Vec<String>, Vec<String>, Vec<String>) {
+    let mut processor = Processors::get_processor(selection).create_edit(PathBuf::new());
+    let (mut colors, mut numbers, mut files, mut names) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    let _ = processor.fill_steps(&mut |processor| {
+        let input = match processor.get_current_step_type() {
+            ProcessingStepTypes::Color => {
+                colors.push(bench_colors()[colors.len() % bench_colors().len()].clone());
+                colors[colors.len() - 1].clone()
+            }
+            ProcessingStepTypes::Number => {
+                numbers.push(bench_number());
+                bench_number()
+            }
+            ProcessingStepTypes::FilePath => {
+                // the palette file is written when it is first needed
+                let _ = fs::write(bench_palette_path(), bench_colors().join("\n"));
+                files.push(bench_palette_path().to_string_lossy().to_string());
+                files[files.len() - 1].clone()
+            }
+            ProcessingStepTypes::Name => {
+                names.push(bench_name());
+                bench_name()
+            }
+            ProcessingStepTypes::NoInput => String::new(),
+        };
+        processor.update_current_step_input(input);
+        Ok::<(), Infallible>(())
+    });
+    (colors, numbers, files, names)
+}
+
+/// Returns how long building the palettes of a processor for an image and mapping the image onto them takes on average over a given number of runs, or what went wrong.
+fn measure(selection: usize, image: &DynamicImage, run_count: usize) -> std::result::Result<(Duration, Duration), String> {
+    let (colors, numbers, files, names) = bench_inputs(selection);
+    let processor = Processors::get_processor(selection).create_filled_edit(PathBuf::new(), &colors, &numbers, &files, &names).map_err(|e| e.localized())?;
+
+    let palette_timer = Instant::now();
+    let palette_mapping = processor.get_palette_mapping(image);
+    let palette_duration = palette_timer.elapsed();
+
+    let mapping_timer = Instant::now();
+    for _ in 0..run_count { palette_mapping.process(image.clone()); }
+    Ok((palette_duration, mapping_timer.elapsed() / run_count as u32))
+}
+
+
+
+/// Runs the benchmark from the command line, timing every processor (or a given one) on a given or generated image.
+pub fn run_command(arguments: &[String]) -> Result<()> {
+    let mut image_path = None;
+    let mut processor_name = None;
+    let mut run_count = default_run_count();
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        match (argument.as_str(), arguments.next()) {
+            ("--image", Some(file)) => image_path = Some(PathBuf::from(file)),
+            ("--processor", Some(name)) => processor_name = Some(name.clone()),
+            ("--runs", Some(runs)) if runs.parse::<usize>().is_ok_and(|runs| runs > 0) => run_count = runs.parse().unwrap(),
+            _ => {
+                exit_with_usage(&tr("Usage: oxide bench [--image <image>] [--processor <name|number>] [--runs <n>]"));
+            }
+        }
+    }
+
+    let image = match &image_path {
+        Some(image_path) => open_source_image(image_path).map_err(Error::other)?,
+        None => generate_image(),
+    };
+    let selections: Vec<usize> = match &processor_name {
+        Some(name) => match Processors::find(name) {
+            Some(selection) => vec![selection],
+            None => {
+                report_error(ExitCodes::Usage, None, &trf("Unknown processor: {}", std::slice::from_ref(name)));
+                ExitCodes::Usage.exit();
+            }
+        },
+        None => (0..Processors::number_of_processors()).collect(),
+    };
+
+    println!("{}", trf("Backend: {}", &[GpuInformation::collect().backend.name()]));
+    println!("{}", trf("Image: {}x{}, {} runs per processor", &[image.width().to_string(), image.height().to_string(), run_count.to_string()]));
+    println!();
+    println!("{:<36} {:>12} {:>12} {:>12}", tr("Processor"), tr("Palette ms"), tr("Mapping ms"), tr("MP/s"));
+    let megapixels = image.width() as f64 * image.height() as f64 / 1_000_000.0;
+    for selection in selections {
+        let name = Processors::get_processor(selection).name();
+        match measure(selection, &image, run_count) {
+            Ok((palette_duration, mapping_duration)) => println!(
+                "{:<36} {:>12.1} {:>12.1} {:>12.1}",
+                name,
+                palette_duration.as_secs_f64() * 1000.0,
+                mapping_duration.as_secs_f64() * 1000.0,
+                megapixels / mapping_duration.as_secs_f64().max(f64::EPSILON),
+            ),
+            Err(message) => println!("{:<36} {}", name, trf("failed: {}", &[message])),
+        }
+    }
+    Ok(())
+}
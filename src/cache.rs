@@ -0,0 +1,210 @@
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use image::Rgb;
+use crate::config;
+use crate::locale::{tr, trf};
+
+/// The name of the cache directory created in the working directory.
+pub const CACHE_DIRECTORY_NAME: &str = "cache";
+
+/// The version of the cached files, hashed into every key so changing how anything is cached invalidates the earlier files.
+const CACHE_FORMAT_VERSION: u64 = 1;
+
+
+
+/// Builds cache keys with 64-bit FNV-1a, which gives the same keys on every platform and with every Rust release
+/// (unlike DefaultHasher, whose output may change with the toolchain and silently invalidate the cache).
+pub struct CacheKeyHasher(u64);
+impl CacheKeyHasher {
+    /// Creates a hasher for a new key of the current cache format.
+    pub fn new() -> CacheKeyHasher {
+        let mut hasher = CacheKeyHasher(0xcbf29ce484222325);
+        CACHE_FORMAT_VERSION.hash(&mut hasher);
+        hasher
+    }
+}
+impl Default for CacheKeyHasher {
+    fn default() -> Self { CacheKeyHasher::new() }
+}
+impl Hasher for CacheKeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    // numbers are written in a fixed byte order and sizes as 64 bits, so every platform gets the same keys
+    fn write_u32(&mut self, i: u32) { self.write(&i.to_le_bytes()); }
+    fn write_u64(&mut self, i: u64) { self.write(&i.to_le_bytes()); }
+    fn write_usize(&mut self, i: usize) { self.write(&(i as u64).to_le_bytes()); }
+    fn write_isize(&mut self, i: isize) { self.write(&(i as i64).to_le_bytes()); }
+}
+
+
+
+/// The list of things kept in the cache, each in its own subdirectory.
+#[derive(Copy, Clone)]
+pub enum CacheKinds {
+    Spectrums,
+}
+impl CacheKinds {
+    /// Returns the name of the subdirectory of a given kind.
+    pub fn name(&self) -> String {
+        match self {
+            CacheKinds::Spectrums => "spectrums".to_string(),
+        }
+    }
+
+    /// Returns every kind.
+    pub fn all() -> Vec<CacheKinds> {
+        vec![CacheKinds::Spectrums]
+    }
+
+    /// Returns the directory the files of a given kind are kept in.
+    fn directory(&self) -> PathBuf {
+        cache_directory().join(self.name())
+    }
+}
+
+
+
+/// The disk usage of one kind of cached files.
+pub struct CacheStats {
+    /// The kind of cached files.
+    pub kind: CacheKinds,
+    /// The number of cached files.
+    pub file_count: usize,
+    /// The combined size of the cached files in bytes.
+    pub total_bytes: u64,
+}
+
+
+
+/// Returns the cache directory.
+fn cache_directory() -> PathBuf {
+    std::env::current_dir().expect("Could not get local working directory.").join(CACHE_DIRECTORY_NAME)
+}
+
+/// Returns every cached file with its size and the last time it was used.
+fn get_cached_files(directory: &Path) -> Vec<(PathBuf, u64, SystemTime)> {
+    let Ok(entries) = fs::read_dir(directory) else { return Vec::new(); };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() { return None; }
+            Some((entry.path(), metadata.len(), metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH)))
+        })
+        .collect()
+}
+
+/// Returns a cached list of colors, or builds and caches it if it is not cached yet.
+/// The key has to change whenever anything that affects the colors changes.
+pub fn get_or_build_colors(kind: CacheKinds, key: u64, build: impl FnOnce() -> Vec<Rgb<u8>>) -> Vec<Rgb<u8>> {
+    if !config::get().cache { return build(); }
+
+    let path = kind.directory().join(format!("{:016x}.rgb", key));
+    if let Ok(bytes) = fs::read(&path) && bytes.len() % 3 == 0 {
+        // marks the file as recently used so it is evicted last
+        if let Ok(file) = fs::File::options().append(true).open(&path) { let _ = file.set_modified(SystemTime::now()); }
+        return bytes.chunks_exact(3).map(|channels| Rgb([channels[0], channels[1], channels[2]])).collect();
+    }
+
+    let colors = build();
+    if fs::create_dir_all(kind.directory()).is_ok() {
+        let bytes: Vec<u8> = colors.iter().flat_map(|color| color.0).collect();
+        if fs::write(&path, bytes).is_ok() { enforce_size_limit(); }
+    }
+    colors
+}
+
+/// Removes the least recently used files until the cache fits within the configured size limit.
+fn enforce_size_limit() {
+    let limit = config::get().cache_size_limit_mb.saturating_mul(1024 * 1024);
+    let mut files: Vec<(PathBuf, u64, SystemTime)> = CacheKinds::all().iter().flat_map(|kind| get_cached_files(&kind.directory())).collect();
+    let mut total_bytes: u64 = files.iter().map(|file| file.1).sum();
+    if total_bytes <= limit { return; }
+
+    files.sort_by_key(|file| file.2);
+    for (path, size, _) in files {
+        if total_bytes <= limit { break; }
+        if fs::remove_file(&path).is_ok() { total_bytes -= size; }
+    }
+}
+
+/// Returns the disk usage of every kind of cached files.
+pub fn get_stats() -> Vec<CacheStats> {
+    CacheKinds::all().into_iter().map(|kind| {
+        let files = get_cached_files(&kind.directory());
+        CacheStats { kind, file_count: files.len(), total_bytes: files.iter().map(|file| file.1).sum() }
+    }).collect()
+}
+
+/// Removes every cached file and returns how many were removed.
+pub fn clear() -> Result<usize> {
+    let mut removed_count = 0;
+    for kind in CacheKinds::all() {
+        for (path, _, _) in get_cached_files(&kind.directory()) {
+            fs::remove_file(path)?;
+            removed_count += 1;
+        }
+    }
+    Ok(removed_count)
+}
+
+/// Runs a cache command (`stats` or `clear`) from the command line, returning an InvalidInput error with the usage for any other command.
+pub fn run_command(arguments: &[String]) -> Result<()> {
+    let megabytes = |bytes: u64| bytes as f64 / 1024.0 / 1024.0;
+    match arguments.first().map(|argument| argument.as_str()) {
+        Some("stats") => {
+            let stats = get_stats();
+            println!("{}", trf("Cache: {}", &[cache_directory().to_string_lossy().to_string()]));
+            for kind_stats in &stats {
+                println!("{}", trf("  {}: {} files, {} MB", &[kind_stats.kind.name(), kind_stats.file_count.to_string(), format!("{:.1}", megabytes(kind_stats.total_bytes))]));
+            }
+            println!("{}", trf("Total: {} MB of {} MB", &[format!("{:.1}", megabytes(stats.iter().map(|kind_stats| kind_stats.total_bytes).sum())), config::get().cache_size_limit_mb.to_string()]));
+        }
+        Some("clear") => {
+            println!("{}", trf("Removed {} cached files", &[clear()?.to_string()]));
+        }
+        _ => {
+            // the command line turns this into the usage exit code
+            return Err(Error::new(ErrorKind::InvalidInput, tr("Usage: oxide cache <stats|clear>")));
+        }
+    }
+    Ok(())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_match_the_fnv_reference() {
+        // the published 64-bit FNV-1a hashes of "" and "a"
+        let empty = CacheKeyHasher(0xcbf29ce484222325);
+        assert_eq!(empty.finish(), 0xcbf29ce484222325);
+        let mut hasher = CacheKeyHasher(0xcbf29ce484222325);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn sizes_are_hashed_as_64_bits() {
+        let mut size_hasher = CacheKeyHasher::new();
+        size_hasher.write_usize(442);
+        let mut number_hasher = CacheKeyHasher::new();
+        number_hasher.write_u64(442);
+        assert_eq!(size_hasher.finish(), number_hasher.finish());
+    }
+}
@@ -0,0 +1,281 @@
+use std::path::PathBuf;
+use crate::locale::{tr, trf};
+use crate::results::ResultOutputs;
+
+/// The command line arguments the app can be launched with.
+pub struct Arguments {
+    /// The directory to read source images from instead of the source folder in the working directory.
+    pub source_directory: Option<PathBuf>,
+    /// The directory to save edited images to instead of the output folder in the working directory.
+    pub output_directory: Option<PathBuf>,
+    /// The single image to edit without scanning a source directory.
+    pub image_path: Option<PathBuf>,
+    /// Whether pages are rendered as plain text for screen readers, regardless of the configuration.
+    pub plain_output: bool,
+    /// The processor (by name or number) images are processed with without the terminal interface.
+    pub processor: Option<String>,
+    /// The colors (as HEX) the color steps of the headless processor are filled with, in order.
+    pub colors: Vec<String>,
+    /// The numbers the number steps of the headless processor are filled with, in order.
+    pub numbers: Vec<String>,
+    /// The files the file steps of the headless processor are filled with, in order (e.g. a palette file).
+    pub files: Vec<String>,
+    /// The names the name steps of the headless processor are filled with, in order (e.g. the processor Invert maps onto).
+    pub names: Vec<String>,
+    /// Where a JSON record of every processed image is written to, for headless and batch runs.
+    pub result_output: Option<ResultOutputs>,
+    /// Whether headless runs fail instead of falling back to the CPU when no GPU adapter can be set up.
+    pub require_gpu: bool,
+    /// Whether headless runs only build the palettes and report the output paths, without mapping or saving any image.
+    pub dry_run: bool,
+    /// Whether the headless run resumes the interrupted batch run of the output directory with its processor and inputs.
+    pub resume: bool,
+}
+impl Arguments {
+    /// Parses the given command line arguments (without the binary name), returning what is wrong with them if they are invalid.
+    pub fn parse(arguments: &[String]) -> Result<Arguments, String> {
+        let mut parsed = Arguments { source_directory: None, output_directory: None, image_path: None, plain_output: false, processor: None, colors: Vec::new(), numbers: Vec::new(), files: Vec::new(), names: Vec::new(), result_output: None, require_gpu: false, dry_run: false, resume: false };
+
+        let mut arguments = arguments.iter();
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "--source" => {
+                    let directory = arguments.next().ok_or_else(|| tr("--source needs a directory"))?;
+                    parsed.source_directory = Some(PathBuf::from(directory));
+                }
+                "--output" => {
+                    let directory = arguments.next().ok_or_else(|| tr("--output needs a directory"))?;
+                    parsed.output_directory = Some(PathBuf::from(directory));
+                }
+                "--image" => {
+                    let file = arguments.next().ok_or_else(|| tr("--image needs a file"))?;
+                    if parsed.image_path.is_some() { return Err(trf("Only one image can be edited at a time: {}", &[file.to_string()])); }
+                    parsed.image_path = Some(PathBuf::from(file));
+                }
+                "--processor" => {
+                    let processor = arguments.next().ok_or_else(|| tr("--processor needs a processor name or number"))?;
+                    parsed.processor = Some(processor.to_string());
+                }
+                "--colors" => {
+                    let colors = arguments.next().ok_or_else(|| tr("--colors needs a comma separated list of HEX colors"))?;
+                    parsed.colors.extend(split_list(colors));
+                }
+                "--numbers" => {
+                    let numbers = arguments.next().ok_or_else(|| tr("--numbers needs a comma separated list of numbers"))?;
+                    parsed.numbers.extend(split_list(numbers));
+                }
+                "--files" => {
+                    let files = arguments.next().ok_or_else(|| tr("--files needs a comma separated list of files"))?;
+                    parsed.files.extend(split_list(files));
+                }
+                "--names" => {
+                    let names = arguments.next().ok_or_else(|| tr("--names needs a comma separated list of names"))?;
+                    parsed.names.extend(split_list(names));
+                }
+                "--json" => parsed.result_output = Some(ResultOutputs::Stdout),
+                "--json-file" => {
+                    let file = arguments.next().ok_or_else(|| tr("--json-file needs a file"))?;
+                    parsed.result_output = Some(ResultOutputs::File(PathBuf::from(file)));
+                }
+                "--require-gpu" => parsed.require_gpu = true,
+                "--dry-run" => parsed.dry_run = true,
+                "--resume" => parsed.resume = true,
+                "--plain" => parsed.plain_output = true,
+                option if option.starts_with("--") => {
+                    return Err(trf("Unknown option: {}", &[option.to_string()]));
+                }
+                // a positional file is the single image to edit
+                file if PathBuf::from(file).is_file() => {
+                    if parsed.image_path.is_some() { return Err(trf("Only one image can be edited at a time: {}", &[file.to_string()])); }
+                    parsed.image_path = Some(PathBuf::from(file));
+                }
+                // a positional directory is the source directory
+                directory => {
+                    if parsed.source_directory.is_some() { return Err(trf("The source directory is given more than once: {}", &[directory.to_string()])); }
+                    parsed.source_directory = Some(PathBuf::from(directory));
+                }
+            }
+        }
+
+        if parsed.image_path.is_some() && parsed.source_directory.is_some() {
+            return Err(tr("An image and a source directory cannot be edited at the same time"));
+        }
+        if parsed.processor.is_none() && (!parsed.colors.is_empty() || !parsed.numbers.is_empty() || !parsed.files.is_empty() || !parsed.names.is_empty()) {
+            return Err(tr("--colors, --numbers, --files and --names need a --processor"));
+        }
+        if parsed.processor.is_some() && parsed.resume {
+            return Err(tr("--resume continues with the processor of the interrupted batch and cannot take a --processor"));
+        }
+        // the interface owns stdout, so its records can only go to a file
+        if !parsed.is_headless() && matches!(parsed.result_output, Some(ResultOutputs::Stdout)) {
+            return Err(tr("--json needs a --processor, use --json-file for batch runs in the interface"));
+        }
+        if !parsed.is_headless() && parsed.dry_run {
+            return Err(tr("--dry-run needs a --processor"));
+        }
+        Ok(parsed)
+    }
+
+    /// Returns if the arguments process images without the terminal interface.
+    pub fn is_headless(&self) -> bool {
+        self.processor.is_some() || self.resume
+    }
+}
+
+
+
+/// The commands the app can be launched with, where the first argument names the command.
+pub enum Commands {
+    /// Runs the terminal interface (`oxide tui`, or no command).
+    Tui(Arguments),
+    /// Processes images with a processor without the terminal interface (`oxide process`, or no command with a --processor).
+    Process(Arguments),
+    /// Manages synced palette collections, with the arguments after the command.
+    Palettes(Vec<String>),
+    /// Manages the cache, with the arguments after the command.
+    Cache(Vec<String>),
+    /// Times every processor, with the arguments after the command.
+    Bench(Vec<String>),
+    /// Prints how to use the app.
+    Help,
+}
+impl Commands {
+    /// Parses the given command line arguments (without the binary name) into a command, returning what is wrong with them if they are invalid.
+    pub fn parse(arguments: &[String]) -> Result<Commands, String> {
+        let rest = || arguments[1..].to_vec();
+        match arguments.first().map(|argument| argument.as_str()) {
+            Some("palettes") => return Ok(Commands::Palettes(rest())),
+            Some("cache") => return Ok(Commands::Cache(rest())),
+            Some("bench") => return Ok(Commands::Bench(rest())),
+            _ => {}
+        }
+        if arguments.iter().any(|argument| argument == "-h" || argument == "--help") { return Ok(Commands::Help); }
+
+        match arguments.first().map(|argument| argument.as_str()) {
+            Some("tui") => {
+                let parsed = Arguments::parse(&rest())?;
+                if parsed.is_headless() { return Err(tr("oxide tui does not take a --processor or --resume, use oxide process")); }
+                Ok(Commands::Tui(parsed))
+            }
+            Some("process") => {
+                let parsed = Arguments::parse(&rest())?;
+                if !parsed.is_headless() { return Err(tr("oxide process needs a --processor or --resume")); }
+                Ok(Commands::Process(parsed))
+            }
+            // a leading -- keeps a source directory named like a command (e.g. "palettes") from being read as one
+            Some("--") => {
+                let parsed = Arguments::parse(&rest())?;
+                Ok(if parsed.is_headless() { Commands::Process(parsed) } else { Commands::Tui(parsed) })
+            }
+            // without a command, giving a processor still processes without the interface
+            _ => {
+                let parsed = Arguments::parse(arguments)?;
+                Ok(if parsed.is_headless() { Commands::Process(parsed) } else { Commands::Tui(parsed) })
+            }
+        }
+    }
+}
+
+
+
+/// Splits a comma separated list of arguments, leaving out blank entries.
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',').map(|entry| entry.trim().to_string()).filter(|entry| !entry.is_empty()).collect()
+}
+
+
+
+/// Returns how to use the app from the command line.
+pub fn usage() -> String {
+    [
+        tr("Usage:"),
+        "  oxide [tui] [<source directory>] [--source <directory>] [--output <directory>] [--json-file <file>] [--plain]".to_string(),
+        "  oxide [tui] <image> [--output <directory>] [--plain]".to_string(),
+        "  oxide [process] [--image <image>|<source directory>] --processor <name|number> [--colors <#hex,...>] [--numbers <n,...>] [--files <file,...>] [--names <name,...>] [--output <directory>] [--json|--json-file <file>] [--require-gpu] [--dry-run]".to_string(),
+        "  oxide [process] [<source directory>] --resume [--output <directory>]".to_string(),
+        "  oxide palettes <sync <url>... [--name <name>]|list|update [<name>]|fetch <slug>>".to_string(),
+        "  oxide cache <stats|clear>".to_string(),
+        "  oxide bench [--image <image>] [--processor <name|number>] [--runs <n>]".to_string(),
+        "".to_string(),
+        tr("Without arguments, images are read from ./source and saved to ./output."),
+        tr("Commands are only read from the first argument, a leading -- reads it as a source directory instead."),
+        tr("A single image is saved next to itself unless an output directory is given."),
+        tr("--plain shows every page as linear text without borders or graphics, for screen readers."),
+        tr("--processor processes the image (or every source image) without the interface, e.g. for scripts."),
+        tr("bench times building the palettes and mapping the image for every processor (on a generated image unless one is given)."),
+        tr("--require-gpu fails instead of falling back to the CPU when no GPU adapter can be set up."),
+        tr("--dry-run only builds the palettes and lists the files that would be saved with their palette colors."),
+        tr("--resume continues the interrupted batch run of the output directory, skipping the images it already saved."),
+        tr("--json prints a JSON record of every processed image instead of its path, --json-file appends the records to a file."),
+    ].join("\n")
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the given arguments as the command line passes them.
+    fn arguments(arguments: &[&str]) -> Vec<String> {
+        arguments.iter().map(|argument| argument.to_string()).collect()
+    }
+
+    #[test]
+    fn commands_are_only_read_from_the_first_argument() {
+        assert!(matches!(Commands::parse(&arguments(&["palettes", "sync", "https://example.com/a.gpl"])), Ok(Commands::Palettes(rest)) if rest == arguments(&["sync", "https://example.com/a.gpl"])));
+        assert!(matches!(Commands::parse(&arguments(&["cache", "stats"])), Ok(Commands::Cache(rest)) if rest == arguments(&["stats"])));
+        assert!(matches!(Commands::parse(&arguments(&["bench"])), Ok(Commands::Bench(rest)) if rest.is_empty()));
+
+        // later arguments named like a command are source directories
+        let Ok(Commands::Tui(parsed)) = Commands::parse(&arguments(&["--plain", "palettes"])) else { panic!("expected the interface") };
+        assert!(parsed.plain_output);
+        assert_eq!(parsed.source_directory, Some(PathBuf::from("palettes")));
+        let Ok(Commands::Process(parsed)) = Commands::parse(&arguments(&["--processor", "1", "cache"])) else { panic!("expected a headless run") };
+        assert_eq!(parsed.processor.as_deref(), Some("1"));
+        assert_eq!(parsed.source_directory, Some(PathBuf::from("cache")));
+    }
+
+    #[test]
+    fn a_leading_double_dash_reads_a_command_name_as_a_source_directory() {
+        let Ok(Commands::Tui(parsed)) = Commands::parse(&arguments(&["--", "palettes"])) else { panic!("expected the interface") };
+        assert_eq!(parsed.source_directory, Some(PathBuf::from("palettes")));
+        let Ok(Commands::Process(parsed)) = Commands::parse(&arguments(&["--", "bench", "--processor", "2"])) else { panic!("expected a headless run") };
+        assert_eq!(parsed.source_directory, Some(PathBuf::from("bench")));
+        assert_eq!(parsed.processor.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn commands_pick_the_interface_or_headless_runs() {
+        assert!(matches!(Commands::parse(&arguments(&[])), Ok(Commands::Tui(_))));
+        assert!(matches!(Commands::parse(&arguments(&["tui", "--plain"])), Ok(Commands::Tui(_))));
+        assert!(matches!(Commands::parse(&arguments(&["process", "--resume"])), Ok(Commands::Process(_))));
+        assert!(matches!(Commands::parse(&arguments(&["--processor", "1"])), Ok(Commands::Process(_))));
+        assert!(matches!(Commands::parse(&arguments(&["--source", "photos", "--help"])), Ok(Commands::Help)));
+        assert!(Commands::parse(&arguments(&["process"])).is_err());
+        assert!(Commands::parse(&arguments(&["tui", "--processor", "1"])).is_err());
+        assert!(Commands::parse(&arguments(&["tui", "--resume"])).is_err());
+    }
+
+    #[test]
+    fn resume_processor_and_json_conflicts_are_rejected() {
+        assert!(Arguments::parse(&arguments(&["--resume", "--processor", "1"])).is_err());
+        assert!(Arguments::parse(&arguments(&["--processor", "1", "--resume"])).is_err());
+        // the interface owns stdout, so only headless runs print records
+        assert!(Arguments::parse(&arguments(&["--json"])).is_err());
+        assert!(Arguments::parse(&arguments(&["--dry-run"])).is_err());
+        assert!(Arguments::parse(&arguments(&["--colors", "#ffffff"])).is_err());
+
+        assert!(Arguments::parse(&arguments(&["--json", "--resume"])).is_ok_and(|parsed| parsed.resume && matches!(parsed.result_output, Some(ResultOutputs::Stdout))));
+        assert!(Arguments::parse(&arguments(&["--json", "--processor", "1"])).is_ok_and(|parsed| parsed.is_headless()));
+        assert!(Arguments::parse(&arguments(&["--json-file", "results.json"])).is_ok_and(|parsed| !parsed.is_headless()));
+    }
+
+    #[test]
+    fn options_without_their_values_or_unknown_options_are_rejected() {
+        assert!(Arguments::parse(&arguments(&["--processor"])).is_err());
+        assert!(Arguments::parse(&arguments(&["--source"])).is_err());
+        assert!(Arguments::parse(&arguments(&["--unknown"])).is_err());
+        assert!(Arguments::parse(&arguments(&["photos", "more photos"])).is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use image::Rgb;
+use crate::processor::palette::{as_rgb, WebTopologies};
+use crate::processor::color_space::{ColorDistances, SpectrumSpaces};
+use crate::processor::color_vision::ColorVisionModes;
+use crate::processor::blend::BlendModes;
+use crate::processor::quantize::Quantizers;
+use crate::processor::dither::{bayer_sizes, DitherModes};
+use crate::processor::quality::QualityPresets;
+use crate::locale::{set_language, Languages};
+
+/// The name of the configuration file looked for in the working directory.
+pub const CONFIG_FILENAME: &str = "oxide.conf";
+
+/// The global configuration shared by the application and the processors.
+/// Readers share one copy, and a changed setting replaces it, so reading the configuration never copies it.
+static CONFIG: OnceLock<RwLock<Arc<Config>>> = OnceLock::new();
+
+
+
+/// The user-configurable settings of the application.
+#[derive(Clone)]
+pub struct Config {
+    /// The language of the user interface.
+    pub language: Languages,
+    /// Whether pages are rendered as linear, labeled text without borders, graphics or color-only information (e.g. for screen readers).
+    pub plain_output: bool,
+    /// Whether existing output images are overwritten instead of being saved with a numbered suffix.
+    pub overwrite: bool,
+    /// Whether batch runs skip images a processor with the same inputs has already saved (unless overwriting).
+    pub skip_existing: bool,
+    /// The opacities (1-99%) additional variants of processed images are saved at, blended over the original image.
+    pub blend_opacities: Vec<u8>,
+    /// How the colors of blended variants are kept.
+    pub blend_mode: BlendModes,
+    /// Whether a heatmap of the per-pixel error between the source and the result is saved next to every processed image.
+    pub save_heatmap: bool,
+    /// Whether exported palettes include light and dark stops from each base color's spectrum.
+    pub export_spectrum_stops: bool,
+    /// The number of colors listed in dominant color reports.
+    pub dominant_colors: usize,
+    /// The webcam, video file or stream url palettized in live mode.
+    pub live_source: String,
+    /// The ffmpeg input format of the live source (e.g. v4l2, avfoundation or dshow for webcams), or None to let ffmpeg pick.
+    pub live_format: Option<String>,
+    /// The number of evenly spaced frames the palette of an image sequence is built from.
+    pub sequence_samples: usize,
+    /// The number of frames in palette morph animations.
+    pub morph_frames: usize,
+    /// How animations are reduced to the 256 colors of a gif.
+    pub quantizer: Quantizers,
+    /// Whether shuffling also picks processors that ask for colors, filling them with random colors.
+    pub shuffle_colors: bool,
+    /// The color vision deficiency palettes are adjusted for before processing (normal vision leaves them untouched).
+    pub color_blind_safe: ColorVisionModes,
+    /// The quality preset applied to the interpolation steps, color distance and dithering, or None if they are set one by one.
+    pub quality: Option<QualityPresets>,
+    /// How the error of mapping images onto small palettes is spread out, so gradients do not band.
+    pub dither: DitherModes,
+    /// How strongly (0-100%) runs dither by default, where lower strengths soften the grain for subtle palettes.
+    pub dither_strength: f32,
+    /// The side length (2, 4 or 8) of the threshold matrix Bayer dithering repeats over images.
+    pub bayer_size: usize,
+    /// The seed the grain of blue noise dithering is placed with, where 0 keeps the texture as shipped.
+    pub dither_seed: u64,
+    /// The color space the distance between pixels and palette colors is measured in when mapping images.
+    pub color_distance: ColorDistances,
+    /// The color space spectrums are interpolated through.
+    pub spectrum_space: SpectrumSpaces,
+    /// Whether spectrums keep the chroma of their color while ramping the lightness instead of fading through grey.
+    pub preserve_saturation: bool,
+    /// The tint spectrums start from instead of pure white.
+    pub spectrum_white: Option<Rgb<u8>>,
+    /// The tint spectrums end at instead of pure black.
+    pub spectrum_black: Option<Rgb<u8>>,
+    /// The lightness (0-100) the darkest spectrum colors are lifted to.
+    pub spectrum_min_lightness: f32,
+    /// The lightness (0-100) the brightest spectrum colors are lowered to.
+    pub spectrum_max_lightness: f32,
+    /// The number of steps used between any two spectrum colors.
+    pub interpolation_steps: usize,
+    /// How many rows of a plane spectrum are skipped between the rows that are blended.
+    pub plane_spectrum_stride: usize,
+    /// The max number of colors a palette sent to the GPU can hold.
+    pub max_palette_size: usize,
+    /// How the colors of multi-color palettes are connected into a web spectrum.
+    pub web_topology: WebTopologies,
+    /// Whether generated spectrums are cached on disk to skip regenerating them.
+    pub cache: bool,
+    /// The max combined size of all cached files in megabytes.
+    pub cache_size_limit_mb: u64,
+    /// The host memory (in megabytes) processing tries to stay within, or None for no limit.
+    pub memory_budget_mb: Option<u64>,
+    /// The GPU memory (in megabytes) processing tries to stay within, or None for no limit.
+    pub vram_budget_mb: Option<u64>,
+    /// Whether images are processed on the CPU even when a GPU adapter is available.
+    pub force_cpu: bool,
+    /// The label (or part of the label, e.g. the GPU name) of the adapter images are processed on, or None for the default adapter.
+    pub gpu_adapter: Option<String>,
+}
+impl Config {
+    /// Returns the default configuration.
+    pub fn new() -> Config {
+        Config {
+            language: Languages::from_environment(),
+            plain_output: false,
+            overwrite: false,
+            skip_existing: true,
+            blend_opacities: Vec::new(),
+            blend_mode: BlendModes::Clamped,
+            save_heatmap: false,
+            export_spectrum_stops: false,
+            dominant_colors: 8,
+            morph_frames: 30,
+            quantizer: Quantizers::NeuQuant,
+            shuffle_colors: true,
+            sequence_samples: 5,
+            live_source: "/dev/video0".to_string(),
+            live_format: None,
+            color_blind_safe: ColorVisionModes::Normal,
+            quality: None,
+            dither: DitherModes::None,
+            dither_strength: 100.0,
+            bayer_size: 8,
+            dither_seed: 0,
+            color_distance: ColorDistances::WeightedRgb,
+            spectrum_space: SpectrumSpaces::Rgb,
+            preserve_saturation: false,
+            spectrum_white: None,
+            spectrum_black: None,
+            spectrum_min_lightness: 0.0,
+            spectrum_max_lightness: 100.0,
+            interpolation_steps: 442,
+            plane_spectrum_stride: 1,
+            max_palette_size: 50000,
+            web_topology: WebTopologies::Complete,
+            cache: true,
+            cache_size_limit_mb: 256,
+            memory_budget_mb: None,
+            vram_budget_mb: None,
+            force_cpu: false,
+            gpu_adapter: None,
+        }
+    }
+
+    /// Loads a configuration from a given file, falling back to the defaults for anything missing or invalid.
+    pub fn load(path: &Path) -> Config {
+        let mut config = Config::new();
+        let mut quality = None;
+        if let Ok(contents) = fs::read_to_string(path) {
+            for line in contents.lines() {
+                // skips blank lines and comments
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') { continue; }
+
+                if let Some((key, value)) = line.split_once('=') {
+                    // the quality preset is applied last, so it wins over the settings it stands for wherever it is listed
+                    if key.trim() == "quality" { quality = Some(value.trim().to_string()); continue; }
+                    config.set(key.trim(), value.trim());
+                }
+            }
+        }
+        if let Some(quality) = quality { config.set("quality", &quality); }
+        config
+    }
+
+    /// Sets a single setting from its key and value, ignoring unknown keys and invalid values.
+    pub fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "language" => assign(&mut self.language, Languages::from_name(value)),
+            "plain_output" => assign(&mut self.plain_output, parse_bool(value)),
+            "overwrite" => assign(&mut self.overwrite, parse_bool(value)),
+            "skip_existing" => assign(&mut self.skip_existing, parse_bool(value)),
+            "blend_opacities" => assign(&mut self.blend_opacities, parse_opacities(value)),
+            "blend_mode" => assign(&mut self.blend_mode, BlendModes::from_name(value)),
+            "save_heatmap" => assign(&mut self.save_heatmap, parse_bool(value)),
+            "export_spectrum_stops" => assign(&mut self.export_spectrum_stops, parse_bool(value)),
+            "dominant_colors" => assign(&mut self.dominant_colors, parse_count(value)),
+            "live_source" => assign(&mut self.live_source, Some(value.to_string())),
+            "live_format" => assign(&mut self.live_format, Some((!value.eq_ignore_ascii_case("none")).then(|| value.to_string()))),
+            "sequence_samples" => assign(&mut self.sequence_samples, parse_count(value)),
+            // a morph needs at least a start and an end frame
+            "morph_frames" => assign(&mut self.morph_frames, parse_count(value).filter(|frames| *frames >= 2)),
+            "quantizer" => assign(&mut self.quantizer, Quantizers::from_name(value)),
+            "shuffle_colors" => assign(&mut self.shuffle_colors, parse_bool(value)),
+            "color_blind_safe" => assign(&mut self.color_blind_safe, ColorVisionModes::from_name(value)),
+            "quality" => if value.eq_ignore_ascii_case("custom") {
+                self.quality = None;
+            } else if let Some(preset) = QualityPresets::from_name(value) {
+                self.quality = Some(preset);
+                for (key, value) in preset.settings() { self.set(key, value); }
+            },
+            "dither" => assign(&mut self.dither, DitherModes::from_name(value)),
+            "dither_strength" => assign(&mut self.dither_strength, parse_percent(value)),
+            "bayer_size" => assign(&mut self.bayer_size, parse_count(value).filter(|size| bayer_sizes().contains(size))),
+            "dither_seed" => assign(&mut self.dither_seed, value.parse().ok()),
+            "color_distance" => assign(&mut self.color_distance, ColorDistances::from_name(value)),
+            "spectrum_space" => assign(&mut self.spectrum_space, SpectrumSpaces::from_name(value)),
+            "preserve_saturation" => assign(&mut self.preserve_saturation, parse_bool(value)),
+            "spectrum_white" => assign(&mut self.spectrum_white, parse_anchor(value)),
+            "spectrum_black" => assign(&mut self.spectrum_black, parse_anchor(value)),
+            "spectrum_min_lightness" => assign(&mut self.spectrum_min_lightness, parse_percent(value).filter(|min| *min < self.spectrum_max_lightness)),
+            "spectrum_max_lightness" => assign(&mut self.spectrum_max_lightness, parse_percent(value).filter(|max| *max > self.spectrum_min_lightness)),
+            "interpolation_steps" => assign(&mut self.interpolation_steps, parse_count(value)),
+            "plane_spectrum_stride" => assign(&mut self.plane_spectrum_stride, parse_count(value)),
+            // palettes can only be condensed down to a few hundred colors
+            "max_palette_size" => assign(&mut self.max_palette_size, parse_count(value).filter(|size| *size >= 256)),
+            "web_topology" => assign(&mut self.web_topology, WebTopologies::from_name(value)),
+            "cache" => assign(&mut self.cache, parse_bool(value)),
+            "cache_size_limit_mb" => assign(&mut self.cache_size_limit_mb, value.parse().ok()),
+            "memory_budget_mb" => assign(&mut self.memory_budget_mb, parse_budget(value)),
+            "vram_budget_mb" => assign(&mut self.vram_budget_mb, parse_budget(value)),
+            "force_cpu" => assign(&mut self.force_cpu, parse_bool(value)),
+            "gpu_adapter" => assign(&mut self.gpu_adapter, Some((!value.eq_ignore_ascii_case("auto")).then(|| value.to_string()))),
+            _ => {}
+        }
+    }
+}
+impl Default for Config {
+    fn default() -> Self { Config::new() }
+}
+
+
+
+/// Replaces a setting with a parsed value if the value is valid.
+fn assign<T>(setting: &mut T, value: Option<T>) {
+    if let Some(value) = value { *setting = value; }
+}
+
+/// Parses a boolean setting value.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses a count setting value, which has to be at least 1.
+fn parse_count(value: &str) -> Option<usize> {
+    value.parse().ok().filter(|count| *count > 0)
+}
+
+/// Parses a percentage setting value (0-100), with or without a trailing %.
+fn parse_percent(value: &str) -> Option<f32> {
+    value.trim_end_matches('%').trim().parse().ok().filter(|percent| (0.0..=100.0).contains(percent))
+}
+
+/// Parses a comma separated list of opacities (1-99%), where "none" saves no blended variants.
+fn parse_opacities(value: &str) -> Option<Vec<u8>> {
+    if value.eq_ignore_ascii_case("none") { return Some(Vec::new()); }
+    value.split(',')
+        .map(|opacity| parse_percent(opacity.trim()).map(|opacity| opacity.round() as u8).filter(|opacity| (1..100).contains(opacity)))
+        .collect()
+}
+
+/// Parses a memory budget setting value in megabytes, where "none" removes the limit.
+fn parse_budget(value: &str) -> Option<Option<u64>> {
+    if value.eq_ignore_ascii_case("none") { return Some(None); }
+    value.parse().ok().filter(|budget| *budget > 0).map(Some)
+}
+
+/// Parses a spectrum anchor setting value, where "none" restores the pure anchor.
+fn parse_anchor(value: &str) -> Option<Option<Rgb<u8>>> {
+    if value.eq_ignore_ascii_case("none") { return Some(None); }
+    as_rgb(&value.to_string()).map(Some)
+}
+
+/// Returns the default path of the configuration file.
+pub fn default_path() -> PathBuf {
+    std::env::current_dir().expect("Could not get local working directory.").join(CONFIG_FILENAME)
+}
+
+/// Returns the lock of the global configuration, which holds the defaults until a configuration is loaded.
+fn lock() -> &'static RwLock<Arc<Config>> {
+    CONFIG.get_or_init(|| RwLock::new(Arc::new(Config::new())))
+}
+
+/// Loads the global configuration from a given file.
+pub fn init(path: &Path) {
+    let config = Config::load(path);
+    set_language(config.language);
+    *lock().write().unwrap() = Arc::new(config);
+}
+
+/// Changes a single setting of the global configuration for the rest of the run (e.g. from a command line flag).
+pub fn set(key: &str, value: &str) {
+    let mut config = lock().write().unwrap();
+    Arc::make_mut(&mut config).set(key, value);
+    set_language(config.language);
+}
+
+/// Changes a single setting of the global configuration and remembers it in the configuration file for the next launches.
+pub fn save(key: &str, value: &str) -> std::io::Result<()> {
+    set(key, value);
+
+    // replaces the line of the setting, keeping every other line and comment as it is
+    let contents = fs::read_to_string(default_path()).unwrap_or_default();
+    let mut is_replaced = false;
+    let mut lines: Vec<String> = contents.lines().map(|line| {
+        let is_setting = !line.trim_start().starts_with('#') && line.split_once('=').is_some_and(|(line_key, _)| line_key.trim() == key);
+        if !is_setting { return line.to_string(); }
+        is_replaced = true;
+        format!("{} = {}", key, value)
+    }).collect();
+    if !is_replaced { lines.push(format!("{} = {}", key, value)); }
+    fs::write(default_path(), lines.join("\n") + "\n")
+}
+
+/// Returns the current global configuration.
+/// The returned configuration stays the same while it is held, so hot paths should read it once and pass the settings down.
+pub fn get() -> Arc<Config> {
+    lock().read().unwrap().clone()
+}
@@ -0,0 +1,415 @@
+use std::fs;
+use std::io::{Error, Result};
+use std::path::Path;
+use image::{DynamicImage, ImageBuffer, Rgb};
+use image::imageops::{resize, FilterType};
+use crate::processor::palette::*;
+use crate::processor::compute::PaletteMapping;
+use crate::processor::palette_file::PaletteFile;
+use crate::locale::trf;
+
+/// The minimum contrast ratio WCAG AA requires for normal text.
+fn minimum_text_contrast() -> f64 { 4.5 }
+
+/// Gets the number of points along each axis of exported LUTs (33 is the common size most software expects).
+fn lut_size() -> u32 { 33 }
+
+/// Gets the width in characters of exported ANSI and ASCII art, which fits a standard terminal.
+fn text_art_width() -> u32 { 80 }
+
+/// Gets the characters of exported ASCII art from dark to bright, for light text on a dark background.
+fn ascii_ramp() -> &'static str { " .:-=+*#%@" }
+
+
+
+/// The contrast between a text color and the background it is drawn on.
+pub struct ContrastPair {
+    /// The name of the text color.
+    pub foreground: String,
+    /// The name of the background color.
+    pub background: String,
+    /// The WCAG contrast ratio between the two colors.
+    pub ratio: f64,
+}
+impl ContrastPair {
+    /// Measures the contrast between two named colors.
+    pub fn new(foreground: (&str, &Rgb<u8>), background: (&str, &Rgb<u8>)) -> ContrastPair {
+        ContrastPair {
+            foreground: foreground.0.to_string(),
+            background: background.0.to_string(),
+            ratio: get_contrast_ratio(foreground.1, background.1),
+        }
+    }
+
+    /// Returns whether the pair is readable as normal text according to WCAG AA.
+    pub fn passes_aa(&self) -> bool {
+        self.ratio >= minimum_text_contrast()
+    }
+}
+
+
+
+/// The contrast ratios of the key color pairs of an exported palette.
+pub struct ContrastReport {
+    /// The measured color pairs.
+    pub pairs: Vec<ContrastPair>,
+}
+impl ContrastReport {
+    /// Returns the number of pairs below WCAG AA.
+    pub fn failing_count(&self) -> usize {
+        self.pairs.iter().filter(|pair| !pair.passes_aa()).count()
+    }
+
+    /// Returns a short summary of the report for status messages.
+    pub fn summary(&self) -> String {
+        trf("{} of {} color pairs below WCAG AA contrast", &[self.failing_count().to_string(), self.pairs.len().to_string()])
+    }
+
+    /// Returns the report as comment lines wrapped in a given comment prefix and suffix.
+    fn as_comments(&self, prefix: &str, suffix: &str) -> String {
+        let mut comments = format!("\n{} WCAG contrast report (AA requires {:.1}:1 for normal text){}\n", prefix, minimum_text_contrast(), suffix);
+        for pair in &self.pairs {
+            let verdict = if pair.passes_aa() { "ok" } else { "below AA" };
+            comments += &format!("{} {} on {}: {:.2}:1 {}{}\n", prefix, pair.foreground, pair.background, pair.ratio, verdict, suffix);
+        }
+        comments
+    }
+}
+
+
+
+/// The list of supported style sheet variable formats.
+#[derive(Copy, Clone)]
+pub enum VariableFormats {
+    Css,
+    Scss,
+}
+impl VariableFormats {
+    /// Returns the file extension of a given format.
+    pub fn extension(&self) -> String {
+        match self {
+            VariableFormats::Css => "css".to_string(),
+            VariableFormats::Scss => "scss".to_string(),
+        }
+    }
+}
+
+
+
+/// Returns the named colors exported for a list of base colors.
+/// When enabled, each base color also gets light and dark stops sampled from its spectrum.
+fn get_named_colors(colors: &[Rgb<u8>], include_spectrum_stops: bool) -> Vec<(String, Rgb<u8>)> {
+    let mut named_colors = Vec::new();
+    for (index, color) in colors.iter().enumerate() {
+        let name = format!("oxide-color-{}", index + 1);
+        named_colors.push((name.clone(), *color));
+
+        if include_spectrum_stops {
+            // the spectrum runs white -> color -> black, so the quarter marks sit halfway to each end
+            let spectrum = get_line_spectrum(color);
+            named_colors.push((format!("{}-light", name), spectrum[spectrum.len() / 4]));
+            named_colors.push((format!("{}-dark", name), spectrum[spectrum.len() * 3 / 4]));
+        }
+    }
+
+    named_colors
+}
+
+/// Measures every palette color against white and black text, and every pair of base colors against each other.
+fn get_palette_contrast_report(colors: &[Rgb<u8>], named_colors: &[(String, Rgb<u8>)]) -> ContrastReport {
+    let white = Rgb([255, 255, 255]);
+    let black = Rgb([0, 0, 0]);
+
+    let mut pairs = Vec::new();
+    for (name, color) in named_colors {
+        pairs.push(ContrastPair::new(("white", &white), (name, color)));
+        pairs.push(ContrastPair::new(("black", &black), (name, color)));
+    }
+    for a in 0..colors.len() {
+        for b in (a + 1)..colors.len() {
+            let a_name = format!("oxide-color-{}", a + 1);
+            let b_name = format!("oxide-color-{}", b + 1);
+            pairs.push(ContrastPair::new((&a_name, &colors[a]), (&b_name, &colors[b])));
+        }
+    }
+
+    ContrastReport { pairs }
+}
+
+/// Writes the base colors of a run as style sheet variables in a given format, followed by a contrast report.
+pub fn export_variables(path: &Path, source_name: &str, colors: &[Rgb<u8>], include_spectrum_stops: bool, format: VariableFormats) -> Result<ContrastReport> {
+    let named_colors = get_named_colors(colors, include_spectrum_stops);
+    let report = get_palette_contrast_report(colors, &named_colors);
+
+    let mut contents = format!("/* Oxide palette generated from {} */\n", source_name);
+    match format {
+        VariableFormats::Css => {
+            contents += ":root {\n";
+            for (name, color) in &named_colors {
+                contents += &format!("  --{}: {};\n", name, as_hex(color).to_lowercase());
+            }
+            contents += "}\n";
+        }
+        VariableFormats::Scss => {
+            for (name, color) in &named_colors {
+                contents += &format!("${}: {};\n", name, as_hex(color).to_lowercase());
+            }
+        }
+    }
+    contents += &report.as_comments("/*", " */");
+
+    fs::write(path, contents)?;
+    Ok(report)
+}
+
+/// Writes the base colors of a run as a GIMP palette (.gpl), which GIMP, Aseprite, Krita and Inkscape can load and Oxide reads back as a palette file,
+/// followed by a contrast report.
+pub fn export_gimp_palette(path: &Path, name: &str, colors: &[Rgb<u8>], include_spectrum_stops: bool) -> Result<ContrastReport> {
+    let named_colors = get_named_colors(colors, include_spectrum_stops);
+    let report = get_palette_contrast_report(colors, &named_colors);
+
+    let mut contents = format!("GIMP Palette\nName: {}\nColumns: 0\n#\n", name);
+    for (color_name, color) in &named_colors {
+        contents += &format!("{:>3} {:>3} {:>3}\t{}\n", color[0], color[1], color[2], color_name);
+    }
+    contents += &report.as_comments("#", "");
+
+    fs::write(path, contents)?;
+    Ok(report)
+}
+
+
+
+/// Writes the base colors of a run as a palette file in Oxide's JSON format, which Custom Palette and the palette directory read back.
+pub fn export_palette_file(path: &Path, name: &str, colors: &[Rgb<u8>]) -> Result<ContrastReport> {
+    let report = get_palette_contrast_report(colors, &get_named_colors(colors, false));
+
+    let contents = serde_json::to_string_pretty(&PaletteFile::new(name, colors)).map_err(Error::other)?;
+    fs::write(path, contents + "\n")?;
+    Ok(report)
+}
+
+
+
+/// The list of supported terminal emulator color scheme formats.
+#[derive(Copy, Clone)]
+pub enum TerminalSchemeFormats {
+    Alacritty,
+    Kitty,
+    Wezterm,
+}
+impl TerminalSchemeFormats {
+    /// Returns the file extension of a given format.
+    pub fn extension(&self) -> String {
+        match self {
+            TerminalSchemeFormats::Alacritty => "alacritty.toml".to_string(),
+            TerminalSchemeFormats::Kitty => "kitty.conf".to_string(),
+            TerminalSchemeFormats::Wezterm => "wezterm.toml".to_string(),
+        }
+    }
+
+    /// Returns every supported format.
+    pub fn all() -> Vec<TerminalSchemeFormats> {
+        vec![TerminalSchemeFormats::Alacritty, TerminalSchemeFormats::Kitty, TerminalSchemeFormats::Wezterm]
+    }
+}
+
+
+
+/// The names of the eight ANSI colors in slot order.
+const ANSI_COLOR_NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+/// Returns the standard (xterm) colors of the 16 ANSI slots, used to decide which slot each palette color fills.
+fn ansi_reference_colors() -> [Rgb<u8>; 16] {
+    [
+        Rgb([0, 0, 0]), Rgb([205, 0, 0]), Rgb([0, 205, 0]), Rgb([205, 205, 0]),
+        Rgb([0, 0, 238]), Rgb([205, 0, 205]), Rgb([0, 205, 205]), Rgb([229, 229, 229]),
+        Rgb([127, 127, 127]), Rgb([255, 0, 0]), Rgb([0, 255, 0]), Rgb([255, 255, 0]),
+        Rgb([92, 92, 255]), Rgb([255, 0, 255]), Rgb([0, 255, 255]), Rgb([255, 255, 255]),
+    ]
+}
+
+
+
+/// A terminal color scheme built from the colors of a processed image.
+pub struct TerminalScheme {
+    /// The default background color.
+    pub background: Rgb<u8>,
+    /// The default text color.
+    pub foreground: Rgb<u8>,
+    /// The 16 ANSI colors (8 normal followed by 8 bright).
+    pub ansi: Vec<Rgb<u8>>,
+}
+impl TerminalScheme {
+    /// Builds a scheme from the 16 most distinct colors of a processed image.
+    /// Each ANSI slot takes the closest remaining color to its standard color, the darkest color becomes the background, and the lightest the foreground.
+    pub fn from_image(image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Option<TerminalScheme> {
+        let distinct_colors = get_most_distinct_colors(image, 16);
+        if distinct_colors.is_empty() { return None; }
+
+        // fills the slots, reusing colors only once every color has a slot
+        let mut is_used = vec![false; distinct_colors.len()];
+        let ansi = ansi_reference_colors().iter().map(|reference_color| {
+            if is_used.iter().all(|is_used| *is_used) { is_used.fill(false); }
+            let closest_index = (0..distinct_colors.len())
+                .filter(|index| !is_used[*index])
+                .min_by(|a, b| get_distance(&distinct_colors[*a], reference_color, &None).total_cmp(&get_distance(&distinct_colors[*b], reference_color, &None)))
+                .unwrap();
+            is_used[closest_index] = true;
+            distinct_colors[closest_index]
+        }).collect();
+
+        let background = *distinct_colors.iter().min_by(|a, b| get_brightness(a).total_cmp(&get_brightness(b))).unwrap();
+        let foreground = *distinct_colors.iter().max_by(|a, b| get_brightness(a).total_cmp(&get_brightness(b))).unwrap();
+
+        Some(TerminalScheme { background, foreground, ansi })
+    }
+
+    /// Measures the foreground and every ANSI color against the background.
+    pub fn get_contrast_report(&self) -> ContrastReport {
+        let mut pairs = vec![ContrastPair::new(("foreground", &self.foreground), ("background", &self.background))];
+        for (index, color) in self.ansi.iter().enumerate() {
+            let kind = if index < 8 { "normal" } else { "bright" };
+            let name = format!("{} {}", kind, ANSI_COLOR_NAMES[index % 8]);
+            pairs.push(ContrastPair::new((&name, color), ("background", &self.background)));
+        }
+
+        ContrastReport { pairs }
+    }
+}
+
+/// Writes a terminal color scheme in a given format, followed by a contrast report.
+pub fn export_terminal_scheme(path: &Path, name: &str, scheme: &TerminalScheme, format: TerminalSchemeFormats) -> Result<()> {
+    let hex = |color: &Rgb<u8>| as_hex(color).to_lowercase();
+
+    let mut contents = String::new();
+    match format {
+        TerminalSchemeFormats::Alacritty => {
+            contents += &format!("# Oxide color scheme generated from {}\n\n", name);
+            contents += "[colors.primary]\n";
+            contents += &format!("background = \"{}\"\n", hex(&scheme.background));
+            contents += &format!("foreground = \"{}\"\n", hex(&scheme.foreground));
+            for (table, colors) in [("normal", &scheme.ansi[0..8]), ("bright", &scheme.ansi[8..16])] {
+                contents += &format!("\n[colors.{}]\n", table);
+                for (color_name, color) in ANSI_COLOR_NAMES.iter().zip(colors) {
+                    contents += &format!("{} = \"{}\"\n", color_name, hex(color));
+                }
+            }
+        }
+        TerminalSchemeFormats::Kitty => {
+            contents += &format!("# Oxide color scheme generated from {}\n\n", name);
+            contents += &format!("background {}\n", hex(&scheme.background));
+            contents += &format!("foreground {}\n", hex(&scheme.foreground));
+            for (index, color) in scheme.ansi.iter().enumerate() {
+                contents += &format!("color{} {}\n", index, hex(color));
+            }
+        }
+        TerminalSchemeFormats::Wezterm => {
+            let list = |colors: &[Rgb<u8>]| colors.iter().map(|color| format!("\"{}\"", hex(color))).collect::<Vec<String>>().join(", ");
+            contents += "[colors]\n";
+            contents += &format!("background = \"{}\"\n", hex(&scheme.background));
+            contents += &format!("foreground = \"{}\"\n", hex(&scheme.foreground));
+            contents += &format!("ansi = [{}]\n", list(&scheme.ansi[0..8]));
+            contents += &format!("brights = [{}]\n", list(&scheme.ansi[8..16]));
+            contents += "\n[metadata]\n";
+            contents += &format!("name = \"Oxide {}\"\n", name.replace('"', "'"));
+        }
+    }
+    contents += &scheme.get_contrast_report().as_comments("#", "");
+
+    fs::write(path, contents)
+}
+
+
+
+/// Returns an image holding every point of a LUT lattice in .cube order (red changes fastest, then green, then blue).
+/// Mapping its points onto a palette gives the colors the LUT points map to.
+pub fn get_lut_lattice() -> DynamicImage {
+    let size = lut_size();
+    let level = |index: u32| (index as f32 * 255.0 / (size - 1) as f32).round() as u8;
+    DynamicImage::ImageRgb8(ImageBuffer::from_fn(size, size * size, |x, y| Rgb([level(x), level(y % size), level(y / size)])))
+}
+
+/// Returns the colors the points of a LUT lattice map to, in the order of the lattice.
+/// Every point is mapped on its own, since neighboring points are unrelated colors (so e.g. pixelation, which averages blocks, leaves them as they are).
+/// Effects and plugins can only map whole images, so the lattice is run through them as one.
+pub fn get_mapped_lut_lattice(palette_mapping: &PaletteMapping) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let lattice = get_lut_lattice();
+    match palette_mapping.maps_whole_images() {
+        true => palette_mapping.process(lattice),
+        false => palette_mapping.map_colors(&lattice.to_rgb8()),
+    }
+}
+
+/// Writes a mapped LUT lattice as a 3D LUT (.cube) file.
+pub fn export_cube_lut(path: &Path, name: &str, mapped_lattice: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<()> {
+    let mut contents = format!("# Oxide LUT generated from {}\n", name);
+    contents += &format!("TITLE \"Oxide {}\"\n", name.replace('"', "'"));
+    contents += &format!("LUT_3D_SIZE {}\n", lut_size());
+    contents += "DOMAIN_MIN 0.0 0.0 0.0\n";
+    contents += "DOMAIN_MAX 1.0 1.0 1.0\n";
+    for color in mapped_lattice.pixels() {
+        contents += &format!("{:.6} {:.6} {:.6}\n", color[0] as f32 / 255.0, color[1] as f32 / 255.0, color[2] as f32 / 255.0);
+    }
+
+    fs::write(path, contents)
+}
+
+
+
+/// Returns an image scaled to the width of text art with a given number of pixel rows per character row, keeping the colors of the image.
+/// Characters are about twice as tall as they are wide, so the rows are halved before being split among the characters.
+fn get_text_art_pixels(image: &ImageBuffer<Rgb<u8>, Vec<u8>>, pixel_rows_per_character: u32) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let width = text_art_width().min(image.width()).max(1);
+    let character_rows = ((image.height() as f32 * width as f32 / image.width().max(1) as f32 / 2.0).round() as u32).max(1);
+    // nearest neighbor keeps the palette, which smoother filters would blend
+    resize(image, width, character_rows * pixel_rows_per_character, FilterType::Nearest)
+}
+
+/// Writes an image as ANSI art (.ans) of 24-bit colored half blocks, where every character shows two pixels: the upper one in the foreground and the lower one in the background.
+pub fn export_ansi_art(path: &Path, image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<()> {
+    let pixels = get_text_art_pixels(image, 2);
+    let mut contents = String::new();
+    for y in (0..pixels.height()).step_by(2) {
+        for x in 0..pixels.width() {
+            let (upper, lower) = (pixels.get_pixel(x, y), pixels.get_pixel(x, y + 1));
+            contents += &format!("\x1b[38;2;{};{};{};48;2;{};{};{}m\u{2580}", upper[0], upper[1], upper[2], lower[0], lower[1], lower[2]);
+        }
+        contents += "\x1b[0m\n";
+    }
+
+    fs::write(path, contents)
+}
+
+/// Writes an image as plain ASCII art (.txt), picking every character by the brightness of its pixel.
+pub fn export_ascii_art(path: &Path, image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<()> {
+    let pixels = get_text_art_pixels(image, 1);
+    let ramp: Vec<char> = ascii_ramp().chars().collect();
+    let mut contents = String::new();
+    for y in 0..pixels.height() {
+        let row: String = (0..pixels.width()).map(|x| {
+            let index = (get_brightness(pixels.get_pixel(x, y)) / 255.0 * (ramp.len() - 1) as f32).round() as usize;
+            ramp[index.min(ramp.len() - 1)]
+        }).collect();
+        // trailing spaces only pad the dark areas at the end of each line
+        contents.push_str(row.trim_end_matches(' '));
+        contents.push('\n');
+    }
+
+    fs::write(path, contents)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::compute::Transform;
+
+    #[test]
+    fn pixelated_lut_equals_the_lut_of_the_inner_mapping() {
+        let palette = vec![Rgb([0, 0, 0]), Rgb([255, 255, 255]), Rgb([200, 40, 40]), Rgb([30, 90, 220])];
+        let pixelated = PaletteMapping::Transformed(Box::new(PaletteMapping::Even(palette.clone())), Transform::Pixelate(8));
+        assert_eq!(get_mapped_lut_lattice(&pixelated), get_mapped_lut_lattice(&PaletteMapping::Even(palette)));
+    }
+}
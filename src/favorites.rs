@@ -0,0 +1,31 @@
+use std::fs;
+use std::io::Result;
+use std::path::PathBuf;
+use crate::processor::Processors;
+
+/// The name of the file favorite processors are remembered in, next to the configuration file.
+pub const FAVORITES_FILENAME: &str = "oxide.favorites";
+
+
+
+/// Loads the favorite processors as indices in the processor list, in the order they were starred.
+pub fn load() -> Vec<usize> {
+    let Ok(contents) = fs::read_to_string(path()) else { return Vec::new(); };
+    // processors are remembered by name (one per line) so the list can be reordered
+    contents.lines()
+        .filter_map(|name| (0..Processors::number_of_processors()).find(|selection| Processors::get_processor(*selection).name() == name.trim()))
+        .collect()
+}
+
+/// Remembers the given favorite processors for the next launch.
+pub fn save(favorites: &[usize]) -> Result<()> {
+    let names: Vec<String> = favorites.iter().map(|selection| Processors::get_processor(*selection).name()).collect();
+    fs::write(path(), names.join("\n") + "\n")
+}
+
+
+
+/// Returns the path of the favorites file.
+fn path() -> PathBuf {
+    std::env::current_dir().expect("Could not get local working directory.").join(FAVORITES_FILENAME)
+}
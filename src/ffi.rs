@@ -0,0 +1,92 @@
+//! A small C API for embedding the palettizer in applications written in other languages, declared in `include/oxide.h`.
+//!
+//! Images and palettes are passed as packed RGB bytes (three bytes per pixel or color, rows top to bottom).
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::slice;
+use image::{DynamicImage, Rgb, RgbImage};
+use crate::processor::compute::{process_biased, process_evenly};
+
+/// The image is mapped and written to the output.
+pub const OXIDE_OK: i32 = 0;
+/// A pointer that is needed is null.
+pub const OXIDE_NULL_POINTER: i32 = 1;
+/// The width or height is 0 or the image is too large to address.
+pub const OXIDE_INVALID_SIZE: i32 = 2;
+/// There is no color to map the image onto.
+pub const OXIDE_EMPTY_PALETTE: i32 = 3;
+/// Mapping the image failed unexpectedly.
+pub const OXIDE_INTERNAL_ERROR: i32 = 4;
+/// A palette is too large to address.
+pub const OXIDE_INVALID_PALETTE: i32 = 5;
+
+
+
+/// Returns the number of bytes of a packed RGB image of a given size, or None if it is empty or too large to address.
+fn image_length(width: u32, height: u32) -> Option<usize> {
+    if width == 0 || height == 0 { return None; }
+    (width as usize).checked_mul(height as usize)?.checked_mul(3)
+}
+
+/// Returns the colors of a packed RGB palette, where an empty palette may be null, or None if the palette is too large to address.
+///
+/// # Safety
+/// `palette` must be null or point to at least `palette_len * 3` readable bytes.
+unsafe fn read_palette(palette: *const u8, palette_len: usize) -> Option<Vec<Rgb<u8>>> {
+    if palette.is_null() || palette_len == 0 { return Some(Vec::new()); }
+    let length = palette_len.checked_mul(3).filter(|length| *length <= isize::MAX as usize)?;
+    let bytes = unsafe { slice::from_raw_parts(palette, length) };
+    Some(bytes.chunks_exact(3).map(|color| Rgb([color[0], color[1], color[2]])).collect())
+}
+
+/// Copies a packed RGB image into an image, maps it with a given function and writes the result to the output, catching panics so they do not cross the C boundary.
+///
+/// # Safety
+/// `pixels` must point to `width * height * 3` readable bytes and `output` to as many writable bytes; they may be the same buffer.
+unsafe fn map_image(pixels: *const u8, width: u32, height: u32, output: *mut u8, map: impl FnOnce(DynamicImage) -> RgbImage) -> i32 {
+    if pixels.is_null() || output.is_null() { return OXIDE_NULL_POINTER; }
+    let Some(length) = image_length(width, height) else { return OXIDE_INVALID_SIZE; };
+
+    // the source is copied before the output is written, so mapping in place is allowed
+    let source = unsafe { slice::from_raw_parts(pixels, length) }.to_vec();
+    let Some(source_image) = RgbImage::from_raw(width, height, source) else { return OXIDE_INVALID_SIZE; };
+    let Ok(new_image) = catch_unwind(AssertUnwindSafe(|| map(DynamicImage::ImageRgb8(source_image)))) else { return OXIDE_INTERNAL_ERROR; };
+    unsafe { slice::from_raw_parts_mut(output, length) }.copy_from_slice(new_image.as_raw());
+    OXIDE_OK
+}
+
+
+
+/// Maps every pixel of a packed RGB image onto the closest color of a palette (on the GPU when one is available) and writes the result to `output`.
+/// Returns `OXIDE_OK` or one of the error codes.
+///
+/// # Safety
+/// `pixels` and `output` must point to `width * height * 3` bytes (they may be the same buffer) and `palette` to `palette_len * 3` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn oxide_process_rgb8(pixels: *const u8, width: u32, height: u32, palette: *const u8, palette_len: usize, output: *mut u8) -> i32 {
+    if palette.is_null() { return OXIDE_NULL_POINTER; }
+    if palette_len == 0 { return OXIDE_EMPTY_PALETTE; }
+    let Some(palette) = (unsafe { read_palette(palette, palette_len) }) else { return OXIDE_INVALID_PALETTE; };
+    unsafe { map_image(pixels, width, height, output, |source_image| process_evenly(source_image, palette)) }
+}
+
+/// Maps every pixel of a packed RGB image onto a biased palette, which is preferred, and a standard palette, and writes the result to `output`.
+/// Returns `OXIDE_OK` or one of the error codes.
+///
+/// # Safety
+/// `pixels` and `output` must point to `width * height * 3` bytes (they may be the same buffer), `biased_palette` to `biased_len * 3` bytes
+/// and `standard_palette` to `standard_len * 3` bytes, where an empty palette may be null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn oxide_process_rgb8_biased(pixels: *const u8, width: u32, height: u32, biased_palette: *const u8, biased_len: usize, standard_palette: *const u8, standard_len: usize, output: *mut u8) -> i32 {
+    if (biased_palette.is_null() && biased_len > 0) || (standard_palette.is_null() && standard_len > 0) { return OXIDE_NULL_POINTER; }
+    if biased_len == 0 && standard_len == 0 { return OXIDE_EMPTY_PALETTE; }
+    let Some(biased_palette) = (unsafe { read_palette(biased_palette, biased_len) }) else { return OXIDE_INVALID_PALETTE; };
+    let Some(standard_palette) = (unsafe { read_palette(standard_palette, standard_len) }) else { return OXIDE_INVALID_PALETTE; };
+
+    // the GPU cannot bind an empty palette, and with a single palette there is nothing to prefer anyway
+    if biased_palette.is_empty() || standard_palette.is_empty() {
+        let palette = [biased_palette, standard_palette].concat();
+        return unsafe { map_image(pixels, width, height, output, |source_image| process_evenly(source_image, palette)) };
+    }
+    unsafe { map_image(pixels, width, height, output, |source_image| process_biased(source_image, biased_palette, standard_palette)) }
+}
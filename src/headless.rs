@@ -0,0 +1,229 @@
+use std::io::Result;
+use std::path::Path;
+use std::time::Instant;
+use image::Rgb;
+use crate::app::App;
+use crate::cli::Arguments;
+use crate::processor::{EditProcessor, Processors};
+use crate::processor::palette::as_hex;
+use crate::processor::compute::{is_gpu_available, GpuInformation, ProcessingBackends};
+use crate::batch::BatchState;
+use crate::results::{ResultOutputs, ResultRecord};
+use crate::locale::{tr, trf};
+
+/// Gets the number of palette colors dry runs list for each image, picked evenly across the palettes.
+fn dry_run_color_count() -> usize { 12 }
+
+
+
+/// The codes runs without the terminal interface exit with, so scripts and pipelines can tell failures apart.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ExitCodes {
+    /// An image could not be processed for another reason, e.g. it cannot be read.
+    ProcessingFailed,
+    /// The command line arguments are invalid, e.g. an unknown processor.
+    Usage,
+    /// There are no images to process.
+    NoImagesFound,
+    /// A color or number for the steps of the processor is missing or invalid.
+    InvalidInput,
+    /// A GPU adapter is needed but none can be set up.
+    GpuUnavailable,
+    /// An image is processed but it (or its result record) cannot be saved.
+    SaveFailed,
+}
+impl ExitCodes {
+    /// Gets the code the process exits with.
+    pub fn code(&self) -> i32 {
+        match self {
+            ExitCodes::ProcessingFailed => 1,
+            ExitCodes::Usage => 2,
+            ExitCodes::NoImagesFound => 3,
+            ExitCodes::InvalidInput => 4,
+            ExitCodes::GpuUnavailable => 5,
+            ExitCodes::SaveFailed => 6,
+        }
+    }
+
+    /// Gets the name errors of this kind are reported under on stderr.
+    pub fn name(&self) -> String {
+        match self {
+            ExitCodes::ProcessingFailed => "processing-failed".to_string(),
+            ExitCodes::Usage => "usage".to_string(),
+            ExitCodes::NoImagesFound => "no-images-found".to_string(),
+            ExitCodes::InvalidInput => "invalid-input".to_string(),
+            ExitCodes::GpuUnavailable => "gpu-unavailable".to_string(),
+            ExitCodes::SaveFailed => "save-failed".to_string(),
+        }
+    }
+
+    /// Exits the process with the code.
+    pub fn exit(self) -> ! {
+        std::process::exit(self.code())
+    }
+}
+
+/// Prints how to use a command to stderr and exits with the usage exit code.
+pub fn exit_with_usage(usage: &str) -> ! {
+    eprintln!("{}", usage);
+    ExitCodes::Usage.exit();
+}
+
+
+
+/// Prints an error to stderr as a single line starting with its kind and code, followed by the image it concerns (if any) and the message,
+/// e.g. `error[save-failed/6] beach.png: Permission denied`, so pipelines can match on it.
+pub fn report_error(kind: ExitCodes, image_path: Option<&Path>, message: &str) {
+    let image = image_path.map(|image_path| format!(" {}:", image_path.to_string_lossy())).unwrap_or_default();
+    eprintln!("error[{}/{}]{} {}", kind.name(), kind.code(), image, message);
+}
+
+
+
+/// Returns the record of building the palettes of a processor for the source image at a given index, without mapping or saving the image.
+fn plan(app: &App, image_selection: usize, selection: usize, processor: Box<dyn EditProcessor>) -> ResultRecord {
+    let planning_timer = Instant::now();
+    let inputs = processor.get_step_inputs();
+    let (result, palette) = match app.plan_headless(image_selection, selection, processor) {
+        Ok((output_path, palette)) => (Ok(output_path), palette),
+        Err(message) => (Err(message), Vec::new()),
+    };
+    ResultRecord {
+        input_path: app.source_image_paths[image_selection].clone(),
+        result,
+        processor: Processors::get_processor(selection).name(),
+        inputs,
+        color_count: palette.len(),
+        top_colors: Vec::new(),
+        duration: planning_timer.elapsed(),
+        adjustments: Vec::new(),
+        frames: None,
+        dry_run_palette: Some(palette),
+    }
+}
+
+/// Returns the number of colors in a palette followed by some of them, picked evenly across it.
+fn describe_palette(palette: &[Rgb<u8>]) -> String {
+    if palette.is_empty() { return tr("  no palette (the processor maps colors directly)"); }
+    let step = palette.len().div_ceil(dry_run_color_count());
+    let colors: Vec<String> = palette.iter().step_by(step).map(as_hex).collect();
+    trf("  {} colors: {}", &[palette.len().to_string(), colors.join(", ")])
+}
+
+
+
+/// Processes the given image, or every image of the source directory, with the processor of the command line arguments without entering the terminal interface.
+/// Prints the path (or the JSON result record) of every saved image to stdout and the progress to stderr, and exits with the code of the first failure if any image fails.
+/// Dry runs only build the palettes, printing the paths that would be saved along with the colors of the palettes.
+/// Images that are already saved are skipped, and runs over a source directory are remembered until they finish so they can be resumed.
+pub fn run(arguments: &Arguments) -> Result<()> {
+    let mut app = App::new(arguments);
+
+    // resumed runs take the processor and inputs of the interrupted run instead of the arguments
+    let interrupted_batch = BatchState::load(&app.output_directory);
+    let (selection, resumed_inputs) = match (arguments.resume, &interrupted_batch) {
+        (true, Some(state)) => (state.processor_selection, Some(state.inputs.clone())),
+        (true, None) => {
+            report_error(ExitCodes::Usage, None, &trf("No interrupted batch to resume in {}", &[app.output_directory.to_string_lossy().to_string()]));
+            ExitCodes::Usage.exit();
+        }
+        (false, _) => {
+            let processor_name = arguments.processor.clone().unwrap_or_default();
+            let Some(selection) = Processors::find(&processor_name) else {
+                report_error(ExitCodes::Usage, None, &trf("Unknown processor: {}", &[processor_name]));
+                eprintln!("{}", tr("Available processors:"));
+                for selection in 0..Processors::number_of_processors() {
+                    eprintln!("  {:>2} {}", selection + 1, Processors::get_processor(selection).name());
+                }
+                ExitCodes::Usage.exit();
+            };
+            (selection, None)
+        }
+    };
+    app.current_processor_selection = selection;
+
+    // custom shaders need an adapter even when the CPU is forced, while other processors only need one when it is required and dry runs never do
+    let processor = Processors::get_processor(selection);
+    if !arguments.dry_run && matches!(processor, Processors::Shader(_)) && !is_gpu_available() {
+        report_error(ExitCodes::GpuUnavailable, None, &trf("{} needs a GPU adapter, but none can be set up", &[processor.name()]));
+        ExitCodes::GpuUnavailable.exit();
+    }
+    if !arguments.dry_run && arguments.require_gpu && GpuInformation::collect().backend == ProcessingBackends::Cpu {
+        report_error(ExitCodes::GpuUnavailable, None, &tr("No GPU adapter can be set up or the CPU is forced in the configuration"));
+        ExitCodes::GpuUnavailable.exit();
+    }
+
+    if app.source_image_paths.is_empty() {
+        report_error(ExitCodes::NoImagesFound, None, &trf("No images to process in {}", &[app.source_directory.to_string_lossy().to_string()]));
+        ExitCodes::NoImagesFound.exit();
+    }
+
+    // only runs over a source directory that save images are remembered, where the image an interrupted run of the same processor and inputs stopped at is redone
+    let is_remembered = arguments.image_path.is_none() && !arguments.dry_run;
+    let mut state: Option<BatchState> = None;
+    let mut interrupted_state: Option<&BatchState> = None;
+
+    let mut first_failure = None;
+    for (image_selection, source_image_path) in app.source_image_paths.clone().into_iter().enumerate() {
+        let edit_processor = match &resumed_inputs {
+            Some(inputs) => Ok(processor.create_edit_with_inputs(source_image_path.clone(), inputs)),
+            None => processor.create_filled_edit(source_image_path.clone(), &arguments.colors, &arguments.numbers, &arguments.files, &arguments.names).map_err(|e| e.localized()),
+        };
+
+        if let Ok(edit_processor) = &edit_processor {
+            let state = state.get_or_insert_with(|| {
+                let inputs = edit_processor.get_step_inputs();
+                interrupted_state = interrupted_batch.as_ref().filter(|interrupted_batch| interrupted_batch.is_same_run(selection, &inputs));
+                BatchState { processor_selection: selection, inputs, current_image_path: None, current_output_path: None }
+            });
+            let interrupted_image_path = interrupted_state.and_then(|interrupted_state| interrupted_state.current_image_path.as_ref());
+            if let Some(output_path) = app.find_skipped_output(image_selection, edit_processor.as_ref(), interrupted_image_path) {
+                eprintln!("{}", trf("Skipped {}, already saved as {}", &[source_image_path.to_string_lossy().to_string(), output_path.to_string_lossy().to_string()]));
+                continue;
+            }
+            if let Err(e) = app.remove_interrupted_output(image_selection, interrupted_state) {
+                report_error(ExitCodes::SaveFailed, Some(&source_image_path), &trf("Could not remove the partly written output: {}", &[e.to_string()]));
+                first_failure = first_failure.or(Some(ExitCodes::SaveFailed));
+                continue;
+            }
+            if is_remembered {
+                state.current_image_path = Some(source_image_path.clone());
+                state.current_output_path = Some(app.get_new_output_path(&source_image_path, edit_processor.as_ref()));
+                let _ = state.save(&app.output_directory);
+            }
+        }
+
+        let (record, failure_kind) = match edit_processor {
+            Ok(edit_processor) if arguments.dry_run => (plan(&app, image_selection, selection, edit_processor), ExitCodes::ProcessingFailed),
+            // an image that is processed but has no output could not be saved
+            Ok(edit_processor) => {
+                let record = app.process_headless(image_selection, selection, edit_processor);
+                (record, if app.new_image.is_some() { ExitCodes::SaveFailed } else { ExitCodes::ProcessingFailed })
+            }
+            Err(message) => (ResultRecord::failed(source_image_path.clone(), processor.name(), message), ExitCodes::InvalidInput),
+        };
+        match &record.result {
+            Ok(output_path) => {
+                // stdout only carries the records when they are written there
+                if !matches!(arguments.result_output, Some(ResultOutputs::Stdout)) {
+                    println!("{}", output_path.to_string_lossy());
+                    if let Some(palette) = &record.dry_run_palette { println!("{}", describe_palette(palette)); }
+                }
+                if let Some(status_message) = &app.status_message { eprintln!("{}", status_message); }
+                for adjustment in &record.adjustments { eprintln!("{}", adjustment); }
+            }
+            Err(message) => {
+                report_error(failure_kind, Some(&source_image_path), message);
+                first_failure = first_failure.or(Some(failure_kind));
+            }
+        }
+        if let Some(result_output) = &arguments.result_output && let Err(e) = result_output.write(&record) {
+            report_error(ExitCodes::SaveFailed, Some(&source_image_path), &trf("Could not write the result record: {}", &[e.to_string()]));
+            first_failure = first_failure.or(Some(ExitCodes::SaveFailed));
+        }
+    }
+
+    if is_remembered { let _ = BatchState::clear(&app.output_directory); }
+    if let Some(failure_kind) = first_failure { failure_kind.exit(); }
+    Ok(())
+}
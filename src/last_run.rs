@@ -0,0 +1,75 @@
+use std::fs;
+use std::io::Result;
+use std::path::PathBuf;
+use crate::processor::Processors;
+
+/// The name of the file the last run is remembered in, next to the configuration file.
+pub const LAST_RUN_FILENAME: &str = "oxide.last";
+
+
+
+/// The processor and guide inputs of the last run, used to pre-select and pre-fill the next one.
+pub struct LastRun {
+    /// The index of the processor in the processor list.
+    pub processor_selection: usize,
+    /// The inputs of every guide step.
+    pub inputs: Vec<String>,
+}
+impl LastRun {
+    /// Loads the last run, if one has been remembered.
+    pub fn load() -> Option<LastRun> {
+        LastRun::parse(&fs::read_to_string(path()).ok()?)
+    }
+
+    /// Remembers the run for the next launch.
+    pub fn save(&self) -> Result<()> {
+        fs::write(path(), self.format())
+    }
+
+    /// Reads a run from the contents of a file written by format(), ignoring lines it doesn't know.
+    pub fn parse(contents: &str) -> Option<LastRun> {
+        let mut processor_selection = None;
+        let mut inputs = Vec::new();
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once('=') else { continue; };
+            // only the space after the = is written by format(), the rest belongs to the value
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match key.trim() {
+                // processors are remembered by name so the list can be reordered
+                "processor" => processor_selection = (0..Processors::number_of_processors()).find(|selection| Processors::get_processor(*selection).name() == value.trim()),
+                "input" => inputs.push(value.to_string()),
+                _ => {}
+            }
+        }
+        Some(LastRun { processor_selection: processor_selection?, inputs })
+    }
+
+    /// Writes the run with every input on its own line, so inputs can hold any character but a line break (e.g. commas in paths).
+    pub fn format(&self) -> String {
+        let mut contents = format!("processor = {}\n", Processors::get_processor(self.processor_selection).name());
+        for input in &self.inputs { contents.push_str(&format!("input = {}\n", input)); }
+        contents
+    }
+}
+
+
+
+/// Returns the path of the last run file.
+fn path() -> PathBuf {
+    std::env::current_dir().expect("Could not get local working directory.").join(LAST_RUN_FILENAME)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inputs_with_commas_survive_a_round_trip() {
+        let last_run = LastRun { processor_selection: 0, inputs: vec!["#ff0000".to_string(), "photos/a, b.png".to_string(), " spaced".to_string()] };
+        let parsed = LastRun::parse(&last_run.format()).unwrap();
+        assert_eq!(parsed.processor_selection, 0);
+        assert_eq!(parsed.inputs, last_run.inputs);
+    }
+}
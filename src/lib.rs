@@ -0,0 +1,59 @@
+//! The palettizer behind Oxide, usable without the terminal interface.
+//!
+//! Images are mapped onto palettes built from base colors (or onto a given palette) on the GPU, falling back to the CPU.
+//! ```no_run
+//! let image = image::open("beach.png").unwrap();
+//! let palette = oxide::PaletteSpec::Processor("bichromatic".to_string(), vec!["#ff0000".to_string(), "#00ff00".to_string()], Vec::new());
+//! oxide::process(&image, palette).unwrap().save("beach red-green.png").unwrap();
+//! ```
+
+pub mod processor;
+pub mod config;
+pub mod cache;
+pub mod locale;
+pub mod progress;
+pub mod pipeline;
+pub mod video;
+pub mod ffi;
+
+use std::path::PathBuf;
+use image::{DynamicImage, RgbImage, Rgb};
+use crate::processor::Processors;
+pub use crate::processor::ProcessError;
+use crate::processor::compute::PaletteMapping;
+
+
+
+/// The palettes an image is mapped onto.
+pub enum PaletteSpec {
+    /// One of Oxide's processors by name or number (e.g. "bichromatic" or "volcanic crater"), with the colors (HEX) and numbers filling its steps in order.
+    Processor(String, Vec<String>, Vec<String>),
+    /// Exactly the given colors, without spectrums between them.
+    Colors(Vec<Rgb<u8>>),
+    /// Palettes that are already built, e.g. to reuse them across images.
+    Mapping(PaletteMapping),
+}
+impl PaletteSpec {
+    /// Returns the palettes of the spec for a given image (automatic processors pick their colors from it), or what is wrong with the spec.
+    pub fn get_palette_mapping(self, image: &DynamicImage) -> Result<PaletteMapping, ProcessError> {
+        match self {
+            PaletteSpec::Processor(name, colors, numbers) => {
+                let selection = Processors::find(&name).ok_or(ProcessError::UnknownProcessor(name))?;
+                let processor = Processors::get_processor(selection).create_filled_edit(PathBuf::new(), &colors, &numbers, &[], &[])?;
+                Ok(processor.get_palette_mapping(image))
+            }
+            PaletteSpec::Colors(colors) => Ok(PaletteMapping::Even(colors)),
+            PaletteSpec::Mapping(palette_mapping) => Ok(palette_mapping),
+        }
+    }
+}
+
+
+
+/// Maps every pixel of an image onto the palettes of a given spec, or returns what is wrong with the spec.
+/// Settings are read from the global configuration, which can be loaded from a file with `config::init` or changed with `config::set`.
+/// Errors display in english, `ProcessError::localized` returns them in the configured language.
+pub fn process(image: &DynamicImage, palette: PaletteSpec) -> Result<RgbImage, ProcessError> {
+    let palette_mapping = palette.get_palette_mapping(image)?;
+    Ok(palette_mapping.process(image.clone()))
+}
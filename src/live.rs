@@ -0,0 +1,70 @@
+use std::io::{Error, Read, Result};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use image::{ImageBuffer, Rgb};
+use crate::processor::compute::{Gpu, PaletteMapping};
+
+/// Gets the width live frames are scaled (and letterboxed) to.
+fn live_frame_width() -> u32 { 160 }
+
+/// Gets the height live frames are scaled (and letterboxed) to.
+fn live_frame_height() -> u32 { 90 }
+
+
+
+/// A webcam or video stream decoded by ffmpeg whose frames are palettized as they arrive.
+pub struct LiveStream {
+    /// The ffmpeg process decoding the stream.
+    process: Child,
+    /// The raw rgb frames written by ffmpeg.
+    frames: ChildStdout,
+    /// The gpu set up once and reused for every frame.
+    gpu: Gpu,
+    /// The palettes every frame is mapped onto.
+    palette_mapping: PaletteMapping,
+}
+impl LiveStream {
+    /// Starts decoding a given input (e.g. "/dev/video0" or a stream url), optionally with a given ffmpeg input format (e.g. "v4l2").
+    pub fn start(input: &str, format: Option<&str>, palette_mapping: PaletteMapping) -> Result<LiveStream> {
+        let mut arguments = vec!["-v".to_string(), "error".to_string()];
+        match format {
+            Some(format) => arguments.extend(["-f".to_string(), format.to_string()]),
+            // files are read at their own frame rate instead of as fast as possible
+            None => arguments.push("-re".to_string()),
+        }
+        let (width, height) = (live_frame_width(), live_frame_height());
+        arguments.extend([
+            "-i".to_string(), input.to_string(),
+            "-vf".to_string(), format!("scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2", w = width, h = height),
+            "-f".to_string(), "rawvideo".to_string(),
+            "-pix_fmt".to_string(), "rgb24".to_string(),
+            "-".to_string(),
+        ]);
+
+        let mut process = Command::new("ffmpeg")
+            .args(arguments)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| Error::other(format!("could not run ffmpeg (is ffmpeg installed?): {}", e)))?;
+        let frames = process.stdout.take().ok_or_else(|| Error::other("could not read from ffmpeg"))?;
+
+        Ok(LiveStream { process, frames, gpu: Gpu::new(), palette_mapping })
+    }
+
+    /// Waits for the next frame of the stream and returns it palettized.
+    pub fn next_frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        let (width, height) = (live_frame_width(), live_frame_height());
+        let mut bytes = vec![0u8; (width * height * 3) as usize];
+        self.frames.read_exact(&mut bytes).map_err(|_| Error::other("the stream ended or could not be opened"))?;
+
+        let frame = ImageBuffer::from_raw(width, height, bytes).ok_or_else(|| Error::other("received an incomplete frame"))?;
+        Ok(self.palette_mapping.process_on(&self.gpu, &frame))
+    }
+}
+impl Drop for LiveStream {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
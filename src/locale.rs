@@ -0,0 +1,483 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use crate::config;
+
+/// Marks the language as not read from the configuration yet.
+const UNSET_LANGUAGE: u8 = u8::MAX;
+
+/// The language strings are translated into, as the index of its variant, kept apart from the configuration so translating doesn't read it every time.
+static LANGUAGE: AtomicU8 = AtomicU8::new(UNSET_LANGUAGE);
+
+
+
+/// The languages the user interface can be shown in.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Languages {
+    English,
+    German,
+}
+impl Languages {
+    /// Returns the language with a given name or code (e.g. "de" or "german"), where "auto" follows the system locale.
+    pub fn from_name(name: &str) -> Option<Languages> {
+        match name.to_lowercase().as_str() {
+            "auto" => Some(Languages::from_environment()),
+            "en" | "english" => Some(Languages::English),
+            "de" | "german" | "deutsch" => Some(Languages::German),
+            _ => None,
+        }
+    }
+
+    /// Returns the language of the system locale (LC_ALL, LC_MESSAGES or LANG), falling back to English.
+    pub fn from_environment() -> Languages {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|variable| std::env::var(variable).ok())
+            .find(|locale| !locale.is_empty())
+            .unwrap_or_default();
+        if locale.to_lowercase().starts_with("de") { Languages::German } else { Languages::English }
+    }
+
+    /// Returns the translation of a given english text, if there is one.
+    fn translate(&self, text: &str) -> Option<&'static str> {
+        match self {
+            Languages::English => None,
+            Languages::German => german(text),
+        }
+    }
+}
+
+
+
+/// Changes the language strings are translated into, which the configuration does whenever its language changes.
+pub fn set_language(language: Languages) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// Returns the language strings are translated into, reading it from the configuration the first time.
+fn language() -> Languages {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        UNSET_LANGUAGE => {
+            let language = config::get().language;
+            set_language(language);
+            language
+        }
+        index if index == Languages::German as u8 => Languages::German,
+        _ => Languages::English,
+    }
+}
+
+/// Returns a given english text in the configured language, or the text itself if it has no translation.
+pub fn tr(text: &str) -> String {
+    language().translate(text).unwrap_or(text).to_string()
+}
+
+/// Returns a given english template in the configured language with each {} filled by the next argument.
+/// Arguments are formatted by the caller so translations only have to move whole values around.
+pub fn trf(template: &str, arguments: &[String]) -> String {
+    fill(&tr(template), arguments)
+}
+
+/// Returns a given template with each {} filled by the next argument, without translating it.
+pub fn fill(template: &str, arguments: &[String]) -> String {
+    let mut parts = template.split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for (index, part) in parts.enumerate() {
+        filled += arguments.get(index).map(|argument| argument.as_str()).unwrap_or_default();
+        filled += part;
+    }
+    filled
+}
+
+
+
+/// Returns the german translation of a given english text.
+fn german(text: &str) -> Option<&'static str> {
+    Some(match text {
+        // page names
+        "Launching" => "Start",
+        "Selecting Image Source" => "Bildquelle auswählen",
+        "Analyzing" => "Analyse",
+        "Dominant Colors" => "Dominante Farben",
+        "Selecting Processing Type" => "Verarbeitung auswählen",
+        "Preprocessing" => "Vorbereitung",
+        "Finished" => "Fertig",
+        "Inspecting Spectrum" => "Spektrum untersuchen",
+        "Selecting Morph Target" => "Morph-Ziel auswählen",
+        "Preprocessing Morph Target" => "Morph-Ziel vorbereiten",
+        "Preprocessing Dither" => "Dithering vorbereiten",
+        "Live" => "Live",
+        "Settings" => "Einstellungen",
+        "Batch Processing" => "Stapelverarbeitung",
+        "Choosing Directories" => "Ordner wählen",
+
+        // instructions
+        "next page" => "nächste Seite",
+        "previous page" => "vorherige Seite",
+        "confirm" => "bestätigen",
+        "reset" => "zurücksetzen",
+        "back" => "zurück",
+        "analyze" => "analysieren",
+        "toggle sequence mode" => "Sequenzmodus umschalten",
+        "search" => "suchen",
+        "jump to processor" => "zum Prozessor springen",
+        "two-digit number" => "zweistellige Nummer",
+        "shuffle" => "Zufall",
+        "star/unstar favorite" => "Favorit markieren/entfernen",
+        "dominant colors" => "dominante Farben",
+        "export CSS palette" => "CSS-Palette exportieren",
+        "export SCSS palette" => "SCSS-Palette exportieren",
+        "export GIMP palette" => "GIMP-Palette exportieren",
+        "export JSON palette" => "JSON-Palette exportieren",
+        "export terminal schemes" => "Terminal-Farbschemata exportieren",
+        "export LUT" => "LUT exportieren",
+        "export ANSI/ASCII art" => "ANSI-/ASCII-Art exportieren",
+        "export difference heatmap" => "Abweichungs-Heatmap exportieren",
+        "simulate color vision" => "Farbwahrnehmung simulieren",
+        "export palette cycle GIF" => "Palettenzyklus-GIF exportieren",
+        "live webcam/stream" => "Live-Webcam/-Stream",
+        "morph into another palette" => "in eine andere Palette morphen",
+        "inspect spectrum" => "Spektrum untersuchen",
+        "next blue plane" => "nächste Blauebene",
+        "previous blue plane" => "vorherige Blauebene",
+        "settings" => "Einstellungen",
+        "force CPU on/off" => "CPU erzwingen an/aus",
+        "next GPU adapter" => "nächster GPU-Adapter",
+        "compare dithering" => "Dithering vergleichen",
+        "apply to all images" => "auf alle Bilder anwenden",
+        "resume batch" => "Stapel fortsetzen",
+        "choose folders" => "Ordner wählen",
+        "source/output" => "Quelle/Ausgabe",
+        "run again" => "erneut ausführen",
+        "quit" => "beenden",
+
+        // step labels
+        "Base Color (HEX)" => "Grundfarbe (HEX)",
+        "Base Color 1 (HEX)" => "Grundfarbe 1 (HEX)",
+        "Base Color 2 (HEX)" => "Grundfarbe 2 (HEX)",
+        "Base Color 3 (HEX)" => "Grundfarbe 3 (HEX)",
+        "Base Color 4 (HEX)" => "Grundfarbe 4 (HEX)",
+        "Base Color 5 (HEX)" => "Grundfarbe 5 (HEX)",
+        "Base Color 6 (HEX)" => "Grundfarbe 6 (HEX)",
+        "Base Color 7 (HEX)" => "Grundfarbe 7 (HEX)",
+        "Base Color 8 (HEX)" => "Grundfarbe 8 (HEX)",
+        "Base Color 9 (HEX)" => "Grundfarbe 9 (HEX)",
+        "Base Color 10 (HEX)" => "Grundfarbe 10 (HEX)",
+        "Base Color 11 (HEX)" => "Grundfarbe 11 (HEX)",
+        "Base Color 12 (HEX)" => "Grundfarbe 12 (HEX)",
+        "Number of Colors" => "Anzahl der Farben",
+        "Number of Colors (2-256)" => "Anzahl der Farben (2-256)",
+        "Shadow Color (HEX)" => "Schattenfarbe (HEX)",
+        "Highlight Color (HEX)" => "Lichterfarbe (HEX)",
+        "Threshold (0-1)" => "Schwelle (0-1)",
+        "Balance (0-1)" => "Balance (0-1)",
+        "Intensity (0-1)" => "Stärke (0-1)",
+        "Tint Color (HEX or none)" => "Tönungsfarbe (HEX oder none)",
+        "Light Endpoint (HEX or none)" => "Heller Endpunkt (HEX oder none)",
+        "Dark Endpoint (HEX or none)" => "Dunkler Endpunkt (HEX oder none)",
+        "Palette Processor (name or none)" => "Paletten-Prozessor (Name oder none)",
+        "Palette Processor (name)" => "Paletten-Prozessor (Name)",
+        "Hue Rotation (degrees)" => "Farbtondrehung (Grad)",
+        "Block Size (pixels)" => "Blockgröße (Pixel)",
+        "Palette File (path)" => "Palettendatei (Pfad)",
+        "Ramp (1 Viridis, 2 Magma, 3 Inferno)" => "Verlauf (1 Viridis, 2 Magma, 3 Inferno)",
+        "Film Stock (1 Portra, 2 Ektachrome, 3 Kodachrome, 4 Tri-X)" => "Film (1 Portra, 2 Ektachrome, 3 Kodachrome, 4 Tri-X)",
+        "Seed Color (HEX)" => "Ausgangsfarbe (HEX)",
+        "Harmony (1 Complementary, 2 Triadic, 3 Analogous, 4 Split-Complementary)" => "Harmonie (1 Komplementär, 2 Triadisch, 3 Analog, 4 Geteilt komplementär)",
+        "Key Color (HEX)" => "Schlüsselfarbe (HEX)",
+        "Tolerance (0-1)" => "Toleranz (0-1)",
+        "Fill Color (HEX)" => "Füllfarbe (HEX)",
+        "Press Enter" => "Enter drücken",
+
+        // processor descriptions
+        "One color of your choice from white to black" => "Eine Farbe deiner Wahl von Weiß bis Schwarz",
+        "One color of your choice between light and dark endpoints of your choice" => "Eine Farbe deiner Wahl zwischen hellem und dunklem Endpunkt deiner Wahl",
+        "One color of your choice plus the accent color of the image" => "Eine Farbe deiner Wahl und die Akzentfarbe des Bildes",
+        "The average color of the image from white to black" => "Die Durchschnittsfarbe des Bildes von Weiß bis Schwarz",
+        "The average and accent colors of the image" => "Die Durchschnitts- und Akzentfarbe des Bildes",
+        "Grayscale by brightness with an optional tint of your choice" => "Graustufen nach Helligkeit mit einer optionalen Tönung deiner Wahl",
+        "Two colors of your choice blended together" => "Zwei Farben deiner Wahl ineinander übergeblendet",
+        "Two colors of your choice plus the accent color of the image" => "Zwei Farben deiner Wahl und die Akzentfarbe des Bildes",
+        "The two dominant color families of the image blended together" => "Die zwei vorherrschenden Farbfamilien des Bildes ineinander übergeblendet",
+        "One color of your choice for the shadows and one for the highlights" => "Eine Farbe deiner Wahl für die Schatten und eine für die Lichter",
+        "Grayscale toned with two colors of your choice, blended around a balance" => "Graustufen mit zwei Farben deiner Wahl getönt, um eine Balance übergeblendet",
+        "Three colors of your choice and everything between them" => "Drei Farben deiner Wahl und alles dazwischen",
+        "Any number of colors of your choice woven together" => "Beliebig viele Farben deiner Wahl miteinander verwoben",
+        "A color of your choice and its companions in a color harmony" => "Eine Farbe deiner Wahl und ihre Begleiter in einer Farbharmonie",
+        "Dark slate, deep reds and ash" => "Dunkler Schiefer, tiefe Rottöne und Asche",
+        "Desert browns, ochres and rust" => "Wüstenbraun, Ocker und Rost",
+        "Savanna olives, earthy browns and sunset oranges" => "Savannenoliv, erdige Brauntöne und Abendrot",
+        "Cold slate, steel blues and ice" => "Kalter Schiefer, Stahlblau und Eis",
+        "Mossy greens, glacier blues and dark rock" => "Moosgrün, Gletscherblau und dunkles Gestein",
+        "Deep forest greens and bark browns" => "Tiefes Waldgrün und Rindenbraun",
+        "Golden yellows and harvest browns" => "Goldgelb und Erntebraun",
+        "Dense greens with tropical highlights" => "Dichtes Grün mit tropischen Akzenten",
+        "Turquoise seas, green hills and white stone" => "Türkisfarbene See, grüne Hügel und weißer Stein",
+        "Warm browns of an old photograph, as strong as you like" => "Warme Brauntöne eines alten Fotos, so stark wie du willst",
+        "The colors and contrast of a classic film stock" => "Die Farben und der Kontrast eines klassischen Films",
+        "The brightness of the image on a perceptual heat ramp" => "Die Helligkeit des Bildes auf einem wahrnehmungsgleichen Wärmeverlauf",
+        "The accent color of the image in color and everything else in grayscale" => "Die Akzentfarbe des Bildes in Farbe und alles andere in Graustufen",
+        "A key color replaced by a fill color, optionally with the palette of another processor" => "Eine Schlüsselfarbe durch eine Füllfarbe ersetzt, optional mit der Palette eines anderen Prozessors",
+        "The hues of another processor's palette at the brightness of the image" => "Die Farbtöne der Palette eines anderen Prozessors in der Helligkeit des Bildes",
+        "The negative of the image, optionally with the palette of another processor" => "Das Negativ des Bildes, optional mit der Palette eines anderen Prozessors",
+        "The image with its hues rotated, optionally with the palette of another processor" => "Das Bild mit gedrehten Farbtönen, optional mit der Palette eines anderen Prozessors",
+        "Pixel art from blocks of the image, optionally with the palette of another processor" => "Pixel-Art aus Blöcken des Bildes, optional mit der Palette eines anderen Prozessors",
+        "Vivid tropical greens, teals and bright accents" => "Kräftiges Tropengrün, Petrol und leuchtende Akzente",
+        "The colors of a palette file of your choice" => "Die Farben einer Palettendatei deiner Wahl",
+        "The best colors of the image itself, as few as you like" => "Die besten Farben des Bildes selbst, so wenige wie du willst",
+
+        "Custom shader" => "Eigener Shader",
+        "Invalid shader: {}" => "Ungültiger Shader: {}",
+        "Invalid plugin: {}" => "Ungültiges Plugin: {}",
+        "Invalid script: {}" => "Ungültiges Skript: {}",
+        "Invalid palette: {}" => "Ungültige Palette: {}",
+        "Custom palette" => "Eigene Palette",
+        "{} by {}" => "{} von {}",
+
+        // pages
+        "Processor: {}" => "Prozessor: {}",
+        "Processed {} of {} images" => "{} von {} Bildern verarbeitet",
+        "waiting" => "wartet",
+        "processing..." => "wird verarbeitet...",
+        "saved as {}" => "gespeichert als {}",
+        "failed: {}" => "fehlgeschlagen: {}",
+        "skipped, already saved as {}" => "übersprungen, bereits gespeichert als {}",
+        "Interrupted batch: {} (press B to resume)" => "Unterbrochener Stapel: {} (B zum Fortsetzen)",
+        "Source" => "Quelle",
+        "Output" => "Ausgabe",
+        "Source images are read from the source directory and edited images are saved to the output directory, which is created if it does not exist." => "Quellbilder werden aus dem Quellordner gelesen und bearbeitete Bilder im Ausgabeordner gespeichert, der bei Bedarf angelegt wird.",
+        "Announcement: {}" => "Meldung: {}",
+        "Now on page: {}" => "Jetzt auf Seite: {}",
+        "Page: {}" => "Seite: {}",
+        "Keys:" => "Tasten:",
+        "The preview is hidden in plain output" => "Die Vorschau ist in der Textausgabe ausgeblendet",
+        "The spectrum grid is hidden in plain output" => "Das Spektrumraster ist in der Textausgabe ausgeblendet",
+        "Source: {}  Output: {}" => "Quelle: {}  Ausgabe: {}",
+        "Press any button to continue..." => "Beliebige Taste drücken, um fortzufahren...",
+        "Found {} images" => "{} Bilder gefunden",
+        "In: {}" => "In: {}",
+        "Selected image: {}" => "Ausgewähltes Bild: {}",
+        "Error: No images to edit" => "Fehler: Keine Bilder zum Bearbeiten",
+        "Sequence mode: off" => "Sequenzmodus: aus",
+        "Sequence mode: on (the selected image is not part of a numbered sequence)" => "Sequenzmodus: an (das ausgewählte Bild gehört zu keiner nummerierten Sequenz)",
+        "Sequence mode: on ({} frames share one palette)" => "Sequenzmodus: an ({} Einzelbilder teilen sich eine Palette)",
+        "Image: {}" => "Bild: {}",
+        "Average color" => "Durchschnittsfarbe",
+        "Accent color" => "Akzentfarbe",
+        "Luminance distribution:" => "Helligkeitsverteilung:",
+        "Average saturation: {}%" => "Durchschnittliche Sättigung: {}%",
+        "Max saturation: {}%" => "Maximale Sättigung: {}%",
+        "Strongly saturated pixels: {}%" => "Stark gesättigte Pixel: {}%",
+        "Selected processor: {}" => "Ausgewählter Prozessor: {}",
+        "Search: {}_" => "Suche: {}_",
+        "Processor number: {}_" => "Prozessornummer: {}_",
+        "No processor matches the search" => "Kein Prozessor passt zur Suche",
+        "Favorites" => "Favoriten",
+        "Favorite" => "Favorit",
+        "Not a favorite" => "Kein Favorit",
+        "All Processors" => "Alle Prozessoren",
+        "Morph into: {}" => "Morphen zu: {}",
+        "Step: {}" => "Schritt: {}",
+        "Color: #{}" => "Farbe: #{}",
+        "Input: {}" => "Eingabe: {}",
+        "Error: No processor" => "Fehler: Kein Prozessor",
+        "Saved" => "Gespeichert",
+        "Processed in {} seconds" => "In {} Sekunden verarbeitet",
+        "Frames: {} decoded, {} palettized, {} encoded" => "Einzelbilder: {} dekodiert, {} palettiert, {} kodiert",
+        "Difference from source: mean Delta E {}, max Delta E {}" => "Abweichung von der Quelle: mittleres Delta E {}, maximales Delta E {}",
+        "Tones (source -> output):" => "Tonwerte (Quelle -> Ausgabe):",
+        "Tones:" => "Tonwerte:",
+        "{} colors used, top colors:" => "{} Farben verwendet, häufigste Farben:",
+        "Waiting for the first frame..." => "Warte auf das erste Bild...",
+        "Spectrum: {} colors" => "Spektrum: {} Farben",
+        "Blue {}-{} (slice {}/{}): {} colors" => "Blau {}-{} (Ebene {}/{}): {} Farben",
+        "Red increases to the right, green increases upwards" => "Rot nimmt nach rechts zu, Grün nach oben",
+        "Preview: {}" => "Vorschau: {}",
+        "Normal vision" => "Normale Farbwahrnehmung",
+        "Protanopia" => "Protanopie",
+        "Deuteranopia" => "Deuteranopie",
+        "Tritanopia" => "Tritanopie",
+        "Average luminance" => "Durchschnittliche Helligkeit",
+        "Black clipping" => "Schwarz-Clipping",
+        "White clipping" => "Weiß-Clipping",
+        "Dynamic range" => "Dynamikumfang",
+        "Processing backend: {}" => "Verarbeitung: {}",
+        "Force CPU: {}" => "CPU erzwingen: {}",
+        "Quality: {}" => "Qualität: {}",
+        "Custom" => "Benutzerdefiniert",
+        "Fast" => "Schnell",
+        "Balanced" => "Ausgewogen",
+        "Accurate" => "Genau",
+        "on" => "an",
+        "off" => "aus",
+        "Adapter: {}" => "Adapter: {}",
+        "Type: {}" => "Typ: {}",
+        "Discrete GPU" => "Dedizierte GPU",
+        "Integrated GPU" => "Integrierte GPU",
+        "Virtual GPU" => "Virtuelle GPU",
+        "Software renderer" => "Software-Renderer",
+        "Other" => "Andere",
+        "Graphics API: {}" => "Grafik-API: {}",
+        "Driver: {}" => "Treiber: {}",
+        "Adapter: none (images are processed on the CPU)" => "Adapter: keiner (Bilder werden auf der CPU verarbeitet)",
+        "Limits:" => "Grenzen:",
+        "  Max buffer size: {} MB" => "  Max. Puffergröße: {} MB",
+        "  Max storage buffer binding: {} MB" => "  Max. Speicherpuffer-Bindung: {} MB",
+        "  Max workgroups per dimension: {}" => "  Max. Arbeitsgruppen pro Dimension: {}",
+        "  Max invocations per workgroup: {}" => "  Max. Aufrufe pro Arbeitsgruppe: {}",
+        "Available adapters:" => "Verfügbare Adapter:",
+
+        // progress
+        "Processing..." => "Verarbeitung...",
+        "Progress: {}%" => "Fortschritt: {}%",
+        "Frame {}/{}" => "Bild {}/{}",
+        "Frame {}" => "Bild {}",
+        "Phase: {}" => "Phase: {}",
+        "Decoding" => "Dekodieren",
+        "Palettizing" => "Palettieren",
+        "Encoding" => "Kodieren",
+        "Done" => "Fertig",
+        "Decoded: {}  Palettized: {}  Encoded: {}" => "Dekodiert: {}  Palettiert: {}  Kodiert: {}",
+        "Loading colors..." => "Farben werden geladen...",
+        "Tile {}/{}" => "Kachel {}/{}",
+        "Mapping pixels onto {} colors..." => "Pixel werden auf {} Farben abgebildet...",
+        "Saved to {}" => "Gespeichert unter {}",
+        "Analyzing..." => "Analysiere...",
+        "Finding dominant colors..." => "Suche dominante Farben...",
+        "Exporting LUT..." => "Exportiere LUT...",
+        "Exporting palette cycle..." => "Exportiere Palettenzyklus...",
+        "Starting live mode..." => "Starte Live-Modus...",
+        "Building spectrum..." => "Erzeuge Spektrum...",
+        "Morphing palettes..." => "Morphe Paletten...",
+        "Looking for GPU adapters..." => "Suche GPU-Adapter...",
+        "Dithering..." => "Dithering...",
+
+        // status messages
+        "Not a directory: {}" => "Kein Ordner: {}",
+        "Could not create {}: {}" => "{} konnte nicht angelegt werden: {}",
+        "Could not open source video: {}" => "Quellvideo konnte nicht geöffnet werden: {}",
+        "Could not process video: {}" => "Video konnte nicht verarbeitet werden: {}",
+        "Could not open source image: {}" => "Quellbild konnte nicht geöffnet werden: {}",
+        "Could not process gif: {}" => "GIF konnte nicht verarbeitet werden: {}",
+        "Saved {} blended variants" => "{} überblendete Varianten gespeichert",
+        "Could not save blended variants: {}" => "Überblendete Varianten konnten nicht gespeichert werden: {}",
+        "Could not open image sequence: {}" => "Bildsequenz konnte nicht geöffnet werden: {}",
+        "Could not process image sequence: {}" => "Bildsequenz konnte nicht verarbeitet werden: {}",
+        "Exported palette to {} ({})" => "Palette nach {} exportiert ({})",
+        "Could not export palette: {}" => "Palette konnte nicht exportiert werden: {}",
+        "Exported terminal color schemes to {} ({})" => "Terminal-Farbschemata nach {} exportiert ({})",
+        "Could not export terminal color schemes: {}" => "Terminal-Farbschemata konnten nicht exportiert werden: {}",
+        "{} of {} color pairs below WCAG AA contrast" => "{} von {} Farbpaaren unter WCAG-AA-Kontrast",
+        "Exported LUT to {}" => "LUT nach {} exportiert",
+        "Could not export LUT: {}" => "LUT konnte nicht exportiert werden: {}",
+        "Exported ANSI art to {} and ASCII art to {}" => "ANSI-Art nach {} und ASCII-Art nach {} exportiert",
+        "Could not export text art: {}" => "Text-Art konnte nicht exportiert werden: {}",
+        "Could not start live mode: {}" => "Live-Modus konnte nicht gestartet werden: {}",
+        "Live mode stopped: {}" => "Live-Modus beendet: {}",
+        "Exported morph animation to {} and {}" => "Morph-Animation nach {} und {} exportiert",
+        "Exported morph animation to {} (no WebP: {})" => "Morph-Animation nach {} exportiert (kein WebP: {})",
+        "Could not export morph animation: {}" => "Morph-Animation konnte nicht exportiert werden: {}",
+        "Palette cycling needs {} colors or fewer, the image uses {}" => "Palettenzyklen brauchen höchstens {} Farben, das Bild verwendet {}",
+        "Exported palette cycle to {}" => "Palettenzyklus nach {} exportiert",
+        "Could not export palette cycle: {}" => "Palettenzyklus konnte nicht exportiert werden: {}",
+        "Exported difference heatmap to {}" => "Abweichungs-Heatmap nach {} exportiert",
+        "Saved difference heatmap to {}" => "Abweichungs-Heatmap unter {} gespeichert",
+        "Could not export heatmap: {}" => "Heatmap konnte nicht exportiert werden: {}",
+        "Could not export heatmap: the source and new images differ in size" => "Heatmap konnte nicht exportiert werden: Quell- und neues Bild sind unterschiedlich groß",
+        "Saved {} = {} to {}" => "{} = {} in {} gespeichert",
+        "Could not save {}: {}" => "{} konnte nicht gespeichert werden: {}",
+        "No GPU adapters found" => "Keine GPU-Adapter gefunden",
+        "Exported dither comparison to {} (from left to right: {})" => "Dithering-Vergleich nach {} exportiert (von links nach rechts: {})",
+        "max buffer size of the GPU" => "der maximalen Puffergröße der GPU",
+        "VRAM budget of {} MB" => "dem VRAM-Budget von {} MB",
+        "memory budget of {} MB" => "dem Speicherbudget von {} MB",
+        "Palettized in {} tiles of {} rows to stay within the {}" => "In {} Kacheln zu {} Zeilen palettiert, um unter {} zu bleiben",
+        "Capped palettes at {} colors instead of {} to stay within the memory budget" => "Paletten auf {} statt {} Farben begrenzt, um innerhalb des Speicherbudgets zu bleiben",
+        "Could not export dither comparison: {}" => "Dithering-Vergleich konnte nicht exportiert werden: {}",
+        "Could not export dither comparison: custom shaders and plugins have no palette to dither with" => "Dithering-Vergleich konnte nicht exportiert werden: eigene Shader und Plugins haben keine Palette zum Dithern",
+        "None" => "Keins",
+        "Blue Noise" => "Blaues Rauschen",
+        "Spatial" => "Räumlich",
+        "Quality (fast, balanced, accurate or custom)" => "Qualität (fast, balanced, accurate oder custom)",
+        "Dithering (none, bayer, floyd-steinberg, atkinson, blue-noise or spatial)" => "Dithering (none, bayer, floyd-steinberg, atkinson, blue-noise oder spatial)",
+        "Dither Strength (0-100%)" => "Dithering-Stärke (0-100 %)",
+
+        // command line
+        "Usage:" => "Verwendung:",
+        "Without arguments, images are read from ./source and saved to ./output." => "Ohne Argumente werden Bilder aus ./source gelesen und in ./output gespeichert.",
+        "A single image is saved next to itself unless an output directory is given." => "Ein einzelnes Bild wird neben sich selbst gespeichert, sofern kein Ausgabeverzeichnis angegeben ist.",
+        "--plain shows every page as linear text without borders or graphics, for screen readers." => "--plain zeigt jede Seite als fortlaufenden Text ohne Rahmen oder Grafiken, für Screenreader.",
+        "--source needs a directory" => "--source braucht ein Verzeichnis",
+        "--output needs a directory" => "--output braucht ein Verzeichnis",
+        "Unknown option: {}" => "Unbekannte Option: {}",
+        "Only one image can be edited at a time: {}" => "Es kann nur ein Bild gleichzeitig bearbeitet werden: {}",
+        "The source directory is given more than once: {}" => "Das Quellverzeichnis wurde mehrfach angegeben: {}",
+        "An image and a source directory cannot be edited at the same time" => "Ein Bild und ein Quellverzeichnis können nicht gleichzeitig bearbeitet werden",
+        "Cache: {}" => "Cache: {}",
+        "  {}: {} files, {} MB" => "  {}: {} Dateien, {} MB",
+        "Total: {} MB of {} MB" => "Gesamt: {} MB von {} MB",
+        "Removed {} cached files" => "{} zwischengespeicherte Dateien entfernt",
+        "Usage: oxide cache <stats|clear>" => "Verwendung: oxide cache <stats|clear>",
+        "--processor processes the image (or every source image) without the interface, e.g. for scripts." => "--processor verarbeitet das Bild (oder jedes Quellbild) ohne die Oberfläche, z. B. für Skripte.",
+        "--image needs a file" => "--image braucht eine Datei",
+        "--processor needs a processor name or number" => "--processor braucht einen Prozessornamen oder eine Nummer",
+        "--colors needs a comma separated list of HEX colors" => "--colors braucht eine kommagetrennte Liste von HEX-Farben",
+        "--numbers needs a comma separated list of numbers" => "--numbers braucht eine kommagetrennte Liste von Zahlen",
+        "--files needs a comma separated list of files" => "--files braucht eine kommagetrennte Liste von Dateien",
+        "--names needs a comma separated list of names" => "--names braucht eine kommagetrennte Liste von Namen",
+        "--colors, --numbers, --files and --names need a --processor" => "--colors, --numbers, --files und --names brauchen einen --processor",
+        "Unknown processor: {}" => "Unbekannter Prozessor: {}",
+        "Available processors:" => "Verfügbare Prozessoren:",
+        "No images to process in {}" => "Keine Bilder zum Verarbeiten in {}",
+        "Missing color for {}" => "Fehlende Farbe für {}",
+        "Missing number for {}" => "Fehlende Zahl für {}",
+        "Missing file for {}" => "Fehlende Datei für {}",
+        "Missing name for {}" => "Fehlender Name für {}",
+        "Invalid input for {}: {}" => "Ungültige Eingabe für {}: {}",
+        "{} takes fewer inputs ({} colors and {} numbers left over)" => "{} braucht weniger Eingaben ({} Farben und {} Zahlen übrig)",
+        "{} takes fewer files ({} left over)" => "{} braucht weniger Dateien ({} übrig)",
+        "{} takes fewer names ({} left over)" => "{} braucht weniger Namen ({} übrig)",
+        "{} holds no colors" => "{} enthält keine Farben",
+        "Could not process {}: {}" => "{} konnte nicht verarbeitet werden: {}",
+        "Could not save the processed image" => "Das verarbeitete Bild konnte nicht gespeichert werden",
+        "--json-file needs a file" => "--json-file braucht eine Datei",
+        "oxide tui does not take a --processor or --resume, use oxide process" => "oxide tui nimmt weder --processor noch --resume, stattdessen oxide process verwenden",
+        "oxide process needs a --processor or --resume" => "oxide process braucht einen --processor oder --resume",
+        "--resume continues with the processor of the interrupted batch and cannot take a --processor" => "--resume setzt mit dem Prozessor des unterbrochenen Stapels fort und nimmt keinen --processor",
+        "Commands are only read from the first argument, a leading -- reads it as a source directory instead." => "Befehle werden nur aus dem ersten Argument gelesen, ein vorangestelltes -- liest es stattdessen als Quellordner.",
+        "--resume continues the interrupted batch run of the output directory, skipping the images it already saved." => "--resume setzt die unterbrochene Stapelverarbeitung des Ausgabeordners fort und überspringt bereits gespeicherte Bilder.",
+        "No interrupted batch to resume in {}" => "Kein unterbrochener Stapel zum Fortsetzen in {}",
+        "Skipped {}, already saved as {}" => "{} übersprungen, bereits gespeichert als {}",
+        "--dry-run needs a --processor" => "--dry-run braucht einen --processor",
+        "--dry-run only builds the palettes and lists the files that would be saved with their palette colors." => "--dry-run erstellt nur die Paletten und listet die Dateien auf, die mit ihren Palettenfarben gespeichert würden.",
+        "  no palette (the processor maps colors directly)" => "  keine Palette (der Prozessor bildet Farben direkt ab)",
+        "  {} colors: {}" => "  {} Farben: {}",
+        "--require-gpu fails instead of falling back to the CPU when no GPU adapter can be set up." => "--require-gpu bricht ab, statt auf die CPU auszuweichen, wenn kein GPU-Adapter eingerichtet werden kann.",
+        "{} needs a GPU adapter, but none can be set up" => "{} braucht einen GPU-Adapter, aber keiner kann eingerichtet werden",
+        "No GPU adapter can be set up or the CPU is forced in the configuration" => "Kein GPU-Adapter kann eingerichtet werden oder die CPU ist in der Konfiguration erzwungen",
+        "bench times building the palettes and mapping the image for every processor (on a generated image unless one is given)." => "bench misst für jeden Prozessor das Erstellen der Paletten und das Abbilden des Bildes (auf einem generierten Bild, sofern keines angegeben ist).",
+        "Usage: oxide bench [--image <image>] [--processor <name|number>] [--runs <n>]" => "Verwendung: oxide bench [--image <Bild>] [--processor <Name|Nummer>] [--runs <n>]",
+        "Backend: {}" => "Backend: {}",
+        "Image: {}x{}, {} runs per processor" => "Bild: {}x{}, {} Durchläufe pro Prozessor",
+        "Processor" => "Prozessor",
+        "Palette ms" => "Palette ms",
+        "Mapping ms" => "Abbildung ms",
+        "MP/s" => "MP/s",
+        "--json needs a --processor, use --json-file for batch runs in the interface" => "--json braucht einen --processor, für Stapelverarbeitungen in der Oberfläche --json-file verwenden",
+        "--json prints a JSON record of every processed image instead of its path, --json-file appends the records to a file." => "--json gibt statt des Pfads einen JSON-Eintrag für jedes verarbeitete Bild aus, --json-file hängt die Einträge an eine Datei an.",
+        "Could not write the result record: {}" => "Der Ergebniseintrag konnte nicht geschrieben werden: {}",
+        "Palettes: {}" => "Paletten: {}",
+        "  No synced collections" => "  Keine synchronisierten Sammlungen",
+        "  {}: {} files from {}" => "  {}: {} Dateien von {}",
+        "No synced collections to update" => "Keine synchronisierten Sammlungen zum Aktualisieren",
+        "Synced {}: {} files" => "{} synchronisiert: {} Dateien",
+        "Could not sync {}: {}" => "{} konnte nicht synchronisiert werden: {}",
+        "{} already exists and is not a git repository" => "{} existiert bereits und ist kein Git-Repository",
+        "Could not remove the partly written output: {}" => "Die teilweise geschriebene Ausgabe konnte nicht entfernt werden: {}",
+        "{} already exists and was not synced from a source" => "{} existiert bereits und wurde nicht aus einer Quelle synchronisiert",
+        "{} can't be used as a collection name" => "{} kann nicht als Name einer Sammlung verwendet werden",
+        "Usage: oxide palettes <sync <url>... [--name <name>]|list|update [<name>]|fetch <slug>>" => "Verwendung: oxide palettes <sync <url>... [--name <name>]|list|update [<name>]|fetch <slug>>",
+        "{} is not a Lospec palette slug" => "{} ist kein Lospec-Paletten-Slug",
+        "Fetched {}: {} colors saved to {}" => "{} abgerufen: {} Farben unter {} gespeichert",
+        "Could not fetch {}: {}" => "{} konnte nicht abgerufen werden: {}",
+
+        _ => return None,
+    })
+}
@@ -1,16 +1,83 @@
-mod processor;
 mod app;
 mod ui;
+mod export;
+mod animation;
+mod sequence;
+mod live;
+mod cli;
+mod last_run;
+mod favorites;
+mod shuffle;
+mod palettes;
+mod headless;
+mod batch;
+mod results;
+mod bench;
 
-use std::io::Result;
+use oxide::{cache, config, locale, pipeline, processor, progress, video};
+use std::io::{ErrorKind, Result};
 use std::io;
+use std::path::PathBuf;
 use ratatui::crossterm::execute;
 use ratatui::crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::prelude::*;
 use ratatui::Terminal;
 use crate::app::App;
+use crate::cli::{Arguments, Commands};
+use crate::headless::{exit_with_usage, report_error, ExitCodes};
+
+/// Fills the directories the command line leaves out from the `OXIDE_SOURCE_DIR` and `OXIDE_OUTPUT_DIR` environment variables,
+/// so deployments without a configuration file can set them once for every run.
+fn apply_environment(arguments: &mut Arguments) {
+    if arguments.source_directory.is_none() && let Ok(directory) = std::env::var("OXIDE_SOURCE_DIR") && !directory.is_empty() {
+        arguments.source_directory = Some(PathBuf::from(directory));
+    }
+    if arguments.output_directory.is_none() && let Ok(directory) = std::env::var("OXIDE_OUTPUT_DIR") && !directory.is_empty() {
+        arguments.output_directory = Some(PathBuf::from(directory));
+    }
+}
+
+
 
 fn main() -> Result<()> {
+    // config setup, where the environment overrides the configuration file
+    config::init(&config::default_path());
+    if let Ok(force_cpu) = std::env::var("OXIDE_FORCE_CPU") { config::set("force_cpu", &force_cpu); }
+
+    // running command line commands instead of the app
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let command = match Commands::parse(&arguments) {
+        Ok(command) => command,
+        Err(message) => {
+            report_error(ExitCodes::Usage, None, &message);
+            eprintln!("\n{}", cli::usage());
+            ExitCodes::Usage.exit();
+        }
+    };
+    let mut arguments = match command {
+        Commands::Tui(arguments) => arguments,
+        Commands::Process(mut arguments) => {
+            apply_environment(&mut arguments);
+            return headless::run(&arguments);
+        }
+        Commands::Palettes(arguments) => return palettes::run_command(&arguments).or_else(|e| match e.kind() {
+            ErrorKind::InvalidInput => exit_with_usage(&e.to_string()),
+            _ => Err(e),
+        }),
+        Commands::Cache(arguments) => return cache::run_command(&arguments).or_else(|e| match e.kind() {
+            ErrorKind::InvalidInput => exit_with_usage(&e.to_string()),
+            _ => Err(e),
+        }),
+        Commands::Bench(arguments) => return bench::run_command(&arguments),
+        Commands::Help => {
+            println!("{}", cli::usage());
+            return Ok(());
+        }
+    };
+
+    apply_environment(&mut arguments);
+    if arguments.plain_output { config::set("plain_output", "true"); }
+
     // terminal setup
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -19,7 +86,7 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // app setup
-    let mut app = App::new();
+    let mut app = App::new(&arguments);
 
     // running
     let result = app.run(&mut terminal);
@@ -0,0 +1,269 @@
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::processor::palette_file::{load_palette_definition, PaletteFile, PaletteFileColor};
+use crate::processor::user_palette::palette_directory;
+use crate::locale::{tr, trf};
+
+/// The name of the file in the palette directory the sources of synced collections are remembered in.
+pub const SOURCES_FILENAME: &str = "sources.conf";
+
+/// The name of the folder in the palette directory palettes fetched from Lospec are saved in, which lists them under a "lospec" category.
+pub const LOSPEC_DIRECTORY_NAME: &str = "lospec";
+
+/// Returns the URL of a palette of the Lospec palette list by its slug (the last part of its page URL, e.g. "pico-8").
+fn lospec_url(slug: &str) -> String { format!("https://lospec.com/palette-list/{}.json", slug) }
+
+
+
+/// A palette collection synced from a git repository or a set of raw file URLs.
+pub struct PaletteSource {
+    /// The name of the collection, which is also the name of its folder in the palette directory.
+    pub name: String,
+    /// The git repository or the raw palette file URLs the collection is synced from.
+    pub urls: Vec<String>,
+}
+impl PaletteSource {
+    /// Returns the folder the collection is synced into.
+    pub fn directory(&self) -> PathBuf {
+        palette_directory().join(&self.name)
+    }
+
+    /// Returns if the collection is synced from a git repository instead of raw file URLs.
+    pub fn is_git(&self) -> bool {
+        match self.urls.as_slice() {
+            [url] => is_git_url(url),
+            _ => false,
+        }
+    }
+}
+
+
+
+/// Loads the sources of every synced collection.
+pub fn load_sources() -> Vec<PaletteSource> {
+    let Ok(contents) = fs::read_to_string(palette_directory().join(SOURCES_FILENAME)) else { return Vec::new(); };
+    // each line is "name = url url ..."
+    contents.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, urls)| PaletteSource { name: name.trim().to_string(), urls: urls.split_whitespace().map(|url| url.to_string()).collect() })
+        .filter(|source| !source.name.is_empty() && !source.urls.is_empty())
+        .collect()
+}
+
+/// Remembers the sources of every synced collection.
+fn save_sources(sources: &[PaletteSource]) -> Result<()> {
+    let lines: Vec<String> = sources.iter().map(|source| format!("{} = {}", source.name, source.urls.join(" "))).collect();
+    fs::write(palette_directory().join(SOURCES_FILENAME), lines.join("\n") + "\n")
+}
+
+/// Derives a collection name from a URL (the repository or file name without its extension).
+fn name_from_url(url: &str) -> String {
+    let last_segment = url.trim_end_matches('/').rsplit(['/', ':']).next().unwrap_or_default();
+    let name = sanitize_name(&Path::new(last_segment).file_stem().unwrap_or_default().to_string_lossy());
+    if name.is_empty() { "palettes".to_string() } else { name }
+}
+
+/// Keeps a collection name usable as a folder name by replacing everything but letters, digits, '-' and '_'.
+fn sanitize_name(name: &str) -> String {
+    name.trim().chars().map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+/// Returns an error if a collection name can't be used as its folder in the palette directory,
+/// because it would leave the palette directory, is hidden or belongs to the Lospec folder or the sources file.
+fn check_name(name: &str) -> Result<()> {
+    let is_reserved = [LOSPEC_DIRECTORY_NAME, SOURCES_FILENAME].iter().any(|reserved| name.eq_ignore_ascii_case(reserved));
+    if name.is_empty() || name == "." || name == ".." || name.starts_with('.') || is_reserved || sanitize_name(name) != name {
+        return Err(Error::other(trf("{} can't be used as a collection name", &[name.to_string()])));
+    }
+    Ok(())
+}
+
+/// Returns if a given URL points to a git repository rather than a raw file.
+fn is_git_url(url: &str) -> bool {
+    if url.ends_with(".git") || url.starts_with("git@") || url.starts_with("git://") || url.starts_with("ssh://") { return true; }
+    // asks git without prompting for credentials, raw file URLs simply fail
+    Command::new("git").args(["ls-remote", "--exit-code", "--", url]).env("GIT_TERMINAL_PROMPT", "0").output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Runs git or curl with the given arguments.
+fn run(program: &str, arguments: &[&str]) -> Result<()> {
+    let output = Command::new(program).args(arguments).env("GIT_TERMINAL_PROMPT", "0").output()
+        .map_err(|e| Error::other(format!("could not run {} (is it installed?): {}", program, e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::other(format!("{} failed: {}", program, stderr.lines().last().unwrap_or_default())));
+    }
+    Ok(())
+}
+
+/// Returns how many palette files a collection folder holds, ignoring hidden files (e.g. the .git folder) and linked folders.
+fn count_files(directory: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(directory) else { return 0; };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| {
+            let path = entry.path();
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => count_files(&path),
+                // linked folders are skipped like when palettes are loaded, since a link back to a folder above it would be counted forever
+                Ok(file_type) if file_type.is_symlink() && path.is_dir() => 0,
+                _ => 1,
+            }
+        })
+        .sum()
+}
+
+
+
+/// Pulls a collection into its folder and returns how many palette files it holds.
+/// Git repositories are cloned once and pulled afterwards, raw file URLs are downloaded again and replace the previous files.
+pub fn sync(source: &PaletteSource) -> Result<usize> {
+    check_name(&source.name)?;
+    fs::create_dir_all(palette_directory())?;
+    let directory = source.directory();
+
+    if source.is_git() {
+        let url = &source.urls[0];
+        if directory.join(".git").is_dir() {
+            run("git", &["-C", &directory.to_string_lossy(), "pull", "--ff-only", "--quiet"])?;
+        } else {
+            if directory.exists() { return Err(Error::other(trf("{} already exists and is not a git repository", &[directory.to_string_lossy().to_string()]))); }
+            run("git", &["clone", "--depth", "1", "--quiet", "--", url, &directory.to_string_lossy()])?;
+        }
+    } else {
+        // only folders of remembered collections are replaced, anything else in the palette directory is left alone
+        if directory.exists() && !load_sources().iter().any(|remembered| remembered.name == source.name) {
+            return Err(Error::other(trf("{} already exists and was not synced from a source", &[directory.to_string_lossy().to_string()])));
+        }
+        // downloads into a staging folder first, so a failed download keeps the previous files
+        let staging_directory = palette_directory().join(format!(".{}.partial", source.name));
+        if staging_directory.exists() { fs::remove_dir_all(&staging_directory)?; }
+        fs::create_dir_all(&staging_directory)?;
+        for url in &source.urls {
+            let filename = url.trim_end_matches('/').rsplit('/').next().unwrap_or_default();
+            let file_path = staging_directory.join(filename);
+            if let Err(e) = run("curl", &["--fail", "--silent", "--show-error", "--location", "--output", &file_path.to_string_lossy(), "--", url]) {
+                fs::remove_dir_all(&staging_directory)?;
+                return Err(e);
+            }
+        }
+        if directory.exists() { fs::remove_dir_all(&directory)?; }
+        fs::rename(&staging_directory, &directory)?;
+    }
+
+    // remembers the source for updates, replacing an earlier source of the same collection
+    let mut sources = load_sources();
+    sources.retain(|remembered| remembered.name != source.name);
+    sources.push(PaletteSource { name: source.name.clone(), urls: source.urls.clone() });
+    save_sources(&sources)?;
+
+    Ok(count_files(&directory))
+}
+
+
+
+/// Downloads a palette from the Lospec palette list by its slug into the Lospec folder of the palette directory,
+/// saved as a palette file, and returns its path and how many colors it holds.
+pub fn fetch_lospec_palette(slug: &str) -> Result<(PathBuf, usize)> {
+    let slug = slug.trim().to_lowercase();
+    // keeps the slug usable as a file name and a URL path
+    if slug.is_empty() || !slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(Error::other(trf("{} is not a Lospec palette slug", &[slug])));
+    }
+
+    let directory = palette_directory().join(LOSPEC_DIRECTORY_NAME);
+    fs::create_dir_all(&directory)?;
+    // downloads into a hidden file first, so a failed download keeps a previously fetched palette
+    let download_path = directory.join(format!(".{}.partial", slug));
+    let download = run("curl", &["--fail", "--silent", "--show-error", "--location", "--output", &download_path.to_string_lossy(), "--", &lospec_url(&slug)])
+        .and_then(|_| load_palette_definition(&download_path));
+    if download_path.exists() { fs::remove_file(&download_path)?; }
+    let palette_file = download?;
+
+    // Lospec leaves out the # of its colors and may send an empty author
+    let flagged_colors = palette_file.get_flagged_colors();
+    let palette_file = PaletteFile {
+        author: palette_file.author.filter(|author| !author.is_empty()),
+        colors: flagged_colors.iter().map(|(color, is_biased)| PaletteFileColor::new(color, *is_biased)).collect(),
+        ..palette_file
+    };
+    let path = directory.join(format!("{}.json", slug));
+    fs::write(&path, serde_json::to_string_pretty(&palette_file).map_err(Error::other)? + "\n")?;
+
+    Ok((path, flagged_colors.len()))
+}
+
+
+
+/// Runs a palette command (`sync`, `list`, `update` or `fetch`) from the command line, returning an error if a collection cannot be synced or a palette cannot be fetched,
+/// and an InvalidInput error with the usage if the arguments are incomplete.
+pub fn run_command(arguments: &[String]) -> Result<()> {
+    match arguments.first().map(|argument| argument.as_str()) {
+        Some("sync") => {
+            let mut name = None;
+            let mut urls = Vec::new();
+            let mut arguments = arguments[1..].iter();
+            while let Some(argument) = arguments.next() {
+                if argument == "--name" { name = arguments.next().cloned(); } else { urls.push(argument.clone()); }
+            }
+            let Some(first_url) = urls.first() else {
+                return Err(Error::new(ErrorKind::InvalidInput, tr("Usage: oxide palettes <sync <url>... [--name <name>]|list|update [<name>]|fetch <slug>>")));
+            };
+            // names made of dots only are kept as they are, so they are rejected instead of being turned into underscores
+            let name = name.map(|name| if name.trim().trim_matches('.').is_empty() { name } else { sanitize_name(&name) });
+            let source = PaletteSource { name: name.unwrap_or_else(|| name_from_url(first_url)), urls };
+            check_sync_result(&source, sync(&source))?;
+        }
+        Some("list") => {
+            let sources = load_sources();
+            println!("{}", trf("Palettes: {}", &[palette_directory().to_string_lossy().to_string()]));
+            if sources.is_empty() { println!("{}", tr("  No synced collections")); }
+            for source in &sources {
+                println!("{}", trf("  {}: {} files from {}", &[source.name.clone(), count_files(&source.directory()).to_string(), source.urls.join(" ")]));
+            }
+        }
+        Some("update") => {
+            let sources: Vec<PaletteSource> = load_sources().into_iter()
+                .filter(|source| arguments.get(1).is_none_or(|name| *name == source.name))
+                .collect();
+            if sources.is_empty() { println!("{}", tr("No synced collections to update")); }
+            // every collection is synced and every failure reported, while the first failure fails the command, so scripts see the process fail
+            let mut first_error = None;
+            for source in &sources {
+                if let Err(e) = check_sync_result(source, sync(source)) {
+                    if first_error.is_none() { first_error = Some(e); } else { eprintln!("{}", e); }
+                }
+            }
+            if let Some(e) = first_error { return Err(e); }
+        }
+        Some("fetch") => {
+            let Some(slug) = arguments.get(1) else {
+                return Err(Error::new(ErrorKind::InvalidInput, tr("Usage: oxide palettes <sync <url>... [--name <name>]|list|update [<name>]|fetch <slug>>")));
+            };
+            // failed downloads and unknown slugs are returned, so scripts see the process fail, without being taken for wrong arguments
+            let (path, color_count) = fetch_lospec_palette(slug)
+                .map_err(|e| Error::other(trf("Could not fetch {}: {}", &[slug.clone(), e.to_string()])))?;
+            println!("{}", trf("Fetched {}: {} colors saved to {}", &[slug.clone(), color_count.to_string(), path.to_string_lossy().to_string()]));
+        }
+        _ => {
+            return Err(Error::new(ErrorKind::InvalidInput, tr("Usage: oxide palettes <sync <url>... [--name <name>]|list|update [<name>]|fetch <slug>>")));
+        }
+    }
+    Ok(())
+}
+
+/// Prints how many files a synced collection holds, or returns the error of a failed sync along with the name of the collection,
+/// which is never an InvalidInput error, so it isn't taken for wrong arguments.
+fn check_sync_result(source: &PaletteSource, result: Result<usize>) -> Result<()> {
+    match result {
+        Ok(file_count) => {
+            println!("{}", trf("Synced {}: {} files", &[source.name.clone(), file_count.to_string()]));
+            Ok(())
+        }
+        Err(e) => Err(Error::other(trf("Could not sync {}: {}", &[source.name.clone(), e.to_string()]))),
+    }
+}
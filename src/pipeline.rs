@@ -0,0 +1,145 @@
+use std::io::{Error, Result};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::sync_channel;
+use std::thread;
+
+/// Gets the max number of frames waiting between two stages of the pipeline.
+fn queue_capacity() -> usize { 4 }
+
+
+
+/// The phases every frame of an animation or video job goes through.
+#[derive(Copy, Clone, PartialEq)]
+pub enum FramePhases {
+    Decoding,
+    Palettizing,
+    Encoding,
+    Done,
+}
+impl FramePhases {
+    /// Returns the name of a given phase.
+    pub fn name(&self) -> String {
+        match self {
+            FramePhases::Decoding => "Decoding".to_string(),
+            FramePhases::Palettizing => "Palettizing".to_string(),
+            FramePhases::Encoding => "Encoding".to_string(),
+            FramePhases::Done => "Done".to_string(),
+        }
+    }
+}
+
+
+
+/// The progress of an animation or video job, counted per phase.
+#[derive(Clone)]
+pub struct FrameProgress {
+    /// The number of frames in the job, if it is known up front.
+    pub frame_count: Option<usize>,
+    /// The number of decoded frames.
+    pub decoded_count: usize,
+    /// The number of palettized frames.
+    pub palettized_count: usize,
+    /// The number of encoded frames.
+    pub encoded_count: usize,
+}
+impl FrameProgress {
+    /// Returns the progress of a job that has not started yet.
+    pub fn new(frame_count: Option<usize>) -> FrameProgress {
+        FrameProgress { frame_count, decoded_count: 0, palettized_count: 0, encoded_count: 0 }
+    }
+
+    /// Returns the number of the oldest frame still being worked on, starting at 1.
+    pub fn current_frame(&self) -> usize {
+        let current_frame = self.encoded_count + 1;
+        match self.frame_count {
+            Some(frame_count) => current_frame.min(frame_count),
+            None => current_frame.min(self.decoded_count.max(1)),
+        }
+    }
+
+    /// Returns the phase of the oldest frame still being worked on.
+    pub fn current_phase(&self) -> FramePhases {
+        if self.frame_count.is_some_and(|frame_count| self.encoded_count >= frame_count) { FramePhases::Done }
+        else if self.palettized_count > self.encoded_count { FramePhases::Encoding }
+        else if self.decoded_count > self.palettized_count { FramePhases::Palettizing }
+        else { FramePhases::Decoding }
+    }
+
+    /// Returns how much of the job is finished in percent, if the number of frames is known.
+    pub fn percent_complete(&self) -> Option<f64> {
+        self.frame_count.map(|frame_count| (self.encoded_count as f64 / frame_count.max(1) as f64 * 100.0).min(100.0))
+    }
+}
+
+
+
+/// Runs frames through decoding, processing and encoding with bounded queues between the stages.
+/// Decoding and encoding run on their own threads so all three stages overlap, while no more than a few frames are ever held in memory.
+/// Processing runs on the calling thread (where the gpu lives), which also reports the progress of every phase after each frame.
+pub fn run_pipeline<I: Send, O: Send>(
+    decode: impl Iterator<Item = Result<I>> + Send,
+    mut process: impl FnMut(I) -> Result<O>,
+    mut encode: impl FnMut(O) -> Result<()> + Send,
+    frame_count: Option<usize>,
+    mut on_progress: impl FnMut(&FrameProgress),
+) -> Result<()> {
+    let (decoded_sender, decoded_receiver) = sync_channel::<Result<I>>(queue_capacity());
+    let (processed_sender, processed_receiver) = sync_channel::<O>(queue_capacity());
+    let decoded_count = Arc::new(AtomicUsize::new(0));
+    let encoded_count = Arc::new(AtomicUsize::new(0));
+    let mut progress = FrameProgress::new(frame_count);
+
+    thread::scope(|scope| {
+        // decoding until the frames run out or processing stops listening
+        let decoder_count = decoded_count.clone();
+        let decoder = scope.spawn(move || {
+            for frame in decode {
+                let failed = frame.is_err();
+                if !failed { decoder_count.fetch_add(1, Ordering::Relaxed); }
+                if decoded_sender.send(frame).is_err() || failed { break; }
+            }
+        });
+
+        // encoding until processing stops sending or the encoder fails
+        let encoder_count = encoded_count.clone();
+        let encoder = scope.spawn(move || -> Result<()> {
+            for frame in processed_receiver {
+                encode(frame)?;
+                encoder_count.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(())
+        });
+
+        // processing, stopping early if any stage fails
+        let mut result = Ok(());
+        for frame in decoded_receiver.iter() {
+            let processed = frame.and_then(&mut process);
+            match processed {
+                Ok(processed) => {
+                    // the encoder only stops listening if it failed, which is reported below
+                    if processed_sender.send(processed).is_err() { break; }
+                    progress.palettized_count += 1;
+                    progress.decoded_count = decoded_count.load(Ordering::Relaxed);
+                    progress.encoded_count = encoded_count.load(Ordering::Relaxed);
+                    on_progress(&progress);
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        drop(decoded_receiver);
+        drop(processed_sender);
+
+        let _ = decoder.join();
+        let encoded = encoder.join().unwrap_or_else(|_| Err(Error::other("the encoder stopped unexpectedly")));
+
+        // the last frames are only encoded after processing finished
+        progress.decoded_count = decoded_count.load(Ordering::Relaxed);
+        progress.encoded_count = encoded_count.load(Ordering::Relaxed);
+        on_progress(&progress);
+        result.and(encoded)
+    })
+}
@@ -1,30 +1,77 @@
 pub mod guide;
 pub mod palette;
-mod compute;
+pub mod analysis;
+pub mod color_space;
+pub mod color_vision;
+pub mod compute;
+pub mod theory;
+pub mod shader;
+pub mod plugin;
+pub mod script;
+pub mod user_palette;
+pub mod dither;
+pub mod quantize;
+pub mod memory;
+pub mod blend;
+pub mod quality;
+pub mod swatch;
+pub mod palette_file;
 
 use std::cell::RefCell;
-use std::io::Stdout;
-use std::path::PathBuf;
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use image::{DynamicImage, GenericImageView, ImageBuffer, Pixel, Rgb};
-use ratatui::prelude::CrosstermBackend;
-use ratatui::Terminal;
 use rayon::prelude::*;
 use crate::processor::compute::*;
 use crate::processor::guide::*;
 use crate::processor::palette::*;
-use crate::ui::*;
+use crate::processor::color_space::SpectrumSpaces;
+use crate::processor::dither::{dither, DitherModes};
+use crate::processor::theory::Harmonies;
+use crate::processor::shader::{get_effects, ShaderParameter};
+use crate::processor::plugin::get_plugins;
+use crate::processor::script::get_scripts;
+use crate::processor::user_palette::get_user_palettes;
+use crate::processor::palette_file::{get_palette_file_mapping, load_palette_definition, PaletteFile};
+use crate::locale::{fill, tr, trf};
+use crate::progress::{report, ProgressEvents};
+use crate::video::open_source_image;
+
+/// Gets the fewest colors the polychromatic processor can be set up with.
+fn min_polychromatic_colors() -> usize { 2 }
+
+/// Gets the most colors the polychromatic processor can be set up with.
+fn max_polychromatic_colors() -> usize { 12 }
+
+/// Gets the fewest colors the quantize processor can reduce an image to.
+fn min_quantized_colors() -> usize { 2 }
+
+/// Gets the most colors the quantize processor can reduce an image to.
+fn max_quantized_colors() -> usize { 256 }
+
+/// Gets the largest block size (in pixels) the pixelate processor can be set up with.
+fn max_pixel_block_size() -> u32 { 256 }
+
+
 
 /// The list of available processors.
 pub enum Processors {
     Monochromatic,
+    MonochromaticWithEndpoints,
     MonochromaticWithAccent,
     AutomaticMonochromatic,
     AutomaticMonochromaticWithAccent,
+    TintedGrayscale,
     Bichromatic,
     BichromaticWithAccent,
+    AutomaticBichromatic,
+    Duotone,
+    SplitToning,
     Trichromatic,
+    Polychromatic,
+    Harmony,
     VolcanicCrater,
     RedRocks,
     DeepestAfrica,
@@ -35,18 +82,44 @@ pub enum Processors {
     SouthAmericanJungle,
     EuropeanIslands,
     ColorfulIslands,
+    CustomPalette,
+    Quantize,
+    Sepia,
+    FilmStock,
+    FalseColor,
+    ColorPop,
+    Invert,
+    HueRotation,
+    Pixelate,
+    ChromaKey,
+    PreserveLuminance,
+    /// A palette from the palette directory, by its index in the loaded palettes.
+    UserPalette(usize),
+    /// A custom effect shader from the shader directory, by its index in the loaded effects.
+    Shader(usize),
+    /// A WebAssembly plugin from the plugin directory, by its index in the loaded plugins.
+    Plugin(usize),
+    /// A color mapping script from the script directory, by its index in the loaded scripts.
+    Script(usize),
 }
 impl Processors {
     /// Returns the name of a given processor type.
     pub fn name(&self) -> String {
         match self {
             Processors::Monochromatic =>                    "Monochromatic".to_string(),
+            Processors::MonochromaticWithEndpoints =>       "Monochromatic with Endpoints".to_string(),
             Processors::MonochromaticWithAccent =>          "Monochromatic with Accent".to_string(),
             Processors::AutomaticMonochromatic =>           "Automatic Monochromatic".to_string(),
             Processors::AutomaticMonochromaticWithAccent => "Automatic Monochromatic with Accent".to_string(),
+            Processors::TintedGrayscale =>                  "Tinted Grayscale".to_string(),
             Processors::Bichromatic =>                      "Bichromatic".to_string(),
             Processors::BichromaticWithAccent =>            "Bichromatic with Accent".to_string(),
+            Processors::AutomaticBichromatic =>             "Automatic Bichromatic".to_string(),
+            Processors::Duotone =>                          "Duotone".to_string(),
+            Processors::SplitToning =>                      "Split Toning".to_string(),
             Processors::Trichromatic =>                     "Trichromatic".to_string(),
+            Processors::Polychromatic =>                    "Polychromatic".to_string(),
+            Processors::Harmony =>                          "Harmony".to_string(),
             Processors::VolcanicCrater =>                   "Volcanic Crater".to_string(),
             Processors::RedRocks =>                         "Red Rocks".to_string(),
             Processors::DeepestAfrica =>                    "Deepest Africa".to_string(),
@@ -57,37 +130,301 @@ impl Processors {
             Processors::SouthAmericanJungle =>              "South American Jungle".to_string(),
             Processors::EuropeanIslands =>                  "European Islands".to_string(),
             Processors::ColorfulIslands =>                  "Colorful Islands".to_string(),
+            Processors::CustomPalette =>                    "Custom Palette".to_string(),
+            Processors::Quantize =>                         "Quantize".to_string(),
+            Processors::Sepia =>                            "Sepia".to_string(),
+            Processors::FilmStock =>                        "Film Stock".to_string(),
+            Processors::FalseColor =>                       "False Color".to_string(),
+            Processors::ColorPop =>                         "Color Pop".to_string(),
+            Processors::Invert =>                           "Invert".to_string(),
+            Processors::HueRotation =>                      "Hue Rotation".to_string(),
+            Processors::Pixelate =>                         "Pixelate".to_string(),
+            Processors::ChromaKey =>                        "Chroma Key".to_string(),
+            Processors::PreserveLuminance =>                "Preserve Luminance".to_string(),
+            Processors::UserPalette(palette) =>             get_user_palettes()[*palette].name.clone(),
+            Processors::Shader(effect) =>                   get_effects()[*effect].name.clone(),
+            Processors::Plugin(plugin) =>                   get_plugins()[*plugin].name.clone(),
+            Processors::Script(script) =>                   get_scripts()[*script].name.clone(),
+        }
+    }
+
+    /// Returns a short description of a given processor type.
+    pub fn description(&self) -> String {
+        match self {
+            Processors::Monochromatic =>                    "One color of your choice from white to black".to_string(),
+            Processors::MonochromaticWithEndpoints =>       "One color of your choice between light and dark endpoints of your choice".to_string(),
+            Processors::MonochromaticWithAccent =>          "One color of your choice plus the accent color of the image".to_string(),
+            Processors::AutomaticMonochromatic =>           "The average color of the image from white to black".to_string(),
+            Processors::AutomaticMonochromaticWithAccent => "The average and accent colors of the image".to_string(),
+            Processors::TintedGrayscale =>                  "Grayscale by brightness with an optional tint of your choice".to_string(),
+            Processors::Bichromatic =>                      "Two colors of your choice blended together".to_string(),
+            Processors::BichromaticWithAccent =>            "Two colors of your choice plus the accent color of the image".to_string(),
+            Processors::AutomaticBichromatic =>             "The two dominant color families of the image blended together".to_string(),
+            Processors::Duotone =>                          "One color of your choice for the shadows and one for the highlights".to_string(),
+            Processors::SplitToning =>                      "Grayscale toned with two colors of your choice, blended around a balance".to_string(),
+            Processors::Trichromatic =>                     "Three colors of your choice and everything between them".to_string(),
+            Processors::Polychromatic =>                    "Any number of colors of your choice woven together".to_string(),
+            Processors::Harmony =>                          "A color of your choice and its companions in a color harmony".to_string(),
+            Processors::VolcanicCrater =>                   "Dark slate, deep reds and ash".to_string(),
+            Processors::RedRocks =>                         "Desert browns, ochres and rust".to_string(),
+            Processors::DeepestAfrica =>                    "Savanna olives, earthy browns and sunset oranges".to_string(),
+            Processors::ArcticWilderness =>                 "Cold slate, steel blues and ice".to_string(),
+            Processors::Iceland =>                          "Mossy greens, glacier blues and dark rock".to_string(),
+            Processors::EnglishOaks =>                      "Deep forest greens and bark browns".to_string(),
+            Processors::WheatField =>                       "Golden yellows and harvest browns".to_string(),
+            Processors::SouthAmericanJungle =>              "Dense greens with tropical highlights".to_string(),
+            Processors::EuropeanIslands =>                  "Turquoise seas, green hills and white stone".to_string(),
+            Processors::ColorfulIslands =>                  "Vivid tropical greens, teals and bright accents".to_string(),
+            Processors::CustomPalette =>                    "The colors of a palette file of your choice".to_string(),
+            Processors::Quantize =>                         "The best colors of the image itself, as few as you like".to_string(),
+            Processors::Sepia =>                            "Warm browns of an old photograph, as strong as you like".to_string(),
+            Processors::FilmStock =>                        "The colors and contrast of a classic film stock".to_string(),
+            Processors::FalseColor =>                       "The brightness of the image on a perceptual heat ramp".to_string(),
+            Processors::ColorPop =>                         "The accent color of the image in color and everything else in grayscale".to_string(),
+            Processors::Invert =>                           "The negative of the image, optionally with the palette of another processor".to_string(),
+            Processors::HueRotation =>                      "The image with its hues rotated, optionally with the palette of another processor".to_string(),
+            Processors::Pixelate =>                         "Pixel art from blocks of the image, optionally with the palette of another processor".to_string(),
+            Processors::ChromaKey =>                        "A key color replaced by a fill color, optionally with the palette of another processor".to_string(),
+            Processors::PreserveLuminance =>                "The hues of another processor's palette at the brightness of the image".to_string(),
+            Processors::UserPalette(palette) => match &get_user_palettes()[*palette].error {
+                Some(error) => trf("Invalid palette: {}", std::slice::from_ref(error)),
+                None => match &get_user_palettes()[*palette].author {
+                    Some(author) => trf("{} by {}", &[tr(&get_user_palettes()[*palette].description), author.clone()]),
+                    None => get_user_palettes()[*palette].description.clone(),
+                },
+            },
+            Processors::Shader(effect) => match &get_effects()[*effect].error {
+                Some(error) => trf("Invalid shader: {}", &[error.lines().next().unwrap_or_default().to_string()]),
+                None => get_effects()[*effect].description.clone(),
+            },
+            Processors::Plugin(plugin) => match &get_plugins()[*plugin].error {
+                Some(error) => trf("Invalid plugin: {}", &[error.lines().next().unwrap_or_default().to_string()]),
+                None => get_plugins()[*plugin].description.clone(),
+            },
+            Processors::Script(script) => match &get_scripts()[*script].error {
+                Some(error) => trf("Invalid script: {}", &[error.lines().next().unwrap_or_default().to_string()]),
+                None => get_scripts()[*script].description.clone(),
+            },
+        }
+    }
 
+    /// Returns the category a given processor type is grouped under in the processor list, which are the folders of palettes from the palette directory.
+    pub fn category(&self) -> Option<String> {
+        match self {
+            Processors::UserPalette(palette) => get_user_palettes()[*palette].category.clone(),
+            _ => None,
         }
     }
 
+    /// Returns the number of built-in processors, which are listed before the palettes from the palette directory, the custom effect shaders, the plugins and the scripts.
+    pub fn number_of_built_in_processors() -> usize { 35 }
+
     /// Returns the number of available processors.
-    pub fn number_of_processors() -> usize { 17 }
+    pub fn number_of_processors() -> usize { Processors::number_of_built_in_processors() + get_user_palettes().len() + get_effects().len() + get_plugins().len() + get_scripts().len() }
+
+    /// Returns the index of the processor with a given name or number (counting from 1 as in the processor list).
+    /// Names ignore case and everything but letters and digits, so "bichromatic-with-accent" matches "Bichromatic with Accent".
+    pub fn find(name: &str) -> Option<usize> {
+        if let Ok(number) = name.trim().parse::<usize>() {
+            return (1..=Processors::number_of_processors()).contains(&number).then(|| number - 1);
+        }
+        Processors::find_by_name(name)
+    }
+
+    /// Returns the index of the processor with a given name, ignoring case and everything but letters and digits like find() does, but never by number.
+    pub fn find_by_name(name: &str) -> Option<usize> {
+        let normalize = |name: &str| name.chars().filter(|character| character.is_alphanumeric()).collect::<String>().to_lowercase();
+        (0..Processors::number_of_processors()).find(|selection| normalize(&Processors::get_processor(*selection).name()) == normalize(name))
+    }
 
     /// Gets the processor type that corresponds to a given index.
     pub fn get_processor(selection: usize) -> Processors {
         match selection {
             0 => Processors::Monochromatic,
-            1 => Processors::MonochromaticWithAccent,
-            2 => Processors::AutomaticMonochromatic,
-            3 => Processors::AutomaticMonochromaticWithAccent,
-            4 => Processors::Bichromatic,
-            5 => Processors::BichromaticWithAccent,
-            6 => Processors::Trichromatic,
-            7 => Processors::VolcanicCrater,
-            8 => Processors::RedRocks,
-            9 => Processors::DeepestAfrica,
-            10 => Processors::ArcticWilderness,
-            11 => Processors::Iceland,
-            12 => Processors::EnglishOaks,
-            13 => Processors::WheatField,
-            14 => Processors::SouthAmericanJungle,
-            15 => Processors::EuropeanIslands,
-            16 => Processors::ColorfulIslands,
+            1 => Processors::MonochromaticWithEndpoints,
+            2 => Processors::MonochromaticWithAccent,
+            3 => Processors::AutomaticMonochromatic,
+            4 => Processors::AutomaticMonochromaticWithAccent,
+            5 => Processors::TintedGrayscale,
+            6 => Processors::Bichromatic,
+            7 => Processors::BichromaticWithAccent,
+            8 => Processors::AutomaticBichromatic,
+            9 => Processors::Duotone,
+            10 => Processors::SplitToning,
+            11 => Processors::Trichromatic,
+            12 => Processors::Polychromatic,
+            13 => Processors::Harmony,
+            14 => Processors::VolcanicCrater,
+            15 => Processors::RedRocks,
+            16 => Processors::DeepestAfrica,
+            17 => Processors::ArcticWilderness,
+            18 => Processors::Iceland,
+            19 => Processors::EnglishOaks,
+            20 => Processors::WheatField,
+            21 => Processors::SouthAmericanJungle,
+            22 => Processors::EuropeanIslands,
+            23 => Processors::ColorfulIslands,
+            24 => Processors::CustomPalette,
+            25 => Processors::Quantize,
+            26 => Processors::Sepia,
+            27 => Processors::FilmStock,
+            28 => Processors::FalseColor,
+            29 => Processors::ColorPop,
+            30 => Processors::Invert,
+            31 => Processors::HueRotation,
+            32 => Processors::Pixelate,
+            33 => Processors::ChromaKey,
+            34 => Processors::PreserveLuminance,
+            _ if selection < Processors::number_of_built_in_processors() + get_user_palettes().len() => Processors::UserPalette(selection - Processors::number_of_built_in_processors()),
+            _ if selection < Processors::number_of_built_in_processors() + get_user_palettes().len() + get_effects().len() => Processors::Shader(selection - Processors::number_of_built_in_processors() - get_user_palettes().len()),
+            _ if selection < Processors::number_of_built_in_processors() + get_user_palettes().len() + get_effects().len() + get_plugins().len() => Processors::Plugin(selection - Processors::number_of_built_in_processors() - get_user_palettes().len() - get_effects().len()),
+            _ if selection < Processors::number_of_processors() => Processors::Script(selection - Processors::number_of_built_in_processors() - get_user_palettes().len() - get_effects().len() - get_plugins().len()),
             _ => panic!("Invalid processor selection: {}", selection),
         }
     }
+
+    /// Returns a new processor of a given type for a given image with its steps filled by the given colors, numbers, files and names in order, or what is missing or invalid.
+    pub fn create_filled_edit(&self, source_image_path: PathBuf, colors: &[String], numbers: &[String], files: &[String], names: &[String]) -> Result<Box<dyn EditProcessor>, ProcessError> {
+        let mut processor = self.create_edit(source_image_path);
+        let (mut colors, mut numbers, mut files, mut names) = (colors.iter(), numbers.iter(), files.iter(), names.iter());
+        processor.fill_steps(&mut |processor| {
+            let label = processor.get_current_step_label();
+            let input = match processor.get_current_step_type() {
+                ProcessingStepTypes::Color => colors.next().cloned().ok_or(ProcessError::MissingColor(label.clone())),
+                ProcessingStepTypes::Number => numbers.next().cloned().ok_or(ProcessError::MissingNumber(label.clone())),
+                ProcessingStepTypes::FilePath => files.next().cloned().ok_or(ProcessError::MissingFile(label.clone())),
+                ProcessingStepTypes::Name => names.next().cloned().ok_or(ProcessError::MissingName(label.clone())),
+                ProcessingStepTypes::NoInput => Ok(String::new()),
+            }?;
+            processor.update_current_step_input(input.clone());
+            if !processor.is_current_step_input_valid() { return Err(ProcessError::InvalidInput(label, input)); }
+            Ok(())
+        })?;
+
+        // leftover inputs are most likely a mistake, e.g. the wrong processor
+        let (extra_colors, extra_numbers) = (colors.count(), numbers.count());
+        if extra_colors + extra_numbers > 0 { return Err(ProcessError::ExtraInputs(self.name(), extra_colors, extra_numbers)); }
+        let extra_files = files.count();
+        if extra_files > 0 { return Err(ProcessError::ExtraFiles(self.name(), extra_files)); }
+        let extra_names = names.count();
+        if extra_names > 0 { return Err(ProcessError::ExtraNames(self.name(), extra_names)); }
+        processor.try_populate();
+        Ok(processor)
+    }
+
+    /// Returns a new processor of a given type for a given image with its steps filled by the inputs of another run (e.g. the first image of a batch).
+    pub fn create_edit_with_inputs(&self, source_image_path: PathBuf, inputs: &[String]) -> Box<dyn EditProcessor> {
+        let mut processor = self.create_edit(source_image_path);
+        let mut inputs = inputs.iter();
+        let _ = processor.fill_steps(&mut |processor| {
+            processor.update_current_step_input(inputs.next().cloned().unwrap_or_default());
+            Ok::<(), Infallible>(())
+        });
+        processor.try_populate();
+        processor
+    }
+
+    /// Returns a new processor of a given type ready to be set up for a given image.
+    pub fn create_edit(&self, source_image_path: PathBuf) -> Box<dyn EditProcessor> {
+        match self {
+            Processors::Monochromatic => Box::new(MonochromaticEdit::new(source_image_path)),
+            Processors::MonochromaticWithEndpoints => Box::new(MonochromaticWithEndpointsEdit::new(source_image_path)),
+            Processors::MonochromaticWithAccent => Box::new(MonochromaticWithAccentEdit::new(source_image_path)),
+            Processors::AutomaticMonochromatic => Box::new(AutomaticMonochromaticEdit::new(source_image_path)),
+            Processors::AutomaticMonochromaticWithAccent => Box::new(AutomaticMonochromaticWithAccentEdit::new(source_image_path)),
+            Processors::TintedGrayscale => Box::new(TintedGrayscaleEdit::new(source_image_path)),
+            Processors::Bichromatic => Box::new(BichromaticEdit::new(source_image_path)),
+            Processors::BichromaticWithAccent => Box::new(BichromaticWithAccentEdit::new(source_image_path)),
+            Processors::AutomaticBichromatic => Box::new(AutomaticBichromaticEdit::new(source_image_path)),
+            Processors::Duotone => Box::new(DuotoneEdit::new(source_image_path)),
+            Processors::SplitToning => Box::new(SplitToningEdit::new(source_image_path)),
+            Processors::Trichromatic => Box::new(TrichromaticEdit::new(source_image_path)),
+            Processors::Polychromatic => Box::new(PolychromaticEdit::new(source_image_path)),
+            Processors::Harmony => Box::new(HarmonyEdit::new(source_image_path)),
+            Processors::VolcanicCrater => Box::new(VolcanicCraterEdit::new(source_image_path)),
+            Processors::RedRocks => Box::new(RedRocksEdit::new(source_image_path)),
+            Processors::DeepestAfrica => Box::new(DeepestAfricaEdit::new(source_image_path)),
+            Processors::ArcticWilderness => Box::new(ArcticWildernessEdit::new(source_image_path)),
+            Processors::Iceland => Box::new(IcelandEdit::new(source_image_path)),
+            Processors::EnglishOaks => Box::new(EnglishOaksEdit::new(source_image_path)),
+            Processors::WheatField => Box::new(WheatFieldEdit::new(source_image_path)),
+            Processors::SouthAmericanJungle => Box::new(SouthAmericanJungleEdit::new(source_image_path)),
+            Processors::EuropeanIslands => Box::new(EuropeanIslandsEdit::new(source_image_path)),
+            Processors::ColorfulIslands => Box::new(ColorfulIslandsEdit::new(source_image_path)),
+            Processors::CustomPalette => Box::new(CustomPaletteEdit::new(source_image_path)),
+            Processors::Quantize => Box::new(QuantizeEdit::new(source_image_path)),
+            Processors::Sepia => Box::new(SepiaEdit::new(source_image_path)),
+            Processors::FilmStock => Box::new(FilmStockEdit::new(source_image_path)),
+            Processors::FalseColor => Box::new(FalseColorEdit::new(source_image_path)),
+            Processors::ColorPop => Box::new(ColorPopEdit::new(source_image_path)),
+            Processors::Invert => Box::new(TransformEdit::new(source_image_path, TransformKinds::Invert)),
+            Processors::HueRotation => Box::new(TransformEdit::new(source_image_path, TransformKinds::HueRotation)),
+            Processors::Pixelate => Box::new(TransformEdit::new(source_image_path, TransformKinds::Pixelate)),
+            Processors::ChromaKey => Box::new(TransformEdit::new(source_image_path, TransformKinds::ChromaKey)),
+            Processors::PreserveLuminance => Box::new(TransformEdit::new(source_image_path, TransformKinds::PreserveLuminance)),
+            Processors::UserPalette(palette) => Box::new(UserPaletteEdit::new(source_image_path, *palette)),
+            Processors::Shader(effect) => Box::new(ShaderEdit::new(source_image_path, *effect)),
+            Processors::Plugin(plugin) => Box::new(PluginEdit::new(source_image_path, *plugin)),
+            Processors::Script(script) => Box::new(ScriptEdit::new(source_image_path, *script)),
+        }
+    }
+}
+
+
+
+/// What went wrong setting up a processor outside the terminal interface, e.g. from the command line or the library.
+/// The messages are in english, localized() returns them in the configured language.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessError {
+    /// No processor has the given name or number.
+    UnknownProcessor(String),
+    /// The step with the given label takes a color, but no colors are left.
+    MissingColor(String),
+    /// The step with the given label takes a number, but no numbers are left.
+    MissingNumber(String),
+    /// The step with the given label takes a file, but no files are left.
+    MissingFile(String),
+    /// The step with the given label takes a name, but no names are left.
+    MissingName(String),
+    /// The step with the given label doesn't accept the given input.
+    InvalidInput(String, String),
+    /// The processor with the given name was given the given number of colors and numbers more than it takes.
+    ExtraInputs(String, usize, usize),
+    /// The processor with the given name was given the given number of files more than it takes.
+    ExtraFiles(String, usize),
+    /// The processor with the given name was given the given number of names more than it takes.
+    ExtraNames(String, usize),
+}
+impl ProcessError {
+    /// Returns the english template of the message and its arguments, with step labels translated if the message is localized.
+    fn template(&self, is_localized: bool) -> (&'static str, Vec<String>) {
+        let label = |label: &String| if is_localized { tr(label) } else { label.clone() };
+        match self {
+            ProcessError::UnknownProcessor(name) => ("Unknown processor: {}", vec![name.clone()]),
+            ProcessError::MissingColor(step) => ("Missing color for {}", vec![label(step)]),
+            ProcessError::MissingNumber(step) => ("Missing number for {}", vec![label(step)]),
+            ProcessError::MissingFile(step) => ("Missing file for {}", vec![label(step)]),
+            ProcessError::MissingName(step) => ("Missing name for {}", vec![label(step)]),
+            ProcessError::InvalidInput(step, input) => ("Invalid input for {}: {}", vec![label(step), input.clone()]),
+            ProcessError::ExtraInputs(name, colors, numbers) => ("{} takes fewer inputs ({} colors and {} numbers left over)", vec![name.clone(), colors.to_string(), numbers.to_string()]),
+            ProcessError::ExtraFiles(name, files) => ("{} takes fewer files ({} left over)", vec![name.clone(), files.to_string()]),
+            ProcessError::ExtraNames(name, names) => ("{} takes fewer names ({} left over)", vec![name.clone(), names.to_string()]),
+        }
+    }
+
+    /// Returns the message in the configured language.
+    pub fn localized(&self) -> String {
+        let (template, arguments) = self.template(true);
+        trf(template, &arguments)
+    }
+}
+impl std::fmt::Display for ProcessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (template, arguments) = self.template(false);
+        f.write_str(&fill(template, &arguments))
+    }
 }
+impl std::error::Error for ProcessError {}
 
 
 
@@ -96,6 +433,12 @@ pub trait EditProcessor {
     /// Returns the set of colors used in editing the image in order to print them in the editing image filename
     fn get_descriptor(&self, name: String) -> String;
 
+    /// Returns the base colors the processor builds its spectrums from.
+    fn get_base_colors(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>>;
+
+    /// Returns the palettes the source image is mapped onto.
+    fn get_palette_mapping(&self, source_image: &DynamicImage) -> PaletteMapping;
+
     /// Returns the input type of the current step.
     fn get_current_step_type(&self) -> ProcessingStepTypes;
 
@@ -105,9 +448,30 @@ pub trait EditProcessor {
     /// Returns the input of the current step.
     fn get_current_step_input(&self) -> String;
 
+    /// Returns the numbers the current step takes, if it is a number step with limits.
+    fn get_current_step_range(&self) -> Option<NumberRange> {
+        self.get_guide().get_current_range()
+    }
+
     /// Updates the input of the current step.
     fn update_current_step_input(&mut self, new_input: String);
 
+    /// Returns the steps used to create the processor.
+    fn get_guide(&self) -> &ProcessingGuide;
+
+    /// Returns the steps used to create the processor so they can be filled.
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide;
+
+    /// Returns the inputs of every step.
+    fn get_step_inputs(&self) -> Vec<String> {
+        self.get_guide().get_inputs()
+    }
+
+    /// Fills the steps with given inputs (e.g. the inputs of the last run).
+    fn set_step_inputs(&mut self, inputs: Vec<String>) {
+        self.get_mut_guide().set_inputs(inputs)
+    }
+
     /// Checks if the input is valid for the current step.
     fn is_current_step_input_valid(&self) -> bool;
 
@@ -117,8 +481,43 @@ pub trait EditProcessor {
     /// Populates the processor steps from the guide if the guide is ready.
     fn try_populate(&mut self);
 
+    /// Returns if every step is finished and the processor is ready to process.
+    fn is_ready(&self) -> bool;
+
     /// Processes the image and returns the new image.
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) ->  Option<ImageBuffer<Rgb<u8>, Vec<u8>>>;
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>>;
+}
+impl dyn EditProcessor {
+    /// Fills every step in order, with a given function filling the input of the current step or returning what went wrong, and finishes each one.
+    pub fn fill_steps<E>(&mut self, fill_current_step: &mut dyn FnMut(&mut dyn EditProcessor) -> Result<(), E>) -> Result<(), E> {
+        // the number of steps can grow with the inputs (e.g. a color count), so it is checked again after every step
+        let mut step = 0;
+        while step < self.get_step_inputs().len() {
+            fill_current_step(self)?;
+            self.try_finish_current_step();
+            step += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Processes the image at a given path onto the palettes of a processor once it is ready, which is how every processor's try_process() runs,
+/// optionally spreading the error of the mapping with a dithering mode and strength (0-1).
+/// Custom shaders and plugins (also inside transforms) have no palette to dither with, so their effect is applied as it is.
+pub fn process_source_image(processor: &dyn EditProcessor, source_image_path: &Path, dithering: Option<(DitherModes, f32)>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+    if !processor.is_ready() { return None; }
+
+    let source_image = open_source_image(source_image_path).ok()?;
+    report(ProgressEvents::LoadingColors);
+    let palette_mapping = processor.get_palette_mapping(&source_image);
+
+    report(ProgressEvents::SpectrumBuilt(palette_mapping.colors().len()));
+    match dithering {
+        Some((mode, strength)) if mode != DitherModes::None && !palette_mapping.maps_whole_images() => {
+            Some(dither(&source_image.to_rgb8(), &palette_mapping, mode, strength))
+        }
+        _ => Some(palette_mapping.process(source_image)),
+    }
 }
 
 
@@ -155,6 +554,17 @@ impl EditProcessor for MonochromaticEdit {
         format!("{} {}", name, self.base_color_hex.clone())
     }
 
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![self.base_color_rgb]
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_line_spectrum(&self.base_color_rgb);
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
     fn get_current_step_type(&self) -> ProcessingStepTypes {
         self.guide.get_current_step_type()
     }
@@ -171,6 +581,14 @@ impl EditProcessor for MonochromaticEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
@@ -192,20 +610,140 @@ impl EditProcessor for MonochromaticEdit {
         self.is_ready = true;
     }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_line_spectrum(&self.base_color_rgb);
-            spectrum = condense_color_palette(&spectrum);
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image into a single color spectrum with custom light and dark endpoints.
+pub struct MonochromaticWithEndpointsEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The base color of the spectrum being used as a hex value.
+    base_color_hex: String,
+    /// The base color of the spectrum being used as an rgb color.
+    base_color_rgb: Rgb<u8>,
+    /// The light endpoint of the spectrum being used as a hex value, or "none" for white.
+    pub light_endpoint_hex: String,
+    /// The light endpoint of the spectrum being used as an rgb color, if any.
+    pub light_endpoint_rgb: Option<Rgb<u8>>,
+    /// The dark endpoint of the spectrum being used as a hex value, or "none" for black.
+    pub dark_endpoint_hex: String,
+    /// The dark endpoint of the spectrum being used as an rgb color, if any.
+    pub dark_endpoint_rgb: Option<Rgb<u8>>,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl MonochromaticWithEndpointsEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> MonochromaticWithEndpointsEdit {
+        MonochromaticWithEndpointsEdit {
+            source_image_path,
+            base_color_rgb: Rgb([0, 0, 0]),
+            base_color_hex: "none".to_string(),
+            light_endpoint_hex: "none".to_string(),
+            light_endpoint_rgb: None,
+            dark_endpoint_hex: "none".to_string(),
+            dark_endpoint_rgb: None,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::Color, "Base Color (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Light Endpoint (HEX or none)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Dark Endpoint (HEX or none)".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+
+    /// Returns if the current step is an endpoint step left empty or "none", which keeps the default endpoint.
+    fn is_default_endpoint(&self) -> bool {
+        let input = self.guide.get_current_input();
+        self.guide.current_step > 0 && (input.trim().is_empty() || input.trim().eq_ignore_ascii_case("none"))
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum))
+    /// Returns the endpoint given in a step as a hex value and an rgb color, or "none" and None for the default endpoint.
+    fn get_endpoint(&self, step: usize) -> (String, Option<Rgb<u8>>) {
+        match self.guide.steps[step].as_hex() {
+            Some(endpoint_hex) => (endpoint_hex.clone(), as_rgb(&endpoint_hex)),
+            None => ("none".to_string(), None),
         }
+    }
+}
+impl EditProcessor for MonochromaticWithEndpointsEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{} {}-{}-{}", name, self.light_endpoint_hex, self.base_color_hex, self.dark_endpoint_hex)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![self.base_color_rgb]
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_line_spectrum_between(&self.base_color_rgb, self.light_endpoint_rgb.as_ref(), self.dark_endpoint_rgb.as_ref());
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.is_default_endpoint() || self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        // the guide only advances past valid hex inputs, so default endpoints are skipped here
+        if self.is_default_endpoint() && !self.guide.is_ready() { self.guide.current_step += 1; }
+        else if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        if !self.guide.is_ready() || !self.is_current_step_input_valid() { return; }
+
+        let Some(base_color_hex) = self.guide.steps[0].as_hex() else { return; };
+        let Some(base_color_rgb) = as_rgb(&base_color_hex) else { return; };
+        (self.base_color_hex, self.base_color_rgb) = (base_color_hex, base_color_rgb);
+        (self.light_endpoint_hex, self.light_endpoint_rgb) = self.get_endpoint(1);
+        (self.dark_endpoint_hex, self.dark_endpoint_rgb) = self.get_endpoint(2);
+
+        self.is_ready = true;
+    }
 
-        None
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
@@ -243,6 +781,20 @@ impl EditProcessor for MonochromaticWithAccentEdit {
         format!("{} {}", name, self.base_color_hex.clone())
     }
 
+    fn get_base_colors(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![self.base_color_rgb, get_accent_color(source_image)]
+    }
+
+    fn get_palette_mapping(&self, source_image: &DynamicImage) -> PaletteMapping {
+        let colors = get_color_blind_safe_colors(&[self.base_color_rgb, get_accent_color(source_image)]);
+        let mut accent_spectrum = get_line_spectrum(&colors[1]);
+        accent_spectrum = condense_color_palette(&accent_spectrum);
+        let mut base_spectrum = get_plane_spectrum(&get_line_spectrum(&colors[0]), &accent_spectrum);
+        base_spectrum = condense_color_palette(&base_spectrum);
+
+        PaletteMapping::Biased(base_spectrum, accent_spectrum)
+    }
+
     fn get_current_step_type(&self) -> ProcessingStepTypes {
         self.guide.get_current_step_type()
     }
@@ -259,6 +811,14 @@ impl EditProcessor for MonochromaticWithAccentEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
@@ -280,22 +840,12 @@ impl EditProcessor for MonochromaticWithAccentEdit {
         self.is_ready = true;
     }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
-
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut accent_spectrum = get_line_spectrum(&get_accent_color(&source_image));
-            accent_spectrum = condense_color_palette(&accent_spectrum);
-            let mut base_spectrum = get_plane_spectrum(&get_line_spectrum(&self.base_color_rgb), &accent_spectrum);
-            base_spectrum = condense_color_palette(&base_spectrum);
-
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_biased(source_image, base_spectrum, accent_spectrum))
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
@@ -327,6 +877,17 @@ impl EditProcessor for AutomaticMonochromaticEdit {
         format!("{}", name)
     }
 
+    fn get_base_colors(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![get_average_color_from_image(source_image)]
+    }
+
+    fn get_palette_mapping(&self, source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_line_spectrum(&get_average_color_from_image(source_image));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
     fn get_current_step_type(&self) -> ProcessingStepTypes {
         self.guide.get_current_step_type()
     }
@@ -343,6 +904,14 @@ impl EditProcessor for AutomaticMonochromaticEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
@@ -355,20 +924,12 @@ impl EditProcessor for AutomaticMonochromaticEdit {
         self.is_ready = true;
     }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
-
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_line_spectrum(&get_average_color_from_image(&source_image));
-            spectrum = condense_color_palette(&spectrum);
-
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum))
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
@@ -400,6 +961,20 @@ impl EditProcessor for AutomaticMonochromaticWithAccentEdit {
         format!("{}", name)
     }
 
+    fn get_base_colors(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![get_average_color_from_image(source_image), get_accent_color(source_image)]
+    }
+
+    fn get_palette_mapping(&self, source_image: &DynamicImage) -> PaletteMapping {
+        let colors = get_color_blind_safe_colors(&[get_average_color_from_image(source_image), get_accent_color(source_image)]);
+        let mut accent_spectrum = get_line_spectrum(&colors[1]);
+        accent_spectrum = condense_color_palette(&accent_spectrum);
+        let mut base_spectrum = get_plane_spectrum(&get_line_spectrum(&colors[0]), &accent_spectrum);
+        base_spectrum = condense_color_palette(&base_spectrum);
+
+        PaletteMapping::Biased(base_spectrum, accent_spectrum)
+    }
+
     fn get_current_step_type(&self) -> ProcessingStepTypes {
         self.guide.get_current_step_type()
     }
@@ -416,6 +991,14 @@ impl EditProcessor for AutomaticMonochromaticWithAccentEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
@@ -428,22 +1011,118 @@ impl EditProcessor for AutomaticMonochromaticWithAccentEdit {
         self.is_ready = true;
     }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image into grayscale, optionally tinted with a color.
+pub struct TintedGrayscaleEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The tint color being used as a hex value, or "none" for plain grayscale.
+    pub tint_color_hex: String,
+    /// The tint color being used as an rgb color, if any.
+    pub tint_color_rgb: Option<Rgb<u8>>,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl TintedGrayscaleEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> TintedGrayscaleEdit {
+        TintedGrayscaleEdit {
+            source_image_path,
+            tint_color_hex: "none".to_string(),
+            tint_color_rgb: None,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::Color, "Tint Color (HEX or none)".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+
+    /// Returns if the tint step is left empty or "none", which keeps the grayscale neutral.
+    fn is_untinted(&self) -> bool {
+        let input = self.guide.get_current_input();
+        input.trim().is_empty() || input.trim().eq_ignore_ascii_case("none")
+    }
+}
+impl EditProcessor for TintedGrayscaleEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{} {}", name, self.tint_color_hex.clone())
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![self.tint_color_rgb.unwrap_or(Rgb([128, 128, 128]))]
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        PaletteMapping::Tone(get_tone_ramp(self.tint_color_rgb.as_ref()))
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.is_untinted() || self.guide.is_current_input_valid()
+    }
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut accent_spectrum = get_line_spectrum(&get_accent_color(&source_image));
-            accent_spectrum = condense_color_palette(&accent_spectrum);
-            let mut base_spectrum = get_plane_spectrum(&get_line_spectrum(&get_average_color_from_image(&source_image)), &accent_spectrum);
-            base_spectrum = condense_color_palette(&base_spectrum);
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_biased(source_image, base_spectrum, accent_spectrum))
+    fn try_populate(&mut self) {
+        if !self.guide.is_ready() || !self.is_current_step_input_valid() { return; }
+
+        match self.guide.steps[0].as_hex() {
+            Some(tint_color_hex) => {
+                self.tint_color_rgb = as_rgb(&tint_color_hex);
+                self.tint_color_hex = tint_color_hex;
+            }
+            None => {
+                self.tint_color_rgb = None;
+                self.tint_color_hex = "none".to_string();
+            }
         }
 
-        None
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
@@ -488,6 +1167,20 @@ impl EditProcessor for BichromaticEdit {
         format!("{} {}-{}", name, self.base_color_1_hex.clone(), self.base_color_2_hex.clone())
     }
 
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![self.base_color_1_rgb, self.base_color_2_rgb]
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let colors = get_color_blind_safe_colors(&[self.base_color_1_rgb, self.base_color_2_rgb]);
+        let line_spectrum_1 = get_line_spectrum(&colors[0]);
+        let line_spectrum_2 = get_line_spectrum(&colors[1]);
+        let mut spectrum = get_plane_spectrum(&line_spectrum_1, &line_spectrum_2);
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
     fn get_current_step_type(&self) -> ProcessingStepTypes {
         self.guide.get_current_step_type()
     }
@@ -504,6 +1197,14 @@ impl EditProcessor for BichromaticEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
@@ -531,22 +1232,12 @@ impl EditProcessor for BichromaticEdit {
         self.is_ready = true;
     }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
-
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let line_spectrum_1 = get_line_spectrum(&self.base_color_1_rgb);
-            let line_spectrum_2 = get_line_spectrum(&self.base_color_2_rgb);
-            let mut spectrum = get_plane_spectrum(&line_spectrum_1, &line_spectrum_2);
-            spectrum = condense_color_palette(&spectrum);
-
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum))
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
@@ -591,6 +1282,20 @@ impl EditProcessor for BichromaticWithAccentEdit {
         format!("{} {}-{}", name, self.base_color_1_hex.clone(), self.base_color_2_hex.clone())
     }
 
+    fn get_base_colors(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![self.base_color_1_rgb, self.base_color_2_rgb, get_accent_color(source_image)]
+    }
+
+    fn get_palette_mapping(&self, source_image: &DynamicImage) -> PaletteMapping {
+        let colors = get_color_blind_safe_colors(&[get_average_color_from_image(source_image), get_accent_color(source_image)]);
+        let mut accent_spectrum = get_line_spectrum(&colors[1]);
+        accent_spectrum = condense_color_palette(&accent_spectrum);
+        let mut base_spectrum = get_plane_spectrum(&get_line_spectrum(&colors[0]), &accent_spectrum);
+        base_spectrum = condense_color_palette(&base_spectrum);
+
+        PaletteMapping::Biased(base_spectrum, accent_spectrum)
+    }
+
     fn get_current_step_type(&self) -> ProcessingStepTypes {
         self.guide.get_current_step_type()
     }
@@ -607,7 +1312,15 @@ impl EditProcessor for BichromaticWithAccentEdit {
         self.guide.update_current_input(new_input)
     }
 
-    fn is_current_step_input_valid(&self) -> bool {
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
 
@@ -634,71 +1347,164 @@ impl EditProcessor for BichromaticWithAccentEdit {
         self.is_ready = true;
     }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut accent_spectrum = get_line_spectrum(&get_accent_color(&source_image));
-            accent_spectrum = condense_color_palette(&accent_spectrum);
-            let mut base_spectrum = get_plane_spectrum(&get_line_spectrum(&get_average_color_from_image(&source_image)), &accent_spectrum);
-            base_spectrum = condense_color_palette(&base_spectrum);
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_biased(source_image, base_spectrum, accent_spectrum))
+/// Processes an image into a two-color spectrum blend automatically.
+pub struct AutomaticBichromaticEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl AutomaticBichromaticEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> AutomaticBichromaticEdit {
+        AutomaticBichromaticEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
         }
+    }
+}
+impl EditProcessor for AutomaticBichromaticEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        name
+    }
+
+    fn get_base_colors(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        get_dominant_color_families(source_image).to_vec()
+    }
+
+    fn get_palette_mapping(&self, source_image: &DynamicImage) -> PaletteMapping {
+        let colors = get_color_blind_safe_colors(&get_dominant_color_families(source_image));
+        let line_spectrum_1 = get_line_spectrum(&colors[0]);
+        let line_spectrum_2 = get_line_spectrum(&colors[1]);
+        let mut spectrum = get_plane_spectrum(&line_spectrum_1, &line_spectrum_2);
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
 
-        None
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
 
 
-/// Processes an image into a three-color spectrum blend.
-pub struct TrichromaticEdit {
+/// Processes an image into a shadow spectrum and a highlight spectrum, split by the brightness of each pixel.
+pub struct DuotoneEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
-    /// The first base color of the spectrum being used as a hex value.
-    pub base_color_1_hex: String,
-    /// The first base color of the spectrum being used as an rgb color.
-    pub base_color_1_rgb: Rgb<u8>,
-    /// The second base color of the spectrum being used as a hex value.
-    pub base_color_2_hex: String,
-    /// The second base color of the spectrum being used as an rgb color.
-    pub base_color_2_rgb: Rgb<u8>,
-    /// The third base color of the spectrum being used as a hex value.
-    pub base_color_3_hex: String,
-    /// The third base color of the spectrum being used as an rgb color.
-    pub base_color_3_rgb: Rgb<u8>,
+    /// The base color of the shadow spectrum being used as a hex value.
+    pub shadow_color_hex: String,
+    /// The base color of the shadow spectrum being used as an rgb color.
+    pub shadow_color_rgb: Rgb<u8>,
+    /// The base color of the highlight spectrum being used as a hex value.
+    pub highlight_color_hex: String,
+    /// The base color of the highlight spectrum being used as an rgb color.
+    pub highlight_color_rgb: Rgb<u8>,
+    /// The perceived brightness (0-1) below which pixels count as shadows.
+    pub threshold: f32,
     /// The steps used to create the processor.
     pub guide: ProcessingGuide,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl TrichromaticEdit {
+impl DuotoneEdit {
     /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> TrichromaticEdit {
-        TrichromaticEdit {
+    pub fn new(source_image_path: PathBuf) -> DuotoneEdit {
+        DuotoneEdit {
             source_image_path,
-            base_color_1_rgb: Rgb([0, 0, 0]),
-            base_color_1_hex: "none".to_string(),
-            base_color_2_rgb: Rgb([0, 0, 0]),
-            base_color_2_hex: "none".to_string(),
-            base_color_3_rgb: Rgb([0, 0, 0]),
-            base_color_3_hex: "none".to_string(),
+            shadow_color_rgb: Rgb([0, 0, 0]),
+            shadow_color_hex: "none".to_string(),
+            highlight_color_rgb: Rgb([0, 0, 0]),
+            highlight_color_hex: "none".to_string(),
+            threshold: 0.5,
             guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::Color, "Base Color 1 (HEX)".to_string()),
-                ProcessingStep::new(ProcessingStepTypes::Color, "Base Color 2 (HEX)".to_string()),
-                ProcessingStep::new(ProcessingStepTypes::Color, "Base Color 3 (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Shadow Color (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Highlight Color (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Number, "Threshold (0-1)".to_string()).with_range(NumberRange::between(0.0, 1.0)),
             ]),
             is_ready: false,
         }
     }
+
+    /// Returns the threshold given in the last step, if it is within 0 and 1.
+    fn get_threshold(&self) -> Option<f32> {
+        self.guide.steps[2].as_number().filter(|threshold| (0.0..=1.0).contains(threshold))
+    }
 }
-impl EditProcessor for TrichromaticEdit {
+impl EditProcessor for DuotoneEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{} {}-{}-{}", name, self.base_color_1_hex.clone(), self.base_color_2_hex.clone(), self.base_color_3_hex.clone())
+        format!("{} {}-{} {}", name, self.shadow_color_hex.clone(), self.highlight_color_hex.clone(), self.threshold)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![self.shadow_color_rgb, self.highlight_color_rgb]
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let colors = get_color_blind_safe_colors(&[self.shadow_color_rgb, self.highlight_color_rgb]);
+        let mut shadow_spectrum = get_line_spectrum(&colors[0]);
+        shadow_spectrum = condense_color_palette(&shadow_spectrum);
+        let mut highlight_spectrum = get_line_spectrum(&colors[1]);
+        highlight_spectrum = condense_color_palette(&highlight_spectrum);
+
+        PaletteMapping::Split(self.threshold, shadow_spectrum, highlight_spectrum)
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -717,7 +1523,16 @@ impl EditProcessor for TrichromaticEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
+        if self.guide.current_step == 2 { return self.get_threshold().is_some(); }
         self.guide.is_current_input_valid()
     }
 
@@ -728,74 +1543,84 @@ impl EditProcessor for TrichromaticEdit {
     fn try_populate(&mut self) {
         if !self.guide.is_ready() { return; }
 
-        let base_color_hex_1_result = self.guide.steps[0].as_hex();
-        let base_color_hex_2_result = self.guide.steps[1].as_hex();
-        let base_color_hex_3_result = self.guide.steps[2].as_hex();
-        if base_color_hex_1_result.is_some() {
-            self.base_color_1_hex = base_color_hex_1_result.unwrap();
-            self.base_color_1_rgb = as_rgb(&self.base_color_1_hex).unwrap();
-        }
-        else { return; }
-        if base_color_hex_2_result.is_some() {
-            self.base_color_2_hex = base_color_hex_2_result.unwrap();
-            self.base_color_2_rgb = as_rgb(&self.base_color_2_hex).unwrap();
-        }
-        else { return; }
-        if base_color_hex_3_result.is_some() {
-            self.base_color_3_hex = base_color_hex_3_result.unwrap();
-            self.base_color_3_rgb = as_rgb(&self.base_color_3_hex).unwrap();
-        }
-        else { return; }
+        let Some(shadow_color_hex) = self.guide.steps[0].as_hex() else { return; };
+        let Some(highlight_color_hex) = self.guide.steps[1].as_hex() else { return; };
+        self.shadow_color_rgb = as_rgb(&shadow_color_hex).unwrap();
+        self.shadow_color_hex = shadow_color_hex;
+        self.highlight_color_rgb = as_rgb(&highlight_color_hex).unwrap();
+        self.highlight_color_hex = highlight_color_hex;
+        let Some(threshold) = self.get_threshold() else { return; };
+        self.threshold = threshold;
 
         self.is_ready = true;
     }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
-
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let line_spectrum_1 = get_line_spectrum(&self.base_color_1_rgb);
-            let line_spectrum_2 = get_line_spectrum(&self.base_color_2_rgb);
-            let line_spectrum_3 = get_line_spectrum(&self.base_color_3_rgb);
-            let mut spectrum = get_web_spectrum(&vec![line_spectrum_1, line_spectrum_2, line_spectrum_3]);
-            spectrum = condense_color_palette(&spectrum);
-
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum))
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
 
 
-/// Processes an image with a volcanic crater themed palette.
-pub struct VolcanicCraterEdit {
+/// Processes an image into grayscale toned with a shadow spectrum and a highlight spectrum that blend around a balance.
+pub struct SplitToningEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
+    /// The base color of the shadow spectrum being used as a hex value.
+    pub shadow_color_hex: String,
+    /// The base color of the shadow spectrum being used as an rgb color.
+    pub shadow_color_rgb: Rgb<u8>,
+    /// The base color of the highlight spectrum being used as a hex value.
+    pub highlight_color_hex: String,
+    /// The base color of the highlight spectrum being used as an rgb color.
+    pub highlight_color_rgb: Rgb<u8>,
+    /// The perceived brightness (0-1) around which the shadow and highlight tones blend.
+    pub balance: f32,
     /// The steps used to create the processor.
-    guide: ProcessingGuide,
+    pub guide: ProcessingGuide,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl VolcanicCraterEdit {
+impl SplitToningEdit {
     /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> VolcanicCraterEdit {
-        VolcanicCraterEdit {
+    pub fn new(source_image_path: PathBuf) -> SplitToningEdit {
+        SplitToningEdit {
             source_image_path,
+            shadow_color_rgb: Rgb([0, 0, 0]),
+            shadow_color_hex: "none".to_string(),
+            highlight_color_rgb: Rgb([0, 0, 0]),
+            highlight_color_hex: "none".to_string(),
+            balance: 0.5,
             guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Shadow Color (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Highlight Color (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Number, "Balance (0-1)".to_string()).with_range(NumberRange::between(0.0, 1.0)),
             ]),
             is_ready: false,
         }
     }
+
+    /// Returns the balance given in the last step, if it is within 0 and 1.
+    fn get_balance(&self) -> Option<f32> {
+        self.guide.steps[2].as_number().filter(|balance| (0.0..=1.0).contains(balance))
+    }
 }
-impl EditProcessor for VolcanicCraterEdit {
+impl EditProcessor for SplitToningEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        format!("{} {}-{} {}", name, self.shadow_color_hex.clone(), self.highlight_color_hex.clone(), self.balance)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![self.shadow_color_rgb, self.highlight_color_rgb]
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let colors = get_color_blind_safe_colors(&[self.shadow_color_rgb, self.highlight_color_rgb]);
+        PaletteMapping::Tone(get_split_tone_ramp(&colors[0], &colors[1], self.balance))
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -814,7 +1639,16 @@ impl EditProcessor for VolcanicCraterEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
+        if self.guide.current_step == 2 { return self.get_balance().is_some(); }
         self.guide.is_current_input_valid()
     }
 
@@ -823,52 +1657,90 @@ impl EditProcessor for VolcanicCraterEdit {
     }
 
     fn try_populate(&mut self) {
-        self.is_ready = true;
-    }
+        if !self.guide.is_ready() { return; }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+        let Some(shadow_color_hex) = self.guide.steps[0].as_hex() else { return; };
+        let Some(highlight_color_hex) = self.guide.steps[1].as_hex() else { return; };
+        self.shadow_color_rgb = as_rgb(&shadow_color_hex).unwrap();
+        self.shadow_color_hex = shadow_color_hex;
+        self.highlight_color_rgb = as_rgb(&highlight_color_hex).unwrap();
+        self.highlight_color_hex = highlight_color_hex;
+        let Some(balance) = self.get_balance() else { return; };
+        self.balance = balance;
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::volcanic_crater()));
-            spectrum = condense_color_palette(&spectrum);
+        self.is_ready = true;
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
 
 
-/// Processes an image with a red rocks themed palette.
-pub struct RedRocksEdit {
+/// Processes an image into a three-color spectrum blend.
+pub struct TrichromaticEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
+    /// The first base color of the spectrum being used as a hex value.
+    pub base_color_1_hex: String,
+    /// The first base color of the spectrum being used as an rgb color.
+    pub base_color_1_rgb: Rgb<u8>,
+    /// The second base color of the spectrum being used as a hex value.
+    pub base_color_2_hex: String,
+    /// The second base color of the spectrum being used as an rgb color.
+    pub base_color_2_rgb: Rgb<u8>,
+    /// The third base color of the spectrum being used as a hex value.
+    pub base_color_3_hex: String,
+    /// The third base color of the spectrum being used as an rgb color.
+    pub base_color_3_rgb: Rgb<u8>,
     /// The steps used to create the processor.
-    guide: ProcessingGuide,
+    pub guide: ProcessingGuide,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl RedRocksEdit {
+impl TrichromaticEdit {
     /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> RedRocksEdit {
-        RedRocksEdit {
+    pub fn new(source_image_path: PathBuf) -> TrichromaticEdit {
+        TrichromaticEdit {
             source_image_path,
+            base_color_1_rgb: Rgb([0, 0, 0]),
+            base_color_1_hex: "none".to_string(),
+            base_color_2_rgb: Rgb([0, 0, 0]),
+            base_color_2_hex: "none".to_string(),
+            base_color_3_rgb: Rgb([0, 0, 0]),
+            base_color_3_hex: "none".to_string(),
             guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Base Color 1 (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Base Color 2 (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Base Color 3 (HEX)".to_string()),
             ]),
             is_ready: false,
         }
     }
 }
-impl EditProcessor for RedRocksEdit {
+impl EditProcessor for TrichromaticEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        format!("{} {}-{}-{}", name, self.base_color_1_hex.clone(), self.base_color_2_hex.clone(), self.base_color_3_hex.clone())
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![self.base_color_1_rgb, self.base_color_2_rgb, self.base_color_3_rgb]
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let colors = get_color_blind_safe_colors(&[self.base_color_1_rgb, self.base_color_2_rgb, self.base_color_3_rgb]);
+        let line_spectrum_1 = get_line_spectrum(&colors[0]);
+        let line_spectrum_2 = get_line_spectrum(&colors[1]);
+        let line_spectrum_3 = get_line_spectrum(&colors[2]);
+        let mut spectrum = fill_spectrum_volume(&vec![line_spectrum_1, line_spectrum_2, line_spectrum_3]);
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -887,6 +1759,14 @@ impl EditProcessor for RedRocksEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
@@ -896,52 +1776,94 @@ impl EditProcessor for RedRocksEdit {
     }
 
     fn try_populate(&mut self) {
-        self.is_ready = true;
-    }
+        if !self.guide.is_ready() { return; }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+        let base_color_hex_1_result = self.guide.steps[0].as_hex();
+        let base_color_hex_2_result = self.guide.steps[1].as_hex();
+        let base_color_hex_3_result = self.guide.steps[2].as_hex();
+        if base_color_hex_1_result.is_some() {
+            self.base_color_1_hex = base_color_hex_1_result.unwrap();
+            self.base_color_1_rgb = as_rgb(&self.base_color_1_hex).unwrap();
+        }
+        else { return; }
+        if base_color_hex_2_result.is_some() {
+            self.base_color_2_hex = base_color_hex_2_result.unwrap();
+            self.base_color_2_rgb = as_rgb(&self.base_color_2_hex).unwrap();
+        }
+        else { return; }
+        if base_color_hex_3_result.is_some() {
+            self.base_color_3_hex = base_color_hex_3_result.unwrap();
+            self.base_color_3_rgb = as_rgb(&self.base_color_3_hex).unwrap();
+        }
+        else { return; }
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::red_rocks()));
-            spectrum = condense_color_palette(&spectrum);
+        self.is_ready = true;
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
 
 
-/// Processes an image with a deepest africa themed palette.
-pub struct DeepestAfricaEdit {
+/// Processes an image into a web spectrum between any number of colors.
+pub struct PolychromaticEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
-    /// The steps used to create the processor.
-    guide: ProcessingGuide,
+    /// The base colors of the spectrum being used as hex values.
+    pub base_colors_hex: Vec<String>,
+    /// The base colors of the spectrum being used as rgb colors.
+    pub base_colors_rgb: Vec<Rgb<u8>>,
+    /// The steps used to create the processor, which grow to one color step per color once the number of colors is given.
+    pub guide: ProcessingGuide,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl DeepestAfricaEdit {
+impl PolychromaticEdit {
     /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> DeepestAfricaEdit {
-        DeepestAfricaEdit {
+    pub fn new(source_image_path: PathBuf) -> PolychromaticEdit {
+        let mut steps = vec![ProcessingStep::new(ProcessingStepTypes::Number, "Number of Colors".to_string()).with_range(NumberRange::whole(min_polychromatic_colors(), max_polychromatic_colors()))];
+        steps.extend(PolychromaticEdit::get_color_steps(min_polychromatic_colors()));
+        PolychromaticEdit {
             source_image_path,
-            guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
-            ]),
+            base_colors_hex: Vec::new(),
+            base_colors_rgb: Vec::new(),
+            guide: ProcessingGuide::new(steps),
             is_ready: false,
         }
     }
+
+    /// Returns a color step for each of a given number of colors.
+    fn get_color_steps(count: usize) -> Vec<ProcessingStep> {
+        (1..=count).map(|index| ProcessingStep::new(ProcessingStepTypes::Color, format!("Base Color {} (HEX)", index))).collect()
+    }
+
+    /// Returns the number of colors given in the first step, if it is a whole number within the limits.
+    fn get_color_count(&self) -> Option<usize> {
+        let count = self.guide.steps[0].as_number()?;
+        let is_valid = count.fract() == 0.0 && (min_polychromatic_colors() as f32..=max_polychromatic_colors() as f32).contains(&count);
+        is_valid.then_some(count as usize)
+    }
 }
-impl EditProcessor for DeepestAfricaEdit {
+impl EditProcessor for PolychromaticEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        format!("{} {}", name, self.base_colors_hex.join("-"))
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.base_colors_rgb.clone()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&self.base_colors_rgb)));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -960,8 +1882,1394 @@ impl EditProcessor for DeepestAfricaEdit {
         self.guide.update_current_input(new_input)
     }
 
-    fn is_current_step_input_valid(&self) -> bool {
-        self.guide.is_current_input_valid()
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn set_step_inputs(&mut self, inputs: Vec<String>) {
+        // the color steps are only known once the number of colors is
+        self.guide.set_inputs(inputs.clone());
+        if let Some(count) = self.get_color_count() { self.guide.replace_steps_after(1, PolychromaticEdit::get_color_steps(count)); }
+        self.guide.set_inputs(inputs)
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        if self.guide.current_step == 0 { return self.get_color_count().is_some(); }
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if !self.is_current_step_input_valid() { return; }
+        if self.guide.current_step == 0 && let Some(count) = self.get_color_count() {
+            self.guide.replace_steps_after(1, PolychromaticEdit::get_color_steps(count));
+        }
+        self.guide.try_finish_current_step();
+    }
+
+    fn try_populate(&mut self) {
+        if !self.guide.is_ready() { return; }
+
+        let Some(count) = self.get_color_count() else { return; };
+        let base_colors_hex: Option<Vec<String>> = self.guide.steps[1..=count].iter().map(|step| step.as_hex()).collect();
+        let Some(base_colors_hex) = base_colors_hex else { return; };
+        self.base_colors_rgb = base_colors_hex.iter().map(|hex| as_rgb(hex).unwrap()).collect();
+        self.base_colors_hex = base_colors_hex;
+
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with a seed color and the companion colors of a color harmony.
+pub struct HarmonyEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The seed color of the harmony being used as a hex value.
+    pub seed_color_hex: String,
+    /// The seed color of the harmony being used as an rgb color.
+    pub seed_color_rgb: Rgb<u8>,
+    /// The harmony the companion colors are generated with.
+    pub harmony: Harmonies,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl HarmonyEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> HarmonyEdit {
+        HarmonyEdit {
+            source_image_path,
+            seed_color_hex: "none".to_string(),
+            seed_color_rgb: Rgb([0, 0, 0]),
+            harmony: Harmonies::Complementary,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::Color, "Seed Color (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Number, "Harmony (1 Complementary, 2 Triadic, 3 Analogous, 4 Split-Complementary)".to_string()).with_range(NumberRange::whole(1, 4)),
+            ]),
+            is_ready: false,
+        }
+    }
+
+    /// Returns the harmony chosen in the step, if the input is the number of one.
+    fn get_harmony(&self) -> Option<Harmonies> {
+        match self.guide.steps[1].as_number()? {
+            1.0 => Some(Harmonies::Complementary),
+            2.0 => Some(Harmonies::Triadic),
+            3.0 => Some(Harmonies::Analogous),
+            4.0 => Some(Harmonies::SplitComplementary),
+            _ => None,
+        }
+    }
+}
+impl EditProcessor for HarmonyEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{} {} {}", name, self.harmony.name(), self.seed_color_hex.clone())
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.harmony.colors(&self.seed_color_rgb)
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&self.harmony.colors(&self.seed_color_rgb))));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        match self.guide.current_step {
+            1 => self.get_harmony().is_some(),
+            _ => self.guide.is_current_input_valid(),
+        }
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        if !self.guide.is_ready() { return; }
+
+        let Some(seed_color_hex) = self.guide.steps[0].as_hex() else { return; };
+        let Some(harmony) = self.get_harmony() else { return; };
+        self.seed_color_rgb = as_rgb(&seed_color_hex).unwrap();
+        self.seed_color_hex = seed_color_hex;
+        self.harmony = harmony;
+
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with a volcanic crater themed palette.
+pub struct VolcanicCraterEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl VolcanicCraterEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> VolcanicCraterEdit {
+        VolcanicCraterEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for VolcanicCraterEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::volcanic_crater()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::volcanic_crater())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with a red rocks themed palette.
+pub struct RedRocksEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl RedRocksEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> RedRocksEdit {
+        RedRocksEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for RedRocksEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::red_rocks()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::red_rocks())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with a deepest africa themed palette.
+pub struct DeepestAfricaEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl DeepestAfricaEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> DeepestAfricaEdit {
+        DeepestAfricaEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for DeepestAfricaEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::deepest_africa()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::deepest_africa())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with an arctic wilderness themed palette.
+pub struct ArcticWildernessEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl ArcticWildernessEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> ArcticWildernessEdit {
+        ArcticWildernessEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for ArcticWildernessEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::arctic_wilderness()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::arctic_wilderness())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with an iceland themed palette.
+pub struct IcelandEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl IcelandEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> IcelandEdit {
+        IcelandEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for IcelandEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::iceland()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::iceland())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with an english oaks themed palette.
+pub struct EnglishOaksEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl EnglishOaksEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> EnglishOaksEdit {
+        EnglishOaksEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for EnglishOaksEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::english_oaks()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::english_oaks())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with a wheat field themed palette.
+pub struct WheatFieldEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl WheatFieldEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> WheatFieldEdit {
+        WheatFieldEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for WheatFieldEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::wheat_field()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::wheat_field())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with a south american jungle themed palette.
+pub struct SouthAmericanJungleEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl SouthAmericanJungleEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> SouthAmericanJungleEdit {
+        SouthAmericanJungleEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for SouthAmericanJungleEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::south_american_jungle()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::south_american_jungle())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with a european islands themed palette.
+pub struct EuropeanIslandsEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl EuropeanIslandsEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> EuropeanIslandsEdit {
+        EuropeanIslandsEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for EuropeanIslandsEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::european_islands()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::european_islands())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image with a colorful islands themed palette.
+pub struct ColorfulIslandsEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl ColorfulIslandsEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> ColorfulIslandsEdit {
+        ColorfulIslandsEdit {
+            source_image_path,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for ColorfulIslandsEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{}", name)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        palettes::colorful_islands()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&palettes::colorful_islands())));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+/// Processes an image into the colors of a palette loaded from a file.
+pub struct CustomPaletteEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The path of the palette file.
+    pub palette_path: PathBuf,
+    /// The colors loaded from the palette file.
+    pub colors: Vec<Rgb<u8>>,
+    /// Whether each color loaded from the palette file is biased.
+    pub bias_flags: Vec<bool>,
+    /// The path last given in the step with the palette file loaded from it, so the file is only read again once the path changes.
+    loaded_file: RefCell<Option<(PathBuf, PaletteFile)>>,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl CustomPaletteEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> CustomPaletteEdit {
+        CustomPaletteEdit {
+            source_image_path,
+            palette_path: PathBuf::new(),
+            colors: Vec::new(),
+            bias_flags: Vec::new(),
+            loaded_file: RefCell::new(None),
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::FilePath, "Palette File (path)".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+
+    /// Returns the colors of the palette file given in the step with whether each is biased, if it exists and holds any.
+    /// The file is kept until another path is given, since the input is checked again on every key press.
+    fn get_flagged_colors(&self) -> Option<Vec<(Rgb<u8>, bool)>> {
+        let palette_path = self.guide.steps[0].as_path()?;
+        if let Some((loaded_path, palette_file)) = &*self.loaded_file.borrow() && *loaded_path == palette_path { return Some(palette_file.get_flagged_colors()); }
+
+        let palette_file = load_palette_definition(&palette_path).ok()?;
+        let flagged_colors = palette_file.get_flagged_colors();
+        *self.loaded_file.borrow_mut() = Some((palette_path, palette_file));
+        Some(flagged_colors)
+    }
+}
+impl EditProcessor for CustomPaletteEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{} {}", name, self.palette_path.file_stem().unwrap_or_default().to_string_lossy())
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.colors.clone()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        get_palette_file_mapping(&self.colors, &self.bias_flags)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.get_flagged_colors().is_some()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        if !self.guide.is_ready() { return; }
+
+        let (Some(palette_path), Some(flagged_colors)) = (self.guide.steps[0].as_path(), self.get_flagged_colors()) else { return; };
+        self.palette_path = palette_path;
+        (self.colors, self.bias_flags) = flagged_colors.into_iter().unzip();
+
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+/// Processes an image into the colors of a palette from the palette directory.
+pub struct UserPaletteEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The index of the palette in the loaded palettes.
+    palette: usize,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl UserPaletteEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf, palette: usize) -> UserPaletteEdit {
+        UserPaletteEdit {
+            source_image_path,
+            palette,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+            ]),
+            is_ready: false,
+        }
+    }
+}
+impl EditProcessor for UserPaletteEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        name
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        get_user_palettes()[self.palette].colors.clone()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let user_palette = &get_user_palettes()[self.palette];
+        get_palette_file_mapping(&user_palette.colors, &user_palette.bias_flags)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.guide.is_current_input_valid()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        // a palette that failed to load has no colors to process with
+        if get_user_palettes()[self.palette].error.is_some() { return; }
+
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+
+
+/// Processes an image into its own best colors, found by clustering its pixels (k-means).
+pub struct QuantizeEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// The number of colors the image is reduced to.
+    pub color_count: usize,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl QuantizeEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> QuantizeEdit {
+        QuantizeEdit {
+            source_image_path,
+            color_count: min_quantized_colors(),
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::Number, "Number of Colors (2-256)".to_string()).with_range(NumberRange::whole(min_quantized_colors(), max_quantized_colors())),
+            ]),
+            is_ready: false,
+        }
+    }
+
+    /// Returns the number of colors given in the step, if it is a whole number within the limits.
+    fn get_color_count(&self) -> Option<usize> {
+        let count = self.guide.steps[0].as_number()?;
+        let is_valid = count.fract() == 0.0 && (min_quantized_colors() as f32..=max_quantized_colors() as f32).contains(&count);
+        is_valid.then_some(count as usize)
+    }
+
+    /// Returns the best colors of an image for the number of colors.
+    fn get_clusters(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        let pixels: Vec<Rgb<u8>> = source_image.pixels().map(|pixel| pixel.2.to_rgb()).collect();
+        get_color_clusters(&pixels, self.color_count)
+    }
+}
+impl EditProcessor for QuantizeEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{} {}", name, self.color_count)
+    }
+
+    fn get_base_colors(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.get_clusters(source_image)
+    }
+
+    fn get_palette_mapping(&self, source_image: &DynamicImage) -> PaletteMapping {
+        // the clusters are the palette, so no spectrums are woven between them
+        PaletteMapping::Even(self.get_clusters(source_image))
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.get_color_count().is_some()
+    }
+
+    fn try_finish_current_step(&mut self) {
+        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+    }
+
+    fn try_populate(&mut self) {
+        if !self.guide.is_ready() { return; }
+
+        let Some(color_count) = self.get_color_count() else { return; };
+        self.color_count = color_count;
+
+        self.is_ready = true;
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
+
+
+
+/// Processes an image into a warm-brown sepia spectrum with an adjustable intensity.
+pub struct SepiaEdit {
+    /// The path of the original image to be processed.
+    source_image_path: PathBuf,
+    /// How strongly (0-1) the sepia browns replace neutral greys.
+    pub intensity: f32,
+    /// The steps used to create the processor.
+    guide: ProcessingGuide,
+    /// Tracks if the processor is ready.
+    is_ready: bool,
+}
+impl SepiaEdit {
+    /// Returns a new processor ready to be set up.
+    pub fn new(source_image_path: PathBuf) -> SepiaEdit {
+        SepiaEdit {
+            source_image_path,
+            intensity: 1.0,
+            guide: ProcessingGuide::new(vec![
+                ProcessingStep::new(ProcessingStepTypes::Number, "Intensity (0-1)".to_string()).with_range(NumberRange::between(0.0, 1.0)),
+            ]),
+            is_ready: false,
+        }
+    }
+
+    /// Returns the intensity given in the step, if it is within 0 and 1.
+    fn get_intensity(&self) -> Option<f32> {
+        self.guide.steps[0].as_number().filter(|intensity| (0.0..=1.0).contains(intensity))
+    }
+
+    /// Returns the sepia browns, each blended towards the grey of the same brightness as the intensity drops.
+    fn get_colors(&self) -> Vec<Rgb<u8>> {
+        palettes::sepia().iter().map(|color| {
+            let brightness = get_brightness(color).round() as u8;
+            SpectrumSpaces::Rgb.interpolate(&Rgb([brightness, brightness, brightness]), color, self.intensity)
+        }).collect()
+    }
+}
+impl EditProcessor for SepiaEdit {
+    fn get_descriptor(&self, name: String) -> String {
+        format!("{} {}", name, self.intensity)
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.get_colors()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        let mut spectrum = get_web_spectrum(&get_line_spectrums(&self.get_colors()));
+        spectrum = condense_color_palette(&spectrum);
+
+        PaletteMapping::Even(spectrum)
+    }
+
+    fn get_current_step_type(&self) -> ProcessingStepTypes {
+        self.guide.get_current_step_type()
+    }
+
+    fn get_current_step_label(&self) -> String {
+        self.guide.get_current_label()
+    }
+
+    fn get_current_step_input(&self) -> String {
+        self.guide.get_current_input()
+    }
+
+    fn update_current_step_input(&mut self, new_input: String) {
+        self.guide.update_current_input(new_input)
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn is_current_step_input_valid(&self) -> bool {
+        self.get_intensity().is_some()
     }
 
     fn try_finish_current_step(&mut self) {
@@ -969,52 +3277,81 @@ impl EditProcessor for DeepestAfricaEdit {
     }
 
     fn try_populate(&mut self) {
-        self.is_ready = true;
-    }
+        if !self.guide.is_ready() { return; }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+        let Some(intensity) = self.get_intensity() else { return; };
+        self.intensity = intensity;
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::deepest_africa()));
-            spectrum = condense_color_palette(&spectrum);
+        self.is_ready = true;
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
 
 
-/// Processes an image with an arctic wilderness themed palette.
-pub struct ArcticWildernessEdit {
+/// Processes an image into the look of a film stock with its curated colors and contrast.
+pub struct FilmStockEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
+    /// The film stock being emulated.
+    pub stock: FilmStocks,
     /// The steps used to create the processor.
     guide: ProcessingGuide,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl ArcticWildernessEdit {
+impl FilmStockEdit {
     /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> ArcticWildernessEdit {
-        ArcticWildernessEdit {
+    pub fn new(source_image_path: PathBuf) -> FilmStockEdit {
+        FilmStockEdit {
             source_image_path,
+            stock: FilmStocks::Portra,
             guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Number, "Film Stock (1 Portra, 2 Ektachrome, 3 Kodachrome, 4 Tri-X)".to_string()).with_range(NumberRange::whole(1, 4)),
             ]),
             is_ready: false,
         }
     }
+
+    /// Returns the film stock chosen in the step, if the input is the number of one.
+    fn get_stock(&self) -> Option<FilmStocks> {
+        match self.guide.steps[0].as_number()? {
+            1.0 => Some(FilmStocks::Portra),
+            2.0 => Some(FilmStocks::Ektachrome),
+            3.0 => Some(FilmStocks::Kodachrome),
+            4.0 => Some(FilmStocks::TriX),
+            _ => None,
+        }
+    }
 }
-impl EditProcessor for ArcticWildernessEdit {
+impl EditProcessor for FilmStockEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        format!("{} {}", name, self.stock.name())
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.stock.colors()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        // black and white stocks are toned by brightness, color stocks are woven like the pre-configured palettes
+        let palette_mapping = if self.stock.is_monochrome() {
+            PaletteMapping::Tone(get_gradient_ramp(&self.stock.colors()))
+        }
+        else {
+            let mut spectrum = get_web_spectrum(&get_line_spectrums(&get_color_blind_safe_colors(&self.stock.colors())));
+            spectrum = condense_color_palette(&spectrum);
+            PaletteMapping::Even(spectrum)
+        };
+
+        PaletteMapping::Transformed(Box::new(palette_mapping), Transform::Contrast(self.stock.contrast()))
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -1033,8 +3370,16 @@ impl EditProcessor for ArcticWildernessEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
-        self.guide.is_current_input_valid()
+        self.get_stock().is_some()
     }
 
     fn try_finish_current_step(&mut self) {
@@ -1042,52 +3387,70 @@ impl EditProcessor for ArcticWildernessEdit {
     }
 
     fn try_populate(&mut self) {
-        self.is_ready = true;
-    }
+        if !self.guide.is_ready() { return; }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+        let Some(stock) = self.get_stock() else { return; };
+        self.stock = stock;
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::arctic_wilderness()));
-            spectrum = condense_color_palette(&spectrum);
+        self.is_ready = true;
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
 
 
-/// Processes an image with an iceland themed palette.
-pub struct IcelandEdit {
+/// Processes an image into false colors by mapping the brightness of every pixel onto a perceptual heat ramp.
+pub struct FalseColorEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
+    /// The heat ramp being used.
+    pub ramp: HeatRamps,
     /// The steps used to create the processor.
     guide: ProcessingGuide,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl IcelandEdit {
+impl FalseColorEdit {
     /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> IcelandEdit {
-        IcelandEdit {
+    pub fn new(source_image_path: PathBuf) -> FalseColorEdit {
+        FalseColorEdit {
             source_image_path,
+            ramp: HeatRamps::Viridis,
             guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Number, "Ramp (1 Viridis, 2 Magma, 3 Inferno)".to_string()).with_range(NumberRange::whole(1, 3)),
             ]),
             is_ready: false,
         }
     }
+
+    /// Returns the ramp chosen in the step, if the input is the number of one.
+    fn get_ramp(&self) -> Option<HeatRamps> {
+        match self.guide.steps[0].as_number()? {
+            1.0 => Some(HeatRamps::Viridis),
+            2.0 => Some(HeatRamps::Magma),
+            3.0 => Some(HeatRamps::Inferno),
+            _ => None,
+        }
+    }
 }
-impl EditProcessor for IcelandEdit {
+impl EditProcessor for FalseColorEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        format!("{} {}", name, self.ramp.name())
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.ramp.stops()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        PaletteMapping::Tone(get_gradient_ramp(&self.ramp.stops()))
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -1106,8 +3469,16 @@ impl EditProcessor for IcelandEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
-        self.guide.is_current_input_valid()
+        self.get_ramp().is_some()
     }
 
     fn try_finish_current_step(&mut self) {
@@ -1115,30 +3486,27 @@ impl EditProcessor for IcelandEdit {
     }
 
     fn try_populate(&mut self) {
-        self.is_ready = true;
-    }
+        if !self.guide.is_ready() { return; }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+        let Some(ramp) = self.get_ramp() else { return; };
+        self.ramp = ramp;
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::iceland()));
-            spectrum = condense_color_palette(&spectrum);
+        self.is_ready = true;
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
 
 
-/// Processes an image with an english oaks themed palette.
-pub struct EnglishOaksEdit {
+/// Processes an image into grayscale while keeping the accent color of the image in color, like a color pop.
+pub struct ColorPopEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
     /// The steps used to create the processor.
@@ -1146,10 +3514,10 @@ pub struct EnglishOaksEdit {
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl EnglishOaksEdit {
+impl ColorPopEdit {
     /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> EnglishOaksEdit {
-        EnglishOaksEdit {
+    pub fn new(source_image_path: PathBuf) -> ColorPopEdit {
+        ColorPopEdit {
             source_image_path,
             guide: ProcessingGuide::new(vec![
                 ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
@@ -1158,9 +3526,23 @@ impl EnglishOaksEdit {
         }
     }
 }
-impl EditProcessor for EnglishOaksEdit {
+impl EditProcessor for ColorPopEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        name
+    }
+
+    fn get_base_colors(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        vec![get_accent_color(source_image)]
+    }
+
+    fn get_palette_mapping(&self, source_image: &DynamicImage) -> PaletteMapping {
+        let mut accent_spectrum = get_line_spectrum(&get_accent_color(source_image));
+        accent_spectrum = condense_color_palette(&accent_spectrum);
+        let mut gray_spectrum = get_line_spectrum(&Rgb([128, 128, 128]));
+        gray_spectrum = condense_color_palette(&gray_spectrum);
+
+        // the first palette is held back by the bias, so only pixels clearly closer to the accent than to any gray keep their color
+        PaletteMapping::Biased(accent_spectrum, gray_spectrum)
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -1179,6 +3561,14 @@ impl EditProcessor for EnglishOaksEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
@@ -1191,122 +3581,371 @@ impl EditProcessor for EnglishOaksEdit {
         self.is_ready = true;
     }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::english_oaks()));
-            spectrum = condense_color_palette(&spectrum);
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
+
+/// The processors that change an image around the palettes of another processor, which are all set up as a transform edit.
+#[derive(Clone, Copy, PartialEq)]
+pub enum TransformKinds {
+    Invert,
+    HueRotation,
+    Pixelate,
+    ChromaKey,
+    PreserveLuminance,
+}
+impl TransformKinds {
+    /// Returns the processor in the processor list a given kind of transform is set up by.
+    fn processor(&self) -> Processors {
+        match self {
+            TransformKinds::Invert => Processors::Invert,
+            TransformKinds::HueRotation => Processors::HueRotation,
+            TransformKinds::Pixelate => Processors::Pixelate,
+            TransformKinds::ChromaKey => Processors::ChromaKey,
+            TransformKinds::PreserveLuminance => Processors::PreserveLuminance,
+        }
+    }
+
+    /// Returns the steps setting up a given kind of transform, which come before the step choosing the palette processor.
+    fn steps(&self) -> Vec<ProcessingStep> {
+        match self {
+            TransformKinds::Invert | TransformKinds::PreserveLuminance => Vec::new(),
+            TransformKinds::HueRotation => vec![ProcessingStep::new(ProcessingStepTypes::Number, "Hue Rotation (degrees)".to_string()).with_range(NumberRange::between(0.0, 360.0))],
+            TransformKinds::Pixelate => vec![ProcessingStep::new(ProcessingStepTypes::Number, "Block Size (pixels)".to_string()).with_range(NumberRange::whole(1, max_pixel_block_size() as usize))],
+            TransformKinds::ChromaKey => vec![
+                ProcessingStep::new(ProcessingStepTypes::Color, "Key Color (HEX)".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::Number, "Tolerance (0-1)".to_string()).with_range(NumberRange::between(0.0, 1.0)),
+                ProcessingStep::new(ProcessingStepTypes::Color, "Fill Color (HEX)".to_string()),
+            ],
+        }
+    }
+
+    /// Returns the transform of a given kind before its steps are filled.
+    fn default_transform(&self) -> Transform {
+        match self {
+            TransformKinds::Invert => Transform::Invert,
+            TransformKinds::HueRotation => Transform::HueRotate(0.0),
+            TransformKinds::Pixelate => Transform::Pixelate(1),
+            TransformKinds::ChromaKey => Transform::Key(Rgb([0, 0, 0]), 0.0, Rgb([0, 0, 0])),
+            TransformKinds::PreserveLuminance => Transform::PreserveLuminance,
         }
+    }
 
-        None
+    /// Returns if a given kind of transform changes the image on its own, so it can be used without a palette.
+    fn is_palette_optional(&self) -> bool {
+        !matches!(self, TransformKinds::PreserveLuminance)
     }
 }
 
 
 
-/// Processes an image with a wheat field themed palette.
-pub struct WheatFieldEdit {
+/// Processes an image by changing it around the palettes of another processor (e.g. inverting it before they are applied),
+/// or by only changing it if the transform works without a palette.
+pub struct TransformEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
-    /// The steps used to create the processor.
+    /// The kind of transform being set up.
+    kind: TransformKinds,
+    /// The transform being used.
+    pub transform: Transform,
+    /// The index of the processor whose palettes the transformed image is mapped onto, if any.
+    pub palette_selection: Option<usize>,
+    /// The processor whose palettes the transformed image is mapped onto, which takes over the remaining steps.
+    pub palette_processor: Option<Box<dyn EditProcessor>>,
+    /// The steps setting up the transform, followed by the step choosing the palette processor by name.
     guide: ProcessingGuide,
+    /// Tracks if the palette processor is chosen, after which its steps are the current ones.
+    is_palette_chosen: bool,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl WheatFieldEdit {
-    /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> WheatFieldEdit {
-        WheatFieldEdit {
+impl TransformEdit {
+    /// Returns a new processor of a given kind ready to be set up.
+    pub fn new(source_image_path: PathBuf, kind: TransformKinds) -> TransformEdit {
+        let palette_label = if kind.is_palette_optional() { "Palette Processor (name or none)" } else { "Palette Processor (name)" };
+        let mut steps = kind.steps();
+        steps.push(ProcessingStep::new(ProcessingStepTypes::Name, palette_label.to_string()));
+        TransformEdit {
             source_image_path,
-            guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
-            ]),
+            kind,
+            transform: kind.default_transform(),
+            palette_selection: None,
+            palette_processor: None,
+            guide: ProcessingGuide::new(steps),
+            is_palette_chosen: false,
             is_ready: false,
         }
     }
+
+    /// Returns the index of the step choosing the palette processor, which is the last one.
+    fn palette_step(&self) -> usize {
+        self.guide.steps.len() - 1
+    }
+
+    /// Returns the rotation given in a step in degrees, if it is a finite number.
+    fn as_degrees(step: &ProcessingStep) -> Option<f32> {
+        step.as_number().filter(|degrees| degrees.is_finite())
+    }
+
+    /// Returns the block size given in a step, if it is a whole number within the limits.
+    fn as_block_size(step: &ProcessingStep) -> Option<u32> {
+        let block_size = step.as_number()?;
+        let is_valid = block_size.fract() == 0.0 && (1.0..=max_pixel_block_size() as f32).contains(&block_size);
+        is_valid.then_some(block_size as u32)
+    }
+
+    /// Returns the tolerance given in a step, if it is within 0 and 1.
+    fn as_tolerance(step: &ProcessingStep) -> Option<f32> {
+        step.as_number().filter(|tolerance| (0.0..=1.0).contains(tolerance))
+    }
+
+    /// Returns the transform set up by the steps before the palette step, if their inputs are valid.
+    fn get_transform(&self) -> Option<Transform> {
+        let steps = &self.guide.steps;
+        match self.kind {
+            TransformKinds::HueRotation => TransformEdit::as_degrees(&steps[0]).map(Transform::HueRotate),
+            TransformKinds::Pixelate => TransformEdit::as_block_size(&steps[0]).map(Transform::Pixelate),
+            TransformKinds::ChromaKey => {
+                let key_color = steps[0].as_hex().and_then(|hex| as_rgb(&hex))?;
+                let fill_color = steps[2].as_hex().and_then(|hex| as_rgb(&hex))?;
+                TransformEdit::as_tolerance(&steps[1]).map(|tolerance| Transform::Key(key_color, tolerance, fill_color))
+            }
+            TransformKinds::Invert | TransformKinds::PreserveLuminance => Some(self.kind.default_transform()),
+        }
+    }
+
+    /// Checks if the input is valid for the current step, which is one of the steps setting up the transform.
+    fn is_transform_step_valid(&self) -> bool {
+        let step = &self.guide.steps[self.guide.current_step];
+        match (self.kind, self.guide.current_step) {
+            (TransformKinds::HueRotation, _) => TransformEdit::as_degrees(step).is_some(),
+            (TransformKinds::Pixelate, _) => TransformEdit::as_block_size(step).is_some(),
+            (TransformKinds::ChromaKey, 1) => TransformEdit::as_tolerance(step).is_some(),
+            _ => self.guide.is_current_input_valid(),
+        }
+    }
+
+    /// Returns the processor named in the palette step as its index in the processor list (None for no palette),
+    /// or None as the outer value if the input doesn't name another processor, or is none where a palette is needed.
+    /// Processors are chosen by name only, so the inputs of a run stay the same when palettes, shaders or plugins are added to the list.
+    fn get_chosen_selection(&self) -> Option<Option<usize>> {
+        let name = self.guide.steps[self.palette_step()].as_name()?;
+        if name.eq_ignore_ascii_case("none") { return self.kind.is_palette_optional().then_some(None); }
+        let selection = Processors::find_by_name(&name)?;
+        if Processors::get_processor(selection).name() == self.kind.processor().name() { return None; }
+        Some(Some(selection))
+    }
+
+    /// Creates the palette processor chosen in the palette step, unless it is already set up (e.g. with the inputs of the last run).
+    fn choose_palette(&mut self) {
+        let Some(selection) = self.get_chosen_selection() else { return; };
+        if self.palette_selection != selection || (selection.is_some() && self.palette_processor.is_none()) {
+            self.palette_processor = selection.map(|selection| Processors::get_processor(selection).create_edit(self.source_image_path.clone()));
+            self.palette_selection = selection;
+        }
+        self.is_palette_chosen = true;
+    }
+
+    /// Returns an image changed the way it is before the palettes are applied, which automatic palettes are built from since that is what they are mapped onto.
+    fn get_transformed_image(&self, source_image: &DynamicImage) -> DynamicImage {
+        DynamicImage::ImageRgb8(self.transform.before(&source_image.to_rgb8()).into_owned())
+    }
 }
-impl EditProcessor for WheatFieldEdit {
+impl EditProcessor for TransformEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        let descriptor = match &self.transform {
+            Transform::HueRotate(degrees) => format!("{} {}", name, degrees),
+            Transform::Pixelate(block_size) => format!("{} {}", name, block_size),
+            Transform::Key(key_color, tolerance, fill_color) => format!("{} {}-{} {}", name, as_hex(key_color), as_hex(fill_color), tolerance),
+            Transform::Invert | Transform::Contrast(_) | Transform::PreserveLuminance => name,
+        };
+        match (&self.palette_processor, self.palette_selection) {
+            (Some(palette_processor), Some(selection)) => format!("{} {}", descriptor, palette_processor.get_descriptor(Processors::get_processor(selection).name())),
+            _ => descriptor,
+        }
+    }
+
+    fn get_base_colors(&self, source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        match &self.palette_processor {
+            Some(palette_processor) => palette_processor.get_base_colors(&self.get_transformed_image(source_image)),
+            None => Vec::new(),
+        }
+    }
+
+    fn get_palette_mapping(&self, source_image: &DynamicImage) -> PaletteMapping {
+        let palette_mapping = match &self.palette_processor {
+            Some(palette_processor) => palette_processor.get_palette_mapping(&self.get_transformed_image(source_image)),
+            None => PaletteMapping::Unchanged,
+        };
+        PaletteMapping::Transformed(Box::new(palette_mapping), self.transform.clone())
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
-        self.guide.get_current_step_type()
+        match &self.palette_processor {
+            Some(palette_processor) if self.is_palette_chosen => palette_processor.get_current_step_type(),
+            _ => self.guide.get_current_step_type(),
+        }
     }
 
     fn get_current_step_label(&self) -> String {
-        self.guide.get_current_label()
+        match &self.palette_processor {
+            Some(palette_processor) if self.is_palette_chosen => palette_processor.get_current_step_label(),
+            _ => self.guide.get_current_label(),
+        }
     }
 
     fn get_current_step_input(&self) -> String {
-        self.guide.get_current_input()
+        match &self.palette_processor {
+            Some(palette_processor) if self.is_palette_chosen => palette_processor.get_current_step_input(),
+            _ => self.guide.get_current_input(),
+        }
+    }
+
+    fn get_current_step_range(&self) -> Option<NumberRange> {
+        match &self.palette_processor {
+            Some(palette_processor) if self.is_palette_chosen => palette_processor.get_current_step_range(),
+            _ => self.guide.get_current_range(),
+        }
     }
 
     fn update_current_step_input(&mut self, new_input: String) {
-        self.guide.update_current_input(new_input)
+        match &mut self.palette_processor {
+            Some(palette_processor) if self.is_palette_chosen => palette_processor.update_current_step_input(new_input),
+            _ => self.guide.update_current_input(new_input),
+        }
+    }
+
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
+    fn get_step_inputs(&self) -> Vec<String> {
+        let mut inputs = self.guide.get_inputs();
+        if let Some(palette_processor) = &self.palette_processor { inputs.extend(palette_processor.get_step_inputs()); }
+        inputs
+    }
+
+    fn set_step_inputs(&mut self, inputs: Vec<String>) {
+        // the inputs up to the palette step set up the transform and choose the palette processor, which takes the rest
+        let own_inputs = self.guide.steps.len();
+        self.guide.set_inputs(inputs.iter().take(own_inputs).cloned().collect());
+        let Some(selection) = self.get_chosen_selection() else { return; };
+        self.palette_processor = selection.map(|selection| Processors::get_processor(selection).create_edit(self.source_image_path.clone()));
+        self.palette_selection = selection;
+        if let Some(palette_processor) = &mut self.palette_processor { palette_processor.set_step_inputs(inputs.into_iter().skip(own_inputs).collect()); }
     }
 
     fn is_current_step_input_valid(&self) -> bool {
-        self.guide.is_current_input_valid()
+        match &self.palette_processor {
+            Some(palette_processor) if self.is_palette_chosen => palette_processor.is_current_step_input_valid(),
+            _ if self.guide.current_step < self.palette_step() => self.is_transform_step_valid(),
+            _ => self.get_chosen_selection().is_some(),
+        }
     }
 
     fn try_finish_current_step(&mut self) {
-        if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); }
+        let palette_step = self.palette_step();
+        match &mut self.palette_processor {
+            Some(palette_processor) if self.is_palette_chosen => palette_processor.try_finish_current_step(),
+            _ if self.guide.current_step < palette_step => if self.is_current_step_input_valid() { self.guide.try_finish_current_step(); },
+            _ => self.choose_palette(),
+        }
     }
 
     fn try_populate(&mut self) {
+        if !self.is_palette_chosen { return; }
+
+        let Some(transform) = self.get_transform() else { return; };
+        self.transform = transform;
+        if let Some(palette_processor) = &mut self.palette_processor {
+            palette_processor.try_populate();
+            if !palette_processor.is_ready() { return; }
+        }
+
         self.is_ready = true;
     }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::wheat_field()));
-            spectrum = condense_color_palette(&spectrum);
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
+    }
+}
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
-        }
 
-        None
-    }
+
+/// Returns the steps asking for the parameters of an effect shader or plugin, followed by a last step to confirm.
+fn get_parameter_steps(parameters: &[ShaderParameter]) -> Vec<ProcessingStep> {
+    let mut steps: Vec<ProcessingStep> = parameters.iter()
+        .map(|parameter| ProcessingStep::new(parameter.step_type.clone(), parameter.label.clone()))
+        .collect();
+    steps.push(ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()));
+    steps
 }
 
+/// Returns the values of the parameters of an effect shader or plugin from the inputs of their steps, four floats each
+/// (colors as rgb from 0 to 1, numbers in the first float), or None if an input is invalid.
+fn get_parameter_values(guide: &ProcessingGuide, parameters: &[ShaderParameter]) -> Option<Vec<[f32; 4]>> {
+    guide.steps.iter().zip(parameters).map(|(step, parameter)| match parameter.step_type {
+        ProcessingStepTypes::Color => step.as_hex().and_then(|hex| as_rgb(&hex)).map(|color| [color[0] as f32 / 255.0, color[1] as f32 / 255.0, color[2] as f32 / 255.0, 1.0]),
+        ProcessingStepTypes::Number => step.as_number().map(|number| [number, 0.0, 0.0, 0.0]),
+        ProcessingStepTypes::FilePath | ProcessingStepTypes::Name | ProcessingStepTypes::NoInput => Some([0.0; 4]),
+    }).collect()
+}
 
 
-/// Processes an image with a south american jungle themed palette.
-pub struct SouthAmericanJungleEdit {
+
+/// Processes an image with a custom effect shader from the shader directory.
+pub struct ShaderEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
+    /// The index of the effect in the loaded effects.
+    effect: usize,
+    /// The parameters passed to the shader, four floats each (colors as rgb from 0 to 1, numbers in the first float).
+    parameters: Vec<[f32; 4]>,
     /// The steps used to create the processor.
     guide: ProcessingGuide,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl SouthAmericanJungleEdit {
-    /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> SouthAmericanJungleEdit {
-        SouthAmericanJungleEdit {
+impl ShaderEdit {
+    /// Returns a new processor ready to be set up, with a step for every parameter of the effect.
+    pub fn new(source_image_path: PathBuf, effect: usize) -> ShaderEdit {
+        ShaderEdit {
             source_image_path,
-            guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
-            ]),
+            effect,
+            parameters: Vec::new(),
+            guide: ProcessingGuide::new(get_parameter_steps(&get_effects()[effect].parameters)),
             is_ready: false,
         }
     }
 }
-impl EditProcessor for SouthAmericanJungleEdit {
+impl EditProcessor for ShaderEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        let inputs: Vec<String> = self.guide.get_inputs().into_iter().filter(|input| !input.is_empty()).collect();
+        if inputs.is_empty() { return name; }
+        format!("{} {}", name, inputs.join("-"))
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.guide.steps.iter().filter_map(|step| step.as_hex()).filter_map(|hex| as_rgb(&hex)).collect()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        PaletteMapping::Effect(get_effects()[self.effect].source.clone(), self.parameters.clone())
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -1325,6 +3964,14 @@ impl EditProcessor for SouthAmericanJungleEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
@@ -1334,52 +3981,65 @@ impl EditProcessor for SouthAmericanJungleEdit {
     }
 
     fn try_populate(&mut self) {
-        self.is_ready = true;
-    }
+        if !self.guide.is_ready() { return; }
+        // an invalid shader can never be run
+        if get_effects()[self.effect].error.is_some() { return; }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+        let Some(parameters) = get_parameter_values(&self.guide, &get_effects()[self.effect].parameters) else { return; };
+        self.parameters = parameters;
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::south_american_jungle()));
-            spectrum = condense_color_palette(&spectrum);
+        self.is_ready = true;
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
 
 
-/// Processes an image with a european islands themed palette.
-pub struct EuropeanIslandsEdit {
+/// Processes an image with a WebAssembly plugin from the plugin directory.
+pub struct PluginEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
+    /// The index of the plugin in the loaded plugins.
+    plugin: usize,
+    /// The parameters passed to the plugin, four floats each (colors as rgb from 0 to 1, numbers in the first float).
+    parameters: Vec<[f32; 4]>,
     /// The steps used to create the processor.
     guide: ProcessingGuide,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl EuropeanIslandsEdit {
-    /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> EuropeanIslandsEdit {
-        EuropeanIslandsEdit {
+impl PluginEdit {
+    /// Returns a new processor ready to be set up, with a step for every parameter of the plugin.
+    pub fn new(source_image_path: PathBuf, plugin: usize) -> PluginEdit {
+        PluginEdit {
             source_image_path,
-            guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
-            ]),
+            plugin,
+            parameters: Vec::new(),
+            guide: ProcessingGuide::new(get_parameter_steps(&get_plugins()[plugin].parameters)),
             is_ready: false,
         }
     }
 }
-impl EditProcessor for EuropeanIslandsEdit {
+impl EditProcessor for PluginEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        let inputs: Vec<String> = self.guide.get_inputs().into_iter().filter(|input| !input.is_empty()).collect();
+        if inputs.is_empty() { return name; }
+        format!("{} {}", name, inputs.join("-"))
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.guide.steps.iter().filter_map(|step| step.as_hex()).filter_map(|hex| as_rgb(&hex)).collect()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        PaletteMapping::Plugin(self.plugin, self.parameters.clone())
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -1398,6 +4058,14 @@ impl EditProcessor for EuropeanIslandsEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
         self.guide.is_current_input_valid()
     }
@@ -1407,52 +4075,73 @@ impl EditProcessor for EuropeanIslandsEdit {
     }
 
     fn try_populate(&mut self) {
-        self.is_ready = true;
-    }
+        if !self.guide.is_ready() { return; }
+        // an invalid plugin can never be run
+        if get_plugins()[self.plugin].error.is_some() { return; }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+        let Some(parameters) = get_parameter_values(&self.guide, &get_plugins()[self.plugin].parameters) else { return; };
+        self.parameters = parameters;
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::european_islands()));
-            spectrum = condense_color_palette(&spectrum);
+        self.is_ready = true;
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
 }
 
 
 
-/// Processes an image with a colorful islands themed palette.
-pub struct ColorfulIslandsEdit {
+/// Processes an image with a color mapping script from the script directory, which picks a color of a palette file for every pixel.
+pub struct ScriptEdit {
     /// The path of the original image to be processed.
     source_image_path: PathBuf,
+    /// The index of the script in the loaded scripts.
+    script: usize,
+    /// The path of the palette file.
+    pub palette_path: PathBuf,
+    /// The colors loaded from the palette file, which the script picks from.
+    pub colors: Vec<Rgb<u8>>,
     /// The steps used to create the processor.
     guide: ProcessingGuide,
     /// Tracks if the processor is ready.
     is_ready: bool,
 }
-impl ColorfulIslandsEdit {
+impl ScriptEdit {
     /// Returns a new processor ready to be set up.
-    pub fn new(source_image_path: PathBuf) -> ColorfulIslandsEdit {
-        ColorfulIslandsEdit {
+    pub fn new(source_image_path: PathBuf, script: usize) -> ScriptEdit {
+        ScriptEdit {
             source_image_path,
+            script,
+            palette_path: PathBuf::new(),
+            colors: Vec::new(),
             guide: ProcessingGuide::new(vec![
-                ProcessingStep::new(ProcessingStepTypes::NoInput, "Press Enter".to_string()),
+                ProcessingStep::new(ProcessingStepTypes::FilePath, "Palette File (path)".to_string()),
             ]),
             is_ready: false,
         }
     }
+
+    /// Returns the colors of the palette file given in the step, if it exists and holds any.
+    fn get_colors(&self) -> Option<Vec<Rgb<u8>>> {
+        Some(load_palette_definition(&self.guide.steps[0].as_path()?).ok()?.get_colors())
+    }
 }
-impl EditProcessor for ColorfulIslandsEdit {
+impl EditProcessor for ScriptEdit {
     fn get_descriptor(&self, name: String) -> String {
-        format!("{}", name)
+        format!("{} {}", name, self.palette_path.file_stem().unwrap_or_default().to_string_lossy())
+    }
+
+    fn get_base_colors(&self, _source_image: &DynamicImage) -> Vec<Rgb<u8>> {
+        self.colors.clone()
+    }
+
+    fn get_palette_mapping(&self, _source_image: &DynamicImage) -> PaletteMapping {
+        PaletteMapping::Script(self.script, get_color_blind_safe_colors(&self.colors))
     }
 
     fn get_current_step_type(&self) -> ProcessingStepTypes {
@@ -1471,8 +4160,16 @@ impl EditProcessor for ColorfulIslandsEdit {
         self.guide.update_current_input(new_input)
     }
 
+    fn get_guide(&self) -> &ProcessingGuide {
+        &self.guide
+    }
+
+    fn get_mut_guide(&mut self) -> &mut ProcessingGuide {
+        &mut self.guide
+    }
+
     fn is_current_step_input_valid(&self) -> bool {
-        self.guide.is_current_input_valid()
+        self.get_colors().is_some()
     }
 
     fn try_finish_current_step(&mut self) {
@@ -1480,22 +4177,22 @@ impl EditProcessor for ColorfulIslandsEdit {
     }
 
     fn try_populate(&mut self) {
-        self.is_ready = true;
-    }
+        if !self.guide.is_ready() { return; }
+        // an invalid script can never be run
+        if get_scripts()[self.script].error.is_some() { return; }
 
-    fn try_process(&self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-        if !self.is_ready { return None; }
+        let (Some(palette_path), Some(colors)) = (self.guide.steps[0].as_path(), self.get_colors()) else { return; };
+        self.palette_path = palette_path;
+        self.colors = colors;
 
-        let source_image_result = image::open(self.source_image_path.clone());
-        if let Ok(source_image) = source_image_result {
-            let _ = terminal.draw(|frame| render_loading(frame, "Loading colors...".to_string()));
-            let mut spectrum = get_web_spectrum(&get_line_spectrums(&palettes::colorful_islands()));
-            spectrum = condense_color_palette(&spectrum);
+        self.is_ready = true;
+    }
 
-            let _ = terminal.draw(|frame| render_loading(frame, "Processing...".to_string()));
-            return Some(process_evenly(source_image, spectrum));
-        }
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
 
-        None
+    fn try_process(&self) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        process_source_image(self, &self.source_image_path, None)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,194 @@
+use image::{DynamicImage, GenericImageView, ImageBuffer, Pixel, Rgb};
+use rayon::prelude::*;
+use crate::processor::color_space::get_delta_e;
+use crate::processor::palette::*;
+
+/// Gets the number of brightness ranges the luminance distribution is split into.
+pub fn luminance_bins() -> usize { 8 }
+
+/// Gets the saturation above which a pixel is considered strongly saturated (0-1).
+fn strong_saturation_threshold() -> f32 { 0.5 }
+
+/// Gets the share of the darkest and brightest pixels (0-100) ignored when measuring the dynamic range, so a few stray pixels do not count.
+fn dynamic_range_cutoff_percent() -> f64 { 1.0 }
+
+/// Gets the Delta E shown as the hottest heatmap color; larger differences are clamped to it.
+/// A fixed scale keeps heatmaps of different runs comparable.
+fn heatmap_max_delta_e() -> f32 { 50.0 }
+
+/// Returns the heatmap color stops from no difference to the largest difference.
+fn heatmap_stops() -> Vec<Rgb<u8>> {
+    vec![Rgb([0, 0, 0]), Rgb([0, 0, 255]), Rgb([0, 255, 255]), Rgb([0, 255, 0]), Rgb([255, 255, 0]), Rgb([255, 0, 0])]
+}
+
+
+
+/// The color statistics of a source image.
+pub struct ImageAnalysis {
+    /// The average color of the image.
+    pub average_color: Rgb<u8>,
+    /// The detected accent color of the image.
+    pub accent_color: Rgb<u8>,
+    /// The share of pixels (0-100) falling into each brightness range, from darkest to brightest.
+    pub luminance_distribution: Vec<f64>,
+    /// The exposure and tonal statistics.
+    pub tones: ToneStatistics,
+    /// The average saturation (0-1).
+    pub average_saturation: f32,
+    /// The highest saturation of any pixel (0-1).
+    pub max_saturation: f32,
+    /// The share of pixels (0-100) that are strongly saturated.
+    pub strongly_saturated_percent: f64,
+}
+impl ImageAnalysis {
+    /// Analyzes a given image.
+    pub fn new(image: &DynamicImage) -> ImageAnalysis {
+        let (width, height) = image.dimensions();
+        let pixel_count = (width as f64 * height as f64).max(1.0);
+
+        let mut luminance_counts = vec![0usize; luminance_bins()];
+        let mut saturation_total: f64 = 0.0;
+        let mut max_saturation: f32 = 0.0;
+        let mut strongly_saturated_count: usize = 0;
+        image.pixels().for_each(|pixel| {
+            let color = pixel.2.to_rgb();
+
+            let luminance = get_brightness(&color);
+            luminance_counts[((luminance / 256.0) * luminance_bins() as f32) as usize] += 1;
+
+            let saturation = get_saturation(&color);
+            saturation_total += saturation as f64;
+            max_saturation = max_saturation.max(saturation);
+            if saturation > strong_saturation_threshold() { strongly_saturated_count += 1; }
+        });
+
+        ImageAnalysis {
+            average_color: get_average_color_from_image(image),
+            accent_color: get_accent_color(image),
+            luminance_distribution: luminance_counts.iter().map(|count| *count as f64 / pixel_count * 100.0).collect(),
+            tones: ToneStatistics::new(&image.to_rgb8()),
+            average_saturation: (saturation_total / pixel_count) as f32,
+            max_saturation,
+            strongly_saturated_percent: strongly_saturated_count as f64 / pixel_count * 100.0,
+        }
+    }
+}
+
+
+
+/// The exposure and tonal statistics of an image.
+pub struct ToneStatistics {
+    /// The average perceived brightness (0-255).
+    pub average_luminance: f32,
+    /// The share of pure black pixels (0-100).
+    pub black_clipped_percent: f64,
+    /// The share of pure white pixels (0-100).
+    pub white_clipped_percent: f64,
+    /// The brightness the darkest pixels reach, ignoring the cutoff (0-255).
+    pub shadow_luminance: u8,
+    /// The brightness the brightest pixels reach, ignoring the cutoff (0-255).
+    pub highlight_luminance: u8,
+}
+impl ToneStatistics {
+    /// Measures the tones of a given image.
+    pub fn new(image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> ToneStatistics {
+        let pixel_count = (image.width() as f64 * image.height() as f64).max(1.0);
+
+        let mut histogram = [0usize; 256];
+        let mut luminance_total: f64 = 0.0;
+        let mut black_count: usize = 0;
+        let mut white_count: usize = 0;
+        for color in image.pixels() {
+            let luminance = get_brightness(color);
+            luminance_total += luminance as f64;
+            histogram[(luminance.round() as usize).min(255)] += 1;
+            if color.0 == [0, 0, 0] { black_count += 1; }
+            if color.0 == [255, 255, 255] { white_count += 1; }
+        }
+
+        // walks the histogram from both ends until the cutoff share of pixels is passed
+        let cutoff = pixel_count * dynamic_range_cutoff_percent() / 100.0;
+        ToneStatistics {
+            average_luminance: (luminance_total / pixel_count) as f32,
+            black_clipped_percent: black_count as f64 / pixel_count * 100.0,
+            white_clipped_percent: white_count as f64 / pixel_count * 100.0,
+            shadow_luminance: get_histogram_edge(&histogram, 0..256, cutoff),
+            highlight_luminance: get_histogram_edge(&histogram, (0..256).rev(), cutoff),
+        }
+    }
+
+    /// Returns the share of the brightness range (0-100) the image spans.
+    pub fn dynamic_range_percent(&self) -> f32 {
+        self.highlight_luminance.saturating_sub(self.shadow_luminance) as f32 / 255.0 * 100.0
+    }
+}
+
+
+/// Returns the first brightness level at which more than a cutoff number of pixels have been passed, walking a histogram in a given order.
+fn get_histogram_edge(histogram: &[usize; 256], mut levels: impl Iterator<Item = usize>, cutoff: f64) -> u8 {
+    let mut count: usize = 0;
+    levels.find(|level| {
+        count += histogram[*level];
+        count as f64 > cutoff
+    }).unwrap_or(0) as u8
+}
+
+
+
+/// The perceptual difference between a source image and its processed version.
+pub struct ImageDifference {
+    /// The average Delta E over all pixels.
+    pub mean_delta_e: f32,
+    /// The largest Delta E of any pixel.
+    pub max_delta_e: f32,
+}
+impl ImageDifference {
+    /// Compares a source image with its processed version pixel by pixel.
+    /// Returns None if the images do not have the same dimensions.
+    pub fn new(source_image: &DynamicImage, new_image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Option<ImageDifference> {
+        if source_image.dimensions() != new_image.dimensions() { return None; }
+        let source_image = source_image.to_rgb8();
+
+        let (total, max_delta_e) = source_image.as_raw().par_chunks_exact(3)
+            .zip(new_image.as_raw().par_chunks_exact(3))
+            .map(|(source_color, new_color)| get_delta_e(Rgb::from_slice(source_color), Rgb::from_slice(new_color)))
+            .fold(|| (0.0f64, 0.0f32), |(total, max), delta_e| (total + delta_e as f64, max.max(delta_e)))
+            .reduce(|| (0.0, 0.0), |a, b| (a.0 + b.0, a.1.max(b.1)));
+        let pixel_count = (source_image.width() as f64 * source_image.height() as f64).max(1.0);
+
+        Some(ImageDifference {
+            mean_delta_e: (total / pixel_count) as f32,
+            max_delta_e,
+        })
+    }
+}
+
+/// Returns the heatmap color of a given Delta E.
+fn get_heatmap_color(delta_e: f32, stops: &[Rgb<u8>]) -> Rgb<u8> {
+    // finds the two stops the difference falls between and blends them
+    let position = (delta_e / heatmap_max_delta_e()).clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let index = (position as usize).min(stops.len() - 2);
+    let blend = position - index as f32;
+    let (from, to) = (stops[index], stops[index + 1]);
+    Rgb(std::array::from_fn(|channel| (from[channel] as f32 + (to[channel] as f32 - from[channel] as f32) * blend).round() as u8))
+}
+
+/// Creates a false-color heatmap of where a processed image deviates from its source.
+/// Black means no difference, and the colors run through blue, cyan, green and yellow to red for the largest differences.
+/// Returns None if the images do not have the same dimensions.
+pub fn get_difference_heatmap(source_image: &DynamicImage, new_image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Option<ImageBuffer<Rgb<u8>, Vec<u8>>> {
+    if source_image.dimensions() != new_image.dimensions() { return None; }
+    let source_image = source_image.to_rgb8();
+    let stops = heatmap_stops();
+
+    let mut heatmap = ImageBuffer::new(new_image.width(), new_image.height());
+    heatmap.as_mut().par_chunks_exact_mut(3)
+        .zip(source_image.as_raw().par_chunks_exact(3))
+        .zip(new_image.as_raw().par_chunks_exact(3))
+        .for_each(|((heatmap_color, source_color), new_color)| {
+            let delta_e = get_delta_e(Rgb::from_slice(source_color), Rgb::from_slice(new_color));
+            heatmap_color.copy_from_slice(&get_heatmap_color(delta_e, &stops).0);
+        });
+
+    Some(heatmap)
+}
@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+use image::{DynamicImage, ImageBuffer, Rgb};
+use rayon::prelude::*;
+use crate::processor::compute::PaletteMapping;
+
+
+
+/// The ways the colors of a blend between the processed and the original image are kept.
+#[derive(Copy, Clone, PartialEq)]
+pub enum BlendModes {
+    /// The blended colors are kept as they are, only clamped to valid colors.
+    Clamped,
+    /// The blended colors are mapped back onto the colors of the processed image, so the variant keeps its palette.
+    Repalettized,
+}
+impl BlendModes {
+    /// Returns the blend mode with a given name (case insensitive).
+    pub fn from_name(name: &str) -> Option<BlendModes> {
+        match name.to_lowercase().as_str() {
+            "clamped" => Some(BlendModes::Clamped),
+            "repalettized" | "re-palettized" => Some(BlendModes::Repalettized),
+            _ => None,
+        }
+    }
+}
+
+
+
+/// Returns the processed image laid over the original image at a given opacity (0-100%).
+pub fn get_blended_image(source_image: &DynamicImage, new_image: &ImageBuffer<Rgb<u8>, Vec<u8>>, opacity: u8, mode: BlendModes) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let source_image = source_image.to_rgb8();
+    let opacity = opacity.min(100) as f32 / 100.0;
+
+    let mut blended_image = ImageBuffer::new(new_image.width(), new_image.height());
+    blended_image.as_mut().par_iter_mut()
+        .zip(new_image.as_raw().par_iter().zip(source_image.as_raw().par_iter()))
+        .for_each(|(blended, (new, source))| {
+            *blended = (*source as f32 + (*new as f32 - *source as f32) * opacity).round().clamp(0.0, 255.0) as u8;
+        });
+
+    match mode {
+        BlendModes::Clamped => blended_image,
+        BlendModes::Repalettized => {
+            let palette: Vec<Rgb<u8>> = new_image.pixels().copied().collect::<HashSet<Rgb<u8>>>().into_iter().collect();
+            PaletteMapping::Even(palette).process(DynamicImage::ImageRgb8(blended_image))
+        }
+    }
+}
@@ -0,0 +1,56 @@
+// converts sRGB channels (0-255) to linear light (0-1), the same way to_linear_channel() does on the CPU
+fn to_linear(color: vec3<f32>) -> vec3<f32> {
+    let channels = color / 255.0;
+    return select(pow((channels + 0.055) / 1.055, vec3<f32>(2.4)), channels / 12.92, channels <= vec3<f32>(0.04045));
+}
+
+// converts an sRGB color (0-255) to OKLab, the same way to_oklab() does on the CPU
+fn to_oklab(color: vec3<f32>) -> vec3<f32> {
+    let linear = to_linear(color);
+    let lms = pow(vec3<f32>(
+        0.4122214708 * linear.x + 0.5363325363 * linear.y + 0.0514459929 * linear.z,
+        0.2119034982 * linear.x + 0.6806995451 * linear.y + 0.1073969566 * linear.z,
+        0.0883024619 * linear.x + 0.2817188376 * linear.y + 0.6299787005 * linear.z,
+    ), vec3<f32>(1.0 / 3.0));
+    return vec3<f32>(
+        0.2104542553 * lms.x + 0.7936177850 * lms.y - 0.0040720468 * lms.z,
+        1.9779984951 * lms.x - 2.4285922050 * lms.y + 0.4505937099 * lms.z,
+        0.0259040371 * lms.x + 0.7827717662 * lms.y - 0.8086757660 * lms.z,
+    );
+}
+
+// converts an sRGB color (0-255) to CIELAB, the same way to_lab() does on the CPU
+fn to_lab(color: vec3<f32>) -> vec3<f32> {
+    let linear = to_linear(color);
+    let xyz = vec3<f32>(
+        0.4124 * linear.x + 0.3576 * linear.y + 0.1805 * linear.z,
+        0.2126 * linear.x + 0.7152 * linear.y + 0.0722 * linear.z,
+        0.0193 * linear.x + 0.1192 * linear.y + 0.9505 * linear.z,
+    ) / vec3<f32>(0.95047, 1.0, 1.08883);
+    let f = select((24389.0 / 27.0 * xyz + 16.0) / 116.0, pow(xyz, vec3<f32>(1.0 / 3.0)), xyz > vec3<f32>(216.0 / 24389.0));
+    return vec3<f32>(116.0 * f.y - 16.0, 500.0 * (f.x - f.y), 200.0 * (f.y - f.z));
+}
+
+// returns the coordinates of an sRGB color (0-255) in the space distances are measured in, numbered like ColorDistances::shader_index()
+fn to_coordinates(color: vec3<f32>, space: u32) -> vec3<f32> {
+    switch space {
+        case 1u: { return to_oklab(color); }
+        case 2u, 3u: { return to_lab(color); }
+        default: { return color * vec3<f32>(0.299, 0.587, 0.114); }
+    }
+}
+
+// returns the distance between the coordinates of the matched pixel and a palette color
+// CIEDE2000 (space 3) is approximated with the CIE94 Delta E, taking the pixel as the reference color
+fn get_color_distance(pixel: vec3<f32>, color: vec3<f32>, space: u32) -> f32 {
+    if space != 3u {
+        return length(pixel - color);
+    }
+    let chroma_1 = length(pixel.yz);
+    let delta_l = pixel.x - color.x;
+    let delta_c = chroma_1 - length(color.yz);
+    let delta_h_squared = max(dot(pixel.yz - color.yz, pixel.yz - color.yz) - delta_c * delta_c, 0.0);
+    let weight_c = 1.0 + 0.045 * chroma_1;
+    let weight_h = 1.0 + 0.015 * chroma_1;
+    return sqrt(delta_l * delta_l + pow(delta_c / weight_c, 2.0) + delta_h_squared / (weight_h * weight_h));
+}
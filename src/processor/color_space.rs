@@ -0,0 +1,426 @@
+use image::Rgb;
+
+/// Returns the D65 reference white in CIE XYZ.
+fn reference_white() -> [f32; 3] { [0.95047, 1.0, 1.08883] }
+
+/// Gets the saturation/chroma below which a color is treated as grey, so its hue is ignored while interpolating.
+fn achromatic_threshold() -> f32 { 0.0001 }
+
+
+
+/// The list of color spaces spectrums can be interpolated through.
+#[derive(Copy, Clone, PartialEq)]
+pub enum SpectrumSpaces {
+    /// Straight lines between RGB colors.
+    Rgb,
+    /// Straight lines between linear light RGB colors, which keeps the midpoints of saturated colors from darkening.
+    Linear,
+    /// Straight lines between OKLab colors, which blends perceived lightness evenly.
+    Oklab,
+    /// Keeps the hue and blends saturation and value.
+    Hsv,
+    /// Keeps the hue and blends perceptual lightness and chroma.
+    Oklch,
+}
+impl SpectrumSpaces {
+    /// Returns the space with a given name (case insensitive).
+    pub fn from_name(name: &str) -> Option<SpectrumSpaces> {
+        match name.to_lowercase().as_str() {
+            "rgb" => Some(SpectrumSpaces::Rgb),
+            "linear" | "linear-rgb" => Some(SpectrumSpaces::Linear),
+            "oklab" => Some(SpectrumSpaces::Oklab),
+            "hsv" => Some(SpectrumSpaces::Hsv),
+            "oklch" => Some(SpectrumSpaces::Oklch),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of a given space, as it is set in the config.
+    pub fn name(&self) -> String {
+        match self {
+            SpectrumSpaces::Rgb => "rgb".to_string(),
+            SpectrumSpaces::Linear => "linear".to_string(),
+            SpectrumSpaces::Oklab => "oklab".to_string(),
+            SpectrumSpaces::Hsv => "hsv".to_string(),
+            SpectrumSpaces::Oklch => "oklch".to_string(),
+        }
+    }
+
+    /// Returns the color a given fraction (0-1) of the way between two colors.
+    pub fn interpolate(&self, color_1: &Rgb<u8>, color_2: &Rgb<u8>, fraction: f32) -> Rgb<u8> {
+        let mix = |a: f32, b: f32| a + (b - a) * fraction;
+        match self {
+            SpectrumSpaces::Rgb => Rgb(std::array::from_fn(|channel| mix(color_1[channel] as f32, color_2[channel] as f32).round() as u8)),
+            SpectrumSpaces::Linear => Rgb(std::array::from_fn(|channel| {
+                from_linear_channel(mix(to_linear_channel(color_1[channel]), to_linear_channel(color_2[channel])))
+            })),
+            SpectrumSpaces::Oklab => {
+                let (a, b) = (to_oklab(color_1), to_oklab(color_2));
+                from_oklab(std::array::from_fn(|axis| mix(a[axis], b[axis])))
+            }
+            SpectrumSpaces::Hsv => {
+                let (a, b) = (to_hsv(color_1), to_hsv(color_2));
+                from_hsv([mix_hue(a[0], a[1], b[0], b[1], fraction), mix(a[1], b[1]), mix(a[2], b[2])])
+            }
+            SpectrumSpaces::Oklch => {
+                let (a, b) = (to_oklch(color_1), to_oklch(color_2));
+                from_oklch([mix(a[0], b[0]), mix(a[1], b[1]), mix_hue(a[2], a[1], b[2], b[1], fraction)])
+            }
+        }
+    }
+}
+
+
+
+/// The color spaces the distance between a color and the palette colors it can be mapped onto is measured in.
+#[derive(Copy, Clone, PartialEq)]
+pub enum ColorDistances {
+    /// Straight lines between RGB colors with the channels weighted by how bright they are perceived (the fastest).
+    WeightedRgb,
+    /// Straight lines in OKLab, which follows perceived differences in hue and lightness more evenly.
+    Oklab,
+    /// Straight lines in CIELAB (the CIE76 Delta E).
+    Cielab,
+    /// The CIEDE2000 Delta E, which corrects CIELAB for how differences in lightness, chroma and hue are actually perceived (the slowest).
+    /// The GPU approximates it with the cheaper CIE94 Delta E.
+    Ciede2000,
+}
+impl ColorDistances {
+    /// Returns the space with a given name (case insensitive).
+    pub fn from_name(name: &str) -> Option<ColorDistances> {
+        match name.to_lowercase().as_str() {
+            "rgb" | "weighted-rgb" => Some(ColorDistances::WeightedRgb),
+            "oklab" => Some(ColorDistances::Oklab),
+            "cielab" | "lab" => Some(ColorDistances::Cielab),
+            "ciede2000" => Some(ColorDistances::Ciede2000),
+            _ => None,
+        }
+    }
+
+    /// Returns the coordinates of a color in the space, where the straight distance between the coordinates of two colors is their distance.
+    pub fn to_coordinates(&self, color: &Rgb<u8>) -> [f32; 3] {
+        match self {
+            ColorDistances::WeightedRgb => [color[0] as f32 * 0.299, color[1] as f32 * 0.587, color[2] as f32 * 0.114],
+            ColorDistances::Oklab => to_oklab(color),
+            ColorDistances::Cielab | ColorDistances::Ciede2000 => to_lab(color),
+        }
+    }
+
+    /// Returns the distance between the coordinates of two colors, where the first color is the one being matched.
+    pub fn get_distance(&self, coordinates_1: &[f32; 3], coordinates_2: &[f32; 3]) -> f32 {
+        match self {
+            ColorDistances::Ciede2000 => get_ciede2000(coordinates_1, coordinates_2),
+            _ => (0..3).map(|axis| (coordinates_1[axis] - coordinates_2[axis]).powi(2)).sum::<f32>().sqrt(),
+        }
+    }
+
+    /// Returns the number the palettize shaders tell the spaces apart by.
+    pub fn shader_index(&self) -> u32 {
+        match self {
+            ColorDistances::WeightedRgb => 0,
+            ColorDistances::Oklab => 1,
+            ColorDistances::Cielab => 2,
+            ColorDistances::Ciede2000 => 3,
+        }
+    }
+}
+
+
+
+/// Blends two hues (in degrees) along the shorter way around the color wheel.
+/// A grey color has no meaningful hue, so the hue of the other color is used instead.
+fn mix_hue(hue_1: f32, chroma_1: f32, hue_2: f32, chroma_2: f32, fraction: f32) -> f32 {
+    if chroma_1 < achromatic_threshold() { return hue_2; }
+    if chroma_2 < achromatic_threshold() { return hue_1; }
+
+    let difference = (hue_2 - hue_1 + 540.0) % 360.0 - 180.0;
+    (hue_1 + difference * fraction + 360.0) % 360.0
+}
+
+
+
+/// Converts an sRGB channel (0-255) to linear light (0-1).
+pub fn to_linear_channel(channel: u8) -> f32 {
+    let channel = channel as f32 / 255.0;
+    if channel <= 0.04045 { channel / 12.92 } else { ((channel + 0.055) / 1.055).powf(2.4) }
+}
+
+/// Converts a linear light channel (0-1) back to sRGB (0-255).
+pub fn from_linear_channel(channel: f32) -> u8 {
+    let channel = channel.clamp(0.0, 1.0);
+    let channel = if channel <= 0.0031308 { channel * 12.92 } else { 1.055 * channel.powf(1.0 / 2.4) - 0.055 };
+    (channel * 255.0).round() as u8
+}
+
+/// Converts an sRGB color to CIE XYZ (D65).
+pub fn to_xyz(color: &Rgb<u8>) -> [f32; 3] {
+    let r = to_linear_channel(color[0]);
+    let g = to_linear_channel(color[1]);
+    let b = to_linear_channel(color[2]);
+    [
+        0.4124 * r + 0.3576 * g + 0.1805 * b,
+        0.2126 * r + 0.7152 * g + 0.0722 * b,
+        0.0193 * r + 0.1192 * g + 0.9505 * b,
+    ]
+}
+
+/// Converts an sRGB color to CIELAB (L: 0-100).
+pub fn to_lab(color: &Rgb<u8>) -> [f32; 3] {
+    let xyz = to_xyz(color);
+    let white = reference_white();
+
+    // compresses each axis relative to the reference white
+    let f = |t: f32| {
+        if t > 216.0 / 24389.0 { t.cbrt() } else { (24389.0 / 27.0 * t + 16.0) / 116.0 }
+    };
+    let fx = f(xyz[0] / white[0]);
+    let fy = f(xyz[1] / white[1]);
+    let fz = f(xyz[2] / white[2]);
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Converts a CIE XYZ (D65) color to sRGB, clamping colors outside of the sRGB gamut.
+pub fn from_xyz(xyz: [f32; 3]) -> Rgb<u8> {
+    let r = 3.2406 * xyz[0] - 1.5372 * xyz[1] - 0.4986 * xyz[2];
+    let g = -0.9689 * xyz[0] + 1.8758 * xyz[1] + 0.0415 * xyz[2];
+    let b = 0.0557 * xyz[0] - 0.2040 * xyz[1] + 1.0570 * xyz[2];
+    Rgb([from_linear_channel(r), from_linear_channel(g), from_linear_channel(b)])
+}
+
+/// Converts a CIELAB color to sRGB, clamping colors outside of the sRGB gamut.
+pub fn from_lab(lab: [f32; 3]) -> Rgb<u8> {
+    let white = reference_white();
+
+    // expands each axis back relative to the reference white
+    let f = |t: f32| {
+        if t > 6.0 / 29.0 { t.powi(3) } else { 3.0 * (6.0f32 / 29.0).powi(2) * (t - 4.0 / 29.0) }
+    };
+    let fy = (lab[0] + 16.0) / 116.0;
+    let fx = fy + lab[1] / 500.0;
+    let fz = fy - lab[2] / 200.0;
+
+    from_xyz([f(fx) * white[0], f(fy) * white[1], f(fz) * white[2]])
+}
+
+/// Converts an sRGB color to HSV (hue: 0-360, saturation: 0-1, value: 0-1).
+pub fn to_hsv(color: &Rgb<u8>) -> [f32; 3] {
+    let [r, g, b] = color.0.map(|channel| channel as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 { 0.0 }
+        else if max == r { 60.0 * (((g - b) / delta) % 6.0) }
+        else if max == g { 60.0 * ((b - r) / delta + 2.0) }
+        else { 60.0 * ((r - g) / delta + 4.0) };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+    [(hue + 360.0) % 360.0, saturation, max]
+}
+
+/// Converts an HSV color (hue: 0-360, saturation: 0-1, value: 0-1) to sRGB.
+pub fn from_hsv(hsv: [f32; 3]) -> Rgb<u8> {
+    let [hue, saturation, value] = hsv;
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let m = value - chroma;
+
+    let (r, g, b) = match (hue / 60.0) as u32 % 6 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    Rgb([r, g, b].map(|channel| ((channel + m).clamp(0.0, 1.0) * 255.0).round() as u8))
+}
+
+/// Converts an sRGB color to OKLab (L: 0-1).
+pub fn to_oklab(color: &Rgb<u8>) -> [f32; 3] {
+    // the reference matrices are more precise than f32, so the conversion runs in f64
+    let r = to_linear_channel(color[0]) as f64;
+    let g = to_linear_channel(color[1]) as f64;
+    let b = to_linear_channel(color[2]) as f64;
+
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    [
+        (0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s) as f32,
+        (1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s) as f32,
+        (0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s) as f32,
+    ]
+}
+
+/// Converts an OKLab color to linear sRGB channels, which fall outside of 0-1 for colors outside of the sRGB gamut.
+fn oklab_to_linear(lab: [f32; 3]) -> [f32; 3] {
+    let lab = lab.map(|value| value as f64);
+    let l = (lab[0] + 0.3963377774 * lab[1] + 0.2158037573 * lab[2]).powi(3);
+    let m = (lab[0] - 0.1055613458 * lab[1] - 0.0638541728 * lab[2]).powi(3);
+    let s = (lab[0] - 0.0894841775 * lab[1] - 1.2914855480 * lab[2]).powi(3);
+    [
+        (4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s) as f32,
+        (-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s) as f32,
+        (-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s) as f32,
+    ]
+}
+
+/// Converts an OKLab color to sRGB, clamping colors outside of the sRGB gamut.
+pub fn from_oklab(lab: [f32; 3]) -> Rgb<u8> {
+    Rgb(oklab_to_linear(lab).map(from_linear_channel))
+}
+
+/// Converts an sRGB color to OKLCH (L: 0-1, C: 0-~0.4, h: 0-360).
+pub fn to_oklch(color: &Rgb<u8>) -> [f32; 3] {
+    let lab = to_oklab(color);
+    let hue = lab[2].atan2(lab[1]).to_degrees();
+    [lab[0], (lab[1].powi(2) + lab[2].powi(2)).sqrt(), (hue + 360.0) % 360.0]
+}
+
+/// Converts an OKLCH color to sRGB, clamping colors outside of the sRGB gamut.
+pub fn from_oklch(lch: [f32; 3]) -> Rgb<u8> {
+    let hue = lch[2].to_radians();
+    from_oklab([lch[0], lch[1] * hue.cos(), lch[1] * hue.sin()])
+}
+
+/// Converts an OKLCH color to sRGB, lowering the chroma as little as needed to fit the sRGB gamut so the hue and lightness stay intact.
+pub fn from_oklch_in_gamut(lch: [f32; 3]) -> Rgb<u8> {
+    let is_in_gamut = |chroma: f32| {
+        let hue = lch[2].to_radians();
+        oklab_to_linear([lch[0], chroma * hue.cos(), chroma * hue.sin()]).iter().all(|channel| (-0.0001..=1.0001).contains(channel))
+    };
+    if is_in_gamut(lch[1]) { return from_oklch(lch); }
+
+    // searches for the highest chroma that still fits
+    let (mut low, mut high) = (0.0, lch[1]);
+    for _ in 0..16 {
+        let middle = (low + high) / 2.0;
+        if is_in_gamut(middle) { low = middle; } else { high = middle; }
+    }
+    from_oklch([lch[0], low, lch[2]])
+}
+
+/// Gets the perceptual difference between two colors as the CIE76 Delta E (0 = identical, ~2.3 = just noticeable).
+pub fn get_delta_e(a: &Rgb<u8>, b: &Rgb<u8>) -> f32 {
+    let a = to_lab(a);
+    let b = to_lab(b);
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// Gets the perceptual difference between two CIELAB colors as the CIEDE2000 Delta E (0 = identical, ~1 = just noticeable).
+pub fn get_ciede2000(lab_1: &[f32; 3], lab_2: &[f32; 3]) -> f32 {
+    let [l_1, a_1, b_1] = *lab_1;
+    let [l_2, a_2, b_2] = *lab_2;
+    let pow_25_7 = 25.0f32.powi(7);
+
+    // stretches the a axis of low chroma colors, where CIELAB underestimates differences
+    let mean_chroma = ((a_1.powi(2) + b_1.powi(2)).sqrt() + (a_2.powi(2) + b_2.powi(2)).sqrt()) / 2.0;
+    let g = 0.5 * (1.0 - (mean_chroma.powi(7) / (mean_chroma.powi(7) + pow_25_7)).sqrt());
+    let (a_1, a_2) = (a_1 * (1.0 + g), a_2 * (1.0 + g));
+    let (chroma_1, chroma_2) = ((a_1.powi(2) + b_1.powi(2)).sqrt(), (a_2.powi(2) + b_2.powi(2)).sqrt());
+    let hue = |a: f32, b: f32| if a == 0.0 && b == 0.0 { 0.0 } else { (b.atan2(a).to_degrees() + 360.0) % 360.0 };
+    let (hue_1, hue_2) = (hue(a_1, b_1), hue(a_2, b_2));
+
+    // the differences, where grey colors have no hue difference
+    let is_grey = chroma_1 * chroma_2 == 0.0;
+    let hue_difference = if is_grey { 0.0 } else { (hue_2 - hue_1 + 540.0) % 360.0 - 180.0 };
+    let delta_l = l_2 - l_1;
+    let delta_c = chroma_2 - chroma_1;
+    let delta_h = 2.0 * (chroma_1 * chroma_2).sqrt() * (hue_difference / 2.0).to_radians().sin();
+
+    // the means the weights are taken at
+    let mean_l = (l_1 + l_2) / 2.0;
+    let mean_chroma = (chroma_1 + chroma_2) / 2.0;
+    let mean_hue = if is_grey { hue_1 + hue_2 }
+        else if (hue_1 - hue_2).abs() <= 180.0 { (hue_1 + hue_2) / 2.0 }
+        else if hue_1 + hue_2 < 360.0 { (hue_1 + hue_2 + 360.0) / 2.0 }
+        else { (hue_1 + hue_2 - 360.0) / 2.0 };
+
+    let t = 1.0 - 0.17 * (mean_hue - 30.0).to_radians().cos() + 0.24 * (2.0 * mean_hue).to_radians().cos()
+        + 0.32 * (3.0 * mean_hue + 6.0).to_radians().cos() - 0.20 * (4.0 * mean_hue - 63.0).to_radians().cos();
+    let weight_l = 1.0 + 0.015 * (mean_l - 50.0).powi(2) / (20.0 + (mean_l - 50.0).powi(2)).sqrt();
+    let weight_c = 1.0 + 0.045 * mean_chroma;
+    let weight_h = 1.0 + 0.015 * mean_chroma * t;
+    // rotates the differences in the blue region, where hue and chroma interact
+    let rotation = 30.0 * (-((mean_hue - 275.0) / 25.0).powi(2)).exp();
+    let rotation_term = -(2.0 * rotation).to_radians().sin() * 2.0 * (mean_chroma.powi(7) / (mean_chroma.powi(7) + pow_25_7)).sqrt();
+
+    let (l_term, c_term, h_term) = (delta_l / weight_l, delta_c / weight_c, delta_h / weight_h);
+    (l_term.powi(2) + c_term.powi(2) + h_term.powi(2) + rotation_term * c_term * h_term).max(0.0).sqrt()
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The CIELAB pairs and their CIEDE2000 Delta E from Sharma, Wu and Dalal,
+    /// "The CIEDE2000 Color-Difference Formula: Implementation Notes, Supplementary Test Data, and Mathematical Observations" (2005).
+    const SHARMA_PAIRS: [([f32; 3], [f32; 3], f32); 34] = [
+        ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+        ([50.0, 3.1571, -77.2803], [50.0, 0.0, -82.7485], 2.8615),
+        ([50.0, 2.8361, -74.0200], [50.0, 0.0, -82.7485], 3.4412),
+        ([50.0, -1.3802, -84.2814], [50.0, 0.0, -82.7485], 1.0000),
+        ([50.0, -1.1848, -84.8006], [50.0, 0.0, -82.7485], 1.0000),
+        ([50.0, -0.9009, -85.5211], [50.0, 0.0, -82.7485], 1.0000),
+        ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+        ([50.0, -1.0, 2.0], [50.0, 0.0, 0.0], 2.3669),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0009], 7.1792),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0010], 7.1792),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0011], 7.2195),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0012], 7.2195),
+        ([50.0, -0.001, 2.49], [50.0, 0.0009, -2.49], 4.8045),
+        ([50.0, -0.001, 2.49], [50.0, 0.0010, -2.49], 4.8045),
+        ([50.0, -0.001, 2.49], [50.0, 0.0011, -2.49], 4.7461),
+        ([50.0, 2.5, 0.0], [50.0, 0.0, -2.5], 4.3065),
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+        ([50.0, 2.5, 0.0], [61.0, -5.0, 29.0], 22.8977),
+        ([50.0, 2.5, 0.0], [56.0, -27.0, -3.0], 31.9030),
+        ([50.0, 2.5, 0.0], [58.0, 24.0, 15.0], 19.4535),
+        ([50.0, 2.5, 0.0], [50.0, 3.1736, 0.5854], 1.0000),
+        ([50.0, 2.5, 0.0], [50.0, 3.2972, 0.0], 1.0000),
+        ([50.0, 2.5, 0.0], [50.0, 1.8634, 0.5757], 1.0000),
+        ([50.0, 2.5, 0.0], [50.0, 3.2592, 0.3350], 1.0000),
+        ([60.2574, -34.0099, 36.2677], [60.4626, -34.1751, 39.4387], 1.2644),
+        ([63.0109, -31.0961, -5.8663], [62.8187, -29.7946, -4.0864], 1.2630),
+        ([61.2901, 3.7196, -5.3901], [61.4292, 2.2480, -4.9620], 1.8731),
+        ([35.0831, -44.1164, 3.7933], [35.0232, -40.0716, 1.5901], 1.8645),
+        ([22.7233, 20.0904, -46.6940], [23.0331, 14.9730, -42.5619], 2.0373),
+        ([36.4612, 47.8580, 18.3852], [36.2715, 50.5065, 21.2231], 1.4146),
+        ([90.8027, -2.0831, 1.4410], [91.1528, -1.6435, 0.0447], 1.4441),
+        ([90.9257, -0.5406, -0.9208], [88.6381, -0.8985, -0.7239], 1.5381),
+        ([6.7747, -0.2908, -2.4247], [5.8714, -0.0985, -2.2286], 0.6377),
+        ([2.0776, 0.0795, -1.1350], [0.9033, -0.0636, -0.5514], 0.9082),
+    ];
+
+    #[test]
+    fn ciede2000_matches_the_reference_pairs() {
+        for (index, (lab_1, lab_2, expected)) in SHARMA_PAIRS.iter().enumerate() {
+            let delta_e = get_ciede2000(lab_1, lab_2);
+            assert!((delta_e - expected).abs() < 1e-4, "pair {}: expected {}, got {}", index + 1, expected, delta_e);
+        }
+    }
+
+    #[test]
+    fn ciede2000_is_symmetric() {
+        for (index, (lab_1, lab_2, _)) in SHARMA_PAIRS.iter().enumerate() {
+            let (forwards, backwards) = (get_ciede2000(lab_1, lab_2), get_ciede2000(lab_2, lab_1));
+            assert!((forwards - backwards).abs() < 1e-4, "pair {}: {} forwards, {} backwards", index + 1, forwards, backwards);
+        }
+    }
+
+    #[test]
+    fn ciede2000_wraps_hues_around_zero_degrees() {
+        // nearly opposite hues on both sides of the a axis, where the mean hue is taken across 0°/360° once they are more than 180° apart
+        let (near, across) = (get_ciede2000(&[50.0, 2.49, -0.001], &[50.0, -2.49, 0.0010]), get_ciede2000(&[50.0, 2.49, -0.001], &[50.0, -2.49, 0.0011]));
+        assert!((near - 7.1792).abs() < 1e-4 && (across - 7.2195).abs() < 1e-4, "got {} and {}", near, across);
+        // the same on both sides of the b axis
+        let (near, across) = (get_ciede2000(&[50.0, -0.001, 2.49], &[50.0, 0.0010, -2.49]), get_ciede2000(&[50.0, -0.001, 2.49], &[50.0, 0.0011, -2.49]));
+        assert!((near - 4.8045).abs() < 1e-4 && (across - 4.7461).abs() < 1e-4, "got {} and {}", near, across);
+        // identical colors just below 360° have no difference
+        assert_eq!(get_ciede2000(&[50.0, 2.49, -0.001], &[50.0, 2.49, -0.001]), 0.0);
+    }
+}
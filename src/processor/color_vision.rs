@@ -0,0 +1,121 @@
+use image::Rgb;
+use crate::processor::color_space::*;
+
+/// Gets the smallest Delta E two simulated palette colors need to be told apart.
+fn min_simulated_distance() -> f32 { 15.0 }
+
+/// Gets the max number of passes used to separate palette colors.
+fn max_separation_passes() -> usize { 100 }
+
+/// Gets how far (in CIELAB units) each color of a pair is moved per separation pass.
+fn separation_step() -> f32 { 1.0 }
+
+/// The list of color vision types colors can be simulated for.
+#[derive(Copy, Clone, PartialEq)]
+pub enum ColorVisionModes {
+    Normal,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+impl ColorVisionModes {
+    /// Returns the name of a given mode.
+    pub fn name(&self) -> String {
+        match self {
+            ColorVisionModes::Normal => "Normal vision".to_string(),
+            ColorVisionModes::Protanopia => "Protanopia".to_string(),
+            ColorVisionModes::Deuteranopia => "Deuteranopia".to_string(),
+            ColorVisionModes::Tritanopia => "Tritanopia".to_string(),
+        }
+    }
+
+    /// Returns the mode with a given name (case insensitive), treating "none" as normal vision.
+    pub fn from_name(name: &str) -> Option<ColorVisionModes> {
+        match name.to_lowercase().as_str() {
+            "none" | "normal" => Some(ColorVisionModes::Normal),
+            "protanopia" => Some(ColorVisionModes::Protanopia),
+            "deuteranopia" => Some(ColorVisionModes::Deuteranopia),
+            "tritanopia" => Some(ColorVisionModes::Tritanopia),
+            _ => None,
+        }
+    }
+
+    /// Returns the mode after a given mode, wrapping around to normal vision.
+    pub fn next(&self) -> ColorVisionModes {
+        match self {
+            ColorVisionModes::Normal => ColorVisionModes::Protanopia,
+            ColorVisionModes::Protanopia => ColorVisionModes::Deuteranopia,
+            ColorVisionModes::Deuteranopia => ColorVisionModes::Tritanopia,
+            ColorVisionModes::Tritanopia => ColorVisionModes::Normal,
+        }
+    }
+
+    /// Returns the linear RGB simulation matrix of a given mode (Machado et al. 2009, full severity).
+    fn matrix(&self) -> Option<[[f32; 3]; 3]> {
+        match self {
+            ColorVisionModes::Normal => None,
+            ColorVisionModes::Protanopia => Some([
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ]),
+            ColorVisionModes::Deuteranopia => Some([
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ]),
+            ColorVisionModes::Tritanopia => Some([
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ]),
+        }
+    }
+
+    /// Returns how a given color appears to someone with a given color vision type.
+    pub fn simulate(&self, color: &Rgb<u8>) -> Rgb<u8> {
+        let Some(matrix) = self.matrix() else { return *color; };
+
+        // the matrices work on linear light, not on the gamma encoded channels
+        let linear = [to_linear_channel(color[0]), to_linear_channel(color[1]), to_linear_channel(color[2])];
+        Rgb(std::array::from_fn(|channel| {
+            from_linear_channel(matrix[channel][0] * linear[0] + matrix[channel][1] * linear[1] + matrix[channel][2] * linear[2])
+        }))
+    }
+
+    /// Nudges palette colors apart until every pair stays distinguishable to someone with a given color vision type.
+    /// Colors are pushed apart in lightness and along the color axis the deficiency keeps (blue-yellow for protanopia and deuteranopia, red-green for tritanopia).
+    pub fn separate(&self, colors: &[Rgb<u8>]) -> Vec<Rgb<u8>> {
+        if *self == ColorVisionModes::Normal || colors.len() < 2 { return colors.to_vec(); }
+
+        let kept_axis = if *self == ColorVisionModes::Tritanopia { 1 } else { 2 };
+        let mut labs: Vec<[f32; 3]> = colors.iter().map(to_lab).collect();
+        let mut is_moved = vec![false; colors.len()];
+        for _ in 0..max_separation_passes() {
+            let mut is_separated = true;
+            for a in 0..labs.len() {
+                for b in (a + 1)..labs.len() {
+                    let simulated_a = self.simulate(&from_lab(labs[a]));
+                    let simulated_b = self.simulate(&from_lab(labs[b]));
+                    if get_delta_e(&simulated_a, &simulated_b) >= min_simulated_distance() { continue; }
+                    is_separated = false;
+                    is_moved[a] = true;
+                    is_moved[b] = true;
+
+                    // keeps the existing order of the pair on each axis while moving them apart
+                    for axis in [0, kept_axis] {
+                        let direction = if labs[a][axis] >= labs[b][axis] { 1.0 } else { -1.0 };
+                        labs[a][axis] += direction * separation_step();
+                        labs[b][axis] -= direction * separation_step();
+                    }
+                    labs[a][0] = labs[a][0].clamp(0.0, 100.0);
+                    labs[b][0] = labs[b][0].clamp(0.0, 100.0);
+                }
+            }
+            if is_separated { break; }
+        }
+
+        // untouched colors are returned as they were to avoid rounding drift
+        colors.iter().zip(labs).zip(is_moved).map(|((color, lab), is_moved)| if is_moved { from_lab(lab) } else { *color }).collect()
+    }
+}
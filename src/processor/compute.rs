@@ -1,13 +1,24 @@
+use std::borrow::Cow;
 use image::{DynamicImage, GenericImageView, ImageBuffer, Pixel, Rgb};
 use rayon::iter::IntoParallelIterator;
 use wgpu::util::DeviceExt;
 use rayon::prelude::*;
+use crate::processor::plugin::get_plugins;
+use crate::processor::script::get_scripts;
+use crate::config;
+use crate::processor::memory::get_tile_rows;
+use crate::processor::theory::get_hue_rotated_color;
+use crate::processor::color_space::{from_oklch_in_gamut, to_oklch, ColorDistances};
+use crate::processor::palette::{get_brightness, get_distance};
+use crate::progress::{report, ProgressEvents};
 
 
 
 const WORKGROUP_SIZE: u32 = 64;
 const WORKGROUP_COMPONENT_SIZE: u32 = 8;
 const MAX_DISPATCH: u32 = 65535;
+/// The color space conversions both palettize shaders measure distances with, prepended to their source.
+const COLOR_DISTANCE_SHADER: &str = include_str!("color_distance.wgsl");
 
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
@@ -25,62 +36,217 @@ impl GpuImageInformation {
     }
 }
 
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuMatchingInformation {
+    mask_size: u32,
+    spread: f32,
+    space: u32,
+    /// The brightness (0-1) below which pixels are mapped onto the shadow colors of a split palette, only read by palettize_evenly.wgsl.
+    split_threshold: f32,
+}
+impl GpuMatchingInformation {
+    fn new(mask: &ThresholdMask, color_distance: ColorDistances, split_threshold: f32) -> GpuMatchingInformation {
+        GpuMatchingInformation { mask_size: mask.size, spread: mask.spread, space: color_distance.shader_index(), split_threshold }
+    }
+}
+
+
+/// The hardware images are processed on.
+#[derive(Copy, Clone, PartialEq)]
+pub enum ProcessingBackends {
+    Gpu,
+    Cpu,
+}
+impl ProcessingBackends {
+    /// Gets the name of the backend.
+    pub fn name(&self) -> String {
+        match self {
+            ProcessingBackends::Gpu => "GPU".to_string(),
+            ProcessingBackends::Cpu => "CPU".to_string(),
+        }
+    }
+}
 
+
+
+/// What is known about the processing backend and the adapters it can pick from.
+pub struct GpuInformation {
+    /// The backend images are processed on.
+    pub backend: ProcessingBackends,
+    /// The adapter images are processed on, or None when the CPU is used.
+    pub adapter: Option<wgpu::AdapterInfo>,
+    /// The limits of the adapter images are processed on.
+    pub limits: Option<wgpu::Limits>,
+    /// The labels of every adapter that can be picked.
+    pub adapter_labels: Vec<String>,
+}
+impl GpuInformation {
+    /// Collects the information of the adapter that is picked with the current configuration.
+    pub fn collect() -> GpuInformation {
+        let instance = wgpu::Instance::default();
+        let adapter_labels = instance.enumerate_adapters(wgpu::Backends::all()).iter().map(|adapter| adapter_label(&adapter.get_info())).collect();
+        let adapter = if config::get().force_cpu { None } else { select_adapter(&instance) };
+        GpuInformation {
+            backend: if adapter.is_some() { ProcessingBackends::Gpu } else { ProcessingBackends::Cpu },
+            limits: adapter.as_ref().map(|adapter| adapter.limits()),
+            adapter: adapter.map(|adapter| adapter.get_info()),
+            adapter_labels,
+        }
+    }
+}
+
+/// Gets the label an adapter is listed and configured by, its name followed by its graphics api (e.g. "Radeon RX 7600 (Vulkan)").
+/// The graphics api tells apart the same GPU being offered through several apis.
+pub fn adapter_label(adapter_info: &wgpu::AdapterInfo) -> String {
+    format!("{} ({})", adapter_info.name, graphics_api_name(adapter_info.backend))
+}
+
+/// Gets the readable name of a graphics api (e.g. Vulkan, Metal or DX12).
+pub fn graphics_api_name(backend: wgpu::Backend) -> String {
+    match backend {
+        wgpu::Backend::Vulkan => "Vulkan".to_string(),
+        wgpu::Backend::Metal => "Metal".to_string(),
+        wgpu::Backend::Dx12 => "DX12".to_string(),
+        wgpu::Backend::Gl => "OpenGL".to_string(),
+        wgpu::Backend::BrowserWebGpu => "WebGPU".to_string(),
+        wgpu::Backend::Empty => "None".to_string(),
+    }
+}
+
+/// Gets the readable kind of an adapter (e.g. a discrete or integrated GPU).
+pub fn device_type_name(device_type: wgpu::DeviceType) -> String {
+    match device_type {
+        wgpu::DeviceType::DiscreteGpu => "Discrete GPU".to_string(),
+        wgpu::DeviceType::IntegratedGpu => "Integrated GPU".to_string(),
+        wgpu::DeviceType::VirtualGpu => "Virtual GPU".to_string(),
+        wgpu::DeviceType::Cpu => "Software renderer".to_string(),
+        wgpu::DeviceType::Other => "Other".to_string(),
+    }
+}
+
+/// Returns if a GPU adapter can be set up, regardless of whether the CPU is forced.
+pub fn is_gpu_available() -> bool {
+    select_adapter(&wgpu::Instance::default()).is_some()
+}
+
+/// Picks the configured adapter (the first one whose label contains the configured name), falling back to the default adapter.
+fn select_adapter(instance: &wgpu::Instance) -> Option<wgpu::Adapter> {
+    if let Some(adapter_name) = &config::get().gpu_adapter {
+        let adapter_name = adapter_name.to_lowercase();
+        let configured_adapter = instance.enumerate_adapters(wgpu::Backends::all()).into_iter()
+            .find(|adapter| adapter_label(&adapter.get_info()).to_lowercase().contains(&adapter_name));
+        if configured_adapter.is_some() { return configured_adapter; }
+    }
+    pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+}
+
+
+/// The device images are processed on, where None processes them on the CPU instead.
 pub struct Gpu {
-    device: wgpu::Device,
-    queue: wgpu::Queue,
+    hardware: Option<(wgpu::Device, wgpu::Queue)>,
 }
 impl Gpu {
+    /// Sets up the configured adapter, falling back to the CPU when it is forced or no adapter can be set up.
     pub fn new() -> Self {
+        Self::request(config::get().force_cpu)
+    }
+
+    /// Sets up the configured adapter, or the CPU if it is forced.
+    fn request(force_cpu: bool) -> Self {
+        if force_cpu { return Self { hardware: None }; }
         pollster::block_on(async {
             let instance = wgpu::Instance::default();
-            let adapter = instance
-                .request_adapter(&wgpu::RequestAdapterOptions::default())
-                .await
-                .expect("Failed to find GPU adapter");
-
-            let (device, queue) = adapter
+            let Some(adapter) = select_adapter(&instance) else { return Self { hardware: None }; };
+            let hardware = adapter
                 .request_device(&wgpu::DeviceDescriptor::default(), None)
                 .await
-                .expect("Failed to create GPU device");
+                .ok();
 
-            Self { device, queue }
+            Self { hardware }
         })
     }
-
-    fn colors_as_vec_u32(colors: &Vec<Rgb<u8>>) -> Vec<u32> {
+    fn colors_as_vec_u32(colors: &[Rgb<u8>]) -> Vec<u32> {
         colors
             .iter()
             .map(|pixel| { (pixel[0] as u32) | ((pixel[1] as u32) << 8) | ((pixel[2] as u32) << 16) })
             .collect()
     }
 
-    pub fn palettize_evenly(&self, width: u32, height: u32, pixels: &Vec<Rgb<u8>>, palette: &Vec<Rgb<u8>>) -> Vec<Rgb<u8>> {
+    /// Converts the colors of a palette into the space distances are measured in once, so the shaders only convert the pixels.
+    /// The coordinates are padded to four components, since arrays of vec3 are aligned like arrays of vec4 in WGSL storage buffers.
+    fn colors_as_coordinates(colors: &[Rgb<u8>], color_distance: ColorDistances) -> Vec<[f32; 4]> {
+        colors
+            .iter()
+            .map(|color| { let [x, y, z] = color_distance.to_coordinates(color); [x, y, z, 0.0] })
+            .collect()
+    }
+
+    pub fn palettize_evenly(&self, width: u32, height: u32, pixels: &[Rgb<u8>], palette: &[Rgb<u8>], mask: &ThresholdMask) -> Vec<Rgb<u8>> {
+        self.palettize_partitioned(width, height, pixels, palette, None, mask)
+    }
+
+    /// Maps every pixel onto the closest color of a palette, which may be split into a given number of shadow colors at its start and a brightness threshold (0-1).
+    /// Only pixels darker than the threshold are mapped onto the shadow colors and every other pixel onto the remaining colors, without a split every pixel is mapped onto the whole palette.
+    fn palettize_partitioned(&self, width: u32, height: u32, pixels: &[Rgb<u8>], palette: &[Rgb<u8>], split: Option<(usize, f32)>, mask: &ThresholdMask) -> Vec<Rgb<u8>> {
+        let Some((device, queue)) = &self.hardware else {
+            let Some((shadow_length, threshold)) = split else { return palettize_on_cpu(width, pixels, &[], palette, mask); };
+            return palettize_split_on_cpu(width, pixels, threshold, &palette[..shadow_length], &palette[shadow_length..], mask);
+        };
+        let (shadow_length, threshold) = split.unwrap_or((0, 0.0));
+
+        // splits images that do not fit the memory budgets or the GPU buffers into tiles of rows
+        let tile_rows = get_tile_rows(width, height, palette.len(), device.limits().max_storage_buffer_binding_size as u64);
+        if tile_rows < height {
+            let tile_count = height.div_ceil(tile_rows) as usize;
+            return pixels.chunks((tile_rows * width) as usize)
+                .enumerate()
+                .flat_map(|(index, tile)| {
+                    let new_pixels = self.palettize_partitioned(width, tile.len() as u32 / width, tile, palette, split, &mask.starting_at_row(index as u32 * tile_rows));
+                    report(ProgressEvents::TileDone(index + 1, tile_count));
+                    new_pixels
+                })
+                .collect();
+        }
+
         // Convert to f32 for GPU
+        let color_distance = config::get().color_distance;
         let u32_pixels: Vec<u32> = Self::colors_as_vec_u32(pixels);
-        let u32_palette: Vec<u32> = Self::colors_as_vec_u32(palette);
+        let palette_coordinates = Self::colors_as_coordinates(palette, color_distance);
 
         // Create GPU buffers
-        let dimensions = GpuImageInformation::new(width, height, 0, palette.len());
-        let dimensions_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        let dimensions = GpuImageInformation::new(width, height, shadow_length, palette.len());
+        let dimensions_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Dimensions Buffer"),
             contents: bytemuck::bytes_of(&dimensions),
             usage: wgpu::BufferUsages::UNIFORM,
         });
 
-        let pixels_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        let pixels_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Pixel Buffer"),
             contents: bytemuck::cast_slice(&u32_pixels),
             usage: wgpu::BufferUsages::STORAGE,
         });
 
-        let palette_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        let palette_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Palette Buffer"),
-            contents: bytemuck::cast_slice(&u32_palette),
+            contents: bytemuck::cast_slice(&palette_coordinates),
             usage: wgpu::BufferUsages::STORAGE,
         });
 
-        let shader_results_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+        let matching_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Matching Buffer"),
+            contents: bytemuck::bytes_of(&GpuMatchingInformation::new(mask, color_distance, threshold)),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let thresholds_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Thresholds Buffer"),
+            contents: bytemuck::cast_slice(&mask.thresholds),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let shader_results_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Shader Results Buffer"),
             size: (pixels.len() * size_of::<u32>()) as u64,
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
@@ -88,13 +254,13 @@ impl Gpu {
         });
 
         // Load shader
-        let shader = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Palettize Evenly"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("palettize_evenly.wgsl").into()),
+            source: wgpu::ShaderSource::Wgsl((COLOR_DISTANCE_SHADER.to_string() + include_str!("palettize_evenly.wgsl")).into()),
         });
 
         // Create pipeline
-        let bind_group_layout = self.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: None,
             entries: &[
                 wgpu::BindGroupLayoutEntry {
@@ -137,10 +303,30 @@ impl Gpu {
                     },
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
-        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: None,
             layout: &bind_group_layout,
             entries: &[
@@ -160,16 +346,24 @@ impl Gpu {
                     binding: 3,
                     resource: shader_results_buffer.as_entire_binding(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: matching_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: thresholds_buffer.as_entire_binding(),
+                },
             ],
         });
 
-        let pipeline_layout = self.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: None,
             bind_group_layouts: &[&bind_group_layout],
             push_constant_ranges: &[],
         });
 
-        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
             label: None,
             layout: Some(&pipeline_layout),
             module: &shader,
@@ -179,7 +373,7 @@ impl Gpu {
         });
 
         // Execute
-        let mut encoder = self.device.create_command_encoder(&Default::default());
+        let mut encoder = device.create_command_encoder(&Default::default());
         {
             let mut compute_pass = encoder.begin_compute_pass(&Default::default());
             compute_pass.set_bind_group(0, &bind_group, &[]);
@@ -191,7 +385,7 @@ impl Gpu {
         }
 
         // Read results back
-        let staging_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: None,
             size: shader_results_buffer.size(),
             usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
@@ -199,11 +393,11 @@ impl Gpu {
         });
 
         encoder.copy_buffer_to_buffer(&shader_results_buffer, 0, &staging_buffer, 0, shader_results_buffer.size());
-        self.queue.submit(Some(encoder.finish()));
+        queue.submit(Some(encoder.finish()));
 
         let buffer_slice = staging_buffer.slice(..);
         buffer_slice.map_async(wgpu::MapMode::Read, |_| {});
-        self.device.poll(wgpu::Maintain::Wait);
+        device.poll(wgpu::Maintain::Wait);
 
         let shader_results_data = buffer_slice.get_mapped_range();
         let shader_results: Vec<u32> = bytemuck::cast_slice(&shader_results_data).to_vec();
@@ -218,39 +412,256 @@ impl Gpu {
         new_pixels
     }
 
-    pub fn palettize_biased(&self, width: u32, height: u32, pixels: &Vec<Rgb<u8>>, biased_palette: &Vec<Rgb<u8>>, standard_palette: &Vec<Rgb<u8>>) -> Vec<Rgb<u8>> {
+    /// Maps every pixel darker than a threshold (perceived brightness from 0 to 1) onto the shadow palette and every other pixel onto the highlight palette,
+    /// with the threshold and both palettes given together like in a split mapping.
+    /// Both palettes are mapped in a single pass over one palette holding the shadow colors first, so tiling works the same as for even mapping.
+    /// Pixels are sorted into shadows and highlights by their source brightness, so dithering only nudges them within their palette.
+    pub fn palettize_split(&self, width: u32, height: u32, pixels: &[Rgb<u8>], split: (f32, &[Rgb<u8>], &[Rgb<u8>]), mask: &ThresholdMask) -> Vec<Rgb<u8>> {
+        let (threshold, shadow_palette, highlight_palette) = split;
+        let palette = [shadow_palette, highlight_palette].concat();
+        // without colors on one side there is nothing to split
+        if shadow_palette.is_empty() || highlight_palette.is_empty() { return self.palettize_evenly(width, height, pixels, &palette, mask); }
+        self.palettize_partitioned(width, height, pixels, &palette, Some((shadow_palette.len(), threshold)), mask)
+    }
+
+    /// Runs a user-supplied effect shader over every pixel with the given parameters (four floats each).
+    /// Returns why the shader could not be run if the device rejects it (e.g. a shader that doesn't fit the buffers it is dispatched with).
+    pub fn run_effect(&self, width: u32, height: u32, pixels: &Vec<Rgb<u8>>, source: &str, parameters: &[[f32; 4]]) -> Result<Vec<Rgb<u8>>, String> {
+        // effects are WGSL, so they need an adapter even when the CPU is forced
+        let Some((device, queue)) = &self.hardware else {
+            let gpu = Gpu::request(false);
+            if gpu.hardware.is_none() { return Err("custom shaders need a GPU adapter".to_string()); }
+            return gpu.run_effect(width, height, pixels, source, parameters);
+        };
+
+        // Convert to u32 for GPU
+        let u32_pixels: Vec<u32> = Self::colors_as_vec_u32(pixels);
+        // storage buffers cannot be empty
+        let parameter_count = parameters.len();
+        let parameters: Vec<[f32; 4]> = if parameters.is_empty() { vec![[0.0; 4]] } else { parameters.to_vec() };
+
+        // Create GPU buffers
+        let dimensions = GpuImageInformation::new(width, height, 0, parameter_count);
+        let dimensions_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Dimensions Buffer"),
+            contents: bytemuck::bytes_of(&dimensions),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let pixels_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Pixel Buffer"),
+            contents: bytemuck::cast_slice(&u32_pixels),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let parameters_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Parameters Buffer"),
+            contents: bytemuck::cast_slice(&parameters),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let shader_results_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Shader Results Buffer"),
+            size: (pixels.len() * size_of::<u32>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        // Load shader, catching the errors of the user-supplied shader instead of letting the device panic on them
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Effect"),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+
+        // Create pipeline
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: dimensions_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: parameters_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: pixels_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: shader_results_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: "main",
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        if let Some(error) = pollster::block_on(device.pop_error_scope()) { return Err(error.to_string()); }
+
+        // Execute
+        let mut encoder = device.create_command_encoder(&Default::default());
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&Default::default());
+            compute_pass.set_bind_group(0, &bind_group, &[]);
+            compute_pass.set_pipeline(&pipeline);
+
+            let dispatch_x = width.div_ceil(WORKGROUP_COMPONENT_SIZE);
+            let dispatch_y = height.div_ceil(WORKGROUP_COMPONENT_SIZE);
+            compute_pass.dispatch_workgroups(dispatch_x, dispatch_y, 1);
+        }
+
+        // Read results back
+        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: shader_results_buffer.size(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        encoder.copy_buffer_to_buffer(&shader_results_buffer, 0, &staging_buffer, 0, shader_results_buffer.size());
+        queue.submit(Some(encoder.finish()));
+
+        let buffer_slice = staging_buffer.slice(..);
+        buffer_slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+
+        let shader_results_data = buffer_slice.get_mapped_range();
+        let shader_results: Vec<u32> = bytemuck::cast_slice(&shader_results_data).to_vec();
+        drop(shader_results_data);
+        staging_buffer.unmap();
+
+        let new_pixels: Vec<Rgb<u8>> = shader_results.iter().map(|&color| {
+            Rgb([(color & 0xFF) as u8, ((color >> 8) & 0xFF) as u8, ((color >> 16) & 0xFF) as u8])
+        }).collect();
+        if new_pixels.len() != pixels.len() { return Err(format!("the shader produced {} pixels instead of {}", new_pixels.len(), pixels.len())); }
+
+        Ok(new_pixels)
+    }
+
+    pub fn palettize_biased(&self, width: u32, height: u32, pixels: &[Rgb<u8>], biased_palette: &[Rgb<u8>], standard_palette: &[Rgb<u8>], mask: &ThresholdMask) -> Vec<Rgb<u8>> {
+        let Some((device, queue)) = &self.hardware else { return palettize_on_cpu(width, pixels, biased_palette, standard_palette, mask); };
+
+        // splits images that do not fit the memory budgets or the GPU buffers into tiles of rows
+        let tile_rows = get_tile_rows(width, height, biased_palette.len() + standard_palette.len(), device.limits().max_storage_buffer_binding_size as u64);
+        if tile_rows < height {
+            let tile_count = height.div_ceil(tile_rows) as usize;
+            return pixels.chunks((tile_rows * width) as usize)
+                .enumerate()
+                .flat_map(|(index, tile)| {
+                    let new_pixels = self.palettize_biased(width, tile.len() as u32 / width, tile, biased_palette, standard_palette, &mask.starting_at_row(index as u32 * tile_rows));
+                    report(ProgressEvents::TileDone(index + 1, tile_count));
+                    new_pixels
+                })
+                .collect();
+        }
+
         // Convert to f32 for GPU
+        let color_distance = config::get().color_distance;
         let u32_pixels: Vec<u32> = Self::colors_as_vec_u32(pixels);
-        let u32_biased_palette: Vec<u32> = Self::colors_as_vec_u32(biased_palette);
-        let u32_standard_palette: Vec<u32> = Self::colors_as_vec_u32(standard_palette);
+        let biased_palette_coordinates = Self::colors_as_coordinates(biased_palette, color_distance);
+        let standard_palette_coordinates = Self::colors_as_coordinates(standard_palette, color_distance);
 
         // Create GPU buffers
         let dimensions = GpuImageInformation::new(width, height, biased_palette.len(), standard_palette.len());
-        let dimensions_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        let dimensions_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Dimensions Buffer"),
             contents: bytemuck::bytes_of(&dimensions),
             usage: wgpu::BufferUsages::UNIFORM,
         });
 
-        let pixels_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        let pixels_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Pixel Buffer"),
             contents: bytemuck::cast_slice(&u32_pixels),
             usage: wgpu::BufferUsages::STORAGE,
         });
 
-        let biased_palette_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        let biased_palette_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Biased Palette Buffer"),
-            contents: bytemuck::cast_slice(&u32_biased_palette),
+            contents: bytemuck::cast_slice(&biased_palette_coordinates),
             usage: wgpu::BufferUsages::STORAGE,
         });
 
-        let standard_palette_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        let standard_palette_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Standard Palette Buffer"),
-            contents: bytemuck::cast_slice(&u32_standard_palette),
+            contents: bytemuck::cast_slice(&standard_palette_coordinates),
             usage: wgpu::BufferUsages::STORAGE,
         });
 
-        let shader_results_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+        let matching_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Matching Buffer"),
+            contents: bytemuck::bytes_of(&GpuMatchingInformation::new(mask, color_distance, 0.0)),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let thresholds_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Thresholds Buffer"),
+            contents: bytemuck::cast_slice(&mask.thresholds),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let shader_results_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Shader Results Buffer"),
             size: (pixels.len() * size_of::<u32>()) as u64,
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
@@ -258,13 +669,13 @@ impl Gpu {
         });
 
         // Load shader
-        let shader = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Palettize Biased"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("palettize_biased.wgsl").into()),
+            source: wgpu::ShaderSource::Wgsl((COLOR_DISTANCE_SHADER.to_string() + include_str!("palettize_biased.wgsl")).into()),
         });
 
         // Create pipeline
-        let bind_group_layout = self.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: None,
             entries: &[
                 wgpu::BindGroupLayoutEntry {
@@ -317,10 +728,30 @@ impl Gpu {
                     },
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
-        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: None,
             layout: &bind_group_layout,
             entries: &[
@@ -344,16 +775,24 @@ impl Gpu {
                     binding: 4,
                     resource: shader_results_buffer.as_entire_binding(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: matching_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: thresholds_buffer.as_entire_binding(),
+                },
             ],
         });
 
-        let pipeline_layout = self.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: None,
             bind_group_layouts: &[&bind_group_layout],
             push_constant_ranges: &[],
         });
 
-        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
             label: None,
             layout: Some(&pipeline_layout),
             module: &shader,
@@ -363,7 +802,7 @@ impl Gpu {
         });
 
         // Execute
-        let mut encoder = self.device.create_command_encoder(&Default::default());
+        let mut encoder = device.create_command_encoder(&Default::default());
         {
             let mut compute_pass = encoder.begin_compute_pass(&Default::default());
             compute_pass.set_bind_group(0, &bind_group, &[]);
@@ -375,7 +814,7 @@ impl Gpu {
         }
 
         // Read results back
-        let staging_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: None,
             size: shader_results_buffer.size(),
             usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
@@ -383,11 +822,11 @@ impl Gpu {
         });
 
         encoder.copy_buffer_to_buffer(&shader_results_buffer, 0, &staging_buffer, 0, shader_results_buffer.size());
-        self.queue.submit(Some(encoder.finish()));
+        queue.submit(Some(encoder.finish()));
 
         let buffer_slice = staging_buffer.slice(..);
         buffer_slice.map_async(wgpu::MapMode::Read, |_| {});
-        self.device.poll(wgpu::Maintain::Wait);
+        device.poll(wgpu::Maintain::Wait);
 
         let shader_results_data = buffer_slice.get_mapped_range();
         let shader_results: Vec<u32> = bytemuck::cast_slice(&shader_results_data).to_vec();
@@ -403,6 +842,416 @@ impl Gpu {
         new_pixels
     }
 }
+impl Default for Gpu {
+    fn default() -> Self { Gpu::new() }
+}
+
+/// Both palettes of a mapping along with the coordinates of their colors in the space distances are measured in,
+/// so many pixels can be matched without converting the palettes again.
+pub struct MatchingPalettes {
+    /// The space distances are measured in.
+    color_distance: ColorDistances,
+    /// The coordinates and colors of the biased palette.
+    biased_palette: Vec<([f32; 3], Rgb<u8>)>,
+    /// The coordinates and colors of the standard palette.
+    standard_palette: Vec<([f32; 3], Rgb<u8>)>,
+}
+impl MatchingPalettes {
+    /// Converts both palettes into a given space.
+    pub fn new(biased_palette: &[Rgb<u8>], standard_palette: &[Rgb<u8>], color_distance: ColorDistances) -> MatchingPalettes {
+        let with_coordinates = |palette: &[Rgb<u8>]| palette.iter().map(|color| (color_distance.to_coordinates(color), *color)).collect();
+        MatchingPalettes { color_distance, biased_palette: with_coordinates(biased_palette), standard_palette: with_coordinates(standard_palette) }
+    }
+
+    /// Returns the closest color of both palettes on the CPU, the same way the palettize shaders pick it.
+    /// Biased colors are preferred by shrinking their distance, an empty biased palette picks evenly, and the color itself is returned if both palettes are empty.
+    pub fn closest_color(&self, pixel: &Rgb<u8>) -> Rgb<u8> {
+        let pixel_coordinates = self.color_distance.to_coordinates(pixel);
+        let distance = |coordinates: &[f32; 3], bias: f32| self.color_distance.get_distance(&pixel_coordinates, coordinates) / bias;
+        let closest = |palette: &[([f32; 3], Rgb<u8>)], bias: f32| {
+            palette.iter()
+                .map(|(coordinates, color)| (distance(coordinates, bias), *color))
+                .fold(None, |closest: Option<(f32, Rgb<u8>)>, candidate| match closest {
+                    Some(closest) if closest.0 <= candidate.0 => Some(closest),
+                    _ => Some(candidate),
+                })
+        };
+
+        match (closest(&self.biased_palette, 0.625), closest(&self.standard_palette, 1.0)) {
+            (Some(biased), Some(standard)) => if biased.0 <= standard.0 { biased.1 } else { standard.1 },
+            (Some(closest), None) | (None, Some(closest)) => closest.1,
+            (None, None) => *pixel,
+        }
+    }
+}
+
+/// A palette mapping prepared for matching single colors on the CPU, with the palettes converted once,
+/// so many colors (e.g. of dithered pixels or LUT lattices) can be mapped without converting them again for every color.
+pub enum ColorMatcher<'a> {
+    /// The palettes of an even or biased mapping.
+    Palettes(MatchingPalettes),
+    /// The brightness threshold (0-1) of a split mapping, with the palettes of its shadows and highlights.
+    Split(f32, MatchingPalettes, MatchingPalettes),
+    /// The matcher of a transformed mapping with its transform.
+    Transformed(Box<ColorMatcher<'a>>, &'a Transform),
+    /// A mapping without palettes to prepare, which maps colors itself.
+    Direct(&'a PaletteMapping),
+}
+impl ColorMatcher<'_> {
+    /// Returns the color a single color is mapped onto.
+    pub fn closest_color(&self, color: &Rgb<u8>) -> Rgb<u8> {
+        match self {
+            ColorMatcher::Palettes(palettes) => palettes.closest_color(color),
+            ColorMatcher::Split(threshold, shadow_palettes, _) if is_shadow(color, *threshold) => shadow_palettes.closest_color(color),
+            ColorMatcher::Split(_, _, highlight_palettes) => highlight_palettes.closest_color(color),
+            ColorMatcher::Transformed(matcher, transform) => transform.color_after(color, &matcher.closest_color(&transform.color_before(color))),
+            ColorMatcher::Direct(PaletteMapping::Tone(ramp)) => get_tone(color, ramp),
+            ColorMatcher::Direct(PaletteMapping::Script(script, palette)) => get_scripts()[*script].run(&[*color], palette).map(|colors| colors[0]).unwrap_or(*color),
+            // effects and plugins have no palette to pick from, so colors stay as they are
+            ColorMatcher::Direct(_) => *color,
+        }
+    }
+}
+
+/// Returns if a color is darker than a threshold (perceived brightness from 0 to 1).
+pub fn is_shadow(color: &Rgb<u8>, threshold: f32) -> bool {
+    get_brightness(color) / 255.0 < threshold
+}
+
+/// Returns the color of a tone ramp at the perceived brightness of a color, from the first color for black to the last for white.
+/// The color itself is returned if the ramp is empty.
+pub fn get_tone(color: &Rgb<u8>, ramp: &[Rgb<u8>]) -> Rgb<u8> {
+    if ramp.is_empty() { return *color; }
+    let index = (get_brightness(color) / 255.0 * (ramp.len() - 1) as f32).round() as usize;
+    ramp[index.min(ramp.len() - 1)]
+}
+
+/// Returns the negative of a color.
+pub fn get_inverted_color(color: &Rgb<u8>) -> Rgb<u8> {
+    Rgb(color.0.map(|channel| 255 - channel))
+}
+
+/// Returns a color pushed along an S-shaped contrast curve with a given strength (0 keeps the color, 1 is the full curve), darkening the shadows and brightening the highlights.
+pub fn get_contrasted_color(color: &Rgb<u8>, strength: f32) -> Rgb<u8> {
+    Rgb(color.0.map(|channel| {
+        let level = channel as f32 / 255.0;
+        let curved_level = level * level * (3.0 - 2.0 * level);
+        ((level + (curved_level - level) * strength) * 255.0).round() as u8
+    }))
+}
+
+/// Returns if a color is within a tolerance (0-1, from the same color only to every color) of a key color.
+pub fn is_keyed(color: &Rgb<u8>, key_color: &Rgb<u8>, tolerance: f32) -> bool {
+    let max_distance = get_distance(&Rgb([0, 0, 0]), &Rgb([255, 255, 255]), &None);
+    get_distance(color, key_color, &None) / max_distance <= tolerance
+}
+
+/// Returns a processed image with every pixel whose source pixel matches a key color replaced by a fill color.
+pub fn get_keyed_image(source_image: &ImageBuffer<Rgb<u8>, Vec<u8>>, new_image: ImageBuffer<Rgb<u8>, Vec<u8>>, key_color: &Rgb<u8>, tolerance: f32, fill_color: &Rgb<u8>) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let mut keyed_image = new_image;
+    for (source_pixel, new_pixel) in source_image.pixels().zip(keyed_image.pixels_mut()) {
+        if is_keyed(source_pixel, key_color, tolerance) { *new_pixel = *fill_color; }
+    }
+    keyed_image
+}
+
+/// Returns the hue and chroma of a mapped color at the perceptual lightness of its source color, lowering the chroma where it would leave the sRGB gamut.
+pub fn get_luminance_preserved_color(source_color: &Rgb<u8>, mapped_color: &Rgb<u8>) -> Rgb<u8> {
+    let mapped_color = to_oklch(mapped_color);
+    from_oklch_in_gamut([to_oklch(source_color)[0], mapped_color[1], mapped_color[2]])
+}
+
+/// Returns a processed image with every pixel moved to the perceptual lightness of its source pixel.
+pub fn get_luminance_preserved_image(source_image: &ImageBuffer<Rgb<u8>, Vec<u8>>, new_image: ImageBuffer<Rgb<u8>, Vec<u8>>) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let (width, height) = new_image.dimensions();
+    let channels: Vec<u8> = new_image.as_raw().par_chunks(3).zip(source_image.as_raw().par_chunks(3)).flat_map_iter(|(new_pixel, source_pixel)| {
+        get_luminance_preserved_color(&Rgb([source_pixel[0], source_pixel[1], source_pixel[2]]), &Rgb([new_pixel[0], new_pixel[1], new_pixel[2]])).0
+    }).collect();
+    ImageBuffer::from_raw(width, height, channels).unwrap_or(new_image)
+}
+
+/// Returns an image with every block of a given size (in pixels) averaged into a single pixel, where the blocks at the right and bottom edges may be smaller.
+pub fn get_block_averages(image: &ImageBuffer<Rgb<u8>, Vec<u8>>, block_size: u32) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let (width, height) = image.dimensions();
+    let (block_columns, block_rows) = (width.div_ceil(block_size), height.div_ceil(block_size));
+    let averages: Vec<u8> = (0..block_columns * block_rows).into_par_iter().flat_map_iter(|block| {
+        let (left, top) = (block % block_columns * block_size, block / block_columns * block_size);
+        let (right, bottom) = ((left + block_size).min(width), (top + block_size).min(height));
+        let mut sum = [0u64; 3];
+        for y in top..bottom {
+            for x in left..right {
+                let pixel = image.get_pixel(x, y);
+                for channel in 0..3 { sum[channel] += pixel[channel] as u64; }
+            }
+        }
+        let count = ((right - left) * (bottom - top)) as u64;
+        sum.map(|channel| ((channel + count / 2) / count) as u8)
+    }).collect();
+    ImageBuffer::from_raw(block_columns, block_rows, averages).unwrap()
+}
+
+/// Returns an image of a given size with every pixel of a block image scaled up into a block of a given size (in pixels).
+pub fn get_scaled_up_blocks(blocks: &ImageBuffer<Rgb<u8>, Vec<u8>>, block_size: u32, width: u32, height: u32) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    ImageBuffer::from_fn(width, height, |x, y| *blocks.get_pixel(x / block_size, y / block_size))
+}
+
+/// Maps every pixel, nudged by the threshold mask, onto the closest color of both palettes on the CPU.
+fn palettize_on_cpu(width: u32, pixels: &[Rgb<u8>], biased_palette: &[Rgb<u8>], standard_palette: &[Rgb<u8>], mask: &ThresholdMask) -> Vec<Rgb<u8>> {
+    let palettes = MatchingPalettes::new(biased_palette, standard_palette, config::get().color_distance);
+    pixels.par_iter().enumerate()
+        .map(|(index, pixel)| palettes.closest_color(&mask.nudge(pixel, index as u32 % width, index as u32 / width)))
+        .collect()
+}
+
+/// Maps every pixel darker than a threshold onto the shadow palette and every other pixel onto the highlight palette on the CPU, the same way the palettize shader does.
+fn palettize_split_on_cpu(width: u32, pixels: &[Rgb<u8>], threshold: f32, shadow_palette: &[Rgb<u8>], highlight_palette: &[Rgb<u8>], mask: &ThresholdMask) -> Vec<Rgb<u8>> {
+    let shadow_palettes = MatchingPalettes::new(&[], shadow_palette, config::get().color_distance);
+    let highlight_palettes = MatchingPalettes::new(&[], highlight_palette, config::get().color_distance);
+    pixels.par_iter().enumerate()
+        .map(|(index, pixel)| {
+            let palettes = if is_shadow(pixel, threshold) { &shadow_palettes } else { &highlight_palettes };
+            palettes.closest_color(&mask.nudge(pixel, index as u32 % width, index as u32 / width))
+        })
+        .collect()
+}
+
+
+/// A square mask of thresholds repeated over an image, nudging every pixel before it is mapped so ordered dithering runs inside the palettize shaders.
+#[derive(Clone)]
+pub struct ThresholdMask {
+    /// The side length of the mask.
+    size: u32,
+    /// The thresholds (-0.5 to 0.5) of the mask, row by row.
+    thresholds: Vec<f32>,
+    /// How far (0-255) the channels of a color are nudged in total, half of it in either direction.
+    spread: f32,
+}
+impl ThresholdMask {
+    /// Returns a mask of a given side length from its thresholds (0-1) row by row, nudging colors by a given spread.
+    pub fn new(size: u32, thresholds: &[f32], spread: f32) -> ThresholdMask {
+        ThresholdMask { size, thresholds: thresholds.iter().map(|threshold| threshold - 0.5).collect(), spread }
+    }
+
+    /// Returns a mask that leaves every color as it is.
+    pub fn none() -> ThresholdMask {
+        ThresholdMask { size: 1, thresholds: vec![0.0], spread: 0.0 }
+    }
+
+    /// Returns the mask starting at a given row, so tiles of rows continue the pattern of the tiles above them.
+    fn starting_at_row(&self, row: u32) -> ThresholdMask {
+        let mut thresholds = self.thresholds.clone();
+        thresholds.rotate_left(((row % self.size) * self.size) as usize);
+        ThresholdMask { size: self.size, thresholds, spread: self.spread }
+    }
+
+    /// Returns a color nudged by the threshold at a given position, the same way the palettize shaders nudge it.
+    pub fn nudge(&self, color: &Rgb<u8>, x: u32, y: u32) -> Rgb<u8> {
+        let offset = self.thresholds[((y % self.size) * self.size + x % self.size) as usize] * self.spread;
+        Rgb(color.0.map(|channel| (channel as f32 + offset + 0.5).floor().clamp(0.0, 255.0) as u8))
+    }
+}
+
+
+/// A change made to every pixel around the palettes of a transformed mapping, before they are applied, after them, or both.
+#[derive(Clone)]
+pub enum Transform {
+    /// Every pixel is negated before being mapped.
+    Invert,
+    /// Every pixel has its hue rotated by the given degrees before being mapped.
+    HueRotate(f32),
+    /// Every pixel is pushed along a contrast curve of the given strength (0-1) before being mapped.
+    Contrast(f32),
+    /// Every block of the given size (in pixels) is averaged into a single color before being mapped and scaled back up afterwards,
+    /// so each block is mapped once and stays solid.
+    Pixelate(u32),
+    /// Every pixel within the tolerance (0-1) of the key color (first) is replaced by the fill color (second) after being mapped.
+    Key(Rgb<u8>, f32, Rgb<u8>),
+    /// Every pixel takes the hue and chroma of its mapped color at its own perceptual lightness after being mapped.
+    PreserveLuminance,
+}
+impl Transform {
+    /// Returns an image changed the way it is before the palettes are applied.
+    pub fn before<'a>(&self, image: &'a ImageBuffer<Rgb<u8>, Vec<u8>>) -> Cow<'a, ImageBuffer<Rgb<u8>, Vec<u8>>> {
+        match self {
+            Transform::Pixelate(block_size) => Cow::Owned(get_block_averages(image, *block_size)),
+            Transform::Key(_, _, _) | Transform::PreserveLuminance => Cow::Borrowed(image),
+            _ => Cow::Owned(ImageBuffer::from_fn(image.width(), image.height(), |x, y| self.color_before(image.get_pixel(x, y)))),
+        }
+    }
+
+    /// Returns a mapped image changed the way it is after the palettes are applied, from the source image it was mapped from.
+    pub fn after(&self, source_image: &ImageBuffer<Rgb<u8>, Vec<u8>>, mapped_image: ImageBuffer<Rgb<u8>, Vec<u8>>) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        match self {
+            Transform::Pixelate(block_size) => get_scaled_up_blocks(&mapped_image, *block_size, source_image.width(), source_image.height()),
+            Transform::Key(key_color, tolerance, fill_color) => get_keyed_image(source_image, mapped_image, key_color, *tolerance, fill_color),
+            Transform::PreserveLuminance => get_luminance_preserved_image(source_image, mapped_image),
+            _ => mapped_image,
+        }
+    }
+
+    /// Returns a single color changed the way it is before the palettes are applied, where a single color is its own block.
+    pub fn color_before(&self, color: &Rgb<u8>) -> Rgb<u8> {
+        match self {
+            Transform::Invert => get_inverted_color(color),
+            Transform::HueRotate(degrees) => get_hue_rotated_color(color, *degrees),
+            Transform::Contrast(strength) => get_contrasted_color(color, *strength),
+            Transform::Pixelate(_) | Transform::Key(_, _, _) | Transform::PreserveLuminance => *color,
+        }
+    }
+
+    /// Returns a single mapped color changed the way it is after the palettes are applied, from the source color it was mapped from.
+    pub fn color_after(&self, source_color: &Rgb<u8>, mapped_color: &Rgb<u8>) -> Rgb<u8> {
+        match self {
+            Transform::Key(key_color, tolerance, fill_color) if is_keyed(source_color, key_color, *tolerance) => *fill_color,
+            Transform::PreserveLuminance => get_luminance_preserved_color(source_color, mapped_color),
+            _ => *mapped_color,
+        }
+    }
+
+    /// Returns the colors the transform adds to the palettes.
+    pub fn colors(&self) -> Vec<Rgb<u8>> {
+        match self {
+            Transform::Key(_, _, fill_color) => vec![*fill_color],
+            _ => Vec::new(),
+        }
+    }
+}
+
+
+
+/// The palettes an image is mapped onto.
+#[derive(Clone)]
+pub enum PaletteMapping {
+    /// Every pixel takes the closest color of the palette.
+    Even(Vec<Rgb<u8>>),
+    /// Every pixel takes the closest color of both palettes, with the colors of the first (biased) palette being preferred.
+    Biased(Vec<Rgb<u8>>, Vec<Rgb<u8>>),
+    /// Every pixel darker than the threshold (perceived brightness from 0 to 1) takes the closest color of the first (shadow) palette
+    /// and every other pixel the closest color of the second (highlight) palette.
+    Split(f32, Vec<Rgb<u8>>, Vec<Rgb<u8>>),
+    /// Every pixel takes the color of the tone ramp at its perceived brightness (grayscale, then recolored), from the first color for black to the last for white.
+    Tone(Vec<Rgb<u8>>),
+    /// Every pixel is changed by the transform before and after being mapped by the inner mapping.
+    Transformed(Box<PaletteMapping>, Transform),
+    /// Every pixel keeps its color, e.g. inside a transform that is used without a palette.
+    Unchanged,
+    /// Every pixel is mapped by a user-supplied WGSL shader with the given parameters, without a palette.
+    Effect(String, Vec<[f32; 4]>),
+    /// Every pixel is mapped by the plugin at the given index in the loaded plugins with the given parameters, without a palette.
+    Plugin(usize, Vec<[f32; 4]>),
+    /// Every pixel takes the color of the palette the script at the given index in the loaded scripts picks for it.
+    Script(usize, Vec<Rgb<u8>>),
+}
+impl PaletteMapping {
+    /// Maps every pixel of an image onto the palettes.
+    pub fn process(&self, source_image: DynamicImage) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        match self {
+            PaletteMapping::Even(palette) => process_evenly(source_image, palette.clone()),
+            PaletteMapping::Biased(biased_palette, standard_palette) => process_biased(source_image, biased_palette.clone(), standard_palette.clone()),
+            PaletteMapping::Split(_, _, _) | PaletteMapping::Effect(_, _) => self.process_on(&Gpu::new(), &source_image.to_rgb8()),
+            // tones, plugins and scripts run on the CPU, so no adapter is set up
+            PaletteMapping::Tone(_) | PaletteMapping::Plugin(_, _) | PaletteMapping::Script(_, _) => self.process_on(&Gpu::request(true), &source_image.to_rgb8()),
+            PaletteMapping::Transformed(palette_mapping, transform) => {
+                let source_image = source_image.to_rgb8();
+                let new_image = palette_mapping.process(DynamicImage::ImageRgb8(transform.before(&source_image).into_owned()));
+                transform.after(&source_image, new_image)
+            }
+            PaletteMapping::Unchanged => source_image.to_rgb8(),
+        }
+    }
+
+    /// Maps every pixel of an image onto the palettes with a gpu that is already set up, so repeated frames skip the setup.
+    pub fn process_on(&self, gpu: &Gpu, source_image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        self.process_dithered_on(gpu, source_image, &ThresholdMask::none())
+    }
+
+    /// Maps every pixel of an image, nudged by a threshold mask, onto the palettes with a gpu that is already set up.
+    /// Effects and plugins have no palette to pick from, so they ignore the mask.
+    pub fn process_dithered_on(&self, gpu: &Gpu, source_image: &ImageBuffer<Rgb<u8>, Vec<u8>>, mask: &ThresholdMask) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        let (width, height) = source_image.dimensions();
+        let pixels: Vec<Rgb<u8>> = source_image.pixels().copied().collect();
+        let new_pixels = match self {
+            PaletteMapping::Even(palette) => gpu.palettize_evenly(width, height, &pixels, palette, mask),
+            PaletteMapping::Biased(biased_palette, standard_palette) => gpu.palettize_biased(width, height, &pixels, biased_palette, standard_palette, mask),
+            PaletteMapping::Split(threshold, shadow_palette, highlight_palette) => gpu.palettize_split(width, height, &pixels, (*threshold, shadow_palette, highlight_palette), mask),
+            PaletteMapping::Tone(ramp) => pixels.par_iter().enumerate().map(|(index, pixel)| get_tone(&mask.nudge(pixel, index as u32 % width, index as u32 / width), ramp)).collect(),
+            PaletteMapping::Transformed(palette_mapping, transform) => {
+                let new_image = palette_mapping.process_dithered_on(gpu, &transform.before(source_image), mask);
+                return transform.after(source_image, new_image);
+            }
+            PaletteMapping::Unchanged => return source_image.clone(),
+            // an effect that can't be run leaves the image untouched
+            PaletteMapping::Effect(source, parameters) => gpu.run_effect(width, height, &pixels, source, parameters).unwrap_or_else(|_| pixels.clone()),
+            // like effects, a plugin that fails leaves the image untouched
+            PaletteMapping::Plugin(plugin, parameters) => get_plugins()[*plugin].run(width, height, &pixels, parameters).unwrap_or(pixels),
+            // scripts pick their colors themselves, so they ignore the mask, and leave the image untouched if they fail
+            PaletteMapping::Script(script, palette) => get_scripts()[*script].run(&pixels, palette).unwrap_or(pixels),
+        };
+        ImageBuffer::from_fn(width, height, |x, y| new_pixels[(y * width + x) as usize])
+    }
+
+    /// Returns whether the mapping runs an effect or a plugin, also inside a transform, which only map whole images instead of single colors.
+    pub fn maps_whole_images(&self) -> bool {
+        match self {
+            PaletteMapping::Effect(_, _) | PaletteMapping::Plugin(_, _) => true,
+            PaletteMapping::Transformed(palette_mapping, _) => palette_mapping.maps_whole_images(),
+            _ => false,
+        }
+    }
+
+    /// Returns whether the mapping needs whole images instead of single colors, since it runs an effect or a plugin or pixelates blocks of pixels.
+    pub fn needs_whole_images(&self) -> bool {
+        match self {
+            PaletteMapping::Transformed(_, Transform::Pixelate(_)) => true,
+            PaletteMapping::Transformed(palette_mapping, _) => palette_mapping.needs_whole_images(),
+            _ => self.maps_whole_images(),
+        }
+    }
+
+    /// Maps every pixel of an image onto the palettes on its own on the CPU, for images whose neighboring pixels are unrelated colors (e.g. LUT lattices or gif color tables).
+    pub fn map_colors(&self, image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        let matcher = self.matcher();
+        ImageBuffer::from_fn(image.width(), image.height(), |x, y| matcher.closest_color(image.get_pixel(x, y)))
+    }
+
+    /// Prepares the mapping for matching many single colors on the CPU with the configured color distance.
+    pub fn matcher(&self) -> ColorMatcher<'_> {
+        let color_distance = config::get().color_distance;
+        match self {
+            PaletteMapping::Even(palette) => ColorMatcher::Palettes(MatchingPalettes::new(&[], palette, color_distance)),
+            PaletteMapping::Biased(biased_palette, standard_palette) => ColorMatcher::Palettes(MatchingPalettes::new(biased_palette, standard_palette, color_distance)),
+            PaletteMapping::Split(threshold, shadow_palette, highlight_palette) => ColorMatcher::Split(
+                *threshold,
+                MatchingPalettes::new(&[], shadow_palette, color_distance),
+                MatchingPalettes::new(&[], highlight_palette, color_distance),
+            ),
+            PaletteMapping::Transformed(palette_mapping, transform) => ColorMatcher::Transformed(Box::new(palette_mapping.matcher()), transform),
+            _ => ColorMatcher::Direct(self),
+        }
+    }
+
+    /// Returns the color of the palettes a single color is mapped onto, computed on the CPU.
+    /// Effects and plugins have no palette to pick from, so colors stay as they are. Many colors are better mapped through a single matcher.
+    pub fn closest_color(&self, color: &Rgb<u8>) -> Rgb<u8> {
+        self.matcher().closest_color(color)
+    }
+
+    /// Returns every color of the palettes.
+    pub fn colors(&self) -> Vec<Rgb<u8>> {
+        match self {
+            PaletteMapping::Even(palette) => palette.clone(),
+            PaletteMapping::Biased(biased_palette, standard_palette) => biased_palette.iter().chain(standard_palette).copied().collect(),
+            PaletteMapping::Split(_, shadow_palette, highlight_palette) => shadow_palette.iter().chain(highlight_palette).copied().collect(),
+            PaletteMapping::Tone(ramp) => ramp.clone(),
+            PaletteMapping::Transformed(palette_mapping, transform) => palette_mapping.colors().into_iter().chain(transform.colors()).collect(),
+            PaletteMapping::Unchanged => Vec::new(),
+            PaletteMapping::Script(_, palette) => palette.clone(),
+            // effects and plugins map colors directly instead of picking them from a palette
+            PaletteMapping::Effect(_, _) | PaletteMapping::Plugin(_, _) => Vec::new(),
+        }
+    }
+}
+
 
 
 /// Evenly processes and image using only the colors in a given palette.
@@ -414,7 +1263,7 @@ pub fn process_evenly(source_image: DynamicImage, palette: Vec<Rgb<u8>>) -> Imag
     // editing
     let pixels: Vec<Rgb<u8>> = source_image.pixels().map(|pixel| { pixel.2.to_rgb() }).collect();
     let gpu = Gpu::new();
-    let new_pixels = gpu.palettize_evenly(width, height, &pixels, &palette);
+    let new_pixels = gpu.palettize_evenly(width, height, &pixels, &palette, &ThresholdMask::none());
 
     // filling the new image with the new pixels
     for x in 0..new_pixels.len() {
@@ -436,7 +1285,7 @@ pub fn process_biased(source_image: DynamicImage, biased_palette: Vec<Rgb<u8>>,
     // editing
     let pixels: Vec<Rgb<u8>> = source_image.pixels().map(|pixel| { pixel.2.to_rgb() }).collect();
     let gpu = Gpu::new();
-    let new_pixels = gpu.palettize_biased(width, height, &pixels, &biased_palette, &standard_palette);
+    let new_pixels = gpu.palettize_biased(width, height, &pixels, &biased_palette, &standard_palette, &ThresholdMask::none());
 
     // filling the new image with the new pixels
     for x in 0..new_pixels.len() {
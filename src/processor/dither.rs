@@ -0,0 +1,500 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use image::{DynamicImage, GenericImageView, ImageBuffer, Rgb};
+use rayon::prelude::*;
+use crate::config;
+use crate::processor::compute::{is_shadow, Gpu, MatchingPalettes, PaletteMapping, ThresholdMask};
+use crate::processor::guide::{NumberRange, ProcessingGuide, ProcessingStep, ProcessingStepTypes};
+use crate::processor::quality::QualityPresets;
+
+/// Gets the side lengths the Bayer matrix ordered dithering repeats over the image can be built with.
+pub fn bayer_sizes() -> Vec<usize> { vec![2, 4, 8] }
+
+/// Gets how far (0-255) ordered dithering nudges the channels of a color in total, half of it in either direction.
+fn ordered_dither_spread() -> f32 { 48.0 }
+
+/// Gets the most times spatial quantization sweeps over the image, stopping early once no pixel changes anymore.
+fn spatial_sweeps() -> usize { 8 }
+
+/// Gets the weights of the 3x3 blur spatial quantization compares the source and the result through, as (x offset, y offset, weight).
+/// The eye blurs neighboring pixels together in much the same way, so mixes of palette colors read as the colors in between.
+fn spatial_filter() -> Vec<(i64, i64, f32)> {
+    let weights = [1.0, 2.0, 1.0];
+    (-1..=1).flat_map(|y: i64| (-1..=1).map(move |x: i64| (x, y, weights[(x + 1) as usize] * weights[(y + 1) as usize] / 16.0))).collect()
+}
+
+/// Gets the max width and height of the tiles in dither comparisons.
+/// Tiles are cropped instead of scaled, so the dither patterns show at their actual pixel size.
+fn comparison_tile_size() -> u32 { 192 }
+
+/// Gets the gap between and around the tiles of dither comparisons.
+fn comparison_gap() -> u32 { 8 }
+
+/// Gets the background color between the tiles of dither comparisons.
+fn comparison_background() -> Rgb<u8> { Rgb([24, 24, 24]) }
+
+/// The square 16-bit grayscale texture blue noise dithering repeats over the image, generated ahead of time with the void-and-cluster method.
+/// Every pixel holds the order it was placed in, so every threshold appears exactly once.
+const BLUE_NOISE_TEXTURE: &[u8] = include_bytes!("blue_noise.png");
+
+/// The side length and the thresholds of the blue noise mask, decoded once on first use.
+static BLUE_NOISE: OnceLock<(u32, Vec<f32>)> = OnceLock::new();
+
+
+
+/// The ways the error of mapping colors onto a palette can be spread out to fake the colors in between.
+#[derive(Copy, Clone, PartialEq)]
+pub enum DitherModes {
+    /// Every pixel takes the closest palette color.
+    None,
+    /// Pixels are nudged by a repeating 2x2, 4x4 or 8x8 threshold matrix (as configured), which gives a regular crosshatch.
+    Bayer,
+    /// The error of every pixel is pushed onto the pixels right of and below it.
+    FloydSteinberg,
+    /// Like Floyd-Steinberg, but only three quarters of the error is pushed on, which keeps more contrast.
+    Atkinson,
+    /// Pixels are nudged by a repeating 64x64 blue noise texture, which gives an even grain without visible patterns.
+    BlueNoise,
+    /// Pixels are picked so the blurred result matches the blurred image as closely as possible, which gives the smoothest mixes with tiny palettes.
+    Spatial,
+}
+impl DitherModes {
+    /// Gets the name of the dithering mode.
+    pub fn name(&self) -> String {
+        match self {
+            DitherModes::None => "None".to_string(),
+            DitherModes::Bayer => "Bayer".to_string(),
+            DitherModes::FloydSteinberg => "Floyd-Steinberg".to_string(),
+            DitherModes::Atkinson => "Atkinson".to_string(),
+            DitherModes::BlueNoise => "Blue Noise".to_string(),
+            DitherModes::Spatial => "Spatial".to_string(),
+        }
+    }
+
+    /// Gets the name of the dithering mode as it is written in the settings and typed into the dither guide (e.g. floyd-steinberg).
+    pub fn setting_name(&self) -> String {
+        self.name().to_lowercase().replace(' ', "-")
+    }
+
+    /// Returns every dithering mode.
+    pub fn all() -> Vec<DitherModes> {
+        vec![DitherModes::None, DitherModes::Bayer, DitherModes::FloydSteinberg, DitherModes::Atkinson, DitherModes::BlueNoise, DitherModes::Spatial]
+    }
+
+    /// Returns the dithering mode with a given name (case insensitive).
+    pub fn from_name(name: &str) -> Option<DitherModes> {
+        match name.to_lowercase().as_str() {
+            "none" => Some(DitherModes::None),
+            "bayer" => Some(DitherModes::Bayer),
+            "floyd-steinberg" | "floydsteinberg" => Some(DitherModes::FloydSteinberg),
+            "atkinson" => Some(DitherModes::Atkinson),
+            "blue-noise" | "bluenoise" => Some(DitherModes::BlueNoise),
+            "spatial" => Some(DitherModes::Spatial),
+            _ => None,
+        }
+    }
+
+    /// Returns the mode after a given mode, wrapping around to no dithering.
+    pub fn next(&self) -> DitherModes {
+        match self {
+            DitherModes::None => DitherModes::FloydSteinberg,
+            DitherModes::FloydSteinberg => DitherModes::Atkinson,
+            DitherModes::Atkinson => DitherModes::Bayer,
+            DitherModes::Bayer => DitherModes::BlueNoise,
+            DitherModes::BlueNoise => DitherModes::Spatial,
+            DitherModes::Spatial => DitherModes::None,
+        }
+    }
+}
+
+
+
+/// Returns the Bayer threshold matrix (0-1) of a given side length, built by recursively tiling the 2x2 matrix.
+/// Smaller matrices give a coarser pattern with fewer levels in between the palette colors.
+fn bayer_matrix(matrix_size: usize) -> Vec<f32> {
+    let mut matrix = vec![0];
+    let mut size = 1;
+    while size < matrix_size {
+        let next_size = size * 2;
+        let mut next_matrix = vec![0; next_size * next_size];
+        for y in 0..size {
+            for x in 0..size {
+                let value = matrix[y * size + x] * 4;
+                next_matrix[y * next_size + x] = value;
+                next_matrix[y * next_size + x + size] = value + 2;
+                next_matrix[(y + size) * next_size + x] = value + 3;
+                next_matrix[(y + size) * next_size + x + size] = value + 1;
+            }
+        }
+        matrix = next_matrix;
+        size = next_size;
+    }
+    matrix.iter().map(|value| (*value as f32 + 0.5) / (size * size) as f32).collect()
+}
+
+/// Returns the side length and the thresholds (0-1) of the blue noise mask, decoded from the shipped texture.
+/// Unlike a Bayer matrix, the thresholds have no regular structure, so photographs get an even grain instead of a crosshatch.
+fn blue_noise_mask() -> &'static (u32, Vec<f32>) {
+    BLUE_NOISE.get_or_init(|| {
+        let texture = image::load_from_memory(BLUE_NOISE_TEXTURE).expect("Could not decode the blue noise texture.").to_luma16();
+        let area = (texture.width() * texture.height()) as f32;
+        let thresholds = texture.pixels()
+            .map(|pixel| (pixel[0] as f32 / u16::MAX as f32 * (area - 1.0) + 0.5) / area)
+            .collect();
+        (texture.width(), thresholds)
+    })
+}
+
+
+
+/// Returns the thresholds of a square mask shifted (wrapping around) by an offset derived from a seed, where seed 0 keeps the mask as it is.
+/// The same seed always gives the same offset, so runs with the same image, palette and seed stay byte-identical,
+/// while different seeds give different grain (e.g. for variations of the same asset).
+fn get_seeded_thresholds(size: u32, thresholds: &[f32], seed: u64) -> Vec<f32> {
+    if seed == 0 { return thresholds.to_vec(); }
+
+    // splitmix64, which spreads neighboring seeds across very different offsets
+    let mut hash = seed.wrapping_add(0x9e3779b97f4a7c15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+    hash ^= hash >> 31;
+    let (x_offset, y_offset) = ((hash % size as u64) as u32, ((hash >> 32) % size as u64) as u32);
+
+    (0..size * size).map(|index| {
+        let (x, y) = ((index % size + x_offset) % size, (index / size + y_offset) % size);
+        thresholds[(y * size + x) as usize]
+    }).collect()
+}
+
+/// Maps every pixel of an image onto the palettes with a given dithering mode and strength (0-1, from no dithering to the full effect).
+/// Ordered modes only nudge every pixel by its position, so they run in the palettize shaders, while error diffusion and spatial quantization run on the CPU.
+pub fn dither(image: &ImageBuffer<Rgb<u8>, Vec<u8>>, palette_mapping: &PaletteMapping, mode: DitherModes, strength: f32) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    match mode {
+        DitherModes::None => palette_mapping.process_on(&Gpu::new(), image),
+        DitherModes::Bayer | DitherModes::BlueNoise => palette_mapping.process_dithered_on(&Gpu::new(), image, &get_ordered_mask(mode, strength)),
+        // (x offset, y offset, share of the error)
+        DitherModes::FloydSteinberg => {
+            let weights = get_weakened_weights(&[(1, 0, 7.0 / 16.0), (-1, 1, 3.0 / 16.0), (0, 1, 5.0 / 16.0), (1, 1, 1.0 / 16.0)], strength);
+            dither_on_cpu(image, palette_mapping, &|image, palette_mapping| dither_diffused(image, palette_mapping, &weights))
+        }
+        DitherModes::Atkinson => {
+            let weights = get_weakened_weights(&[(1, 0, 0.125), (2, 0, 0.125), (-1, 1, 0.125), (0, 1, 0.125), (1, 1, 0.125), (0, 2, 0.125)], strength);
+            dither_on_cpu(image, palette_mapping, &|image, palette_mapping| dither_diffused(image, palette_mapping, &weights))
+        }
+        DitherModes::Spatial => dither_on_cpu(image, palette_mapping, &|image, palette_mapping| quantize_spatially(image, palette_mapping, strength)),
+    }
+}
+
+/// Returns the threshold mask of an ordered dithering mode with a given strength (0-1), which the palettize shaders nudge every pixel by.
+/// Frames of videos and image sequences are dithered with it as well, since the pattern stays in place from frame to frame.
+/// Error diffusion and spatial quantization spread differently in every frame, which would flicker, so they (like no dithering) leave every color as it is.
+pub fn get_ordered_mask(mode: DitherModes, strength: f32) -> ThresholdMask {
+    let spread = ordered_dither_spread() * strength;
+    match mode {
+        DitherModes::Bayer => {
+            let size = config::get().bayer_size;
+            ThresholdMask::new(size as u32, &bayer_matrix(size), spread)
+        }
+        DitherModes::BlueNoise => {
+            let (size, thresholds) = blue_noise_mask();
+            ThresholdMask::new(*size, &get_seeded_thresholds(*size, thresholds, config::get().dither_seed), spread)
+        }
+        DitherModes::None | DitherModes::FloydSteinberg | DitherModes::Atkinson | DitherModes::Spatial => ThresholdMask::none(),
+    }
+}
+
+/// Returns error diffusion weights that push on only a given share (0-1) of the error, so weaker dithering keeps closer to the plain mapping.
+fn get_weakened_weights(weights: &[(i64, u32, f32)], strength: f32) -> Vec<(i64, u32, f32)> {
+    weights.iter().map(|(x_offset, y_offset, share)| (*x_offset, *y_offset, share * strength)).collect()
+}
+
+/// Returns the guide asking for the quality preset of a run, its dithering mode and its strength (0-100%), filled with the configured preset, mode and strength.
+pub fn get_dither_guide() -> ProcessingGuide {
+    let mut guide = ProcessingGuide::new(vec![
+        ProcessingStep::new(ProcessingStepTypes::Name, "Quality (fast, balanced, accurate or custom)".to_string()),
+        ProcessingStep::new(ProcessingStepTypes::Name, "Dithering (none, bayer, floyd-steinberg, atkinson, blue-noise or spatial)".to_string()),
+        ProcessingStep::new(ProcessingStepTypes::Number, "Dither Strength (0-100%)".to_string()).with_range(NumberRange::between(0.0, 100.0)),
+    ]);
+    let quality = config::get().quality.map(|preset| preset.name().to_lowercase()).unwrap_or("custom".to_string());
+    guide.set_inputs(vec![quality, config::get().dither.setting_name(), config::get().dither_strength.to_string()]);
+    guide
+}
+
+/// Returns the quality filled into a dither guide, as Some(None) for custom settings and None if it names neither a preset nor custom.
+pub fn get_quality(guide: &ProcessingGuide) -> Option<Option<QualityPresets>> {
+    let name = guide.steps[0].as_name()?;
+    if name.eq_ignore_ascii_case("custom") { return Some(None); }
+    QualityPresets::from_name(&name).map(Some)
+}
+
+/// Returns the dithering mode filled into a dither guide, if it names one.
+pub fn get_dither_mode(guide: &ProcessingGuide) -> Option<DitherModes> {
+    DitherModes::from_name(&guide.steps[1].as_name()?)
+}
+
+/// Returns the strength (0-1) filled into a dither guide, where inputs outside of 0-100% are clamped and invalid inputs dither fully.
+pub fn get_dither_strength(guide: &ProcessingGuide) -> f32 {
+    guide.steps[2].as_number().map(|percent| percent.clamp(0.0, 100.0) / 100.0).unwrap_or(1.0)
+}
+
+/// A dithering function that maps every pixel of an image onto the palettes of a mapping without transforms.
+type CpuDither<'a> = dyn Fn(&ImageBuffer<Rgb<u8>, Vec<u8>>, &PaletteMapping) -> ImageBuffer<Rgb<u8>, Vec<u8>> + 'a;
+
+/// Maps every pixel of an image onto the palettes with a dithering function that runs on the CPU,
+/// after unwrapping the mappings that change the image before or after the palettes are applied.
+/// Effects and plugins only map whole images, so they run undithered.
+fn dither_on_cpu(image: &ImageBuffer<Rgb<u8>, Vec<u8>>, palette_mapping: &PaletteMapping, dither_palettes: &CpuDither) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    if palette_mapping.maps_whole_images() { return palette_mapping.process_on(&Gpu::new(), image); }
+
+    // the error has to be diffused in the space the palettes are in, so transforms change the image before dithering,
+    // and their changes afterwards (e.g. keyed fills) spread no error
+    if let PaletteMapping::Transformed(palette_mapping, transform) = palette_mapping {
+        return transform.after(image, dither_on_cpu(&transform.before(image), palette_mapping, dither_palettes));
+    }
+    if let PaletteMapping::Unchanged = palette_mapping { return image.clone(); }
+
+    dither_palettes(image, palette_mapping)
+}
+
+/// Picks the palette colors of every pixel so the result, blurred the way the eye blurs neighboring pixels, matches the blurred image as closely as possible,
+/// in the spirit of scolorq. Starting from the closest colors, every pixel is swept over repeatedly and switched to the palette color
+/// that brings the blurred result of its neighborhood closest to the blurred image, so tiny palettes (e.g. four Game Boy greens) mix into smooth shades.
+/// Colors are matched through the configured color distance with biased colors preferred, and pixels of split mappings only take the colors of their side of the threshold.
+/// A lower strength (0-1) only corrects that share of the blurred error, which keeps closer to the plain mapping.
+fn quantize_spatially(image: &ImageBuffer<Rgb<u8>, Vec<u8>>, palette_mapping: &PaletteMapping, strength: f32) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let (width, height) = image.dimensions();
+    if palette_mapping.colors().is_empty() { return palette_mapping.process_on(&Gpu::new(), image); }
+    let palettes = SpatialPalettes::new(palette_mapping);
+    let sides: Vec<usize> = image.pixels().map(|pixel| palettes.get_side(pixel)).collect();
+
+    // starts from the closest colors
+    let matcher = palette_mapping.matcher();
+    let mut closest_colors: HashMap<Rgb<u8>, Rgb<u8>> = HashMap::new();
+    let mut colors: Vec<[f32; 3]> = image.pixels()
+        .map(|pixel| closest_colors.entry(*pixel).or_insert_with(|| matcher.closest_color(pixel)).0.map(|channel| channel as f32))
+        .collect();
+
+    // the blurred difference between the result and the image at every pixel
+    let filter = spatial_filter();
+    let neighbors = |x: u32, y: u32| filter.iter().filter_map(move |(x_offset, y_offset, weight)| {
+        let (neighbor_x, neighbor_y) = (x as i64 + x_offset, y as i64 + y_offset);
+        if neighbor_x < 0 || neighbor_y < 0 || neighbor_x >= width as i64 || neighbor_y >= height as i64 { return None; }
+        Some(((neighbor_y as u32 * width + neighbor_x as u32) as usize, *weight))
+    });
+    let mut errors = vec![[0.0f32; 3]; colors.len()];
+    for (index, pixel) in image.pixels().enumerate() {
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        for (neighbor, weight) in neighbors(x, y) {
+            for channel in 0..3 { errors[neighbor][channel] += weight * (colors[index][channel] - pixel[channel] as f32); }
+        }
+    }
+
+    // the palette colors closest to the colors that would cancel out the blurred error, looked up once per color and side
+    let mut targets: HashMap<(usize, [u8; 3]), [f32; 3]> = HashMap::new();
+    let mut closest_target = |side: usize, target: [f32; 3]| {
+        let target = Rgb(target.map(|channel| channel.round().clamp(0.0, 255.0) as u8));
+        *targets.entry((side, target.0)).or_insert_with(|| palettes.closest_color(side, &target).0.map(|channel| channel as f32))
+    };
+
+    for _ in 0..spatial_sweeps() {
+        let mut has_changed = false;
+        for y in 0..height {
+            for step in 0..width {
+                let x = if y % 2 == 1 { width - 1 - step } else { step };
+                let index = (y * width + x) as usize;
+
+                // the color that cancels out the blurred error around the pixel best, ignoring the palette
+                let (mut pull, mut weight_sum) = ([0.0f32; 3], 0.0);
+                for (neighbor, weight) in neighbors(x, y) {
+                    for channel in 0..3 { pull[channel] += weight * errors[neighbor][channel]; }
+                    weight_sum += weight * weight;
+                }
+                let current_color = colors[index];
+                let new_color = closest_target(sides[index], std::array::from_fn(|channel| current_color[channel] - strength * pull[channel] / weight_sum));
+                if new_color == current_color { continue; }
+
+                for (neighbor, weight) in neighbors(x, y) {
+                    for channel in 0..3 { errors[neighbor][channel] += weight * (new_color[channel] - current_color[channel]); }
+                }
+                colors[index] = new_color;
+                has_changed = true;
+            }
+        }
+        if !has_changed { break; }
+    }
+
+    ImageBuffer::from_fn(width, height, |x, y| Rgb(colors[(y * width + x) as usize].map(|channel| channel.round() as u8)))
+}
+
+/// The palettes spatial quantization matches colors in, converted into the configured color distance once.
+enum SpatialPalettes {
+    /// Every pixel takes the colors of the same palettes.
+    Shared(MatchingPalettes),
+    /// Pixels darker than the threshold (perceived brightness from 0 to 1) take the colors of the first (shadow) palette and every other pixel those of the second (highlight) palette.
+    Split(f32, MatchingPalettes, MatchingPalettes),
+}
+impl SpatialPalettes {
+    /// Converts the palettes of a mapping without transforms.
+    fn new(palette_mapping: &PaletteMapping) -> SpatialPalettes {
+        let color_distance = config::get().color_distance;
+        match palette_mapping {
+            PaletteMapping::Biased(biased_palette, standard_palette) => SpatialPalettes::Shared(MatchingPalettes::new(biased_palette, standard_palette, color_distance)),
+            PaletteMapping::Split(threshold, shadow_palette, highlight_palette) => SpatialPalettes::Split(
+                *threshold,
+                MatchingPalettes::new(&[], shadow_palette, color_distance),
+                MatchingPalettes::new(&[], highlight_palette, color_distance),
+            ),
+            _ => SpatialPalettes::Shared(MatchingPalettes::new(&[], &palette_mapping.colors(), color_distance)),
+        }
+    }
+
+    /// Returns the side of the palettes a source pixel takes its colors from, 0 for shared palettes and shadows and 1 for highlights.
+    fn get_side(&self, pixel: &Rgb<u8>) -> usize {
+        match self {
+            SpatialPalettes::Split(threshold, _, _) if !is_shadow(pixel, *threshold) => 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns the closest color to a given color on a given side of the palettes.
+    fn closest_color(&self, side: usize, color: &Rgb<u8>) -> Rgb<u8> {
+        match self {
+            SpatialPalettes::Shared(palettes) => palettes.closest_color(color),
+            SpatialPalettes::Split(_, shadow_palettes, highlight_palettes) => if side == 0 { shadow_palettes.closest_color(color) } else { highlight_palettes.closest_color(color) },
+        }
+    }
+}
+
+/// Dithers an image by pushing the error of every mapped pixel onto its unmapped neighbors, with the given offsets and shares of the error.
+/// Rows are walked in a serpentine order, every other row from right to left with the offsets mirrored,
+/// so the error does not keep drifting the same way and leave diagonal streaks across large flat areas.
+fn dither_diffused(image: &ImageBuffer<Rgb<u8>, Vec<u8>>, palette_mapping: &PaletteMapping, weights: &[(i64, u32, f32)]) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let (width, height) = image.dimensions();
+    let mut colors: Vec<[f32; 3]> = image.pixels().map(|pixel| pixel.0.map(|channel| channel as f32)).collect();
+    let mut dithered = ImageBuffer::new(width, height);
+    // the pixels depend on each other, so repeated colors are looked up once instead of searching the palettes again
+    let matcher = palette_mapping.matcher();
+    let mut closest_colors: HashMap<Rgb<u8>, Rgb<u8>> = HashMap::new();
+
+    for y in 0..height {
+        let is_reversed = y % 2 == 1;
+        let direction = if is_reversed { -1 } else { 1 };
+        for step in 0..width {
+            let x = if is_reversed { width - 1 - step } else { step };
+            let color = colors[(y * width + x) as usize];
+            let clamped_color = Rgb(color.map(|channel| channel.round().clamp(0.0, 255.0) as u8));
+            let new_color = *closest_colors.entry(clamped_color).or_insert_with(|| matcher.closest_color(&clamped_color));
+            dithered.put_pixel(x, y, new_color);
+
+            let error = [0, 1, 2].map(|channel| color[channel] - new_color[channel] as f32);
+            for (x_offset, y_offset, share) in weights {
+                let (neighbor_x, neighbor_y) = (x as i64 + x_offset * direction, y + y_offset);
+                if neighbor_x < 0 || neighbor_x >= width as i64 || neighbor_y >= height { continue; }
+                let neighbor = &mut colors[(neighbor_y * width + neighbor_x as u32) as usize];
+                for channel in 0..3 { neighbor[channel] += error[channel] * share; }
+            }
+        }
+    }
+    dithered
+}
+
+/// Renders a crop from the center of an image under every dithering mode with a given strength (0-1) side by side, in the order of DitherModes::all().
+pub fn get_dither_comparison(source_image: &DynamicImage, palette_mapping: &PaletteMapping, strength: f32) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let (width, height) = source_image.dimensions();
+    let (tile_width, tile_height) = (width.min(comparison_tile_size()), height.min(comparison_tile_size()));
+    let tile = source_image.crop_imm((width - tile_width) / 2, (height - tile_height) / 2, tile_width, tile_height).to_rgb8();
+
+    let modes = DitherModes::all();
+    let tiles: Vec<ImageBuffer<Rgb<u8>, Vec<u8>>> = modes.par_iter().map(|mode| dither(&tile, palette_mapping, *mode, strength)).collect();
+
+    let gap = comparison_gap();
+    let mut comparison = ImageBuffer::from_pixel(gap + tiles.len() as u32 * (tile_width + gap), tile_height + 2 * gap, comparison_background());
+    for (index, tile) in tiles.iter().enumerate() {
+        image::imageops::replace(&mut comparison, tile, (gap + index as u32 * (tile_width + gap)) as i64, gap as i64);
+    }
+    comparison
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a horizontal gray gradient from black to white.
+    fn gradient(width: u32, height: u32) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        ImageBuffer::from_fn(width, height, |x, _| Rgb([(x * 255 / (width - 1)) as u8; 3]))
+    }
+
+    /// Returns a mapping onto black and white.
+    fn black_and_white() -> PaletteMapping {
+        PaletteMapping::Even(vec![Rgb([0, 0, 0]), Rgb([255, 255, 255])])
+    }
+
+    #[test]
+    fn error_is_diffused_in_serpentine_order() {
+        // every error is pushed onto the next pixel of the row, which is the left one on odd rows
+        let image = ImageBuffer::from_pixel(4, 2, Rgb([128, 128, 128]));
+        let dithered = dither_diffused(&image, &black_and_white(), &[(1, 0, 1.0)]);
+        let row = |y: u32| (0..4).map(|x| dithered.get_pixel(x, y)[0]).collect::<Vec<u8>>();
+        assert_eq!(row(0), vec![255, 0, 255, 0]);
+        assert_eq!(row(1), vec![0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn bayer_matrices_hold_every_level_once() {
+        assert_eq!(bayer_matrix(2), vec![0.125, 0.625, 0.875, 0.375]);
+        for size in bayer_sizes() {
+            let mut matrix = bayer_matrix(size);
+            matrix.sort_by(f32::total_cmp);
+            let levels: Vec<f32> = (0..size * size).map(|level| (level as f32 + 0.5) / (size * size) as f32).collect();
+            assert_eq!(matrix, levels);
+        }
+    }
+
+    #[test]
+    fn strength_scales_the_dithering() {
+        assert_eq!(get_weakened_weights(&[(1, 0, 0.5), (-1, 1, 0.25)], 0.5), vec![(1, 0, 0.25), (-1, 1, 0.125)]);
+
+        // without strength, error diffusion is the plain mapping
+        let image = gradient(32, 4);
+        let palette_mapping = black_and_white();
+        let undithered = dither_diffused(&image, &palette_mapping, &get_weakened_weights(&[(1, 0, 7.0 / 16.0), (-1, 1, 3.0 / 16.0), (0, 1, 5.0 / 16.0), (1, 1, 1.0 / 16.0)], 0.0));
+        assert_eq!(undithered, palette_mapping.map_colors(&image));
+
+        // ordered masks nudge colors by a share of their spread
+        let gray = Rgb([128, 128, 128]);
+        let shift = |strength: f32| get_ordered_mask(DitherModes::Bayer, strength).nudge(&gray, 0, 0)[0] as i32 - 128;
+        assert_eq!(shift(0.0), 0);
+        assert!(shift(1.0) != 0);
+        assert!((shift(0.5) * 2 - shift(1.0)).abs() <= 1);
+    }
+
+    #[test]
+    fn seeds_shift_the_blue_noise_mask() {
+        let (size, thresholds) = blue_noise_mask();
+        assert_eq!(&get_seeded_thresholds(*size, thresholds, 0), thresholds);
+        assert_eq!(get_seeded_thresholds(*size, thresholds, 7), get_seeded_thresholds(*size, thresholds, 7));
+        assert_ne!(get_seeded_thresholds(*size, thresholds, 1), get_seeded_thresholds(*size, thresholds, 2));
+
+        // seeds only move the mask around, so every threshold still appears once
+        let levels: Vec<f32> = (0..16).map(|level| level as f32).collect();
+        let seeded = get_seeded_thresholds(4, &levels, 42);
+        let offset = seeded[0] as u32;
+        for index in 0..16 {
+            let (x, y) = ((index % 4 + offset % 4) % 4, (index / 4 + offset / 4) % 4);
+            assert_eq!(seeded[index as usize], levels[(y * 4 + x) as usize]);
+        }
+    }
+
+    #[test]
+    fn dithering_is_byte_identical_between_runs() {
+        let image = gradient(24, 6);
+        let palette_mapping = black_and_white();
+        for mode in DitherModes::all() {
+            let first = dither(&image, &palette_mapping, mode, 0.75);
+            let second = dither(&image, &palette_mapping, mode, 0.75);
+            assert_eq!(first.into_raw(), second.into_raw(), "{} differs between runs", mode.name());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use crate::processor::palette::*;
 
 
@@ -7,6 +8,12 @@ use crate::processor::palette::*;
 pub enum ProcessingStepTypes {
     /// A standard color input (as HEX with #).
     Color,
+    /// A decimal number input.
+    Number,
+    /// A path to an existing file.
+    FilePath,
+    /// A name, e.g. of another processor.
+    Name,
     /// No input required.
     NoInput,
 }
@@ -15,6 +22,9 @@ impl ProcessingStepTypes {
     fn is_step_valid(&self, input: String) -> bool {
         match self {
             ProcessingStepTypes::Color => is_hex(&input),
+            ProcessingStepTypes::Number => input.trim().parse::<f32>().is_ok(),
+            ProcessingStepTypes::FilePath => expand_home(&input).is_file(),
+            ProcessingStepTypes::Name => !input.trim().is_empty(),
             ProcessingStepTypes::NoInput => true,
         }
     }
@@ -51,12 +61,40 @@ impl ProcessingGuide {
         self.steps[self.current_step].input.clone()
     }
 
+    /// Returns the numbers the current step takes, if it is a number step with limits.
+    pub fn get_current_range(&self) -> Option<NumberRange> {
+        self.steps[self.current_step].range
+    }
+
     /// Updates the input of the current step.
     pub fn update_current_input(&mut self, new_input: String) {
         if self.steps[self.current_step].step_type == ProcessingStepTypes::NoInput { return; }
         self.steps[self.current_step].input = new_input;
     }
 
+    /// Returns the inputs of every step.
+    pub fn get_inputs(&self) -> Vec<String> {
+        self.steps.iter().map(|step| step.input.clone()).collect()
+    }
+
+    /// Fills the steps that take an input with given inputs, in order.
+    pub fn set_inputs(&mut self, inputs: Vec<String>) {
+        for (step, input) in self.steps.iter_mut().zip(inputs) {
+            if step.step_type != ProcessingStepTypes::NoInput { step.input = input; }
+        }
+    }
+
+    /// Replaces every step after the first given number of steps, for guides whose length depends on an earlier input.
+    /// Replaced steps keep the inputs of the steps they take the place of if those are of the same kind.
+    pub fn replace_steps_after(&mut self, kept_steps: usize, mut steps: Vec<ProcessingStep>) {
+        for (step, old_step) in steps.iter_mut().zip(self.steps.iter().skip(kept_steps)) {
+            if step.step_type == old_step.step_type { step.input = old_step.input.clone(); }
+        }
+        self.steps.truncate(kept_steps);
+        self.steps.extend(steps);
+        self.current_step = self.current_step.min(self.steps.len() - 1);
+    }
+
     /// Checks if the input is valid for the current step.
     pub fn is_current_input_valid(&self) -> bool {
         self.steps[self.current_step].step_type.is_step_valid(self.get_current_input())
@@ -75,6 +113,30 @@ impl ProcessingGuide {
 
 
 
+/// The numbers a number step takes, e.g. for picking one at random.
+#[derive(Clone, Copy, PartialEq)]
+pub struct NumberRange {
+    /// The smallest number.
+    pub min: f32,
+    /// The largest number.
+    pub max: f32,
+    /// Whether only whole numbers are taken, e.g. a number of colors or a choice from a numbered list.
+    pub is_whole: bool,
+}
+impl NumberRange {
+    /// Returns the range of every number between two bounds.
+    pub fn between(min: f32, max: f32) -> NumberRange {
+        NumberRange { min, max, is_whole: false }
+    }
+
+    /// Returns the range of the whole numbers between two bounds.
+    pub fn whole(min: usize, max: usize) -> NumberRange {
+        NumberRange { min: min as f32, max: max as f32, is_whole: true }
+    }
+}
+
+
+
 /// A single step in the processing guide.
 pub struct ProcessingStep {
     /// The kind of step/value.
@@ -83,11 +145,19 @@ pub struct ProcessingStep {
     label: String,
     /// The input of the step.
     input: String,
+    /// The numbers a number step takes, if they are limited.
+    range: Option<NumberRange>,
 }
 impl ProcessingStep {
     /// Creates a new step with a given step type and label.
     pub fn new(step_type: ProcessingStepTypes, label: String) -> ProcessingStep {
-        ProcessingStep { step_type, label, input: "".to_string() }
+        ProcessingStep { step_type, label, input: "".to_string(), range: None }
+    }
+
+    /// Returns the step taking only the numbers of a given range.
+    pub fn with_range(mut self, range: NumberRange) -> ProcessingStep {
+        self.range = Some(range);
+        self
     }
 
     /// Returns the input as a hex.
@@ -95,4 +165,33 @@ impl ProcessingStep {
         if is_hex(&self.input) { return Some(self.input.clone()); }
         None
     }
-}
\ No newline at end of file
+
+    /// Returns the input as a number.
+    pub fn as_number(&self) -> Option<f32> {
+        self.input.trim().parse().ok()
+    }
+
+    /// Returns the input as a name, if it isn't blank.
+    pub fn as_name(&self) -> Option<String> {
+        let name = self.input.trim();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// Returns the input as the path of an existing file, where a leading ~ stands for the home directory.
+    pub fn as_path(&self) -> Option<PathBuf> {
+        let path = expand_home(&self.input);
+        path.is_file().then_some(path)
+    }
+}
+
+
+
+/// Returns a typed path as a path, where a leading ~ stands for the home directory.
+pub fn expand_home(input: &str) -> PathBuf {
+    let input = input.trim();
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    match (input.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(input),
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Mutex;
+use crate::config;
+use crate::locale::{tr, trf};
+
+/// Gets how many bytes a pixel takes up on the GPU while it is palettized (the pixel, its result and the copy of the result read back).
+fn gpu_bytes_per_pixel() -> u64 { 12 }
+
+/// Gets how many bytes a pixel takes up on the host while it is palettized, on top of the source and new images (the packed pixel and result).
+fn host_bytes_per_pixel() -> u64 { 8 }
+
+/// Gets how many bytes a palette color takes up on the GPU, as its padded coordinates.
+fn gpu_bytes_per_color() -> u64 { 16 }
+
+/// Gets how many bytes a spectrum color takes up on the host while spectrums are deduplicated and condensed.
+fn host_bytes_per_color() -> u64 { 16 }
+
+/// Gets the share of a memory budget a palette may take up (1 / this), leaving the rest for the pixels.
+fn palette_budget_divisor() -> u64 { 4 }
+
+/// Gets the smallest palette size a memory budget can cap palettes at, the same floor as the max palette size setting.
+fn min_palette_size_cap() -> usize { 256 }
+
+/// The adjustments made to stay within the memory budgets since they were last taken.
+static ADJUSTMENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+
+
+/// Returns a budget in megabytes as bytes.
+fn as_bytes(megabytes: u64) -> u64 { megabytes.saturating_mul(1024 * 1024) }
+
+/// Remembers an adjustment made to stay within the memory budgets, once per message.
+fn record_adjustment(adjustment: String) {
+    let mut adjustments = ADJUSTMENTS.lock().unwrap();
+    if !adjustments.contains(&adjustment) { adjustments.push(adjustment); }
+}
+
+/// Returns and forgets the adjustments made to stay within the memory budgets, e.g. to report them after a run.
+pub fn take_adjustments() -> Vec<String> {
+    std::mem::take(&mut *ADJUSTMENTS.lock().unwrap())
+}
+
+/// Returns how many rows of an image are palettized at once to stay within the memory budgets and the max buffer size of the GPU.
+/// Records an adjustment when the image has to be split into tiles.
+pub fn get_tile_rows(width: u32, height: u32, palette_length: usize, max_buffer_size: u64) -> u32 {
+    let config = config::get();
+    let width = width.max(1) as u64;
+    let palette_bytes = palette_length as u64 * gpu_bytes_per_color();
+
+    // the most rows each limit allows, with what is reported when it is the one splitting the image
+    let mut limits = vec![(max_buffer_size / (4 * width), tr("max buffer size of the GPU"))];
+    if let Some(vram_budget_mb) = config.vram_budget_mb {
+        limits.push((as_bytes(vram_budget_mb).saturating_sub(palette_bytes) / (gpu_bytes_per_pixel() * width), trf("VRAM budget of {} MB", &[vram_budget_mb.to_string()])));
+    }
+    if let Some(memory_budget_mb) = config.memory_budget_mb {
+        limits.push((as_bytes(memory_budget_mb) / (host_bytes_per_pixel() * width), trf("memory budget of {} MB", &[memory_budget_mb.to_string()])));
+    }
+
+    let Some((max_rows, reason)) = limits.into_iter().min_by_key(|(max_rows, _)| *max_rows) else { return height; };
+    let tile_rows = max_rows.clamp(1, height.max(1) as u64) as u32;
+    if tile_rows < height {
+        record_adjustment(trf("Palettized in {} tiles of {} rows to stay within the {}", &[height.div_ceil(tile_rows).to_string(), tile_rows.to_string(), reason]));
+    }
+    tile_rows
+}
+
+/// Returns the max palette size lowered to what fits the memory budgets.
+/// Records an adjustment when a palette of a given length is capped because of it.
+pub fn cap_palette_size(max_palette_size: usize, palette_length: usize) -> usize {
+    let config = config::get();
+    let mut caps = Vec::new();
+    if let Some(vram_budget_mb) = config.vram_budget_mb {
+        caps.push(as_bytes(vram_budget_mb) / palette_budget_divisor() / gpu_bytes_per_color());
+    }
+    if let Some(memory_budget_mb) = config.memory_budget_mb {
+        caps.push(as_bytes(memory_budget_mb) / palette_budget_divisor() / host_bytes_per_color());
+    }
+
+    let Some(cap) = caps.into_iter().min() else { return max_palette_size; };
+    let cap = (cap as usize).max(min_palette_size_cap());
+    if cap >= max_palette_size { return max_palette_size; }
+    if palette_length > cap {
+        record_adjustment(trf("Capped palettes at {} colors instead of {} to stay within the memory budget", &[cap.to_string(), max_palette_size.to_string()]));
+    }
+    cap
+}
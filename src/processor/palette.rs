@@ -1,15 +1,17 @@
 use std::cmp::min;
-use std::collections::HashSet;
-use std::io::Stdout;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 use image::{DynamicImage, GenericImageView, ImageBuffer, Pixel, Rgb};
-use ratatui::backend::CrosstermBackend;
-use ratatui::Terminal;
 use rayon::prelude::*;
-use crate::ui::render_loading;
+use crate::cache;
+use crate::cache::{CacheKeyHasher, CacheKinds};
+use crate::config;
+use crate::processor::memory::cap_palette_size;
+use crate::processor::color_space::{from_lab, from_oklch_in_gamut, to_lab, to_oklch, SpectrumSpaces};
 
 /// Gets the standard distance difference used to define whether two colors are in the same or different color regions.
 /// Greater differentiation -> colors need to be further apart in 3d color space to be considered different colors.
@@ -19,11 +21,27 @@ fn color_region_differentiation() -> f32 { 8.0 }
 /// Greater multiplier -> accent colors need to be further from their greyscale equivalents to be considered accent colors.
 fn accent_color_multiplier() -> f32 { 1.5 }
 
-/// Gets the max size a palette can be.
-fn max_palette_size() -> usize { 50000 }
+/// Gets the max size a palette can be (configurable, 50000 by default).
+/// Greater size -> more colors reach the GPU, which is slower but keeps more detail.
+fn max_palette_size() -> usize { config::get().max_palette_size }
 
-/// Gets the standard step count required to catch all colors between any two different colors.
-fn interpolation_steps() -> usize { 442 }
+/// Gets the step count used between any two colors (configurable, 442 by default).
+/// The default is the standard step count required to catch all colors between any two different colors; fewer steps generate faster but coarser spectrums.
+fn interpolation_steps() -> usize { config::get().interpolation_steps }
+
+/// Gets the spacing (per channel) of the colors filled into a spectrum volume.
+/// Greater step -> sparser volumes that generate faster.
+fn volume_fill_step() -> usize { 2 }
+
+/// Gets the max number of colors sampled when clustering colors.
+fn max_cluster_samples() -> usize { 65536 }
+
+/// Gets the max number of passes used to settle color clusters.
+fn max_cluster_passes() -> usize { 24 }
+
+/// Gets the width (in brightness from 0 to 1) of the transition between the shadow and highlight tones of a split tone ramp.
+/// Greater width -> softer blends where both tones mix, smaller width -> a harder split at the balance.
+fn split_tone_transition() -> f32 { 0.4 }
 
 /// Returns a standard white color.
 fn white() -> Rgb<u8> { Rgb([255, 255, 255]) }
@@ -67,11 +85,17 @@ pub fn as_rgb(hex: &String) -> Option<Rgb<u8>> {
     Some(Rgb([r, g, b]))
 }
 
+/// Converts an RGB color to a HEX color code (with #).
+pub fn as_hex(color: &Rgb<u8>) -> String {
+    format!("#{:02X}{:02X}{:02X}", color[0], color[1], color[2])
+}
+
 /// Reduces the palette size to be used efficiently.
 pub fn condense_color_palette(palette: &Vec<Rgb<u8>>) -> Vec<Rgb<u8>> {
     // checks if the palette is already small enough
     let palette = remove_duplicates_unordered(palette.clone());
-    if palette.len() < max_palette_size() { return palette; }
+    let max_palette_size = cap_palette_size(max_palette_size(), palette.len());
+    if palette.len() < max_palette_size { return palette; }
 
     // sets up tracking variables
     let mut similar_color_threshold: u8 = 2;
@@ -79,10 +103,10 @@ pub fn condense_color_palette(palette: &Vec<Rgb<u8>>) -> Vec<Rgb<u8>> {
     // the condensed palette being built
     let mut condensed_palette = palette.clone();
     // continues iterating until the palette is small enough
-    while condensed_palette.len() > max_palette_size() {
+    while condensed_palette.len() > max_palette_size {
         // checks if the palette is not being reduced fast enough to prevent infinite loops
         current_reduction_iteration += 1;
-        if current_reduction_iteration > 50 { panic!("Failed to condense palette in 1000 passes. Max size: {} Got: {}", max_palette_size(), condensed_palette.len()); }
+        if current_reduction_iteration > 50 { panic!("Failed to condense palette in 1000 passes. Max size: {} Got: {}", max_palette_size, condensed_palette.len()); }
 
         // increments the similar_color_threshold with each iteration
         similar_color_threshold += 1;
@@ -118,7 +142,7 @@ fn remove_duplicates_unordered<T: Eq + std::hash::Hash + Clone>(data: Vec<T>) ->
 
 /// Gets the distance between two colors.
 /// Increasing the bias makes the two colors read as closer (in most use cases that means more likely)
-fn get_distance(color_1: &Rgb<u8>, color_2: &Rgb<u8>, bias: &Option<f32>) -> f32 {
+pub fn get_distance(color_1: &Rgb<u8>, color_2: &Rgb<u8>, bias: &Option<f32>) -> f32 {
     let r = ((color_1[0] as f32 - color_2[0] as f32).abs() * 0.299) / bias.unwrap_or(1.0);
     let g = ((color_1[1] as f32 - color_2[1] as f32).abs() * 0.587) / bias.unwrap_or(1.0);
     let b = ((color_1[2] as f32 - color_2[2] as f32).abs() * 0.114) / bias.unwrap_or(1.0);
@@ -126,17 +150,25 @@ fn get_distance(color_1: &Rgb<u8>, color_2: &Rgb<u8>, bias: &Option<f32>) -> f32
     (r.powi(2) + g.powi(2) + b.powi(2)).sqrt()
 }
 
-/// Gets all the colors between two other colors.
-fn get_colors_between(color_1: &Rgb<u8>, color_2: &Rgb<u8>) -> Vec<Rgb<u8>> {
+/// Gets all the colors between two other colors in a given number of steps, interpolated through a given color space.
+fn get_colors_between(color_1: &Rgb<u8>, color_2: &Rgb<u8>, space: SpectrumSpaces, steps: usize) -> Vec<Rgb<u8>> {
+    // the alternative spaces blend each step separately instead of cutting straight through gamma encoded rgb
+    if space != SpectrumSpaces::Rgb {
+        let spectrum = (0..=steps).map(|step| {
+            space.interpolate(color_1, color_2, step as f32 / steps as f32)
+        }).collect();
+        return remove_duplicates_ordered(spectrum);
+    }
+
     // step information
-    let r_difference = (color_2[0] as f64 - color_1[0] as f64) / interpolation_steps() as f64;
-    let g_difference = (color_2[1] as f64 - color_1[1] as f64) / interpolation_steps() as f64;
-    let b_difference = (color_2[2] as f64 - color_1[2] as f64) / interpolation_steps() as f64;
+    let r_difference = (color_2[0] as f64 - color_1[0] as f64) / steps as f64;
+    let g_difference = (color_2[1] as f64 - color_1[1] as f64) / steps as f64;
+    let b_difference = (color_2[2] as f64 - color_1[2] as f64) / steps as f64;
     let mut spectrum = vec![];
 
     // getting the spectrum
     let mut current_color = (color_1[0] as f64, color_1[1] as f64, color_1[2] as f64);
-    for _ in 0..=interpolation_steps() {
+    for _ in 0..=steps {
         spectrum.push(Rgb([current_color.0.round() as u8, current_color.1.round() as u8, current_color.2.round() as u8]));
         current_color.0 = r_difference + current_color.0;
         current_color.1 = g_difference + current_color.1;
@@ -151,12 +183,32 @@ fn get_colors_between(color_1: &Rgb<u8>, color_2: &Rgb<u8>) -> Vec<Rgb<u8>> {
 }
 
 /// Gets the spectrum for a given color.
-/// Each spectrum is a smooth gradient from white -> color -> black.
+/// Each spectrum is a smooth gradient from white -> color -> black, where white and black can be replaced by configured tints.
 pub fn get_line_spectrum(color: &Rgb<u8>) -> Vec<Rgb<u8>> {
+    get_line_spectrum_between(color, None, None)
+}
+
+/// Gets the spectrum for a given color running from a light endpoint -> color -> a dark endpoint.
+/// Missing endpoints fall back to white and black (or their configured tints).
+pub fn get_line_spectrum_between(color: &Rgb<u8>, light_endpoint: Option<&Rgb<u8>>, dark_endpoint: Option<&Rgb<u8>>) -> Vec<Rgb<u8>> {
+    let config = config::get();
+    let white = light_endpoint.copied().or(config.spectrum_white).unwrap_or(white());
+    let black = dark_endpoint.copied().or(config.spectrum_black).unwrap_or(black());
+
     // getting the spectrum
     let mut spectrum = vec![];
-    spectrum.extend(get_colors_between(&white(), color));
-    spectrum.extend(get_colors_between(color, &black()));
+    if config.preserve_saturation {
+        spectrum = get_constant_chroma_colors(color, &white, &black);
+    }
+    else {
+        spectrum.extend(get_colors_between(&white, color, config.spectrum_space, config.interpolation_steps));
+        spectrum.extend(get_colors_between(color, &black, config.spectrum_space, config.interpolation_steps));
+    }
+
+    // keeps the spectrum inside the configured lightness range
+    if config.spectrum_min_lightness > 0.0 || config.spectrum_max_lightness < 100.0 {
+        spectrum = compress_lightness(&spectrum, config.spectrum_min_lightness, config.spectrum_max_lightness);
+    }
 
     // removes duplicates from the spectrum
     spectrum = remove_duplicates_ordered(spectrum);
@@ -165,6 +217,63 @@ pub fn get_line_spectrum(color: &Rgb<u8>) -> Vec<Rgb<u8>> {
     spectrum
 }
 
+/// Gets a ramp of 256 colors from black to white for tone mapping, passing through a tint color at its own brightness, or plain greys without a tint.
+pub fn get_tone_ramp(tint: Option<&Rgb<u8>>) -> Vec<Rgb<u8>> {
+    (0..=255u8).map(|level| {
+        let Some(tint) = tint else { return Rgb([level, level, level]); };
+        let tint_level = get_brightness(tint).clamp(1.0, 254.0);
+        let level = level as f32;
+        if level <= tint_level { SpectrumSpaces::Rgb.interpolate(&black(), tint, level / tint_level) }
+        else { SpectrumSpaces::Rgb.interpolate(tint, &white(), (level - tint_level) / (255.0 - tint_level)) }
+    }).collect()
+}
+
+/// Gets a ramp of 256 colors from black to white for tone mapping that follows the tone ramp of a shadow color in the shadows and of a highlight color in the highlights.
+/// The tones blend around the balance (brightness from 0 to 1), where a lower balance gives more of the image to the highlight color.
+pub fn get_split_tone_ramp(shadow_color: &Rgb<u8>, highlight_color: &Rgb<u8>, balance: f32) -> Vec<Rgb<u8>> {
+    let (shadow_ramp, highlight_ramp) = (get_tone_ramp(Some(shadow_color)), get_tone_ramp(Some(highlight_color)));
+    shadow_ramp.iter().zip(highlight_ramp.iter()).enumerate().map(|(level, (shadow_tone, highlight_tone))| {
+        let highlight_share = ((level as f32 / 255.0 - balance) / split_tone_transition() + 0.5).clamp(0.0, 1.0);
+        SpectrumSpaces::Rgb.interpolate(shadow_tone, highlight_tone, highlight_share)
+    }).collect()
+}
+
+/// Gets a ramp of 256 colors for tone mapping that runs through evenly spaced color stops, from the first stop for black to the last for white.
+pub fn get_gradient_ramp(stops: &[Rgb<u8>]) -> Vec<Rgb<u8>> {
+    if stops.len() < 2 { return get_tone_ramp(stops.first()); }
+    (0..=255u8).map(|level| {
+        // finds the two stops the level falls between and blends them
+        let position = level as f32 / 255.0 * (stops.len() - 1) as f32;
+        let index = (position as usize).min(stops.len() - 2);
+        SpectrumSpaces::Rgb.interpolate(&stops[index], &stops[index + 1], position - index as f32)
+    }).collect()
+}
+
+/// Gets the colors of a spectrum that keeps the chroma and hue of a given color while ramping the lightness from white to black.
+/// The chroma is only lowered where the sRGB gamut cannot hold it, which happens close to the ends.
+fn get_constant_chroma_colors(color: &Rgb<u8>, white: &Rgb<u8>, black: &Rgb<u8>) -> Vec<Rgb<u8>> {
+    let [_, chroma, hue] = to_oklch(color);
+    let white_lightness = to_oklch(white)[0];
+    let black_lightness = to_oklch(black)[0];
+
+    let steps = interpolation_steps() * 2;
+    let spectrum = (0..=steps).map(|step| {
+        let lightness = white_lightness + (black_lightness - white_lightness) * step as f32 / steps as f32;
+        from_oklch_in_gamut([lightness, chroma, hue])
+    }).collect();
+
+    remove_duplicates_ordered(spectrum)
+}
+
+/// Squeezes the lightness (CIELAB L, 0-100) of every color into a given range, keeping the gradient between the colors intact.
+fn compress_lightness(colors: &[Rgb<u8>], min_lightness: f32, max_lightness: f32) -> Vec<Rgb<u8>> {
+    colors.iter().map(|color| {
+        let mut lab = to_lab(color);
+        lab[0] = min_lightness + lab[0] / 100.0 * (max_lightness - min_lightness);
+        from_lab(lab)
+    }).collect()
+}
+
 /// Gets the 1d spectrums for all the colors in a given palette and returns the results as a single palette.
 pub fn get_line_spectrums(palette: &Vec<Rgb<u8>>) -> Vec<Vec<Rgb<u8>>> {
     let mut line_spectrums = Vec::new();
@@ -175,14 +284,34 @@ pub fn get_line_spectrums(palette: &Vec<Rgb<u8>>) -> Vec<Vec<Rgb<u8>>> {
     line_spectrums
 }
 
+/// Gets the cache key of a spectrum built from given line spectrums with the current settings.
+fn get_spectrum_cache_key(label: &str, line_spectrums: &[&Vec<Rgb<u8>>]) -> u64 {
+    let config = config::get();
+    let mut hasher = CacheKeyHasher::new();
+    label.hash(&mut hasher);
+    // the settings enums are hashed by name, so reordering their variants keeps the cached spectrums valid
+    (config.interpolation_steps, config.plane_spectrum_stride, config.spectrum_space.name(), config.web_topology.name(), volume_fill_step()).hash(&mut hasher);
+    line_spectrums.hash(&mut hasher);
+    hasher.finish()
+}
+
 /// Gets the spectrum for a given pair of colors.
 /// Each spectrum is a region of 3d color space that envelopes white -> colors -> black in one or two connected planes.
 pub fn get_plane_spectrum(line_spectrum_1: &Vec<Rgb<u8>>, line_spectrum_2: &Vec<Rgb<u8>>) -> Vec<Rgb<u8>> {
+    let key = get_spectrum_cache_key("plane", &[line_spectrum_1, line_spectrum_2]);
+    cache::get_or_build_colors(CacheKinds::Spectrums, key, || build_plane_spectrum(line_spectrum_1, line_spectrum_2))
+}
+
+/// Builds the plane spectrum for a given pair of colors without the cache.
+fn build_plane_spectrum(line_spectrum_1: &Vec<Rgb<u8>>, line_spectrum_2: &Vec<Rgb<u8>>) -> Vec<Rgb<u8>> {
     let spectrum_steps = min(line_spectrum_1.len(), line_spectrum_2.len());
+    // reads the settings once instead of for every pair of colors
+    let config = config::get();
+    let (space, steps) = (config.spectrum_space, config.interpolation_steps);
 
-    let mut spectrum: Vec<Rgb<u8>> = (0..spectrum_steps).into_par_iter().flat_map(|i| {
-        let mut colors_between = get_colors_between(&line_spectrum_1[i], &line_spectrum_2[i]);
-        colors_between.extend(get_colors_between(&line_spectrum_1[line_spectrum_1.len() - 1 - i], &line_spectrum_2[line_spectrum_2.len() - 1 - i]));
+    let mut spectrum: Vec<Rgb<u8>> = (0..spectrum_steps).into_par_iter().step_by(config.plane_spectrum_stride).flat_map(|i| {
+        let mut colors_between = get_colors_between(&line_spectrum_1[i], &line_spectrum_2[i], space, steps);
+        colors_between.extend(get_colors_between(&line_spectrum_1[line_spectrum_1.len() - 1 - i], &line_spectrum_2[line_spectrum_2.len() - 1 - i], space, steps));
         colors_between
     }).collect();
 
@@ -191,18 +320,202 @@ pub fn get_plane_spectrum(line_spectrum_1: &Vec<Rgb<u8>>, line_spectrum_2: &Vec<
     spectrum
 }
 
-/// Combines all the plane spectrums between all line spectrums in a given list.
+/// Combines the plane spectrums between the line spectrums in a given list, connected in the configured topology.
 pub fn get_web_spectrum(line_spectrums: &Vec<Vec<Rgb<u8>>>) -> Vec<Rgb<u8>> {
-    let mut spectrum = Vec::new();
-    for x in 0..line_spectrums.len() {
-        for y in 0..line_spectrums.len() {
-            if x != y { spectrum.extend(get_plane_spectrum(&line_spectrums[x], &line_spectrums[y])); }
+    let key = get_spectrum_cache_key("web", &line_spectrums.iter().collect::<Vec<&Vec<Rgb<u8>>>>());
+    cache::get_or_build_colors(CacheKinds::Spectrums, key, || build_web_spectrum(line_spectrums))
+}
+
+/// Builds the web spectrum for the line spectrums in a given list without the cache.
+fn build_web_spectrum(line_spectrums: &Vec<Vec<Rgb<u8>>>) -> Vec<Rgb<u8>> {
+    let count = line_spectrums.len();
+    let pairs: Vec<(usize, usize)> = match config::get().web_topology {
+        WebTopologies::Complete => (0..count).flat_map(|x| ((x + 1)..count).map(move |y| (x, y))).collect(),
+        // a ring of two colors is a single connection
+        WebTopologies::Ring if count == 2 => vec![(0, 1)],
+        WebTopologies::Ring => (0..count).map(|x| (x, (x + 1) % count)).collect(),
+        WebTopologies::Star => {
+            let hub = get_average_line_spectrum(line_spectrums);
+            let mut spectrum = Vec::new();
+            for line_spectrum in line_spectrums {
+                spectrum.extend(build_plane_spectrum(line_spectrum, &hub));
+            }
+            return remove_duplicates_unordered(spectrum);
         }
+    };
+
+    let mut spectrum = Vec::new();
+    for (x, y) in pairs {
+        spectrum.extend(build_plane_spectrum(&line_spectrums[x], &line_spectrums[y]));
     }
 
     remove_duplicates_unordered(spectrum)
 }
 
+/// Gets a line spectrum running through the average of the given line spectrums at each point along them.
+fn get_average_line_spectrum(line_spectrums: &[Vec<Rgb<u8>>]) -> Vec<Rgb<u8>> {
+    // the line spectrums differ slightly in length, so each is sampled at the same fraction of its length
+    let length = line_spectrums.iter().map(|line_spectrum| line_spectrum.len()).min().unwrap_or(0);
+    let spectrum = (0..length).map(|i| {
+        let samples: Vec<Rgb<u8>> = line_spectrums.iter().map(|line_spectrum| line_spectrum[i * (line_spectrum.len() - 1) / (length - 1).max(1)]).collect();
+        get_average_color_from_pixels(&samples)
+    }).collect();
+
+    remove_duplicates_ordered(spectrum)
+}
+
+/// Fills the convex hull around all colors of the given line spectrums, so every blend between the seed colors is part of the spectrum.
+/// Falls back to the web spectrum if the seed colors do not span a volume (e.g. when they all lie on one plane).
+pub fn fill_spectrum_volume(line_spectrums: &Vec<Vec<Rgb<u8>>>) -> Vec<Rgb<u8>> {
+    let key = get_spectrum_cache_key("volume", &line_spectrums.iter().collect::<Vec<&Vec<Rgb<u8>>>>());
+    cache::get_or_build_colors(CacheKinds::Spectrums, key, || build_spectrum_volume(line_spectrums))
+}
+
+/// Builds the spectrum volume around the given line spectrums without the cache.
+fn build_spectrum_volume(line_spectrums: &Vec<Vec<Rgb<u8>>>) -> Vec<Rgb<u8>> {
+    let seeds: Vec<[i64; 3]> = remove_duplicates_unordered(line_spectrums.concat()).iter()
+        .map(|color| [color[0] as i64, color[1] as i64, color[2] as i64])
+        .collect();
+    let faces = get_hull_faces(&seeds);
+    if faces.is_empty() { return build_web_spectrum(line_spectrums); }
+
+    // walks every red and green column of the color cube and keeps the blue range inside the hull
+    let step = volume_fill_step();
+    (0..256).into_par_iter().step_by(step).flat_map_iter(|r| {
+        let faces = &faces;
+        (0..256).step_by(step).flat_map(move |g| get_volume_column(faces, r, g, step))
+    }).collect()
+}
+
+/// Gets the faces of the convex hull around a set of colors as outward normals with offsets (inside: normal · color <= offset).
+/// Returns no faces if the colors do not span a volume.
+/// The hull is built incrementally with exact integer math, so nearly coplanar colors can't flip a face.
+fn get_hull_faces(seeds: &[[i64; 3]]) -> Vec<([i64; 3], i64)> {
+    let Some(tetrahedron) = get_initial_tetrahedron(seeds) else { return Vec::new(); };
+
+    // the triangles of the hull as corner indices, ordered counterclockwise when seen from outside
+    let mut triangles: Vec<[usize; 3]> = vec![[0, 1, 2], [0, 3, 1], [1, 3, 2], [2, 3, 0]].into_iter()
+        .map(|corners| corners.map(|corner| tetrahedron[corner]))
+        .collect();
+    // the triangles face inward if the fourth corner lies in front of the first one, so every triangle is flipped
+    let (normal, offset) = get_plane(seeds, &triangles[0]);
+    if dot(&normal, &seeds[tetrahedron[3]]) > offset {
+        triangles.iter_mut().for_each(|triangle| triangle.swap(1, 2));
+    }
+
+    for seed in 0..seeds.len() {
+        if tetrahedron.contains(&seed) { continue; }
+
+        // the triangles the seed sees are replaced by a fan from the seed to the edge of the visible region
+        let (visible, hidden): (Vec<[usize; 3]>, Vec<[usize; 3]>) = triangles.into_iter().partition(|triangle| {
+            let (normal, offset) = get_plane(seeds, triangle);
+            dot(&normal, &seeds[seed]) > offset
+        });
+        triangles = hidden;
+        if visible.is_empty() { continue; }
+
+        let visible_edges: HashSet<(usize, usize)> = visible.iter()
+            .flat_map(|[a, b, c]| [(*a, *b), (*b, *c), (*c, *a)])
+            .collect();
+        // an edge is on the horizon if the triangle on its other side stays
+        triangles.extend(visible_edges.iter()
+            .filter(|(a, b)| !visible_edges.contains(&(*b, *a)))
+            .map(|(a, b)| [*a, *b, seed]));
+    }
+
+    // reduces every plane so coplanar triangles make up a single face
+    let faces: HashSet<([i64; 3], i64)> = triangles.iter().map(|triangle| {
+        let (normal, offset) = get_plane(seeds, triangle);
+        let divisor = [normal[1], normal[2], offset].into_iter().fold(normal[0], greatest_common_divisor);
+        (normal.map(|axis| axis / divisor), offset / divisor)
+    }).collect();
+
+    faces.into_iter().collect()
+}
+
+/// Gets the indices of four colors that span a volume, or None if all colors lie on one plane.
+fn get_initial_tetrahedron(seeds: &[[i64; 3]]) -> Option<[usize; 4]> {
+    let first = 0;
+    let second = (0..seeds.len()).find(|&seed| seeds[seed] != seeds[first])?;
+    let third = (0..seeds.len()).find(|&seed| get_normal(&seeds[first], &seeds[second], &seeds[seed]) != [0, 0, 0])?;
+    let (normal, offset) = get_plane(seeds, &[first, second, third]);
+    let fourth = (0..seeds.len()).find(|&seed| dot(&normal, &seeds[seed]) != offset)?;
+    Some([first, second, third, fourth])
+}
+
+/// Gets the plane through the corners of a triangle as its normal and offset, facing the side the corners turn counterclockwise on.
+fn get_plane(seeds: &[[i64; 3]], triangle: &[usize; 3]) -> ([i64; 3], i64) {
+    let normal = get_normal(&seeds[triangle[0]], &seeds[triangle[1]], &seeds[triangle[2]]);
+    (normal, dot(&normal, &seeds[triangle[0]]))
+}
+
+/// Gets the (unnormalized) normal of the plane through three points.
+fn get_normal(a: &[i64; 3], b: &[i64; 3], c: &[i64; 3]) -> [i64; 3] {
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]]
+}
+
+/// Gets the dot product of two integer vectors.
+fn dot(a: &[i64; 3], b: &[i64; 3]) -> i64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Gets the colors of a single red and green column of the color cube that lie inside a hull.
+fn get_volume_column(faces: &[([i64; 3], i64)], r: usize, g: usize, step: usize) -> Vec<Rgb<u8>> {
+    let mut low: f64 = 0.0;
+    let mut high: f64 = 255.0;
+    for (normal, offset) in faces {
+        let rest = (offset - normal[0] * r as i64 - normal[1] * g as i64) as f64;
+        if normal[2] > 0 { high = high.min(rest / normal[2] as f64); }
+        else if normal[2] < 0 { low = low.max(rest / normal[2] as f64); }
+        else if rest < 0.0 { return Vec::new(); }
+    }
+    if low > high { return Vec::new(); }
+
+    // starts on the same grid as the red and green columns
+    let start = (low.ceil() as usize).div_ceil(step) * step;
+    (start..=high.floor() as usize).step_by(step).map(|b| Rgb([r as u8, g as u8, b as u8])).collect()
+}
+
+/// Gets the greatest common divisor of two numbers (always positive, 0 only for two zeros).
+fn greatest_common_divisor(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 { (a, b) = (b, a % b); }
+    a
+}
+
+/// The list of ways the line spectrums of a multi-color palette can be connected.
+#[derive(Copy, Clone, PartialEq)]
+pub enum WebTopologies {
+    /// Every color blends with every other color.
+    Complete,
+    /// Each color blends with the next one, and the last with the first.
+    Ring,
+    /// Each color only blends with a hub at the average of all colors.
+    Star,
+}
+impl WebTopologies {
+    /// Returns the topology with a given name (case insensitive).
+    pub fn from_name(name: &str) -> Option<WebTopologies> {
+        match name.to_lowercase().as_str() {
+            "complete" => Some(WebTopologies::Complete),
+            "ring" => Some(WebTopologies::Ring),
+            "star" => Some(WebTopologies::Star),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of a given topology, as it is set in the config.
+    pub fn name(&self) -> String {
+        match self {
+            WebTopologies::Complete => "complete".to_string(),
+            WebTopologies::Ring => "ring".to_string(),
+            WebTopologies::Star => "star".to_string(),
+        }
+    }
+}
+
 /// Gets the average color from an image.
 pub fn get_average_color_from_image(image: &DynamicImage) -> Rgb<u8> {
     let (width, height) = image.dimensions();
@@ -237,12 +550,44 @@ pub fn get_average_color_from_pixels(pixels: &Vec<Rgb<u8>>) -> Rgb<u8> {
     Rgb([(r / pixels.len() as f32).round() as u8, (g / pixels.len() as f32).round() as u8, (b / pixels.len() as f32).round() as u8])
 }
 
+/// Gets the perceived brightness of a color (0-255).
+pub fn get_brightness(color: &Rgb<u8>) -> f32 {
+    color[0] as f32 * 0.299 + color[1] as f32 * 0.587 + color[2] as f32 * 0.114
+}
+
+/// Gets the saturation of a color (0-1).
+pub fn get_saturation(color: &Rgb<u8>) -> f32 {
+    let max = color[0].max(color[1]).max(color[2]) as f32;
+    let min = color[0].min(color[1]).min(color[2]) as f32;
+    if max == 0.0 { return 0.0; }
+    (max - min) / max
+}
+
+/// Gets the relative luminance of a color as defined by WCAG (0-1).
+pub fn get_relative_luminance(color: &Rgb<u8>) -> f64 {
+    // linearizes each sRGB channel before weighting it
+    let linearize = |channel: u8| {
+        let channel = channel as f64 / 255.0;
+        if channel <= 0.03928 { channel / 12.92 } else { ((channel + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * linearize(color[0]) + 0.7152 * linearize(color[1]) + 0.0722 * linearize(color[2])
+}
+
+/// Gets the WCAG contrast ratio between two colors (1-21).
+pub fn get_contrast_ratio(a: &Rgb<u8>, b: &Rgb<u8>) -> f64 {
+    let a = get_relative_luminance(a);
+    let b = get_relative_luminance(b);
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Returns the given base colors adjusted to stay distinguishable under the configured color vision deficiency.
+pub fn get_color_blind_safe_colors(colors: &[Rgb<u8>]) -> Vec<Rgb<u8>> {
+    config::get().color_blind_safe.separate(colors)
+}
+
 /// Returns whether a color is considered an accent color.
 fn is_accent_color(color: &Rgb<u8>) -> bool {
-    let r_value = color[0] as f32 * 0.299;
-    let g_value = color[1] as f32 * 0.587;
-    let b_value = color[2] as f32 * 0.114;
-    let brightness = r_value + g_value + b_value;
+    let brightness = get_brightness(color);
     let perceived_greyscale_color = Rgb([brightness as u8, brightness as u8, brightness as u8]);
     get_distance(color, &perceived_greyscale_color, &None) > color_region_differentiation() * accent_color_multiplier()
 }
@@ -263,6 +608,230 @@ pub fn get_accent_color(image: &DynamicImage) -> Rgb<u8> {
     accent_map.accent()
 }
 
+/// Counts how many pixels of an image use each color.
+fn get_color_counts(image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> HashMap<Rgb<u8>, usize> {
+    let mut color_counts: HashMap<Rgb<u8>, usize> = HashMap::new();
+    image.pixels().for_each(|pixel| { *color_counts.entry(*pixel).or_insert(0) += 1; });
+    color_counts
+}
+
+/// Gets how much of an image each of its colors covers, from the most to the least used.
+pub fn get_color_usage(image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Vec<ColorUsage> {
+    let pixel_count = image.pixels().len().max(1) as f64;
+    let mut color_usage: Vec<ColorUsage> = get_color_counts(image).into_iter().map(|(color, count)| {
+        ColorUsage { color, pixel_count: count, percent: count as f64 / pixel_count * 100.0 }
+    }).collect();
+    color_usage.sort_by(|a, b| b.pixel_count.cmp(&a.pixel_count).then(a.color.0.cmp(&b.color.0)));
+    color_usage
+}
+
+/// Gets up to a given number of colors from an image that are as far apart from each other as possible.
+/// The most common color is always picked first, after which each pick is the color furthest from all previous picks.
+pub fn get_most_distinct_colors(image: &ImageBuffer<Rgb<u8>, Vec<u8>>, count: usize) -> Vec<Rgb<u8>> {
+    let color_counts = get_color_counts(image);
+    let Some(most_common_color) = color_counts.iter().max_by_key(|(_, count)| **count).map(|(color, _)| *color) else { return Vec::new(); };
+    let colors: Vec<Rgb<u8>> = color_counts.into_keys().collect();
+
+    // tracks how close every color is to the nearest already picked color
+    let mut distinct_colors = vec![most_common_color];
+    let mut closest_distances: Vec<f32> = colors.iter().map(|color| get_distance(color, &most_common_color, &None)).collect();
+    while distinct_colors.len() < count.min(colors.len()) {
+        let (furthest_index, _) = closest_distances.iter().enumerate().fold((0, -1.0), |furthest, (index, distance)| {
+            if *distance > furthest.1 { (index, *distance) } else { furthest }
+        });
+        let furthest_color = colors[furthest_index];
+        distinct_colors.push(furthest_color);
+
+        closest_distances.iter_mut().zip(colors.iter()).for_each(|(closest_distance, color)| {
+            *closest_distance = closest_distance.min(get_distance(color, &furthest_color, &None));
+        });
+    }
+
+    distinct_colors
+}
+
+/// Gets the index of the closest color in a palette to a given color.
+pub fn get_closest_color_index(color: &Rgb<u8>, palette: &[Rgb<u8>]) -> usize {
+    let mut closest_index = 0;
+    let mut closest_distance = f32::MAX;
+    for (index, palette_color) in palette.iter().enumerate() {
+        let distance = get_distance(color, palette_color, &None);
+        if distance < closest_distance {
+            closest_distance = distance;
+            closest_index = index;
+        }
+    }
+    closest_index
+}
+
+/// Finds up to a given number of colors that best represent a list of colors by clustering them (k-means).
+pub fn get_color_clusters(colors: &[Rgb<u8>], count: usize) -> Vec<Rgb<u8>> {
+    if colors.is_empty() || count == 0 { return Vec::new(); }
+
+    // samples the colors evenly to keep large images fast
+    let stride = (colors.len() / max_cluster_samples()).max(1);
+    let samples: Vec<Rgb<u8>> = colors.iter().step_by(stride).copied().collect();
+
+    // seeds the clusters as far apart as possible, starting from the average color
+    let mut clusters = vec![get_average_color_from_pixels(&samples)];
+    let mut closest_distances: Vec<f32> = samples.iter().map(|color| get_distance(color, &clusters[0], &None)).collect();
+    while clusters.len() < count {
+        let (furthest_index, furthest_distance) = closest_distances.iter().enumerate().fold((0, 0.0), |furthest, (index, distance)| {
+            if *distance > furthest.1 { (index, *distance) } else { furthest }
+        });
+        // stops early if there are fewer different colors than clusters
+        if furthest_distance <= 0.0 { break; }
+
+        let furthest_color = samples[furthest_index];
+        clusters.push(furthest_color);
+        closest_distances.iter_mut().zip(samples.iter()).for_each(|(closest_distance, color)| {
+            *closest_distance = closest_distance.min(get_distance(color, &furthest_color, &None));
+        });
+    }
+
+    // moves each cluster to the average of its colors until the clusters settle
+    for _ in 0..max_cluster_passes() {
+        let sums = samples.par_iter().fold(|| vec![[0u64; 4]; clusters.len()], |mut sums, color| {
+            let sum = &mut sums[get_closest_color_index(color, &clusters)];
+            sum[0] += color[0] as u64;
+            sum[1] += color[1] as u64;
+            sum[2] += color[2] as u64;
+            sum[3] += 1;
+            sums
+        }).reduce(|| vec![[0u64; 4]; clusters.len()], |mut a, b| {
+            a.iter_mut().zip(b.iter()).for_each(|(a, b)| { for i in 0..4 { a[i] += b[i]; } });
+            a
+        });
+
+        let new_clusters: Vec<Rgb<u8>> = clusters.iter().zip(sums.iter()).map(|(cluster, sum)| {
+            if sum[3] == 0 { return *cluster; }
+            Rgb([
+                (sum[0] as f64 / sum[3] as f64).round() as u8,
+                (sum[1] as f64 / sum[3] as f64).round() as u8,
+                (sum[2] as f64 / sum[3] as f64).round() as u8,
+            ])
+        }).collect();
+
+        if new_clusters == clusters { break; }
+        clusters = new_clusters;
+    }
+
+    clusters
+}
+
+/// Gets a given number of dominant colors of an image and how much of the image each covers, from the most to the least used.
+pub fn get_dominant_colors(image: &DynamicImage, count: usize) -> Vec<ColorUsage> {
+    let pixels: Vec<Rgb<u8>> = image.pixels().map(|pixel| pixel.2.to_rgb()).collect();
+    let clusters = get_color_clusters(&pixels, count);
+
+    // counts every pixel towards its closest cluster
+    let counts = pixels.par_iter().fold(|| vec![0usize; clusters.len()], |mut counts, color| {
+        counts[get_closest_color_index(color, &clusters)] += 1;
+        counts
+    }).reduce(|| vec![0usize; clusters.len()], |mut a, b| {
+        a.iter_mut().zip(b.iter()).for_each(|(a, b)| *a += b);
+        a
+    });
+
+    let pixel_count = pixels.len().max(1) as f64;
+    let mut dominant_colors: Vec<ColorUsage> = clusters.into_iter().zip(counts).map(|(color, count)| {
+        ColorUsage { color, pixel_count: count, percent: count as f64 / pixel_count * 100.0 }
+    }).collect();
+    dominant_colors.sort_by_key(|usage| std::cmp::Reverse(usage.pixel_count));
+    dominant_colors
+}
+
+/// Gets the average colors of the two dominant color families of an image, the most used first.
+/// Images with a single color have it as both families.
+pub fn get_dominant_color_families(image: &DynamicImage) -> [Rgb<u8>; 2] {
+    let dominant_colors = get_dominant_colors(image, 2);
+    let first = dominant_colors.first().map(|usage| usage.color).unwrap_or(black());
+    [first, dominant_colors.get(1).map(|usage| usage.color).unwrap_or(first)]
+}
+
+
+
+/// The list of film stocks whose looks can be emulated.
+#[derive(Copy, Clone, PartialEq)]
+pub enum FilmStocks {
+    /// Soft contrast with warm skin tones and muted greens and blues.
+    Portra,
+    /// Cool and clean with saturated blues and teals.
+    Ektachrome,
+    /// Punchy contrast with deep reds, warm yellows and dark blues.
+    Kodachrome,
+    /// Black and white with deep blacks and a hint of paper warmth.
+    TriX,
+}
+impl FilmStocks {
+    /// Returns the name of a given film stock.
+    pub fn name(&self) -> String {
+        match self {
+            FilmStocks::Portra => "Portra".to_string(),
+            FilmStocks::Ektachrome => "Ektachrome".to_string(),
+            FilmStocks::Kodachrome => "Kodachrome".to_string(),
+            FilmStocks::TriX => "Tri-X".to_string(),
+        }
+    }
+
+    /// Returns the curated colors of a given film stock, which are the tone stops from dark to bright for black and white stocks.
+    pub fn colors(&self) -> Vec<Rgb<u8>> {
+        match self {
+            FilmStocks::Portra => palettes::portra(),
+            FilmStocks::Ektachrome => palettes::ektachrome(),
+            FilmStocks::Kodachrome => palettes::kodachrome(),
+            FilmStocks::TriX => palettes::tri_x(),
+        }
+    }
+
+    /// Returns the strength (0-1) of the contrast curve shaping the image before it is mapped onto a given film stock.
+    pub fn contrast(&self) -> f32 {
+        match self {
+            FilmStocks::Portra => 0.15,
+            FilmStocks::Ektachrome => 0.3,
+            FilmStocks::Kodachrome => 0.45,
+            FilmStocks::TriX => 0.5,
+        }
+    }
+
+    /// Returns if a given film stock is black and white.
+    pub fn is_monochrome(&self) -> bool {
+        matches!(self, FilmStocks::TriX)
+    }
+}
+
+
+
+/// The list of perceptual heat ramps false colors can be mapped onto.
+#[derive(Copy, Clone, PartialEq)]
+pub enum HeatRamps {
+    /// Dark blue through teal and green to yellow.
+    Viridis,
+    /// Black through purple and pink to pale yellow.
+    Magma,
+    /// Black through purple, red and orange to pale yellow.
+    Inferno,
+}
+impl HeatRamps {
+    /// Returns the name of a given ramp.
+    pub fn name(&self) -> String {
+        match self {
+            HeatRamps::Viridis => "Viridis".to_string(),
+            HeatRamps::Magma => "Magma".to_string(),
+            HeatRamps::Inferno => "Inferno".to_string(),
+        }
+    }
+
+    /// Returns the evenly spaced color stops of a given ramp, from dark to bright.
+    pub fn stops(&self) -> Vec<Rgb<u8>> {
+        match self {
+            HeatRamps::Viridis => palettes::viridis(),
+            HeatRamps::Magma => palettes::magma(),
+            HeatRamps::Inferno => palettes::inferno(),
+        }
+    }
+}
+
 
 
 pub mod palettes {
@@ -369,6 +938,109 @@ pub mod palettes {
             Rgb([255, 215, 0]),
         ]
     }
+
+    pub fn sepia() -> Vec<Rgb<u8>> {
+        vec![
+            Rgb([112, 66, 20]),
+            Rgb([166, 123, 91]),
+            Rgb([222, 196, 164]),
+        ]
+    }
+
+    pub fn viridis() -> Vec<Rgb<u8>> {
+        vec![
+            Rgb([68, 1, 84]),
+            Rgb([71, 45, 123]),
+            Rgb([59, 82, 139]),
+            Rgb([44, 114, 142]),
+            Rgb([33, 145, 140]),
+            Rgb([40, 174, 128]),
+            Rgb([94, 201, 98]),
+            Rgb([173, 220, 48]),
+            Rgb([253, 231, 37]),
+        ]
+    }
+
+    pub fn magma() -> Vec<Rgb<u8>> {
+        vec![
+            Rgb([0, 0, 4]),
+            Rgb([28, 16, 68]),
+            Rgb([79, 18, 123]),
+            Rgb([129, 37, 129]),
+            Rgb([181, 54, 122]),
+            Rgb([229, 80, 100]),
+            Rgb([251, 135, 97]),
+            Rgb([254, 194, 135]),
+            Rgb([252, 253, 191]),
+        ]
+    }
+
+    pub fn inferno() -> Vec<Rgb<u8>> {
+        vec![
+            Rgb([0, 0, 4]),
+            Rgb([31, 12, 72]),
+            Rgb([85, 15, 109]),
+            Rgb([136, 34, 106]),
+            Rgb([186, 54, 85]),
+            Rgb([227, 89, 51]),
+            Rgb([249, 142, 9]),
+            Rgb([249, 203, 53]),
+            Rgb([252, 255, 164]),
+        ]
+    }
+
+    pub fn portra() -> Vec<Rgb<u8>> {
+        vec![
+            Rgb([226, 180, 142]),
+            Rgb([196, 122, 86]),
+            Rgb([118, 140, 108]),
+            Rgb([96, 130, 148]),
+            Rgb([240, 216, 172]),
+        ]
+    }
+
+    pub fn ektachrome() -> Vec<Rgb<u8>> {
+        vec![
+            Rgb([36, 90, 162]),
+            Rgb([42, 142, 152]),
+            Rgb([92, 150, 84]),
+            Rgb([212, 92, 74]),
+            Rgb([226, 222, 200]),
+        ]
+    }
+
+    pub fn kodachrome() -> Vec<Rgb<u8>> {
+        vec![
+            Rgb([188, 38, 40]),
+            Rgb([232, 178, 58]),
+            Rgb([30, 68, 130]),
+            Rgb([68, 118, 58]),
+            Rgb([150, 88, 50]),
+        ]
+    }
+
+    pub fn tri_x() -> Vec<Rgb<u8>> {
+        vec![
+            Rgb([14, 14, 14]),
+            Rgb([68, 68, 67]),
+            Rgb([138, 137, 135]),
+            Rgb([204, 203, 199]),
+            Rgb([244, 242, 238]),
+        ]
+    }
+}
+
+
+
+/// How much of an image a single color covers.
+#[derive(Clone)]
+pub struct ColorUsage {
+    /// The color being counted.
+    pub color: Rgb<u8>,
+    /// The number of pixels using the color.
+    pub pixel_count: usize,
+    /// The share of the image using the color (0-100).
+    pub percent: f64,
 }
 
 
@@ -412,4 +1084,65 @@ impl AccentMap {
         let b = self.greatest_accent_index.2 * 8 + 4;
         Rgb([r as u8, g as u8, b as u8])
     }
-}
\ No newline at end of file
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the corners of a cube from 0 to 255 on every axis.
+    fn cube_corners() -> Vec<[i64; 3]> {
+        (0..8).map(|corner| [(corner & 1) * 255, (corner >> 1 & 1) * 255, (corner >> 2 & 1) * 255]).collect()
+    }
+
+    /// Returns whether a point lies inside (or on) the hull given by its faces.
+    fn is_inside(faces: &[([i64; 3], i64)], point: &[i64; 3]) -> bool {
+        faces.iter().all(|(normal, offset)| dot(normal, point) <= *offset)
+    }
+
+    #[test]
+    fn cube_corners_give_six_faces() {
+        let faces = get_hull_faces(&cube_corners());
+        assert_eq!(faces.len(), 6);
+        for (normal, offset) in &faces {
+            // every face is axis aligned and lies on a side of the cube
+            assert_eq!(normal.iter().filter(|axis| **axis != 0).count(), 1);
+            assert!(*offset == 0 || *offset == 255);
+        }
+    }
+
+    #[test]
+    fn inner_points_leave_the_hull_unchanged() {
+        let mut seeds = vec![[128, 128, 128], [10, 200, 30], [255, 100, 0]];
+        seeds.extend(cube_corners());
+        seeds.push([0, 128, 255]);
+        let mut faces = get_hull_faces(&seeds);
+        let mut cube_faces = get_hull_faces(&cube_corners());
+        faces.sort();
+        cube_faces.sort();
+        assert_eq!(faces, cube_faces);
+    }
+
+    #[test]
+    fn points_are_classified_against_the_hull() {
+        let seeds = vec![[0, 0, 0], [200, 0, 0], [0, 200, 0], [0, 0, 200]];
+        let faces = get_hull_faces(&seeds);
+        assert_eq!(faces.len(), 4);
+
+        for inside in [[0, 0, 0], [50, 50, 50], [100, 100, 0], [0, 0, 200], [1, 1, 197]] {
+            assert!(is_inside(&faces, &inside), "{:?} should be inside", inside);
+        }
+        for outside in [[100, 100, 1], [201, 0, 0], [-1, 0, 0], [67, 67, 67], [255, 255, 255]] {
+            assert!(!is_inside(&faces, &outside), "{:?} should be outside", outside);
+        }
+    }
+
+    #[test]
+    fn coplanar_points_have_no_volume() {
+        let seeds = vec![[0, 0, 0], [255, 0, 0], [0, 255, 0], [255, 255, 0], [128, 64, 0]];
+        assert!(get_hull_faces(&seeds).is_empty());
+        assert!(get_hull_faces(&[[1, 2, 3], [1, 2, 3], [4, 5, 6]]).is_empty());
+    }
+}
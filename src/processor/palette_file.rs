@@ -0,0 +1,228 @@
+use std::fs;
+use std::io::{Error, Result};
+use std::path::Path;
+use image::Rgb;
+use serde::{Deserialize, Serialize};
+use crate::locale::trf;
+use crate::processor::compute::PaletteMapping;
+use crate::processor::palette::{as_hex, as_rgb, is_hex};
+use crate::processor::swatch::{is_adobe_swatch_file, parse_adobe_swatches};
+
+/// Gets the word that marks the colors of a line in a HEX list or GIMP palette as biased.
+fn bias_flag() -> &'static str { "biased" }
+
+
+
+/// A palette in Oxide's palette file format, which every palette file is read into and the palette file export writes.
+/// As JSON it looks like `{"name": "Dusk", "author": "Ada", "colors": ["#2b2d42", {"color": "#ef233c", "biased": true}]}`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct PaletteFile {
+    /// The name of the palette.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Who made the palette.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// What the palette is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The colors of the palette, in order.
+    pub colors: Vec<PaletteFileColor>,
+}
+impl PaletteFile {
+    /// Creates a palette file with a given name from colors without flags.
+    pub fn new(name: &str, colors: &[Rgb<u8>]) -> PaletteFile {
+        PaletteFile {
+            name: Some(name.to_string()),
+            colors: colors.iter().map(|color| PaletteFileColor::new(color, false)).collect(),
+            ..PaletteFile::default()
+        }
+    }
+
+    /// Returns the valid colors of the palette in order, each with whether it is biased.
+    pub fn get_flagged_colors(&self) -> Vec<(Rgb<u8>, bool)> {
+        self.colors.iter()
+            .filter(|color| is_hex(color.hex()))
+            .filter_map(|color| Some((as_rgb(color.hex())?, color.is_biased())))
+            .collect()
+    }
+
+    /// Returns the valid colors of the palette in order.
+    pub fn get_colors(&self) -> Vec<Rgb<u8>> {
+        self.get_flagged_colors().into_iter().map(|(color, _)| color).collect()
+    }
+}
+
+
+
+/// A color of a palette file, either a plain HEX color or a HEX color with flags.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PaletteFileColor {
+    /// A HEX color without flags.
+    Plain(String),
+    /// A HEX color with flags.
+    Flagged {
+        /// The HEX color.
+        color: String,
+        /// Whether pixels are mapped to this color in preference to the unbiased colors.
+        #[serde(default)]
+        biased: bool,
+    },
+}
+impl PaletteFileColor {
+    /// Creates a color, which is only written with flags if it is biased.
+    pub fn new(color: &Rgb<u8>, is_biased: bool) -> PaletteFileColor {
+        if is_biased { PaletteFileColor::Flagged { color: as_hex(color), biased: true } } else { PaletteFileColor::Plain(as_hex(color)) }
+    }
+
+    /// Gets the HEX color.
+    fn hex(&self) -> &String {
+        match self {
+            PaletteFileColor::Plain(color) | PaletteFileColor::Flagged { color, .. } => color,
+        }
+    }
+
+    /// Returns whether the color is biased.
+    fn is_biased(&self) -> bool {
+        matches!(self, PaletteFileColor::Flagged { biased: true, .. })
+    }
+}
+
+
+
+/// Returns the mapping onto exactly the colors of a palette file, without spectrums woven between them,
+/// preferring its biased colors if it has both biased and unbiased ones (the GPU can't bind an empty palette, and with a single palette there is nothing to prefer).
+pub fn get_palette_file_mapping(colors: &[Rgb<u8>], bias_flags: &[bool]) -> PaletteMapping {
+    let (biased_colors, standard_colors): (Vec<_>, Vec<_>) = colors.iter().copied().zip(bias_flags.iter().copied()).partition(|(_, is_biased)| *is_biased);
+    if biased_colors.is_empty() || standard_colors.is_empty() { return PaletteMapping::Even(colors.to_vec()); }
+
+    PaletteMapping::Biased(biased_colors.into_iter().map(|(color, _)| color).collect(), standard_colors.into_iter().map(|(color, _)| color).collect())
+}
+
+/// Loads a palette file, which is either a palette file in JSON, an Adobe swatch file (.ase or .aco), a GIMP palette (.gpl),
+/// a plain list with a HEX color on every line or any other text with HEX colors written with a leading #.
+/// Text files may carry `name`, `author` and `description` entries, and colors on a line containing the word "biased" are biased.
+pub fn load_palette_definition(path: &Path) -> Result<PaletteFile> {
+    let extension = path.extension().unwrap_or_default().to_string_lossy().to_string();
+    let palette_file = if is_adobe_swatch_file(&extension) {
+        let colors = parse_adobe_swatches(&fs::read(path)?);
+        PaletteFile { colors: colors.iter().map(|color| PaletteFileColor::new(color, false)).collect(), ..PaletteFile::default() }
+    } else {
+        let contents = fs::read_to_string(path)?;
+        // JSON that doesn't follow the format (e.g. exports of other tools) is still scanned for HEX colors
+        match serde_json::from_str::<PaletteFile>(&contents) {
+            Ok(palette_file) => palette_file,
+            Err(_) => parse_text_palette(&contents, extension.eq_ignore_ascii_case("gpl")),
+        }
+    };
+
+    if palette_file.get_colors().is_empty() { return Err(Error::other(trf("{} holds no colors", &[path.to_string_lossy().to_string()]))); }
+    Ok(palette_file)
+}
+
+/// Reads the colors and entries of a text palette file's contents, either a GIMP palette or text with HEX colors.
+fn parse_text_palette(contents: &str, is_gimp_palette: bool) -> PaletteFile {
+    let colors = contents.lines()
+        .flat_map(|line| {
+            let is_biased = line.split_whitespace().any(|word| word.eq_ignore_ascii_case(bias_flag()));
+            let colors = if is_gimp_palette { parse_gimp_line(line).into_iter().collect() } else { parse_hex_line(line) };
+            colors.into_iter().map(move |color| PaletteFileColor::new(&color, is_biased))
+        })
+        .collect();
+
+    PaletteFile {
+        name: get_entry(contents, "name"),
+        author: get_entry(contents, "author"),
+        description: get_entry(contents, "description"),
+        colors,
+    }
+}
+
+/// Returns the color of a line of a GIMP palette, which starts with three channels from 0 to 255.
+fn parse_gimp_line(line: &str) -> Option<Rgb<u8>> {
+    let mut channels = line.split_whitespace().map(|channel| channel.parse::<u8>());
+    match (channels.next(), channels.next(), channels.next()) {
+        (Some(Ok(r)), Some(Ok(g)), Some(Ok(b))) => Some(Rgb([r, g, b])),
+        _ => None,
+    }
+}
+
+/// Returns the HEX colors of a line of text in order.
+fn parse_hex_line(line: &str) -> Vec<Rgb<u8>> {
+    let mut words = line.split_whitespace();
+    let first_word = words.next().unwrap_or_default().to_string();
+    // plain lists may leave out the #, other text needs it so words like "facade" are not read as colors
+    if first_word.len() == 6 && is_hex(&first_word) && words.all(|word| word.eq_ignore_ascii_case(bias_flag())) {
+        return as_rgb(&first_word).into_iter().collect();
+    }
+    line.split(|c: char| !c.is_ascii_alphanumeric() && c != '#')
+        .filter(|token| token.starts_with('#'))
+        .map(|token| token.to_string())
+        .filter(is_hex)
+        .filter_map(|token| as_rgb(&token))
+        .collect()
+}
+
+/// Returns the value of the first entry with a given key (case insensitive) in a palette file, written as `key = value`, `key: value` or `"key": "value"`.
+fn get_entry(contents: &str, key: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        let (line_key, value) = line.split_once(['=', ':'])?;
+        if !line_key.trim().trim_matches(['{', '"', ' ']).eq_ignore_ascii_case(key) { return None; }
+        // quoted values end at their closing quote, so the rest of a one-line JSON object is ignored
+        let value = value.trim();
+        let value = match value.strip_prefix('"') {
+            Some(quoted_value) => quoted_value.split('"').next().unwrap_or_default(),
+            None => value,
+        };
+        Some(value.trim().to_string()).filter(|value| !value.is_empty())
+    })
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_colors_keep_their_flags() {
+        let contents = r##"{"name": "Dusk", "author": "Ada", "colors": ["#2b2d42", {"color": "#ef233c", "biased": true}, {"color": "#8d99ae"}, "dusk"]}"##;
+        let palette_file: PaletteFile = serde_json::from_str(contents).unwrap();
+        assert_eq!(palette_file.name.as_deref(), Some("Dusk"));
+        assert_eq!(palette_file.author.as_deref(), Some("Ada"));
+        assert_eq!(palette_file.get_flagged_colors(), vec![(Rgb([0x2b, 0x2d, 0x42]), false), (Rgb([0xef, 0x23, 0x3c]), true), (Rgb([0x8d, 0x99, 0xae]), false)]);
+    }
+
+    #[test]
+    fn gimp_palette_lines_are_read() {
+        let contents = "GIMP Palette\nName: Sunset\nColumns: 4\n#\n255   0   0\tRed\n  0 128 255\tSky biased\n12 34\n";
+        let palette_file = parse_text_palette(contents, true);
+        assert_eq!(palette_file.name.as_deref(), Some("Sunset"));
+        assert_eq!(palette_file.get_flagged_colors(), vec![(Rgb([255, 0, 0]), false), (Rgb([0, 128, 255]), true)]);
+    }
+
+    #[test]
+    fn hex_lists_read_entries_and_colors() {
+        let contents = "name = Dusk\nauthor: Ada\n2b2d42\n#ef233c biased\nThe #8d99ae accent of the facade\n";
+        let palette_file = parse_text_palette(contents, false);
+        assert_eq!(palette_file.name.as_deref(), Some("Dusk"));
+        assert_eq!(palette_file.author.as_deref(), Some("Ada"));
+        assert_eq!(palette_file.description, None);
+        assert_eq!(palette_file.get_flagged_colors(), vec![(Rgb([0x2b, 0x2d, 0x42]), false), (Rgb([0xef, 0x23, 0x3c]), true), (Rgb([0x8d, 0x99, 0xae]), false)]);
+    }
+
+    #[test]
+    fn palettes_with_a_single_kind_of_colors_map_evenly() {
+        let colors = [Rgb([0, 0, 0]), Rgb([255, 255, 255])];
+        assert!(matches!(get_palette_file_mapping(&colors, &[true, true]), PaletteMapping::Even(even_colors) if even_colors == colors));
+        assert!(matches!(get_palette_file_mapping(&colors, &[false, false]), PaletteMapping::Even(even_colors) if even_colors == colors));
+        assert!(matches!(get_palette_file_mapping(&colors, &[true, false]), PaletteMapping::Biased(biased, standard) if biased == [colors[0]] && standard == [colors[1]]));
+    }
+
+    #[test]
+    fn quoted_entries_end_at_their_closing_quote() {
+        assert_eq!(get_entry(r#"{"name": "Dusk", "colors": []}"#, "name").as_deref(), Some("Dusk"));
+        assert_eq!(get_entry("description =\n", "description"), None);
+    }
+}
@@ -1,9 +1,23 @@
+// to_coordinates(), get_color_distance() and the color space conversions are prepended from color_distance.wgsl
+// the palettes hold the coordinates of their colors, converted once on the CPU by colors_as_coordinates()
+
 @group(0) @binding(0) var<uniform> dimensions: vec4<u32>;
 @group(0) @binding(1) var<storage, read> pixels: array<u32>;
-@group(0) @binding(2) var<storage, read> biased_palette: array<u32>;
-@group(0) @binding(3) var<storage, read> standard_palette: array<u32>;
+@group(0) @binding(2) var<storage, read> biased_palette: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read> standard_palette: array<vec4<f32>>;
 @group(0) @binding(4) var<storage, read_write> shader_results: array<u32>;
 
+struct MatchingInformation {
+    mask_size: u32,
+    spread: f32,
+    space: u32,
+    // only read by palettize_evenly.wgsl
+    split_threshold: f32,
+}
+
+@group(0) @binding(5) var<uniform> matching_information: MatchingInformation;
+@group(0) @binding(6) var<storage, read> thresholds: array<f32>;
+
 fn unpack(color: u32) -> vec3<f32> {
     return vec3<f32>(
         f32(color & 0xFF),
@@ -12,6 +26,13 @@ fn unpack(color: u32) -> vec3<f32> {
     );
 }
 
+// nudges a color by the threshold of the mask at its position, the same way ThresholdMask::nudge() does on the CPU
+fn nudge(color: vec3<f32>, x: u32, y: u32) -> vec3<f32> {
+    let mask_size = matching_information.mask_size;
+    let offset = thresholds[((y % mask_size) * mask_size) + (x % mask_size)] * matching_information.spread;
+    return clamp(floor(color + vec3<f32>(offset + 0.5)), vec3<f32>(0.0), vec3<f32>(255.0));
+}
+
 @compute @workgroup_size(8, 8, 1)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let width = dimensions.x;
@@ -27,18 +48,14 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
 
     let pixel_index = (y * width) + x;
-    let pixel = unpack(pixels[pixel_index]);
+    let pixel = to_coordinates(nudge(unpack(pixels[pixel_index]), x, y), matching_information.space);
 
     var closest_biased_color_distance = 999999.0;
     var closest_biased_color_index = 0u;
 
     for (var i = 0u; i < biased_palette_length; i++) {
-        let color = unpack(biased_palette[i]);
-
-        let r_value = (pixel.x - color.x) * 0.299 / 0.625;
-        let g_value = (pixel.y - color.y) * 0.587 / 0.625;
-        let b_value = (pixel.z - color.z) * 0.114 / 0.625;
-        let distance = sqrt((r_value * r_value) + (g_value * g_value) + (b_value * b_value));
+        let color = biased_palette[i].xyz;
+        let distance = get_color_distance(pixel, color, matching_information.space) / 0.625;
 
         if (distance < closest_biased_color_distance) {
             closest_biased_color_distance = distance;
@@ -50,12 +67,8 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     var closest_standard_color_index = 0u;
 
     for (var i = 0u; i < standard_palette_length; i++) {
-        let color = unpack(standard_palette[i]);
-
-        let r_value = (pixel.x - color.x) * 0.299;
-        let g_value = (pixel.y - color.y) * 0.587;
-        let b_value = (pixel.z - color.z) * 0.114;
-        let distance = sqrt((r_value * r_value) + (g_value * g_value) + (b_value * b_value));
+        let color = standard_palette[i].xyz;
+        let distance = get_color_distance(pixel, color, matching_information.space);
 
         if (distance < closest_standard_color_distance) {
             closest_standard_color_distance = distance;
@@ -0,0 +1,243 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use image::Rgb;
+use wasmtime::{Config, Engine, Instance, Memory, Module, Store};
+use crate::processor::shader::{parse_manifest, ShaderParameter};
+
+/// The name of the directory processor plugins are loaded from, next to the source and output folders.
+pub const PLUGIN_DIRECTORY_NAME: &str = "plugins";
+
+/// The processor plugins, loaded once on first use.
+static PLUGINS: OnceLock<Vec<Plugin>> = OnceLock::new();
+
+/// The engine every plugin is compiled and run with.
+static ENGINE: OnceLock<Option<Engine>> = OnceLock::new();
+
+/// Gets the number of WebAssembly instructions a plugin may run for every pixel of an image, so a plugin that never returns can't freeze the run.
+fn fuel_per_pixel() -> u64 { 2_000 }
+
+/// Gets the number of WebAssembly instructions a plugin may run on top of its pixels (e.g. to set up its memory or read its manifest).
+fn base_fuel() -> u64 { 10_000_000 }
+
+
+
+/// A WebAssembly module run as a processor.
+/// Modules can't import anything, so they have no access to files, the network or the clock, and export:
+/// - `memory`, the memory pixels and parameters are passed in,
+/// - `alloc(size: i32) -> i32`, which returns the address of a given number of free bytes,
+/// - `process(pixels: i32, width: i32, height: i32, parameters: i32, parameter_count: i32) -> i32`, which recolors the rgb bytes of the image in place
+///   from the parameters (four little-endian f32s each, like shader parameters) and returns 0 on success,
+/// - optionally `manifest() -> i32`, which returns the address of a NUL-terminated manifest in the format of shader manifests.
+#[derive(Clone)]
+pub struct Plugin {
+    /// The name listed in the processor list.
+    pub name: String,
+    /// The description listed next to the name.
+    pub description: String,
+    /// The compiled module, if it could be compiled.
+    module: Option<Module>,
+    /// The parameters the plugin reads, in order.
+    pub parameters: Vec<ShaderParameter>,
+    /// Why the plugin cannot be used, if it failed to load or doesn't follow the plugin interface.
+    pub error: Option<String>,
+}
+impl Plugin {
+    /// Loads a plugin from a given module and reads its manifest, if it has one.
+    fn load(plugin_path: &Path) -> Plugin {
+        let mut plugin = Plugin {
+            name: plugin_path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+            description: "Plugin".to_string(),
+            module: None,
+            parameters: Vec::new(),
+            error: None,
+        };
+
+        let manifest = compile(plugin_path).and_then(|module| {
+            let manifest = read_manifest(&module)?;
+            plugin.module = Some(module);
+            Ok(manifest)
+        });
+        match manifest {
+            Ok(Some(manifest)) => {
+                let manifest = parse_manifest(&manifest);
+                if let Some(name) = manifest.name { plugin.name = name; }
+                if let Some(description) = manifest.description { plugin.description = description; }
+                plugin.parameters = manifest.parameters;
+                plugin.error = manifest.error;
+            }
+            Ok(None) => {}
+            Err(e) => plugin.error = Some(e),
+        }
+        plugin
+    }
+
+    /// Runs the plugin on the pixels of an image with given parameters and returns the new pixels, or what went wrong.
+    pub fn run(&self, width: u32, height: u32, pixels: &[Rgb<u8>], parameters: &[[f32; 4]]) -> Result<Vec<Rgb<u8>>, String> {
+        let module = self.module.as_ref().ok_or_else(|| self.error.clone().unwrap_or_default())?;
+        let fuel = fuel_per_pixel().saturating_mul(pixels.len() as u64).saturating_add(base_fuel());
+        let (mut store, instance, memory) = instantiate(module, fuel)?;
+        let process = instance.get_typed_func::<(i32, i32, i32, i32, i32), i32>(&mut store, "process").map_err(|e| e.to_string())?;
+
+        let pixel_bytes: Vec<u8> = pixels.iter().flat_map(|pixel| pixel.0).collect();
+        let parameter_bytes: Vec<u8> = parameters.iter().flatten().flat_map(|value| value.to_le_bytes()).collect();
+        let pixels_address = write(&mut store, &instance, &memory, &pixel_bytes)?;
+        let parameters_address = write(&mut store, &instance, &memory, &parameter_bytes)?;
+
+        let status = process.call(&mut store, (pixels_address, width as i32, height as i32, parameters_address, parameters.len() as i32)).map_err(|e| e.to_string())?;
+        if status != 0 { return Err(format!("process returned {}", status)); }
+
+        let mut new_pixel_bytes = vec![0u8; pixel_bytes.len()];
+        memory.read(&store, pixels_address as u32 as usize, &mut new_pixel_bytes).map_err(|e| e.to_string())?;
+        Ok(new_pixel_bytes.chunks_exact(3).map(|channels| Rgb([channels[0], channels[1], channels[2]])).collect())
+    }
+}
+
+
+
+/// Returns the engine plugins are compiled and run with, which counts the instructions they run, or None if it could not be set up.
+fn engine() -> Option<&'static Engine> {
+    ENGINE.get_or_init(|| Engine::new(Config::new().consume_fuel(true)).ok()).as_ref()
+}
+
+/// Compiles a plugin and checks that it follows the plugin interface.
+fn compile(plugin_path: &Path) -> Result<Module, String> {
+    let engine = engine().ok_or("could not set up the WebAssembly engine")?;
+    let bytes = fs::read(plugin_path).map_err(|e| e.to_string())?;
+    let module = Module::new(engine, bytes).map_err(|e| e.to_string())?;
+    if let Some(import) = module.imports().next() { return Err(format!("plugins can't import anything, but it imports {}", import.name())); }
+
+    // the exports are checked by instantiating the module once
+    let (mut store, instance, _) = instantiate(&module, base_fuel())?;
+    instance.get_typed_func::<i32, i32>(&mut store, "alloc").map_err(|e| format!("alloc: {}", e))?;
+    instance.get_typed_func::<(i32, i32, i32, i32, i32), i32>(&mut store, "process").map_err(|e| format!("process: {}", e))?;
+    Ok(module)
+}
+
+/// Creates a new instance of a compiled plugin that may run a given number of instructions, with its memory.
+fn instantiate(module: &Module, fuel: u64) -> Result<(Store<()>, Instance, Memory), String> {
+    let mut store = Store::new(module.engine(), ());
+    store.set_fuel(fuel).map_err(|e| e.to_string())?;
+    let instance = Instance::new(&mut store, module, &[]).map_err(|e| e.to_string())?;
+    let memory = instance.get_memory(&mut store, "memory").ok_or("missing an exported memory")?;
+    Ok((store, instance, memory))
+}
+
+/// Copies bytes into memory the plugin allocates for them and returns their address.
+fn write(store: &mut Store<()>, instance: &Instance, memory: &Memory, bytes: &[u8]) -> Result<i32, String> {
+    let alloc = instance.get_typed_func::<i32, i32>(&mut *store, "alloc").map_err(|e| e.to_string())?;
+    let size = i32::try_from(bytes.len()).map_err(|_| "the image is too large for a plugin".to_string())?;
+    let address = alloc.call(&mut *store, size).map_err(|e| e.to_string())?;
+    memory.write(&mut *store, address as u32 as usize, bytes).map_err(|e| e.to_string())?;
+    Ok(address)
+}
+
+/// Returns the manifest a plugin exports, or None if it doesn't export one.
+fn read_manifest(module: &Module) -> Result<Option<String>, String> {
+    let (mut store, instance, memory) = instantiate(module, base_fuel())?;
+    let Ok(manifest) = instance.get_typed_func::<(), i32>(&mut store, "manifest") else { return Ok(None); };
+    let address = manifest.call(&mut store, ()).map_err(|e| e.to_string())? as u32 as usize;
+    let bytes = memory.data(&store).get(address..).ok_or("the manifest is outside of the memory")?;
+    let length = bytes.iter().position(|byte| *byte == 0).ok_or("the manifest is not NUL-terminated")?;
+    Ok(Some(String::from_utf8_lossy(&bytes[..length]).to_string()))
+}
+
+/// Returns the path of the plugin directory.
+fn plugin_directory() -> PathBuf {
+    std::env::current_dir().expect("Could not get local working directory.").join(PLUGIN_DIRECTORY_NAME)
+}
+
+/// Returns every plugin in the plugin directory, ordered by filename.
+pub fn get_plugins() -> &'static Vec<Plugin> {
+    PLUGINS.get_or_init(|| {
+        let Ok(entries) = fs::read_dir(plugin_directory()) else { return Vec::new(); };
+        let mut plugin_paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("wasm")))
+            .collect();
+        plugin_paths.sort();
+        plugin_paths.iter().map(|plugin_path| Plugin::load(plugin_path)).collect()
+    })
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A plugin that inverts every channel and names itself in its manifest, compiled from:
+    /// ```wat
+    /// (module
+    ///     (memory (export "memory") 1)
+    ///     (global $next (mut i32) (i32.const 1024))
+    ///     (data (i32.const 0) "name = Invert\0aparameter = Strength: number\00")
+    ///     (func (export "manifest") (result i32) (i32.const 0))
+    ///     (func (export "alloc") (param $size i32) (result i32)
+    ///         (local $address i32)
+    ///         (local.set $address (global.get $next))
+    ///         (global.set $next (i32.add (global.get $next) (local.get $size)))
+    ///         (local.get $address))
+    ///     (func (export "process") (param $pixels i32) (param $width i32) (param $height i32) (param $parameters i32) (param $count i32) (result i32)
+    ///         (local $end i32)
+    ///         (local.set $end (i32.add (local.get $pixels) (i32.mul (i32.const 3) (i32.mul (local.get $width) (local.get $height)))))
+    ///         (block $done
+    ///             (loop $next_byte
+    ///                 (br_if $done (i32.ge_u (local.get $pixels) (local.get $end)))
+    ///                 (i32.store8 (local.get $pixels) (i32.sub (i32.const 255) (i32.load8_u (local.get $pixels))))
+    ///                 (local.set $pixels (i32.add (local.get $pixels) (i32.const 1)))
+    ///                 (br $next_byte)))
+    ///         (i32.const 0)))
+    /// ```
+    const INVERT_PLUGIN: &[u8] = include_bytes!("test_plugins/invert.wasm");
+
+    /// A plugin importing a function, compiled from:
+    /// ```wat
+    /// (module (import "env" "clock" (func)) (memory (export "memory") 1))
+    /// ```
+    const IMPORT_PLUGIN: &[u8] = include_bytes!("test_plugins/import.wasm");
+
+    /// A plugin whose process function never returns, compiled from:
+    /// ```wat
+    /// (module
+    ///     (memory (export "memory") 1)
+    ///     (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+    ///     (func (export "process") (param i32 i32 i32 i32 i32) (result i32) (loop $forever (br $forever)) (i32.const 0)))
+    /// ```
+    const ENDLESS_PLUGIN: &[u8] = include_bytes!("test_plugins/endless.wasm");
+
+    /// Writes a compiled plugin to a temporary file and loads it.
+    fn load_plugin(name: &str, bytes: &[u8]) -> Plugin {
+        let path = std::env::temp_dir().join(format!("oxide-test-{}-{}.wasm", name, std::process::id()));
+        fs::write(&path, bytes).unwrap();
+        let plugin = Plugin::load(&path);
+        let _ = fs::remove_file(&path);
+        plugin
+    }
+
+    #[test]
+    fn plugins_declare_their_steps_and_map_pixels() {
+        let plugin = load_plugin("invert", INVERT_PLUGIN);
+        assert_eq!(plugin.error, None);
+        assert_eq!(plugin.name, "Invert");
+        assert_eq!(plugin.parameters.len(), 1);
+        assert_eq!(plugin.parameters[0].label, "Strength");
+
+        let pixels = [Rgb([0, 128, 255]), Rgb([10, 20, 30])];
+        assert_eq!(plugin.run(2, 1, &pixels, &[[1.0, 0.0, 0.0, 0.0]]), Ok(vec![Rgb([255, 127, 0]), Rgb([245, 235, 225])]));
+    }
+
+    #[test]
+    fn plugins_that_import_anything_are_rejected() {
+        let plugin = load_plugin("import", IMPORT_PLUGIN);
+        assert!(plugin.error.is_some_and(|error| error.contains("import")));
+    }
+
+    #[test]
+    fn plugins_that_never_return_run_out_of_fuel() {
+        let plugin = load_plugin("endless", ENDLESS_PLUGIN);
+        assert_eq!(plugin.error, None);
+        assert!(plugin.run(1, 1, &[Rgb([0, 0, 0])], &[]).is_err());
+    }
+}
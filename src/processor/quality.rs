@@ -0,0 +1,39 @@
+/// The presets that trade the quality of processed images for speed with a single setting.
+#[derive(Copy, Clone, PartialEq)]
+pub enum QualityPresets {
+    /// Short spectrums, weighted RGB matching and no dithering.
+    Fast,
+    /// Full spectrums, OKLab matching and Floyd-Steinberg dithering.
+    Balanced,
+    /// Finer spectrums, CIEDE2000 matching and spatial quantization.
+    Accurate,
+}
+impl QualityPresets {
+    /// Gets the name of the preset.
+    pub fn name(&self) -> String {
+        match self {
+            QualityPresets::Fast => "Fast".to_string(),
+            QualityPresets::Balanced => "Balanced".to_string(),
+            QualityPresets::Accurate => "Accurate".to_string(),
+        }
+    }
+
+    /// Returns the preset with a given name (case insensitive).
+    pub fn from_name(name: &str) -> Option<QualityPresets> {
+        match name.to_lowercase().as_str() {
+            "fast" => Some(QualityPresets::Fast),
+            "balanced" => Some(QualityPresets::Balanced),
+            "accurate" => Some(QualityPresets::Accurate),
+            _ => None,
+        }
+    }
+
+    /// Returns the settings the preset stands for as keys and values of the configuration file.
+    pub fn settings(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            QualityPresets::Fast => vec![("interpolation_steps", "128"), ("color_distance", "rgb"), ("dither", "none")],
+            QualityPresets::Balanced => vec![("interpolation_steps", "442"), ("color_distance", "oklab"), ("dither", "floyd-steinberg")],
+            QualityPresets::Accurate => vec![("interpolation_steps", "884"), ("color_distance", "ciede2000"), ("dither", "spatial")],
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use image::{ImageBuffer, Rgb};
+use rayon::prelude::*;
+use crate::processor::palette::get_closest_color_index;
+
+/// Gets the depth of the color octree, which is one level per bit of a channel.
+fn octree_depth() -> usize { 8 }
+
+
+
+/// The ways images are reduced to the few colors of an indexed format (e.g. gif).
+#[derive(Copy, Clone, PartialEq)]
+pub enum Quantizers {
+    /// The neural network quantizer of the gif encoder, which is slower but blends gradients more smoothly.
+    NeuQuant,
+    /// An octree of the colors that merges its least detailed branches, which is fast and keeps rare but distinct colors.
+    Octree,
+}
+impl Quantizers {
+    /// Returns the quantizer with a given name (case insensitive).
+    pub fn from_name(name: &str) -> Option<Quantizers> {
+        match name.to_lowercase().as_str() {
+            "neuquant" => Some(Quantizers::NeuQuant),
+            "octree" => Some(Quantizers::Octree),
+            _ => None,
+        }
+    }
+}
+
+
+
+/// A node of the color octree, which is a leaf once it holds the colors of its whole branch.
+#[derive(Clone, Default)]
+struct OctreeNode {
+    /// The sums of the red, green and blue channels of the colors in the node.
+    channel_sums: [u64; 3],
+    /// The number of colors added to the node.
+    color_count: u64,
+    /// The nodes of the eight branches below the node, by their index in the octree.
+    children: [Option<usize>; 8],
+    /// Tracks if the node holds the colors of its whole branch.
+    is_leaf: bool,
+    /// The index of the node's color in the palette, once the palette is built.
+    palette_index: usize,
+}
+
+
+
+/// Reduces colors to a palette by sorting them into an octree (one level per bit of each channel) and merging the deepest branches until few enough are left.
+pub struct Octree {
+    /// Every node of the octree, with the root first.
+    nodes: Vec<OctreeNode>,
+    /// The branch nodes of every level that can still be merged into leaves.
+    reducible_nodes: Vec<Vec<usize>>,
+    /// The number of leaves, which is the number of colors in the palette.
+    leaf_count: usize,
+    /// The max number of colors in the palette.
+    max_colors: usize,
+    /// The palette of the octree, once it is built.
+    palette: Vec<Rgb<u8>>,
+}
+impl Octree {
+    /// Returns an empty octree that reduces its colors to a given number of colors.
+    pub fn new(max_colors: usize) -> Octree {
+        let mut reducible_nodes = vec![Vec::new(); octree_depth()];
+        reducible_nodes[0].push(0);
+        Octree {
+            nodes: vec![OctreeNode::default()],
+            reducible_nodes,
+            leaf_count: 0,
+            max_colors: max_colors.max(1),
+            palette: Vec::new(),
+        }
+    }
+
+    /// Returns the branch (0-7) a color falls into at a given level, from one bit of each channel.
+    fn get_branch(color: &Rgb<u8>, level: usize) -> usize {
+        let shift = octree_depth() - 1 - level;
+        (((color[0] >> shift) & 1) << 2 | ((color[1] >> shift) & 1) << 1 | ((color[2] >> shift) & 1)) as usize
+    }
+
+    /// Adds a color to the octree, merging branches whenever there are too many leaves.
+    pub fn insert(&mut self, color: &Rgb<u8>) {
+        let mut node = 0;
+        for level in 0..octree_depth() {
+            if self.nodes[node].is_leaf { break; }
+            let branch = Octree::get_branch(color, level);
+            node = match self.nodes[node].children[branch] {
+                Some(child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    let is_leaf = level + 1 == octree_depth();
+                    self.nodes.push(OctreeNode { is_leaf, ..OctreeNode::default() });
+                    self.nodes[node].children[branch] = Some(child);
+                    if is_leaf { self.leaf_count += 1; }
+                    else { self.reducible_nodes[level + 1].push(child); }
+                    child
+                }
+            };
+        }
+
+        let node = &mut self.nodes[node];
+        node.channel_sums.iter_mut().zip(color.0).for_each(|(sum, channel)| *sum += channel as u64);
+        node.color_count += 1;
+
+        while self.leaf_count > self.max_colors { self.reduce(); }
+    }
+
+    /// Merges the children of the deepest branch node into it, turning it into a leaf.
+    fn reduce(&mut self) {
+        let Some(node) = self.reducible_nodes.iter_mut().rev().find_map(|level_nodes| level_nodes.pop()) else { return; };
+
+        let children: Vec<usize> = self.nodes[node].children.iter_mut().filter_map(|child| child.take()).collect();
+        for child in &children {
+            let (channel_sums, color_count) = (self.nodes[*child].channel_sums, self.nodes[*child].color_count);
+            self.nodes[node].channel_sums.iter_mut().zip(channel_sums).for_each(|(sum, child_sum)| *sum += child_sum);
+            self.nodes[node].color_count += color_count;
+        }
+        self.nodes[node].is_leaf = true;
+        self.leaf_count = self.leaf_count + 1 - children.len();
+    }
+
+    /// Builds the palette from the average colors of the leaves and returns it.
+    pub fn build_palette(&mut self) -> Vec<Rgb<u8>> {
+        let mut palette = Vec::new();
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            if self.nodes[node].is_leaf {
+                let node = &mut self.nodes[node];
+                let color_count = node.color_count.max(1);
+                node.palette_index = palette.len();
+                palette.push(Rgb(node.channel_sums.map(|sum| (sum as f64 / color_count as f64).round() as u8)));
+            }
+            else { stack.extend(self.nodes[node].children.iter().flatten()); }
+        }
+        self.palette = palette.clone();
+        palette
+    }
+
+    /// Returns the index of a color in the built palette, which is its leaf or the closest palette color if it was never added.
+    pub fn get_palette_index(&self, color: &Rgb<u8>) -> usize {
+        let mut node = 0;
+        for level in 0..octree_depth() {
+            if self.nodes[node].is_leaf { return self.nodes[node].palette_index; }
+            match self.nodes[node].children[Octree::get_branch(color, level)] {
+                Some(child) => node = child,
+                None => return get_closest_color_index(color, &self.palette),
+            }
+        }
+        self.nodes[node].palette_index
+    }
+}
+
+
+
+/// Reduces frames to a shared palette of at most a given number of colors with an octree, returning the palette and the palette indices of every frame's pixels.
+pub fn quantize_octree(frames: &[ImageBuffer<Rgb<u8>, Vec<u8>>], max_colors: usize) -> (Vec<Rgb<u8>>, Vec<Vec<u8>>) {
+    let mut octree = Octree::new(max_colors.min(256));
+    frames.iter().flat_map(|frame| frame.pixels()).for_each(|pixel| octree.insert(pixel));
+    let palette = octree.build_palette();
+
+    let indexed_frames = frames.par_iter().map(|frame| {
+        frame.pixels().map(|pixel| octree.get_palette_index(pixel) as u8).collect()
+    }).collect();
+    (palette, indexed_frames)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Returns a frame with a different color at almost every pixel.
+    fn colorful_frame() -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        ImageBuffer::from_fn(64, 64, |x, y| Rgb([(x * 4) as u8, (y * 4) as u8, ((x + y) * 2) as u8]))
+    }
+
+    #[test]
+    fn palettes_never_exceed_the_max_colors() {
+        let frames = vec![colorful_frame()];
+        for max_colors in [1, 2, 3, 16, 255, 256, 1000] {
+            let (palette, _) = quantize_octree(&frames, max_colors);
+            assert!(!palette.is_empty());
+            assert!(palette.len() <= max_colors.min(256), "{} colors for at most {}", palette.len(), max_colors);
+        }
+    }
+
+    #[test]
+    fn indices_stay_in_the_reduced_palette() {
+        let frames = vec![colorful_frame(), ImageBuffer::from_fn(16, 16, |x, y| Rgb([255 - x as u8 * 16, 0, y as u8 * 16]))];
+        for max_colors in [2, 7, 64] {
+            let (palette, indexed_frames) = quantize_octree(&frames, max_colors);
+            assert_eq!(indexed_frames.len(), frames.len());
+            for (frame, indices) in frames.iter().zip(&indexed_frames) {
+                assert_eq!(indices.len(), frame.pixels().len());
+                assert!(indices.iter().all(|index| (*index as usize) < palette.len()));
+            }
+        }
+
+        // colors that were never added still fall into the palette
+        let mut octree = Octree::new(4);
+        colorful_frame().pixels().for_each(|pixel| octree.insert(pixel));
+        let palette = octree.build_palette();
+        assert!(octree.get_palette_index(&Rgb([255, 255, 255])) < palette.len());
+    }
+
+    #[test]
+    fn few_colors_are_kept_exactly() {
+        let colors = [Rgb([0, 0, 0]), Rgb([255, 255, 255]), Rgb([12, 200, 99]), Rgb([13, 200, 99]), Rgb([250, 7, 130])];
+        let frame = ImageBuffer::from_fn(10, 10, |x, y| colors[((x + y * 3) % colors.len() as u32) as usize]);
+        let (palette, indexed_frames) = quantize_octree(std::slice::from_ref(&frame), colors.len());
+
+        assert_eq!(palette.iter().collect::<HashSet<_>>(), colors.iter().collect::<HashSet<_>>());
+        for (pixel, index) in frame.pixels().zip(&indexed_frames[0]) {
+            assert_eq!(palette[*index as usize], *pixel);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use image::Rgb;
+use rayon::prelude::*;
+use rhai::{Array, Dynamic, Engine, FnAccess, AST};
+use crate::processor::shader::parse_manifest;
+
+/// The name of the directory color mapping scripts are loaded from, next to the source and output folders.
+pub const SCRIPT_DIRECTORY_NAME: &str = "scripts";
+
+/// The color mapping scripts, loaded once on first use.
+static SCRIPTS: OnceLock<Vec<MappingScript>> = OnceLock::new();
+
+/// The engine every script is compiled and run with.
+static ENGINE: OnceLock<Engine> = OnceLock::new();
+
+/// Gets the number of pixels a script maps in one go, which is also the number of pixels passed to `map_batch`.
+fn batch_size() -> usize { 4096 }
+
+/// Gets the number of operations a script may run for every pixel of a batch, so a script that never returns can't freeze the run.
+fn operations_per_pixel() -> u64 { 10_000 }
+
+
+
+/// A Rhai script deciding the color every pixel is mapped to, run as a processor with the colors of a palette file.
+/// Colors are arrays of three integers from 0 to 255 (`[r, g, b]`) and palettes are arrays of colors. Scripts define either
+/// `fn map(pixel, palette)`, which returns the color of a single pixel, or `fn map_batch(pixels, palette)`, which returns the colors of many pixels at once.
+pub struct MappingScript {
+    /// The name listed in the processor list.
+    pub name: String,
+    /// The description listed next to the name.
+    pub description: String,
+    /// The compiled script, if it could be compiled.
+    ast: Option<AST>,
+    /// Whether the script maps whole batches of pixels with `map_batch` instead of single pixels with `map`.
+    is_batched: bool,
+    /// Why the script cannot be used, if it failed to load or doesn't define a mapping function.
+    pub error: Option<String>,
+}
+impl MappingScript {
+    /// Loads a script and the manifest next to it (the same name with a .conf extension), if there is one.
+    fn load(script_path: &Path) -> MappingScript {
+        let mut script = MappingScript {
+            name: script_path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+            description: "Mapping script".to_string(),
+            ast: None,
+            is_batched: false,
+            error: None,
+        };
+
+        if let Ok(manifest) = fs::read_to_string(script_path.with_extension("conf")) {
+            let manifest = parse_manifest(&manifest);
+            if let Some(name) = manifest.name { script.name = name; }
+            if let Some(description) = manifest.description { script.description = description; }
+        }
+
+        match engine().compile_file(script_path.to_path_buf()) {
+            Ok(ast) => {
+                let has_function = |name: &str| ast.iter_functions().any(|function| function.name == name && function.params.len() == 2 && function.access == FnAccess::Public);
+                script.is_batched = has_function("map_batch");
+                if !script.is_batched && !has_function("map") { script.error = Some("missing a fn map(pixel, palette) or fn map_batch(pixels, palette)".to_string()); }
+                script.ast = Some(ast);
+            }
+            Err(e) => script.error = Some(e.to_string()),
+        }
+        script
+    }
+
+    /// Maps pixels onto the colors of a palette with the script and returns the new pixels, or what went wrong.
+    /// Batches of pixels are mapped in parallel.
+    pub fn run(&self, pixels: &[Rgb<u8>], palette: &[Rgb<u8>]) -> Result<Vec<Rgb<u8>>, String> {
+        let ast = self.ast.as_ref().ok_or_else(|| self.error.clone().unwrap_or_default())?;
+        let palette: Array = palette.iter().map(as_dynamic).collect();
+
+        let batches: Result<Vec<Vec<Rgb<u8>>>, String> = pixels.par_chunks(batch_size()).map(|batch| {
+            if self.is_batched {
+                let colors: Array = engine().call_fn(&mut Default::default(), ast, "map_batch", (batch.iter().map(as_dynamic).collect::<Array>(), palette.clone())).map_err(|e| e.to_string())?;
+                if colors.len() != batch.len() { return Err(format!("map_batch returned {} colors for {} pixels", colors.len(), batch.len())); }
+                colors.iter().map(as_color).collect()
+            } else {
+                batch.iter().map(|pixel| {
+                    let color: Dynamic = engine().call_fn(&mut Default::default(), ast, "map", (as_dynamic(pixel), palette.clone())).map_err(|e| e.to_string())?;
+                    as_color(&color)
+                }).collect()
+            }
+        }).collect();
+        Ok(batches?.concat())
+    }
+}
+
+
+
+/// Returns the engine scripts are compiled and run with, which stops scripts after too many operations.
+fn engine() -> &'static Engine {
+    ENGINE.get_or_init(|| {
+        let mut engine = Engine::new();
+        engine.set_max_operations(operations_per_pixel() * batch_size() as u64);
+        engine
+    })
+}
+
+/// Converts a color into the array of its channels a script works with.
+fn as_dynamic(color: &Rgb<u8>) -> Dynamic {
+    Dynamic::from_array(color.0.iter().map(|channel| Dynamic::from_int(*channel as rhai::INT)).collect())
+}
+
+/// Converts a color returned by a script back, clamping its channels to 0-255.
+fn as_color(color: &Dynamic) -> Result<Rgb<u8>, String> {
+    let invalid_color = || format!("expected a color as [r, g, b] but got {}", color);
+    let channels = color.clone().into_array().map_err(|_| invalid_color())?;
+    if channels.len() != 3 { return Err(invalid_color()); }
+    let mut color = [0u8; 3];
+    for (channel, value) in color.iter_mut().zip(&channels) {
+        let value = value.as_int().or_else(|_| value.as_float().map(|value| value.round() as rhai::INT)).map_err(|_| invalid_color())?;
+        *channel = value.clamp(0, 255) as u8;
+    }
+    Ok(Rgb(color))
+}
+
+/// Returns the path of the script directory.
+fn script_directory() -> PathBuf {
+    std::env::current_dir().expect("Could not get local working directory.").join(SCRIPT_DIRECTORY_NAME)
+}
+
+/// Returns every script in the script directory, ordered by filename.
+pub fn get_scripts() -> &'static Vec<MappingScript> {
+    SCRIPTS.get_or_init(|| {
+        let Ok(entries) = fs::read_dir(script_directory()) else { return Vec::new(); };
+        let mut script_paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("rhai")))
+            .collect();
+        script_paths.sort();
+        script_paths.iter().map(|script_path| MappingScript::load(script_path)).collect()
+    })
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a script to a temporary file and loads it.
+    fn load_script(name: &str, source: &str) -> MappingScript {
+        let path = std::env::temp_dir().join(format!("oxide-test-{}-{}.rhai", name, std::process::id()));
+        fs::write(&path, source).unwrap();
+        let script = MappingScript::load(&path);
+        let _ = fs::remove_file(&path);
+        script
+    }
+
+    #[test]
+    fn pixels_are_mapped_one_by_one() {
+        // picks the first palette color for dark pixels and the last for bright ones
+        let script = load_script("map", "fn map(pixel, palette) { if pixel[0] + pixel[1] + pixel[2] < 384 { palette[0] } else { palette[palette.len() - 1] } }");
+        assert_eq!(script.error, None);
+        let palette = [Rgb([1, 2, 3]), Rgb([250, 251, 252])];
+        assert_eq!(script.run(&[Rgb([0, 0, 0]), Rgb([255, 255, 255])], &palette), Ok(vec![Rgb([1, 2, 3]), Rgb([250, 251, 252])]));
+    }
+
+    #[test]
+    fn pixels_are_mapped_in_batches() {
+        let script = load_script("batch", "fn map_batch(pixels, palette) { pixels.map(|pixel| [255 - pixel[0], 300, -5.4]) }");
+        assert_eq!(script.error, None);
+        let pixels = vec![Rgb([10, 0, 0]); batch_size() + 1];
+        assert_eq!(script.run(&pixels, &[]), Ok(vec![Rgb([245, 255, 0]); batch_size() + 1]));
+    }
+
+    #[test]
+    fn scripts_without_a_mapping_function_are_rejected() {
+        assert!(load_script("missing", "fn other(pixel) { pixel }").error.is_some());
+        assert!(load_script("invalid", "fn map(pixel, palette) {").error.is_some());
+    }
+
+    #[test]
+    fn scripts_that_never_return_are_stopped() {
+        let script = load_script("endless", "fn map(pixel, palette) { loop {} }");
+        assert!(script.run(&[Rgb([0, 0, 0])], &[]).is_err());
+    }
+}
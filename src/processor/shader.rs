@@ -0,0 +1,228 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use wgpu::naga;
+use crate::processor::guide::ProcessingStepTypes;
+
+/// The name of the directory custom effect shaders are loaded from, next to the source and output folders.
+pub const SHADER_DIRECTORY_NAME: &str = "shaders";
+
+/// The custom effect shaders, loaded once on first use.
+static EFFECTS: OnceLock<Vec<ShaderEffect>> = OnceLock::new();
+
+
+
+/// A parameter of an effect shader, filled by the user as a step of the processor guide.
+#[derive(Clone)]
+pub struct ShaderParameter {
+    /// The label of the step asking for the parameter.
+    pub label: String,
+    /// The kind of value the parameter takes (a color or a number).
+    pub step_type: ProcessingStepTypes,
+}
+
+
+
+/// A user-supplied WGSL compute shader run as a processor.
+#[derive(Clone)]
+pub struct ShaderEffect {
+    /// The name listed in the processor list.
+    pub name: String,
+    /// The description listed next to the name.
+    pub description: String,
+    /// The WGSL source of the shader.
+    pub source: String,
+    /// The parameters the shader reads, in order.
+    pub parameters: Vec<ShaderParameter>,
+    /// Why the shader cannot be used, if it failed to load or validate.
+    pub error: Option<String>,
+}
+impl ShaderEffect {
+    /// Loads an effect from a given shader and the manifest next to it (the same name with a .conf extension), if there is one.
+    fn load(shader_path: &Path) -> ShaderEffect {
+        let mut effect = ShaderEffect {
+            name: shader_path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+            description: "Custom shader".to_string(),
+            source: String::new(),
+            parameters: Vec::new(),
+            error: None,
+        };
+
+        if let Ok(manifest) = fs::read_to_string(shader_path.with_extension("conf")) {
+            let manifest = parse_manifest(&manifest);
+            if let Some(name) = manifest.name { effect.name = name; }
+            if let Some(description) = manifest.description { effect.description = description; }
+            effect.parameters = manifest.parameters;
+            effect.error = manifest.error;
+        }
+
+        match fs::read_to_string(shader_path) {
+            Ok(source) => {
+                if let Err(e) = validate(&source) { effect.error = Some(e); }
+                effect.source = source;
+            }
+            Err(e) => effect.error = Some(e.to_string()),
+        }
+        effect
+    }
+}
+
+
+
+/// The entries of the manifest of an effect shader or plugin.
+pub struct Manifest {
+    /// The name listed in the processor list, if the manifest has one.
+    pub name: Option<String>,
+    /// The description listed next to the name, if the manifest has one.
+    pub description: Option<String>,
+    /// The parameters asked for as steps, in order.
+    pub parameters: Vec<ShaderParameter>,
+    /// The last parameter that could not be read, if any.
+    pub error: Option<String>,
+}
+
+
+
+/// Reads a manifest, which holds `name`, `description` and any number of `parameter = Label: color|number` lines.
+pub fn parse_manifest(manifest: &str) -> Manifest {
+    let mut parsed = Manifest { name: None, description: None, parameters: Vec::new(), error: None };
+    for line in manifest.lines() {
+        // skips blank lines and comments
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') { continue; }
+
+        let Some((key, value)) = line.split_once('=') else { continue; };
+        match key.trim() {
+            "name" => parsed.name = Some(value.trim().to_string()),
+            "description" => parsed.description = Some(value.trim().to_string()),
+            "parameter" => match parse_parameter(value) {
+                Some(parameter) => parsed.parameters.push(parameter),
+                None => parsed.error = Some(format!("invalid parameter: {}", value.trim())),
+            },
+            _ => {}
+        }
+    }
+    parsed
+}
+
+/// Parses a parameter from a manifest value (e.g. "Levels: number").
+fn parse_parameter(value: &str) -> Option<ShaderParameter> {
+    let (label, kind) = value.rsplit_once(':')?;
+    let step_type = match kind.trim().to_lowercase().as_str() {
+        "color" => ProcessingStepTypes::Color,
+        "number" => ProcessingStepTypes::Number,
+        _ => return None,
+    };
+    Some(ShaderParameter { label: label.trim().to_string(), step_type })
+}
+
+/// Checks if a given WGSL source compiles and has the compute entry point and buffers effects are dispatched with.
+fn validate(source: &str) -> Result<(), String> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| e.emit_to_string(source).trim().to_string())?;
+    naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::all())
+        .validate(&module)
+        .map_err(|e| e.emit_to_string(source).trim().to_string())?;
+
+    let Some(main) = module.entry_points.iter().find(|entry_point| entry_point.name == "main" && entry_point.stage == naga::ShaderStage::Compute) else {
+        return Err("missing a @compute fn main".to_string());
+    };
+    if main.workgroup_size != [8, 8, 1] { return Err("fn main must use @workgroup_size(8, 8, 1)".to_string()); }
+
+    for (_, global) in module.global_variables.iter() {
+        let Some(binding) = &global.binding else { continue; };
+        let name = global.name.clone().unwrap_or_default();
+        if binding.group != 0 { return Err(format!("{} must be in @group(0)", name)); }
+        let expected = match binding.binding {
+            0 => "var<uniform> of at most 16 bytes (e.g. vec4<u32>)",
+            1 => "var<storage, read> array<vec4<f32>>",
+            2 => "var<storage, read> array<u32>",
+            3 => "var<storage, read_write> array<u32>",
+            _ => return Err(format!("{} must use one of the bindings 0 to 3", name)),
+        };
+        if !is_binding_valid(&module, global, binding.binding) { return Err(format!("@binding({}) {} must be a {}", binding.binding, name, expected)); }
+    }
+    Ok(())
+}
+
+/// Returns if a global of a shader has the address space and type of a given binding of the buffers effects are dispatched with.
+fn is_binding_valid(module: &naga::Module, global: &naga::GlobalVariable, binding: u32) -> bool {
+    let inner = &module.types[global.ty].inner;
+    // runtime sized arrays of a given element type, e.g. array<u32>
+    let is_array_of = |element: &naga::TypeInner| matches!(inner,
+        naga::TypeInner::Array { base, size: naga::ArraySize::Dynamic, .. } if module.types[*base].inner == *element);
+    let u32_scalar = naga::TypeInner::Scalar(naga::Scalar::U32);
+    let f32_vector = naga::TypeInner::Vector { size: naga::VectorSize::Quad, scalar: naga::Scalar::F32 };
+    let is_read_only = |access: &naga::StorageAccess| !access.contains(naga::StorageAccess::STORE);
+
+    match (binding, global.space) {
+        (0, naga::AddressSpace::Uniform) => inner.size(module.to_ctx()) <= 16,
+        (1, naga::AddressSpace::Storage { access }) => is_read_only(&access) && is_array_of(&f32_vector),
+        (2, naga::AddressSpace::Storage { access }) => is_read_only(&access) && is_array_of(&u32_scalar),
+        (3, naga::AddressSpace::Storage { .. }) => is_array_of(&u32_scalar),
+        _ => false,
+    }
+}
+
+/// Returns the path of the shader directory.
+fn shader_directory() -> PathBuf {
+    std::env::current_dir().expect("Could not get local working directory.").join(SHADER_DIRECTORY_NAME)
+}
+
+/// Returns every effect shader in the shader directory, ordered by filename.
+pub fn get_effects() -> &'static Vec<ShaderEffect> {
+    EFFECTS.get_or_init(|| {
+        let Ok(entries) = fs::read_dir(shader_directory()) else { return Vec::new(); };
+        let mut shader_paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("wgsl")))
+            .collect();
+        shader_paths.sort();
+        shader_paths.iter().map(|shader_path| ShaderEffect::load(shader_path)).collect()
+    })
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The bindings effects are dispatched with, as documented in the README.
+    const BINDINGS: &str = "
+        @group(0) @binding(0) var<uniform> dimensions: vec4<u32>;
+        @group(0) @binding(1) var<storage, read> parameters: array<vec4<f32>>;
+        @group(0) @binding(2) var<storage, read> pixels: array<u32>;
+        @group(0) @binding(3) var<storage, read_write> shader_results: array<u32>;
+    ";
+
+    /// An entry point copying every pixel.
+    const MAIN: &str = "
+        @compute @workgroup_size(8, 8, 1)
+        fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+            let index = global_id.y * dimensions.x + global_id.x;
+            shader_results[index] = pixels[index] + u32(parameters[0].x);
+        }
+    ";
+
+    #[test]
+    fn shaders_with_the_documented_layout_are_valid() {
+        assert_eq!(validate(&format!("{}{}", BINDINGS, MAIN)), Ok(()));
+    }
+
+    #[test]
+    fn shaders_with_other_bindings_are_rejected() {
+        let wrong_type = BINDINGS.replace("var<storage, read> pixels: array<u32>", "var<storage, read> pixels: array<f32>");
+        let writable_input = BINDINGS.replace("var<storage, read> pixels", "var<storage, read_write> pixels");
+        let other_group = BINDINGS.replace("@group(0) @binding(3)", "@group(1) @binding(3)");
+        let extra_binding = format!("{}@group(0) @binding(4) var<storage, read> extra: array<u32>;", BINDINGS);
+        for bindings in [wrong_type, writable_input, other_group, extra_binding] {
+            assert!(validate(&format!("{}{}", bindings, MAIN)).is_err(), "{}", bindings);
+        }
+    }
+
+    #[test]
+    fn shaders_with_another_workgroup_size_are_rejected() {
+        assert!(validate(&format!("{}{}", BINDINGS, MAIN.replace("@workgroup_size(8, 8, 1)", "@workgroup_size(16, 16, 1)"))).is_err());
+    }
+}
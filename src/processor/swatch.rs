@@ -0,0 +1,244 @@
+use image::Rgb;
+use crate::processor::color_space::{from_hsv, from_lab};
+
+/// Returns if a palette file is an Adobe swatch file (.ase or .aco), which is binary and read by parse_adobe_swatches().
+pub fn is_adobe_swatch_file(extension: &str) -> bool {
+    extension.eq_ignore_ascii_case("ase") || extension.eq_ignore_ascii_case("aco")
+}
+
+/// Returns the colors of an Adobe Swatch Exchange (.ase) or Photoshop color swatch (.aco) file in order.
+/// Swatches in color models that can't be converted to sRGB (e.g. spot color libraries) are left out.
+pub fn parse_adobe_swatches(bytes: &[u8]) -> Vec<Rgb<u8>> {
+    if bytes.starts_with(b"ASEF") { parse_ase(bytes) } else { parse_aco(bytes) }
+}
+
+
+
+/// Reads big-endian values from the bytes of a swatch file, returning None past its end.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+impl<'a> Reader<'a> {
+    /// Creates a reader at the start of the bytes.
+    fn new(bytes: &'a [u8]) -> Reader<'a> {
+        Reader { bytes, position: 0 }
+    }
+
+    /// Reads the next bytes.
+    fn take(&mut self, length: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(length)?;
+        let taken = self.bytes.get(self.position..end)?;
+        self.position = end;
+        Some(taken)
+    }
+
+    /// Reads the next unsigned 16-bit value.
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Reads the next unsigned 32-bit value.
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4).map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads the next 32-bit float.
+    fn f32(&mut self) -> Option<f32> {
+        self.u32().map(f32::from_bits)
+    }
+}
+
+
+
+/// Returns the colors of a Photoshop color swatch file.
+/// The file starts with a version 1 section and usually repeats the colors with their names in a version 2 section, only the first section is read.
+fn parse_aco(bytes: &[u8]) -> Vec<Rgb<u8>> {
+    let mut reader = Reader::new(bytes);
+    let (Some(version), Some(count)) = (reader.u16(), reader.u16()) else { return Vec::new(); };
+    if version != 1 && version != 2 { return Vec::new(); }
+
+    let mut colors = Vec::new();
+    for _ in 0..count {
+        let (Some(space), Some(w), Some(x), Some(y), Some(z)) = (reader.u16(), reader.u16(), reader.u16(), reader.u16(), reader.u16()) else { break; };
+        // version 2 entries are followed by their name in UTF-16, counted in characters
+        if version == 2 {
+            let Some(name_length) = reader.u32() else { break; };
+            if reader.take(name_length as usize * 2).is_none() { break; }
+        }
+        if let Some(color) = aco_color(space, [w, x, y, z]) { colors.push(color); }
+    }
+    colors
+}
+
+/// Converts the channels of a Photoshop color swatch to sRGB.
+fn aco_color(space: u16, channels: [u16; 4]) -> Option<Rgb<u8>> {
+    let [w, x, y, z] = channels;
+    let unit = |channel: u16| channel as f32 / 65535.0;
+    match space {
+        // RGB
+        0 => Some(Rgb([w, x, y].map(|channel| (channel / 257) as u8))),
+        // HSB
+        1 => Some(from_hsv([unit(w) * 360.0, unit(x), unit(y)])),
+        // CMYK, where 0 is full ink
+        2 => Some(from_cmyk([1.0 - unit(w), 1.0 - unit(x), 1.0 - unit(y), 1.0 - unit(z)])),
+        // Lab, with the lightness from 0 to 10000 and a and b as signed hundredths
+        7 => Some(from_lab([w as f32 / 100.0, x as i16 as f32 / 100.0, y as i16 as f32 / 100.0])),
+        // grayscale from 0 to 10000, counted like ink
+        8 => Some(from_gray(1.0 - (w as f32 / 10000.0).min(1.0))),
+        _ => None,
+    }
+}
+
+/// Returns the colors of an Adobe Swatch Exchange file, including the colors inside groups.
+fn parse_ase(bytes: &[u8]) -> Vec<Rgb<u8>> {
+    let mut reader = Reader::new(bytes);
+    // skips the signature and the version
+    if reader.take(8).is_none() { return Vec::new(); }
+    let Some(block_count) = reader.u32() else { return Vec::new(); };
+
+    let mut colors = Vec::new();
+    for _ in 0..block_count {
+        let (Some(block_type), Some(block_length)) = (reader.u16(), reader.u32()) else { break; };
+        let Some(block) = reader.take(block_length as usize) else { break; };
+        // group starts and ends carry no colors
+        if block_type != 0x0001 { continue; }
+        if let Some(color) = ase_color(block) { colors.push(color); }
+    }
+    colors
+}
+
+/// Converts a color entry block of an Adobe Swatch Exchange file to sRGB.
+fn ase_color(block: &[u8]) -> Option<Rgb<u8>> {
+    let mut reader = Reader::new(block);
+    let name_length = reader.u16()?;
+    reader.take(name_length as usize * 2)?;
+    let model = reader.take(4)?;
+
+    match model {
+        b"RGB " => Some(Rgb([reader.f32()?, reader.f32()?, reader.f32()?].map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8))),
+        b"CMYK" => Some(from_cmyk([reader.f32()?, reader.f32()?, reader.f32()?, reader.f32()?])),
+        // the lightness is stored from 0 to 1
+        b"LAB " => Some(from_lab([reader.f32()? * 100.0, reader.f32()?, reader.f32()?])),
+        b"Gray" => Some(from_gray(reader.f32()?)),
+        _ => None,
+    }
+}
+
+/// Converts a CMYK color (0-1) to sRGB naively, since swatch files carry no print profile.
+fn from_cmyk(cmyk: [f32; 4]) -> Rgb<u8> {
+    let [c, m, y, k] = cmyk.map(|channel| channel.clamp(0.0, 1.0));
+    Rgb([c, m, y].map(|channel| ((1.0 - channel) * (1.0 - k) * 255.0).round() as u8))
+}
+
+/// Converts a gray level (0: black, 1: white) to sRGB.
+fn from_gray(level: f32) -> Rgb<u8> {
+    let channel = (level.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Rgb([channel, channel, channel])
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns an Adobe Swatch Exchange file holding the given blocks.
+    fn ase_file(blocks: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = b"ASEF".to_vec();
+        bytes.extend([0, 1, 0, 0]);
+        bytes.extend((blocks.len() as u32).to_be_bytes());
+        blocks.iter().for_each(|block| bytes.extend(block));
+        bytes
+    }
+
+    /// Returns a block of an Adobe Swatch Exchange file with a given type and contents.
+    fn ase_block(block_type: u16, contents: &[u8]) -> Vec<u8> {
+        let mut bytes = block_type.to_be_bytes().to_vec();
+        bytes.extend((contents.len() as u32).to_be_bytes());
+        bytes.extend(contents);
+        bytes
+    }
+
+    /// Returns a name as it is written in swatch files, in UTF-16 with a closing zero and without its length.
+    fn utf16_name(name: &str) -> Vec<u8> {
+        name.encode_utf16().chain([0]).flat_map(|character| character.to_be_bytes()).collect()
+    }
+
+    /// Returns the contents of a color entry block with a given name, color model and channels.
+    fn ase_color_entry(name: &str, model: &[u8; 4], channels: &[f32]) -> Vec<u8> {
+        let mut bytes = ((name.chars().count() + 1) as u16).to_be_bytes().to_vec();
+        bytes.extend(utf16_name(name));
+        bytes.extend(model);
+        channels.iter().for_each(|channel| bytes.extend(channel.to_be_bytes()));
+        // global color
+        bytes.extend([0, 0]);
+        bytes
+    }
+
+    /// Returns a section of a Photoshop color swatch file with the given color spaces and channels, named in version 2 sections.
+    fn aco_section(version: u16, swatches: &[(u16, [u16; 4], &str)]) -> Vec<u8> {
+        let mut bytes = version.to_be_bytes().to_vec();
+        bytes.extend((swatches.len() as u16).to_be_bytes());
+        for (space, channels, name) in swatches {
+            bytes.extend(space.to_be_bytes());
+            channels.iter().for_each(|channel| bytes.extend(channel.to_be_bytes()));
+            if version == 2 {
+                bytes.extend(((name.chars().count() + 1) as u32).to_be_bytes());
+                bytes.extend(utf16_name(name));
+            }
+        }
+        bytes
+    }
+
+    /// The swatches of the Photoshop fixtures: RGB orange, HSB red, CMYK red, Lab white, a gray at half ink and a Pantone swatch that can't be converted.
+    const ACO_SWATCHES: [(u16, [u16; 4], &str); 6] = [
+        (0, [65535, 32896, 0, 0], "Orange"),
+        (1, [0, 65535, 65535, 0], "HSB Red"),
+        (2, [65535, 0, 0, 65535], "CMYK Red"),
+        (7, [10000, 0, 0, 0], "White"),
+        (8, [5000, 0, 0, 0], "Gray"),
+        (3, [1, 2, 3, 4], "PANTONE 185 C"),
+    ];
+
+    /// The colors the Photoshop fixtures convert to.
+    const ACO_COLORS: [Rgb<u8>; 5] = [Rgb([255, 128, 0]), Rgb([255, 0, 0]), Rgb([255, 0, 0]), Rgb([255, 255, 255]), Rgb([128, 128, 128])];
+
+    #[test]
+    fn ase_colors_inside_groups_are_read() {
+        let bytes = ase_file(&[
+            ase_block(0xc001, &[&[0, 6][..], &utf16_name("Brand")].concat()),
+            ase_block(0x0001, &ase_color_entry("Orange", b"RGB ", &[1.0, 0.5, 0.0])),
+            ase_block(0x0001, &ase_color_entry("Red", b"CMYK", &[0.0, 1.0, 1.0, 0.0])),
+            ase_block(0xc002, &[]),
+            ase_block(0x0001, &ase_color_entry("Gray", b"Gray", &[0.5])),
+            ase_block(0x0001, &ase_color_entry("White", b"LAB ", &[1.0, 0.0, 0.0])),
+        ]);
+        assert_eq!(parse_adobe_swatches(&bytes), vec![Rgb([255, 128, 0]), Rgb([255, 0, 0]), Rgb([128, 128, 128]), Rgb([255, 255, 255])]);
+    }
+
+    #[test]
+    fn ase_blocks_past_the_end_are_ignored() {
+        let mut bytes = ase_file(&[ase_block(0x0001, &ase_color_entry("Orange", b"RGB ", &[1.0, 0.5, 0.0]))]);
+        // claims a second block that is cut off
+        bytes[11] = 2;
+        bytes.extend([0, 1, 0, 0, 0, 64, 0]);
+        assert_eq!(parse_adobe_swatches(&bytes), vec![Rgb([255, 128, 0])]);
+    }
+
+    #[test]
+    fn aco_version_1_converts_every_color_space() {
+        assert_eq!(parse_adobe_swatches(&aco_section(1, &ACO_SWATCHES)), ACO_COLORS.to_vec());
+    }
+
+    #[test]
+    fn aco_version_2_skips_the_names() {
+        assert_eq!(parse_adobe_swatches(&aco_section(2, &ACO_SWATCHES)), ACO_COLORS.to_vec());
+    }
+
+    #[test]
+    fn aco_only_reads_the_first_section() {
+        let bytes = [aco_section(1, &ACO_SWATCHES), aco_section(2, &ACO_SWATCHES)].concat();
+        assert_eq!(parse_adobe_swatches(&bytes), ACO_COLORS.to_vec());
+    }
+}
@@ -0,0 +1,71 @@
+use image::Rgb;
+use crate::processor::color_space::{from_hsv, to_hsv};
+
+
+
+/// Returns a color with its hue rotated by a given number of degrees around the color wheel, keeping its saturation and value.
+pub fn get_hue_rotated_color(color: &Rgb<u8>, degrees: f32) -> Rgb<u8> {
+    let [hue, saturation, value] = to_hsv(color);
+    from_hsv([(hue + degrees).rem_euclid(360.0), saturation, value])
+}
+
+/// Returns a given number of tints of a color, evenly spaced from the color towards white (excluding both), from dark to light.
+pub fn get_tints(color: &Rgb<u8>, count: usize) -> Vec<Rgb<u8>> {
+    get_blends(color, &Rgb([255, 255, 255]), count)
+}
+
+/// Returns a given number of shades of a color, evenly spaced from the color towards black (excluding both), from light to dark.
+pub fn get_shades(color: &Rgb<u8>, count: usize) -> Vec<Rgb<u8>> {
+    get_blends(color, &Rgb([0, 0, 0]), count)
+}
+
+/// Returns a given number of colors evenly spaced between two colors (excluding both), from the first to the second.
+fn get_blends(from: &Rgb<u8>, to: &Rgb<u8>, count: usize) -> Vec<Rgb<u8>> {
+    (1..=count).map(|step| {
+        let weight = step as f32 / (count + 1) as f32;
+        Rgb([0, 1, 2].map(|channel| (from[channel] as f32 + (to[channel] as f32 - from[channel] as f32) * weight).round() as u8))
+    }).collect()
+}
+
+
+
+/// The list of color harmonies companion colors can be generated from a seed color with.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Harmonies {
+    /// The opposite color on the color wheel.
+    Complementary,
+    /// Two colors spaced evenly around the color wheel.
+    Triadic,
+    /// The two neighbouring colors on the color wheel.
+    Analogous,
+    /// The two neighbours of the opposite color on the color wheel.
+    SplitComplementary,
+}
+impl Harmonies {
+    /// Returns the name of a given harmony.
+    pub fn name(&self) -> String {
+        match self {
+            Harmonies::Complementary => "Complementary".to_string(),
+            Harmonies::Triadic => "Triadic".to_string(),
+            Harmonies::Analogous => "Analogous".to_string(),
+            Harmonies::SplitComplementary => "Split-Complementary".to_string(),
+        }
+    }
+
+    /// Returns the hue offsets (in degrees) of the companion colors of a given harmony.
+    fn hue_offsets(&self) -> Vec<f32> {
+        match self {
+            Harmonies::Complementary => vec![180.0],
+            Harmonies::Triadic => vec![120.0, 240.0],
+            Harmonies::Analogous => vec![-30.0, 30.0],
+            Harmonies::SplitComplementary => vec![150.0, 210.0],
+        }
+    }
+
+    /// Returns a seed color followed by its companion colors in a given harmony.
+    pub fn colors(&self, seed_color: &Rgb<u8>) -> Vec<Rgb<u8>> {
+        let mut colors = vec![*seed_color];
+        colors.extend(self.hue_offsets().iter().map(|offset| get_hue_rotated_color(seed_color, *offset)));
+        colors
+    }
+}
@@ -0,0 +1,111 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use image::Rgb;
+use crate::processor::palette_file::load_palette_definition;
+
+/// The name of the directory palette definition files are loaded from, next to the source and output folders.
+/// Synced palette collections live in folders inside it.
+pub const PALETTE_DIRECTORY_NAME: &str = "palettes";
+
+/// The extensions of the files in the palette directory that are loaded as palettes.
+fn palette_extensions() -> [&'static str; 6] { ["hex", "txt", "json", "gpl", "ase", "aco"] }
+
+/// The palettes from the palette directory, loaded once on first use.
+static USER_PALETTES: OnceLock<Vec<UserPalette>> = OnceLock::new();
+
+
+
+/// A palette definition file from the palette directory listed as a processor.
+#[derive(Clone)]
+pub struct UserPalette {
+    /// The name listed in the processor list.
+    pub name: String,
+    /// The description listed next to the name.
+    pub description: String,
+    /// The folders the file is nested in inside the palette directory (e.g. "Retro / Nintendo"), which group it in the processor list,
+    /// or None for files right in the palette directory.
+    pub category: Option<String>,
+    /// Who made the palette, if the file says so.
+    pub author: Option<String>,
+    /// The colors of the palette, in order.
+    pub colors: Vec<Rgb<u8>>,
+    /// Whether each color of the palette is biased.
+    pub bias_flags: Vec<bool>,
+    /// Why the palette cannot be used, if it failed to load.
+    pub error: Option<String>,
+}
+impl UserPalette {
+    /// Loads a palette from a given definition file in a given palette directory.
+    /// The name, author and description are read from the file (e.g. `name = Dusk`, `"name": "Dusk"` or GIMP's `Name: Dusk`), and the name defaults to the filename.
+    fn load(palette_path: &Path, directory: &Path) -> UserPalette {
+        let filename = palette_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let category = get_category(palette_path, directory);
+        match load_palette_definition(palette_path) {
+            Ok(palette_file) => {
+                let (colors, bias_flags) = palette_file.get_flagged_colors().into_iter().unzip();
+                UserPalette {
+                    name: palette_file.name.unwrap_or(filename),
+                    description: palette_file.description.unwrap_or_else(|| "Custom palette".to_string()),
+                    category,
+                    author: palette_file.author,
+                    colors,
+                    bias_flags,
+                    error: None,
+                }
+            }
+            Err(e) => UserPalette {
+                name: filename,
+                description: "Custom palette".to_string(),
+                category,
+                author: None,
+                colors: Vec::new(),
+                bias_flags: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+
+
+/// Returns the folders a palette file is nested in inside a palette directory joined into a category (e.g. "Retro / Nintendo"),
+/// or None if it is right in the palette directory.
+fn get_category(palette_path: &Path, directory: &Path) -> Option<String> {
+    let folders = palette_path.strip_prefix(directory).ok()?.parent()?;
+    let folders: Vec<String> = folders.components().map(|folder| folder.as_os_str().to_string_lossy().to_string()).collect();
+    (!folders.is_empty()).then(|| folders.join(" / "))
+}
+
+/// Returns the path of the palette directory.
+pub fn palette_directory() -> PathBuf {
+    std::env::current_dir().expect("Could not get local working directory.").join(PALETTE_DIRECTORY_NAME)
+}
+
+/// Returns every palette definition file in a directory and its folders, ignoring hidden files (e.g. the .git folder of a synced collection) and linked folders.
+fn get_palette_paths(directory: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else { return Vec::new(); };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .flat_map(|entry| {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else { return Vec::new(); };
+            if file_type.is_dir() { return get_palette_paths(&path); }
+            // linked folders are skipped, since a link back to a folder above it would be read forever
+            if file_type.is_symlink() && path.is_dir() { return Vec::new(); }
+            let is_palette = path.extension().is_some_and(|extension| palette_extensions().iter().any(|palette_extension| extension.eq_ignore_ascii_case(palette_extension)));
+            if is_palette { vec![path] } else { Vec::new() }
+        })
+        .collect()
+}
+
+/// Returns every palette in the palette directory and its collection folders, ordered by path so the palettes of a category stay together.
+pub fn get_user_palettes() -> &'static Vec<UserPalette> {
+    USER_PALETTES.get_or_init(|| {
+        let directory = palette_directory();
+        let mut palette_paths = get_palette_paths(&directory);
+        palette_paths.sort();
+        palette_paths.iter().map(|palette_path| UserPalette::load(palette_path, &directory)).collect()
+    })
+}
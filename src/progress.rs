@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::mpsc::{channel, Sender};
+use std::thread;
+use std::thread::JoinHandle;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use crate::pipeline::FrameProgress;
+use crate::locale::{tr, trf};
+
+/// The channel progress events are reported over while a consumer is running.
+static PROGRESS_SENDER: Mutex<Option<Sender<ProgressEvents>>> = Mutex::new(None);
+
+
+
+/// The steps of processing reported while it runs.
+#[derive(Clone)]
+pub enum ProgressEvents {
+    /// The palettes are being built from the base colors.
+    LoadingColors,
+    /// The palettes are built with the given number of colors and pixels are being mapped onto them.
+    SpectrumBuilt(usize),
+    /// A tile of rows of the image is mapped (the number of finished tiles and the total number of tiles).
+    TileDone(usize, usize),
+    /// An animation or video job progressed.
+    FramesDone(FrameProgress),
+    /// The result is saved to the given path.
+    Saved(PathBuf),
+}
+
+
+
+/// Reports a progress event to the running consumer, if there is one.
+pub fn report(event: ProgressEvents) {
+    if let Some(sender) = PROGRESS_SENDER.lock().unwrap().as_ref() {
+        let _ = sender.send(event);
+    }
+}
+
+
+
+/// Consumes the reported progress events on its own thread (e.g. drawing a gauge in the terminal) until it is dropped.
+pub struct ProgressConsumer {
+    /// The thread the events are consumed on.
+    consumer: Option<JoinHandle<()>>,
+}
+impl ProgressConsumer {
+    /// Starts passing every reported progress event to a given consumer.
+    pub fn start(mut consume: impl FnMut(ProgressEvents) + Send + 'static) -> ProgressConsumer {
+        let (sender, receiver) = channel();
+        *PROGRESS_SENDER.lock().unwrap() = Some(sender);
+        let consumer = thread::spawn(move || {
+            for event in receiver { consume(event); }
+        });
+        ProgressConsumer { consumer: Some(consumer) }
+    }
+}
+impl Drop for ProgressConsumer {
+    /// Closes the channel and waits until every reported event is consumed.
+    fn drop(&mut self) {
+        PROGRESS_SENDER.lock().unwrap().take();
+        if let Some(consumer) = self.consumer.take() { let _ = consumer.join(); }
+    }
+}
+
+
+
+/// Creates the progress bar of headless runs, which is drawn to stderr and only shown when stderr is a terminal.
+fn create_progress_bar() -> ProgressBar {
+    let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr());
+    bar.set_style(ProgressStyle::with_template("[{bar:30}] {percent:>3}% {msg}").expect("The progress bar template is valid.").progress_chars("#-"));
+    bar
+}
+
+/// Starts printing every reported progress event to stderr as lines and a redrawn progress bar, for runs without the terminal interface.
+pub fn start_progress_bar() -> ProgressConsumer {
+    let mut progress_bar: Option<ProgressBar> = None;
+    ProgressConsumer::start(move |event| {
+        let progress = match &event {
+            ProgressEvents::TileDone(tile, tile_count) => Some((Some(*tile_count), *tile, trf("Tile {}/{}", &[tile.to_string(), tile_count.to_string()]))),
+            ProgressEvents::FramesDone(progress) => Some((progress.frame_count, progress.encoded_count, match progress.frame_count {
+                Some(frame_count) => trf("Frame {}/{}", &[progress.current_frame().to_string(), frame_count.to_string()]),
+                None => trf("Frame {}", &[progress.current_frame().to_string()]),
+            })),
+            _ => None,
+        };
+
+        // the bar is redrawn in place, while every other step gets its own line below it
+        if let Some((length, position, message)) = progress {
+            let bar = progress_bar.get_or_insert_with(create_progress_bar);
+            match length {
+                Some(length) => bar.set_length(length as u64),
+                None => bar.unset_length(),
+            }
+            bar.set_position(position as u64);
+            bar.set_message(message);
+            return;
+        }
+        if let Some(bar) = progress_bar.take() { bar.finish(); }
+        eprintln!("{}", match event {
+            ProgressEvents::LoadingColors => tr("Loading colors..."),
+            // effects have no palette to count
+            ProgressEvents::SpectrumBuilt(0) => tr("Processing..."),
+            ProgressEvents::SpectrumBuilt(color_count) => trf("Mapping pixels onto {} colors...", &[color_count.to_string()]),
+            ProgressEvents::Saved(path) => trf("Saved to {}", &[path.to_string_lossy().to_string()]),
+            ProgressEvents::TileDone(_, _) | ProgressEvents::FramesDone(_) => return,
+        });
+    })
+}
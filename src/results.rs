@@ -0,0 +1,124 @@
+use std::fs::OpenOptions;
+use std::io::{Result, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+use image::Rgb;
+use serde::Serialize;
+use oxide::pipeline::FrameProgress;
+use oxide::processor::palette::as_hex;
+
+/// Gets the number of most used colors listed in result records.
+fn top_color_count() -> usize { 8 }
+
+
+
+/// Where machine-readable result records are written to.
+#[derive(Clone)]
+pub enum ResultOutputs {
+    /// Every record is printed to stdout on its own line.
+    Stdout,
+    /// Every record is appended to the given file on its own line.
+    File(PathBuf),
+}
+impl ResultOutputs {
+    /// Writes a record as a line of JSON (JSON Lines), so records can be parsed one by one while a run is still going.
+    pub fn write(&self, record: &ResultRecord) -> Result<()> {
+        match self {
+            ResultOutputs::Stdout => {
+                println!("{}", record.to_json());
+                Ok(())
+            }
+            ResultOutputs::File(path) => {
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{}", record.to_json())
+            }
+        }
+    }
+}
+
+
+
+/// The outcome of processing a single source image, for downstream tooling.
+pub struct ResultRecord {
+    /// The path of the source image.
+    pub input_path: PathBuf,
+    /// Where the result is saved, or why it could not be processed.
+    pub result: std::result::Result<PathBuf, String>,
+    /// The name of the processor.
+    pub processor: String,
+    /// The inputs of every step of the processor.
+    pub inputs: Vec<String>,
+    /// The number of different colors in the result.
+    pub color_count: usize,
+    /// The most used colors of the result with the share of the image they cover (0-100).
+    pub top_colors: Vec<(Rgb<u8>, f64)>,
+    /// How long processing took.
+    pub duration: Duration,
+    /// The adjustments made to stay within the memory budgets, e.g. palettizing in tiles.
+    pub adjustments: Vec<String>,
+    /// How far the frames of a video, gif or image sequence got, for results with frames.
+    pub frames: Option<FrameProgress>,
+    /// The colors of the palettes for dry runs, which only build the palettes instead of mapping and saving the image.
+    pub dry_run_palette: Option<Vec<Rgb<u8>>>,
+}
+impl ResultRecord {
+    /// Returns the record of an image that could not be processed before the processor ran, e.g. because its inputs are invalid.
+    pub fn failed(input_path: PathBuf, processor: String, message: String) -> ResultRecord {
+        ResultRecord { input_path, result: Err(message), processor, inputs: Vec::new(), color_count: 0, top_colors: Vec::new(), duration: Duration::ZERO, adjustments: Vec::new(), frames: None, dry_run_palette: None }
+    }
+
+    /// Returns the record as a single line JSON object.
+    pub fn to_json(&self) -> String {
+        let json_record = JsonRecord {
+            input: self.input_path.to_string_lossy().to_string(),
+            output: self.result.as_ref().ok().map(|path| path.to_string_lossy().to_string()),
+            processor: &self.processor,
+            inputs: &self.inputs,
+            colors_used: self.color_count,
+            top_colors: self.top_colors.iter().take(top_color_count())
+                .map(|(color, percent)| JsonTopColor { color: as_hex(color), percent: (percent * 100.0).round() / 100.0 })
+                .collect(),
+            duration_ms: self.duration.as_millis(),
+            adjustments: &self.adjustments,
+            frames: self.frames.as_ref().map(|frames| JsonFrames { done: frames.encoded_count, total: frames.frame_count }),
+            dry_run: self.dry_run_palette.is_some(),
+            palette: self.dry_run_palette.iter().flatten().map(as_hex).collect(),
+            error: self.result.as_ref().err(),
+        };
+        serde_json::to_string(&json_record).expect("Result records only hold values JSON can represent.")
+    }
+
+}
+
+
+
+/// The fields of a result record as they are written to JSON.
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    input: String,
+    output: Option<String>,
+    processor: &'a String,
+    inputs: &'a Vec<String>,
+    colors_used: usize,
+    top_colors: Vec<JsonTopColor>,
+    duration_ms: u128,
+    adjustments: &'a Vec<String>,
+    frames: Option<JsonFrames>,
+    dry_run: bool,
+    palette: Vec<String>,
+    error: Option<&'a String>,
+}
+
+/// One of the most used colors of a result record as it is written to JSON, with its share rounded to two decimals.
+#[derive(Serialize)]
+struct JsonTopColor {
+    color: String,
+    percent: f64,
+}
+
+/// How far the frames of a result record got as it is written to JSON, where the total is null if it was not known up front.
+#[derive(Serialize)]
+struct JsonFrames {
+    done: usize,
+    total: Option<usize>,
+}
@@ -0,0 +1,88 @@
+use std::fs;
+use std::io::{Error, Result};
+use std::path::{Path, PathBuf};
+use image::{DynamicImage, ImageBuffer, ImageResult, Rgb, RgbImage};
+use crate::pipeline::{run_pipeline, FrameProgress};
+use crate::processor::compute::{Gpu, PaletteMapping, ThresholdMask};
+use crate::video::save_image;
+
+/// Gets the max width and height of each sampled frame in the reference image of a sequence.
+fn max_sample_size() -> u32 { 1024 }
+
+
+
+/// Splits the name of a numbered frame (e.g. "shot_0042") into the name of its sequence ("shot_") and its number.
+fn split_frame_name(path: &Path) -> Option<(String, String)> {
+    let stem = path.file_stem()?.to_string_lossy().to_string();
+    let prefix = stem.trim_end_matches(|character: char| character.is_ascii_digit());
+    if prefix.len() == stem.len() { return None; }
+    Some((prefix.to_string(), stem[prefix.len()..].to_string()))
+}
+
+/// Returns the readable name of the sequence a given frame belongs to (e.g. "shot").
+pub fn get_sequence_name(path: &Path) -> Option<String> {
+    let (prefix, _) = split_frame_name(path)?;
+    let name = prefix.trim_end_matches([' ', '_', '-', '.']).to_string();
+    Some(if name.is_empty() { "sequence".to_string() } else { name })
+}
+
+/// Returns every frame of the numbered image sequence a given image belongs to, ordered by number.
+/// Frames share the name without the trailing number and the extension, and a sequence needs at least two of them.
+pub fn get_sequence_frames(path: &Path) -> Vec<PathBuf> {
+    let Some((prefix, _)) = split_frame_name(path) else { return Vec::new(); };
+    let Some(directory) = path.parent() else { return Vec::new(); };
+    let Ok(entries) = fs::read_dir(directory) else { return Vec::new(); };
+
+    let mut frames: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|frame| frame.extension() == path.extension())
+        .filter(|frame| split_frame_name(frame).is_some_and(|(frame_prefix, _)| frame_prefix == prefix))
+        .collect();
+    frames.sort_by_key(|frame| split_frame_name(frame).and_then(|(_, number)| number.parse::<u64>().ok()));
+
+    if frames.len() < 2 { return Vec::new(); }
+    frames
+}
+
+/// Returns a single image standing in for a whole sequence by stacking evenly spaced frames on top of each other.
+/// One sample uses the middle frame, while more samples make the palette follow the union of the sampled frames.
+pub fn get_reference_image(frames: &[PathBuf], sample_count: usize) -> ImageResult<DynamicImage> {
+    let sample_count = sample_count.clamp(1, frames.len().max(1));
+    let samples = (0..sample_count)
+        .map(|sample| {
+            // evenly spaced from the middle of each part of the sequence
+            let index = (sample * 2 + 1) * frames.len() / (sample_count * 2);
+            image::open(&frames[index]).map(|frame| frame.thumbnail(max_sample_size(), max_sample_size()))
+        })
+        .collect::<ImageResult<Vec<DynamicImage>>>()?;
+
+    let width = samples.iter().map(|sample| sample.width()).max().unwrap_or(1);
+    let height = samples.iter().map(|sample| sample.height()).sum();
+    let mut reference_image = RgbImage::new(width, height);
+    let mut y = 0;
+    for sample in &samples {
+        image::imageops::replace(&mut reference_image, &sample.to_rgb8(), 0, y as i64);
+        y += sample.height();
+    }
+    Ok(DynamicImage::ImageRgb8(reference_image))
+}
+
+/// Maps every frame of a sequence, nudged by a threshold mask for ordered dithering, onto the same palettes and saves them under their original names in a given directory.
+/// Loading, gpu processing and saving overlap, and the progress is reported after each frame.
+pub fn process_sequence(frames: &[PathBuf], output_directory: &Path, palette_mapping: &PaletteMapping, mask: &ThresholdMask, on_progress: impl FnMut(&FrameProgress)) -> Result<()> {
+    fs::create_dir_all(output_directory)?;
+
+    let loaded_frames = frames.iter().map(|frame_path| {
+        let frame = image::open(frame_path).map_err(Error::other)?.to_rgb8();
+        Ok((output_directory.join(frame_path.file_name().unwrap_or_default()), frame))
+    });
+    let gpu = Gpu::new();
+    run_pipeline(
+        loaded_frames,
+        |(output_path, frame): (PathBuf, ImageBuffer<Rgb<u8>, Vec<u8>>)| Ok((output_path, palette_mapping.process_dithered_on(&gpu, &frame, mask))),
+        |(output_path, frame): (PathBuf, ImageBuffer<Rgb<u8>, Vec<u8>>)| save_image(&frame, &output_path).map_err(Error::other),
+        Some(frames.len()),
+        on_progress,
+    )
+}
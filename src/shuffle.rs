@@ -0,0 +1,86 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::convert::Infallible;
+use std::path::PathBuf;
+use crate::processor::{EditProcessor, Processors};
+use crate::processor::guide::{NumberRange, ProcessingStepTypes};
+use crate::processor::shader::get_effects;
+use crate::processor::plugin::get_plugins;
+use crate::processor::user_palette::get_user_palettes;
+
+
+
+/// Returns a random number below a given bound (which has to be at least 1).
+/// Every std hasher is seeded randomly, which is plenty for picking looks without pulling in a random number crate.
+fn random_below(bound: usize) -> usize {
+    (RandomState::new().build_hasher().finish() % bound as u64) as usize
+}
+
+/// Returns a random color as a HEX color code.
+fn random_hex() -> String {
+    format!("#{:06X}", random_below(0x1000000))
+}
+
+/// Returns a random number within a given range, a whole one if the range only takes whole numbers and one with two decimals otherwise.
+fn random_number(range: NumberRange) -> String {
+    if range.is_whole { return (range.min as usize + random_below(range.max as usize - range.min as usize + 1)).to_string(); }
+    format!("{:.2}", range.min + random_below(101) as f32 / 100.0 * (range.max - range.min))
+}
+
+/// Returns if a given processor asks for colors (or other inputs) before it can process.
+fn takes_inputs(selection: usize) -> bool {
+    let processor = Processors::get_processor(selection).create_edit(PathBuf::new());
+    processor.get_current_step_type() != ProcessingStepTypes::NoInput
+}
+
+/// Returns if a given processor can be run, which rules out palettes, custom shaders and plugins that failed to load and processors needing a file that cannot be picked at random.
+fn is_runnable(selection: usize) -> bool {
+    match Processors::get_processor(selection) {
+        Processors::UserPalette(palette) => get_user_palettes()[palette].error.is_none(),
+        Processors::Shader(effect) => get_effects()[effect].error.is_none(),
+        Processors::Plugin(plugin) => get_plugins()[plugin].error.is_none(),
+        Processors::Script(_) => false,
+        Processors::CustomPalette => false,
+        _ => true,
+    }
+}
+
+/// Picks a random processor for a given source image with all of its steps filled, returning its index in the processor list and the processor.
+/// Processors that ask for inputs get random colors and numbers within the range of their step (from 0 to 1 where a step has no range),
+/// and processors mapping onto the palettes of another one get no palette (or a random runnable processor where they need one),
+/// unless they are left out to only pick processors choosing their own colors.
+pub fn shuffle_processor(source_image_path: PathBuf, random_colors: bool) -> (usize, Box<dyn EditProcessor>) {
+    let candidates: Vec<usize> = (0..Processors::number_of_processors())
+        .filter(|selection| is_runnable(*selection) && (random_colors || !takes_inputs(*selection)))
+        .collect();
+    let selection = candidates[random_below(candidates.len())];
+
+    // the processors a palette can be taken from, by the names they are found by
+    let palette_names: Vec<String> = (0..Processors::number_of_processors())
+        .filter(|selection| is_runnable(*selection))
+        .map(|selection| Processors::get_processor(selection).name())
+        .filter(|name| Processors::find_by_name(name).is_some())
+        .collect();
+
+    // fills every step with a random value of its kind
+    let mut processor = Processors::get_processor(selection).create_edit(source_image_path);
+    let _ = processor.fill_steps(&mut |processor| {
+        let input = match processor.get_current_step_type() {
+            ProcessingStepTypes::Color => random_hex(),
+            ProcessingStepTypes::Number => random_number(processor.get_current_step_range().unwrap_or(NumberRange::between(0.0, 1.0))),
+            ProcessingStepTypes::Name => "none".to_string(),
+            ProcessingStepTypes::FilePath | ProcessingStepTypes::NoInput => String::new(),
+        };
+        processor.update_current_step_input(input);
+        // tries the names from a random one on until the step takes one, since e.g. a transform cannot take its own processor
+        if !processor.is_current_step_input_valid() && processor.get_current_step_type() == ProcessingStepTypes::Name {
+            let start = random_below(palette_names.len());
+            for name in palette_names.iter().cycle().skip(start).take(palette_names.len()) {
+                processor.update_current_step_input(name.clone());
+                if processor.is_current_step_input_valid() { break; }
+            }
+        }
+        Ok::<(), Infallible>(())
+    });
+    (selection, processor)
+}
@@ -5,29 +5,124 @@ use ratatui::widgets::*;
 use crate::app::{App, Pages};
 use crate::processor::guide::ProcessingStepTypes;
 use crate::processor::Processors;
+use crate::processor::palette::as_hex;
+use crate::processor::analysis::{luminance_bins, ToneStatistics};
+use crate::processor::color_vision::ColorVisionModes;
+use crate::processor::compute::{adapter_label, device_type_name, graphics_api_name};
+use image::{ImageBuffer, Rgb};
+use crate::pipeline::FrameProgress;
+use crate::progress::{ProgressConsumer, ProgressEvents};
+use crate::locale::{tr, trf};
+use crate::config;
+
+/// Gets the number of most used colors listed on the finished page.
+fn top_color_count() -> usize { 8 }
+
+/// Gets the number of blue planes the spectrum is sliced into on the spectrum inspection page.
+pub fn spectrum_slice_count() -> usize { 16 }
+
+/// Gets the number of red and green values combined into one pixel of a spectrum slice.
+fn spectrum_cell_size() -> usize { 4 }
+
+/// Gets the terminal width below which pages collapse their header, footer and preview.
+fn compact_width() -> u16 { 60 }
+
+/// Gets the terminal height below which pages collapse their header, footer and preview.
+fn compact_height() -> u16 { 20 }
+
+/// Gets the terminal width from which pages spread their lists, previews and details into side by side panels.
+fn wide_width() -> u16 { 140 }
+
+/// Gets the fewest rows the body keeps, so the header and footer never push out the guide input line.
+fn min_body_height() -> u16 { 3 }
+
+/// Returns if pages are rendered as plain text for screen readers.
+fn is_plain_output() -> bool { config::get().plain_output }
+
+/// Returns the borders drawn around sections, which plain output leaves out.
+fn section_borders() -> Borders {
+    if is_plain_output() { Borders::NONE } else { Borders::ALL }
+}
+
+/// The layouts pages adapt to depending on the size of the terminal.
+#[derive(Copy, Clone, PartialEq)]
+enum LayoutSizes {
+    /// Small terminals, where the header and footer are collapsed into single lines without borders and previews are hidden.
+    Compact,
+    /// The standard layout.
+    Regular,
+    /// Wide terminals, where lists, previews and details are drawn as side by side panels.
+    Wide,
+}
+impl LayoutSizes {
+    /// Returns the layout for a given terminal area, where plain output always keeps the standard layout screen readers expect.
+    fn of(area: Rect) -> LayoutSizes {
+        if is_plain_output() { return LayoutSizes::Regular; }
+        if area.width < compact_width() || area.height < compact_height() { return LayoutSizes::Compact; }
+        if area.width >= wide_width() { return LayoutSizes::Wide; }
+        LayoutSizes::Regular
+    }
+}
+
+
 
 /// Renders the current page of the application.
 pub fn render_current_page(frame: &mut Frame, app: &App) {
-    // header
-    let header_block = Block::new().borders(Borders::ALL);
-    let header = Paragraph::new(vec![
-        Line::raw("Oxide"),
-        Line::raw(app.current_page_name()),
-    ]).block(header_block);
+    let layout_size = LayoutSizes::of(frame.area());
 
-    // footer
-    let footer_height = Instruction::get_instructions_for(&app.current_page).len() as u16 + 2;
-    let footer_block = Block::new().borders(Borders::ALL);
-    let instructions = Instruction::get_instructions_for(&app.current_page);
-    let footer = Paragraph::new(instructions).block(footer_block);
+    // header, where plain output leads with the latest announcement and labels the page and compact layouts only name the page
+    let header_block = Block::new().borders(if layout_size == LayoutSizes::Compact { Borders::NONE } else { section_borders() });
+    let header_lines = if layout_size == LayoutSizes::Compact {
+        vec![Line::raw(format!("Oxide | {}", tr(&app.current_page_name())))]
+    }
+    else if is_plain_output() {
+        vec![
+            Line::raw(trf("Announcement: {}", &[app.announcement.clone().unwrap_or_default()])),
+            Line::raw(trf("Page: {}", &[tr(&app.current_page_name())])),
+            Line::raw(trf("Source: {}  Output: {}", &[app.source_directory.to_string_lossy().to_string(), app.output_directory.to_string_lossy().to_string()])),
+        ]
+    }
+    else {
+        vec![
+            Line::raw("Oxide"),
+            Line::raw(tr(&app.current_page_name())),
+            Line::raw(trf("Source: {}  Output: {}", &[app.source_directory.to_string_lossy().to_string(), app.output_directory.to_string_lossy().to_string()])),
+        ]
+    };
+    let header_height = header_lines.len() as u16 + match layout_size {
+        LayoutSizes::Compact => 0,
+        _ if is_plain_output() => 1,
+        _ => 2,
+    };
+    let header = Paragraph::new(header_lines).block(header_block);
+
+    // footer, where compact and wide layouts flow the instructions into as few lines as fit instead of groups
+    let mut instructions = Instruction::get_instructions_for(&app.current_page);
+    if is_plain_output() { instructions.insert(0, Line::raw(tr("Keys:"))); }
+    let (footer, footer_height) = match layout_size {
+        LayoutSizes::Regular => {
+            let footer_height = instructions.len() as u16 + if is_plain_output() { 0 } else { 2 };
+            (Paragraph::new(instructions).block(Block::new().borders(section_borders())), footer_height)
+        }
+        LayoutSizes::Compact | LayoutSizes::Wide => {
+            let borders = if layout_size == LayoutSizes::Compact { Borders::NONE } else { Borders::ALL };
+            let border_size = if borders == Borders::NONE { 0 } else { 2 };
+            let lines = flow_instructions(&instructions, frame.area().width.saturating_sub(border_size) as usize);
+            let footer_height = lines.len() as u16 + border_size;
+            (Paragraph::new(lines).block(Block::new().borders(borders)), footer_height)
+        }
+    };
 
     // The sections of the screen.
     let leaflets = Layout::new(Direction::Vertical, [
-        Constraint::Length(4), // header
-        Constraint::Fill(1), // body
+        Constraint::Length(header_height), // header
+        Constraint::Min(min_body_height()), // body
         Constraint::Length(footer_height), // footer
     ]).split(frame.area());
 
+    // screen readers follow the cursor, so it rests on the announcement
+    if is_plain_output() { frame.set_cursor_position(Position::new(0, 0)); }
+
     // rendering the header and footer
     frame.render_widget(header, leaflets[0]);
     frame.render_widget(footer, leaflets[2]);
@@ -36,60 +131,474 @@ pub fn render_current_page(frame: &mut Frame, app: &App) {
     // body
     match app.current_page {
         Pages::Launching => {
-            let body = Paragraph::new("Press any button to continue...");
+            let body = Paragraph::new(tr("Press any button to continue..."));
             frame.render_widget(body, leaflets[1]);
         }
 
         Pages::SelectingImageSource => {
-            let body = Paragraph::new(vec![
-                Line::raw(format!("Found {} images", app.source_image_paths.len())),
-                Line::raw(format!("In: {}", app.source_directory.to_string_lossy())),
-                Line::raw(format!("Selected image: {}", app.print_selected_image_filename())),
+            let details = Paragraph::new(vec![
+                Line::raw(trf("Found {} images", &[app.source_image_paths.len().to_string()])),
+                Line::raw(trf("In: {}", &[app.source_directory.to_string_lossy().to_string()])),
+                Line::raw(trf("Selected image: {}", &[app.print_selected_image_filename()])),
+                Line::raw(match (app.sequence_mode, app.selected_sequence_frames().len()) {
+                    (false, _) => tr("Sequence mode: off"),
+                    (true, 0) => tr("Sequence mode: on (the selected image is not part of a numbered sequence)"),
+                    (true, frame_count) => trf("Sequence mode: on ({} frames share one palette)", &[frame_count.to_string()]),
+                }),
+                Line::raw(match &app.interrupted_batch {
+                    Some(state) => trf("Interrupted batch: {} (press B to resume)", &[Processors::get_processor(state.processor_selection).name()]),
+                    None => "".to_string(),
+                }),
             ]);
+
+            // wide layouts list the source images next to the details
+            if layout_size != LayoutSizes::Wide {
+                frame.render_widget(details, leaflets[1]);
+                return;
+            }
+            let columns = Layout::new(Direction::Horizontal, [
+                Constraint::Fill(1), // list
+                Constraint::Fill(1), // details
+            ]).split(leaflets[1]);
+            let list: Vec<Line> = app.source_image_paths.iter().enumerate().map(|(index, path)| {
+                let is_selected = index == app.current_image_path_selection;
+                let line = Line::raw(format!("{} {}", if is_selected { ">" } else { " " }, path.file_name().unwrap_or_default().to_string_lossy()));
+                if is_selected { line.bold() } else { line }
+            }).collect();
+            // scrolls the list to keep the selected image in view
+            let scroll = app.current_image_path_selection.saturating_sub(columns[0].height.saturating_sub(3) as usize) as u16;
+            frame.render_widget(Paragraph::new(list).scroll((scroll, 0)).block(Block::new().borders(Borders::ALL)), columns[0]);
+            frame.render_widget(details, columns[1]);
+        }
+
+        Pages::Analyzing => {
+            let mut lines = vec![Line::raw(trf("Image: {}", &[app.print_selected_image_filename()]))];
+            if let Some(analysis) = &app.image_analysis {
+                lines.push(Line::raw(""));
+                lines.push(Line::from(vec![Span::raw(format!("{:<16}", tr("Average color") + ":")), swatch(&analysis.average_color), Span::raw(as_hex(&analysis.average_color))]));
+                lines.push(Line::from(vec![Span::raw(format!("{:<16}", tr("Accent color") + ":")), swatch(&analysis.accent_color), Span::raw(as_hex(&analysis.accent_color))]));
+                lines.push(Line::raw(""));
+                lines.extend(tone_lines(&analysis.tones, None));
+                lines.push(Line::raw(tr("Luminance distribution:")));
+                let bin_size = 256 / luminance_bins();
+                for (bin, percent) in analysis.luminance_distribution.iter().enumerate() {
+                    lines.push(Line::raw(format!("  {:>3}-{:>3} {:<20} {:>5.1}%",
+                        bin * bin_size,
+                        (bin + 1) * bin_size - 1,
+                        "█".repeat((percent / 5.0).round() as usize),
+                        percent,
+                    )));
+                }
+                lines.push(Line::raw(""));
+                lines.push(Line::raw(trf("Average saturation: {}%", &[format!("{:.1}", analysis.average_saturation * 100.0)])));
+                lines.push(Line::raw(trf("Max saturation: {}%", &[format!("{:.1}", analysis.max_saturation * 100.0)])));
+                lines.push(Line::raw(trf("Strongly saturated pixels: {}%", &[format!("{:.1}", analysis.strongly_saturated_percent)])));
+            }
+            if let Some(status_message) = &app.status_message {
+                lines.push(Line::raw(""));
+                lines.push(Line::raw(status_message.clone()));
+            }
+            let body = Paragraph::new(lines);
+            frame.render_widget(body, leaflets[1]);
+        }
+
+        Pages::ReportingDominantColors => {
+            let mut lines = vec![
+                Line::raw(trf("Image: {}", &[app.print_selected_image_filename()])),
+                Line::raw(""),
+            ];
+            for usage in &app.dominant_colors {
+                lines.push(Line::from(vec![
+                    swatch(&usage.color),
+                    Span::raw(format!("{} {:>5.1}%", as_hex(&usage.color), usage.percent)),
+                ]));
+            }
+            if let Some(status_message) = &app.status_message {
+                lines.push(Line::raw(""));
+                lines.push(Line::raw(status_message.clone()));
+            }
+            let body = Paragraph::new(lines);
             frame.render_widget(body, leaflets[1]);
         }
 
         Pages::SelectingProcessingType => {
-            let body = Paragraph::new(format!("Selected processor: {}", Processors::get_processor(app.current_processor_selection).name()));
+            let mut lines = vec![
+                Line::raw(trf("Selected processor: {}", &[Processors::get_processor(app.current_processor_selection).name()])),
+                Line::raw(match (&app.processor_search, &app.processor_number_entry) {
+                    (Some(search), _) => trf("Search: {}_", std::slice::from_ref(search)),
+                    (None, Some(entry)) => trf("Processor number: {}_", std::slice::from_ref(entry)),
+                    (None, None) => "".to_string(),
+                }),
+            ];
+
+            // the favorites are grouped above the other processors
+            let processor_line = |selection: usize| {
+                let processor = Processors::get_processor(selection);
+                let marker = if selection == app.current_processor_selection { ">" } else { " " };
+                let star = if app.favorite_processors.contains(&selection) { "★" } else { " " };
+                let line = Line::raw(format!("{} {} {:>2} {:<36} {}", marker, star, selection + 1, processor.name(), tr(&processor.description())));
+                if selection == app.current_processor_selection { line.bold() } else { line }
+            };
+            let order = app.processor_order();
+            let (favorites, others): (Vec<usize>, Vec<usize>) = order.iter().partition(|selection| app.favorite_processors.contains(selection));
+            if order.is_empty() {
+                lines.push(Line::raw(tr("No processor matches the search")));
+            }
+            if !favorites.is_empty() {
+                lines.push(Line::raw(tr("Favorites")));
+                lines.extend(favorites.iter().map(|selection| processor_line(*selection)));
+                lines.push(Line::raw(""));
+                lines.push(Line::raw(tr("All Processors")));
+            }
+            // palettes from folders in the palette directory are listed under their category
+            let mut current_category = None;
+            for selection in &others {
+                let category = Processors::get_processor(*selection).category();
+                if category.is_some() && category != current_category {
+                    lines.push(Line::raw(format!("  {}", category.clone().unwrap_or_default())).italic());
+                }
+                current_category = category;
+                lines.push(processor_line(*selection));
+            }
+
+            // wide layouts describe the selected processor next to the list
+            if layout_size != LayoutSizes::Wide {
+                frame.render_widget(Paragraph::new(lines), leaflets[1]);
+                return;
+            }
+            let columns = Layout::new(Direction::Horizontal, [
+                Constraint::Fill(2), // list
+                Constraint::Fill(1), // details
+            ]).split(leaflets[1]);
+            let processor = Processors::get_processor(app.current_processor_selection);
+            let details = Paragraph::new(vec![
+                Line::raw(format!("{} {}", app.current_processor_selection + 1, processor.name())).bold(),
+                Line::raw(""),
+                Line::raw(tr(&processor.description())),
+                Line::raw(""),
+                Line::raw(tr(if app.favorite_processors.contains(&app.current_processor_selection) { "Favorite" } else { "Not a favorite" })),
+            ]).wrap(Wrap { trim: true }).block(Block::new().borders(Borders::ALL));
+            frame.render_widget(Paragraph::new(lines), columns[0]);
+            frame.render_widget(details, columns[1]);
+        }
+
+        Pages::SelectingMorphTarget => {
+            let body = Paragraph::new(trf("Morph into: {}", &[Processors::get_processor(app.morph_target_selection).name()]));
             frame.render_widget(body, leaflets[1]);
         }
 
-        Pages::Preprocessing => {
-            if let Some(processor) = &app.selected_processor {
-                let body = Paragraph::new(vec![
-                    Line::raw(format!("Step: {}", processor.get_current_step_label())),
+        Pages::Preprocessing | Pages::PreprocessingMorphTarget => {
+            let processor = if matches!(app.current_page, Pages::PreprocessingMorphTarget) { &app.morph_target } else { &app.selected_processor };
+            if let Some(processor) = processor {
+                let lines = vec![
+                    Line::raw(trf("Step: {}", &[tr(&processor.get_current_step_label())])),
                     Line::raw(if processor.get_current_step_type() == ProcessingStepTypes::Color {
-                        format!("Color: #{}", processor.get_current_step_input())
+                        trf("Color: #{}", &[processor.get_current_step_input()])
                     }
                     else {
-                        format!("Input: {}", processor.get_current_step_input())
+                        trf("Input: {}", &[processor.get_current_step_input()])
                     }),
-                ]);
+                    Line::raw(app.status_message.clone().unwrap_or_default()),
+                ];
+                let body = Paragraph::new(lines);
                 frame.render_widget(body, leaflets[1]);
             }
             else {
-                let body = Paragraph::new("Error: No processor");
+                let body = Paragraph::new(tr("Error: No processor"));
                 frame.render_widget(body, leaflets[1]);
             }
         }
 
-        Pages::Finished => {
+        Pages::PreprocessingDither => {
             let body = Paragraph::new(vec![
-                Line::raw("Saved"),
-                Line::raw(format!("Processed in {:.3} seconds", app.processing_time.as_millis() as f64 / 1000.0)),
+                Line::raw(trf("Step: {}", &[tr(&app.dither_guide.get_current_label())])),
+                Line::raw(trf("Input: {}", &[app.dither_guide.get_current_input()])),
+                Line::raw(app.status_message.clone().unwrap_or_default()),
             ]);
             frame.render_widget(body, leaflets[1]);
         }
+
+        Pages::Finished => {
+            let mut lines = vec![
+                Line::raw(tr("Saved")),
+                Line::raw(trf("Processed in {} seconds", &[format!("{:.3}", app.processing_time.as_millis() as f64 / 1000.0)])),
+            ];
+            if let Some(frame_progress) = &app.frame_progress {
+                lines.push(Line::raw(trf("Frames: {} decoded, {} palettized, {} encoded", &[frame_progress.decoded_count.to_string(), frame_progress.palettized_count.to_string(), frame_progress.encoded_count.to_string()])));
+            }
+            for memory_adjustment in &app.memory_adjustments {
+                lines.push(Line::raw(memory_adjustment.clone()));
+            }
+            if let Some(image_difference) = &app.image_difference {
+                lines.push(Line::raw(trf("Difference from source: mean Delta E {}, max Delta E {}", &[format!("{:.2}", image_difference.mean_delta_e), format!("{:.2}", image_difference.max_delta_e)])));
+            }
+            if let Some(new_tones) = &app.new_tones {
+                lines.push(Line::raw(""));
+                lines.push(Line::raw(tr(if app.source_tones.is_some() { "Tones (source -> output):" } else { "Tones:" })));
+                lines.extend(tone_lines(new_tones, app.source_tones.as_ref()));
+            }
+            let mut color_lines = Vec::new();
+            if !app.color_usage.is_empty() {
+                color_lines.push(Line::raw(trf("{} colors used, top colors:", &[app.color_usage.len().to_string()])));
+                for usage in app.color_usage.iter().take(top_color_count()) {
+                    color_lines.push(Line::from(vec![
+                        swatch(&usage.color),
+                        Span::raw(format!("{} {:>5.1}%", as_hex(&usage.color), usage.percent)),
+                    ]));
+                }
+            }
+            let status_lines = app.status_message.iter().flat_map(|status_message| [Line::raw(""), Line::raw(status_message.clone())]);
+
+            // wide layouts give the top colors their own panel between the details and the preview
+            if layout_size == LayoutSizes::Wide {
+                lines.extend(status_lines);
+                let columns = Layout::new(Direction::Horizontal, [
+                    Constraint::Fill(2), // details
+                    Constraint::Fill(1), // colors
+                    Constraint::Fill(2), // preview
+                ]).split(leaflets[1]);
+                frame.render_widget(Paragraph::new(lines), columns[0]);
+                frame.render_widget(Paragraph::new(color_lines).block(Block::new().borders(Borders::ALL)), columns[1]);
+                if let Some(preview) = &app.preview {
+                    render_preview(frame, columns[2], preview, app.color_vision_mode);
+                }
+                return;
+            }
+            if !color_lines.is_empty() {
+                lines.push(Line::raw(""));
+                lines.extend(color_lines);
+            }
+            lines.extend(status_lines);
+
+            // the preview takes the right half of the body, while plain output only names it below the details and compact layouts leave it out
+            if is_plain_output() || layout_size == LayoutSizes::Compact {
+                if app.preview.is_some() && is_plain_output() { lines.extend(preview_placeholder_lines(app.color_vision_mode)); }
+                frame.render_widget(Paragraph::new(lines), leaflets[1]);
+                return;
+            }
+            let columns = Layout::new(Direction::Horizontal, [
+                Constraint::Fill(1), // details
+                Constraint::Fill(1), // preview
+            ]).split(leaflets[1]);
+            let body = Paragraph::new(lines);
+            frame.render_widget(body, columns[0]);
+            if let Some(preview) = &app.preview {
+                render_preview(frame, columns[1], preview, app.color_vision_mode);
+            }
+        }
+
+        Pages::InspectingSpectrum => {
+            render_spectrum_slice(frame, leaflets[1], app);
+        }
+
+        Pages::Live => {
+            match &app.live_frame {
+                Some(live_frame) => render_preview(frame, leaflets[1], live_frame, app.color_vision_mode),
+                None => frame.render_widget(Paragraph::new(tr("Waiting for the first frame...")), leaflets[1]),
+            }
+        }
+
+        Pages::BatchProcessing => {
+            let done_count = app.batch.iter().filter(|entry| entry.is_done()).count();
+            let mut lines = vec![
+                Line::raw(trf("Processor: {}", &[Processors::get_processor(app.current_processor_selection).name()])),
+                Line::raw(trf("Processed {} of {} images", &[done_count.to_string(), app.batch.len().to_string()])),
+                Line::raw(""),
+            ];
+            lines.extend(app.batch.iter().map(|entry| Line::raw(entry.describe())));
+
+            // scrolls the list to keep the image being processed in view
+            let scroll = (3 + done_count).saturating_sub(leaflets[1].height.saturating_sub(1) as usize) as u16;
+            frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), leaflets[1]);
+        }
+
+        Pages::ChoosingDirectories => {
+            // the directory being typed into gets the cursor
+            let field = |label: &str, input: &str, is_editing: bool| Line::raw(format!("{} {}: {}{}", if is_editing { ">" } else { " " }, tr(label), input, if is_editing { "_" } else { "" }));
+            let mut lines = vec![
+                field("Source", &app.directory_inputs.0, !app.is_editing_output_directory),
+                field("Output", &app.directory_inputs.1, app.is_editing_output_directory),
+                Line::raw(""),
+                Line::raw(tr("Source images are read from the source directory and edited images are saved to the output directory, which is created if it does not exist.")),
+            ];
+            if let Some(status_message) = &app.status_message {
+                lines.push(Line::raw(""));
+                lines.push(Line::raw(status_message.clone()));
+            }
+            frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), leaflets[1]);
+        }
+
+        Pages::Settings => {
+            let mut lines = Vec::new();
+            if let Some(gpu_information) = &app.gpu_information {
+                let megabytes = |bytes: u64| format!("{:.0}", bytes as f64 / 1024.0 / 1024.0);
+                lines.push(Line::raw(trf("Processing backend: {}", &[gpu_information.backend.name()])));
+                lines.push(Line::raw(trf("Force CPU: {}", &[tr(if config::get().force_cpu { "on" } else { "off" })])));
+                lines.push(Line::raw(trf("Quality: {}", &[tr(&config::get().quality.map(|preset| preset.name()).unwrap_or("Custom".to_string()))])));
+                lines.push(Line::raw(""));
+                match &gpu_information.adapter {
+                    Some(adapter) => {
+                        lines.push(Line::raw(trf("Adapter: {}", std::slice::from_ref(&adapter.name))));
+                        lines.push(Line::raw(trf("Type: {}", &[tr(&device_type_name(adapter.device_type))])));
+                        lines.push(Line::raw(trf("Graphics API: {}", &[graphics_api_name(adapter.backend)])));
+                        lines.push(Line::raw(trf("Driver: {}", &[format!("{} {}", adapter.driver, adapter.driver_info).trim().to_string()])));
+                    }
+                    None => lines.push(Line::raw(tr("Adapter: none (images are processed on the CPU)"))),
+                }
+                if let Some(limits) = &gpu_information.limits {
+                    lines.push(Line::raw(""));
+                    lines.push(Line::raw(tr("Limits:")));
+                    lines.push(Line::raw(trf("  Max buffer size: {} MB", &[megabytes(limits.max_buffer_size)])));
+                    lines.push(Line::raw(trf("  Max storage buffer binding: {} MB", &[megabytes(limits.max_storage_buffer_binding_size as u64)])));
+                    lines.push(Line::raw(trf("  Max workgroups per dimension: {}", &[limits.max_compute_workgroups_per_dimension.to_string()])));
+                    lines.push(Line::raw(trf("  Max invocations per workgroup: {}", &[limits.max_compute_invocations_per_workgroup.to_string()])));
+                }
+                lines.push(Line::raw(""));
+                lines.push(Line::raw(tr("Available adapters:")));
+                let current_label = gpu_information.adapter.as_ref().map(adapter_label);
+                for label in &gpu_information.adapter_labels {
+                    let marker = if Some(label) == current_label.as_ref() { ">" } else { " " };
+                    lines.push(Line::raw(format!("{} {}", marker, label)));
+                }
+            }
+            if let Some(status_message) = &app.status_message {
+                lines.push(Line::raw(""));
+                lines.push(Line::raw(status_message.clone()));
+            }
+            let body = Paragraph::new(lines);
+            frame.render_widget(body, leaflets[1]);
+        }
+    }
+}
+
+/// Returns the instructions of grouped instruction lines packed into as few lines of a given width as they fit in.
+fn flow_instructions(instruction_lines: &[Line], width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut current_line = "".to_string();
+    for instruction in instruction_lines.iter().flat_map(|line| line.to_string().split(" | ").map(|instruction| instruction.to_string()).collect::<Vec<String>>()) {
+        if !current_line.is_empty() && current_line.chars().count() + 3 + instruction.chars().count() > width {
+            lines.push(Line::raw(std::mem::take(&mut current_line)));
+        }
+        if !current_line.is_empty() { current_line += " | "; }
+        current_line += &instruction;
+    }
+    if !current_line.is_empty() { lines.push(Line::raw(current_line)); }
+    lines
+}
+
+/// Renders the colors of the spectrum within one blue plane as a red (x) by green (y) grid using half blocks.
+fn render_spectrum_slice(frame: &mut Frame, area: Rect, app: &App) {
+    let slice_thickness = 256 / spectrum_slice_count();
+    let blue_start = app.spectrum_slice * slice_thickness;
+    let blue_end = blue_start + slice_thickness - 1;
+    let cells_per_side = 256 / spectrum_cell_size();
+
+    // averages the spectrum colors falling into each cell of the slice
+    let mut sums = vec![[0usize; 3]; cells_per_side * cells_per_side];
+    let mut counts = vec![0usize; cells_per_side * cells_per_side];
+    let mut slice_color_count = 0;
+    for color in app.spectrum.iter().filter(|color| (blue_start..=blue_end).contains(&(color[2] as usize))) {
+        let index = (255 - color[1] as usize) / spectrum_cell_size() * cells_per_side + color[0] as usize / spectrum_cell_size();
+        for channel in 0..3 { sums[index][channel] += color[channel] as usize; }
+        counts[index] += 1;
+        slice_color_count += 1;
+    }
+    let cell_color = |x: usize, y: usize| {
+        let index = y * cells_per_side + x;
+        if counts[index] == 0 { return Color::Reset; }
+        Color::Rgb((sums[index][0] / counts[index]) as u8, (sums[index][1] / counts[index]) as u8, (sums[index][2] / counts[index]) as u8)
+    };
+
+    let mut lines = vec![
+        Line::raw(trf("Spectrum: {} colors", &[app.spectrum.len().to_string()])),
+        Line::raw(trf("Blue {}-{} (slice {}/{}): {} colors", &[blue_start.to_string(), blue_end.to_string(), (app.spectrum_slice + 1).to_string(), spectrum_slice_count().to_string(), slice_color_count.to_string()])),
+        Line::raw(tr("Red increases to the right, green increases upwards")),
+    ];
+
+    // each cell shows the upper row as the foreground and the lower row as the background, which plain output cannot describe
+    if is_plain_output() { lines.push(Line::raw(tr("The spectrum grid is hidden in plain output"))); }
+    for y in (0..cells_per_side).step_by(2).filter(|_| !is_plain_output()) {
+        lines.push(Line::from((0..cells_per_side).map(|x| {
+            Span::styled("▀", Style::default().fg(cell_color(x, y)).bg(cell_color(x, y + 1)))
+        }).collect::<Vec<Span>>()));
     }
+    if let Some(status_message) = &app.status_message {
+        lines.push(Line::raw(""));
+        lines.push(Line::raw(status_message.clone()));
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Renders an image into a given area using half blocks (two pixels per cell), simulated for a given color vision type.
+fn render_preview(frame: &mut Frame, area: Rect, image: &ImageBuffer<Rgb<u8>, Vec<u8>>, color_vision_mode: ColorVisionModes) {
+    if is_plain_output() {
+        frame.render_widget(Paragraph::new(preview_placeholder_lines(color_vision_mode)), area);
+        return;
+    }
+    let mut lines = vec![Line::raw(trf("Preview: {}", &[tr(&color_vision_mode.name())]))];
+
+    // fits the image into the area below the title while keeping its aspect ratio
+    let (width, height) = image.dimensions();
+    let available_width = area.width as f32;
+    let available_height = area.height.saturating_sub(1) as f32 * 2.0;
+    let scale = (available_width / width as f32).min(available_height / height as f32);
+    let preview_width = (width as f32 * scale) as u32;
+    let preview_height = (height as f32 * scale) as u32;
+    let sample = |x: u32, y: u32| {
+        let color = image.get_pixel((x as f32 / scale) as u32 % width, (y as f32 / scale) as u32 % height);
+        let color = color_vision_mode.simulate(color);
+        Color::Rgb(color[0], color[1], color[2])
+    };
+
+    // each cell shows the upper pixel as the foreground and the lower pixel as the background
+    for y in (0..preview_height.saturating_sub(1)).step_by(2) {
+        lines.push(Line::from((0..preview_width).map(|x| {
+            Span::styled("▀", Style::default().fg(sample(x, y)).bg(sample(x, y + 1)))
+        }).collect::<Vec<Span>>()));
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Returns the lines standing in for a preview in plain output.
+fn preview_placeholder_lines(color_vision_mode: ColorVisionModes) -> Vec<Line<'static>> {
+    vec![
+        Line::raw(trf("Preview: {}", &[tr(&color_vision_mode.name())])),
+        Line::raw(tr("The preview is hidden in plain output")),
+    ]
+}
+
+/// Returns the lines describing the exposure of an image, optionally next to the tones of the image it came from.
+fn tone_lines(tones: &ToneStatistics, source_tones: Option<&ToneStatistics>) -> Vec<Line<'static>> {
+    let line = |label: &str, value: &dyn Fn(&ToneStatistics) -> String| {
+        match source_tones {
+            Some(source_tones) => Line::raw(format!("{}: {} -> {}", tr(label), value(source_tones), value(tones))),
+            None => Line::raw(format!("{}: {}", tr(label), value(tones))),
+        }
+    };
+
+    vec![
+        line("Average luminance", &|tones| format!("{:.1}%", tones.average_luminance / 255.0 * 100.0)),
+        line("Black clipping", &|tones| format!("{:.1}%", tones.black_clipped_percent)),
+        line("White clipping", &|tones| format!("{:.1}%", tones.white_clipped_percent)),
+        line("Dynamic range", &|tones| format!("{}-{} ({:.1}%)", tones.shadow_luminance, tones.highlight_luminance, tones.dynamic_range_percent())),
+    ]
+}
+
+/// Returns a small block of a given color followed by a space, or nothing in plain output where the HEX next to it says the same.
+fn swatch(color: &Rgb<u8>) -> Span<'static> {
+    if is_plain_output() { return Span::raw(""); }
+    Span::styled("██ ", Style::default().fg(Color::Rgb(color[0], color[1], color[2])))
 }
 
 /// Renders a loading screen during processing.
 pub fn render_loading(frame: &mut Frame, info: String) {
     // header
-    let header_block = Block::new().borders(Borders::ALL);
+    let header_block = Block::new().borders(section_borders());
     let header = Paragraph::new(vec![
         Line::raw("Oxide"),
-        Line::raw("Processing..."),
+        Line::raw(tr("Processing...")),
     ]).block(header_block);
 
     // The sections of the screen.
@@ -106,7 +615,7 @@ pub fn render_loading(frame: &mut Frame, info: String) {
         Line::raw(""),
         Line::raw(""),
         Line::raw(""),
-        Line::raw(info),
+        Line::raw(tr(&info)),
     ]);
     frame.render_widget(body, leaflets[1]);
 }
@@ -114,10 +623,10 @@ pub fn render_loading(frame: &mut Frame, info: String) {
 /// Renders a progress indicator during processing.
 pub fn render_progress(frame: &mut Frame, percent_complete: f64) {
     // header
-    let header_block = Block::new().borders(Borders::ALL);
+    let header_block = Block::new().borders(section_borders());
     let header = Paragraph::new(vec![
         Line::raw("Oxide"),
-        Line::raw("Processing..."),
+        Line::raw(tr("Processing...")),
     ]).block(header_block);
 
     // The sections of the screen.
@@ -134,13 +643,80 @@ pub fn render_progress(frame: &mut Frame, percent_complete: f64) {
         Line::raw(""),
         Line::raw(""),
         Line::raw(""),
-        Line::raw(format!("Progress: {:.1}%", percent_complete)),
+        Line::raw(trf("Progress: {}%", &[format!("{:.1}", percent_complete)])),
     ]);
     frame.render_widget(body, leaflets[1]);
 }
 
 
 
+/// Renders the progress of an animation or video job as a gauge with the current frame and its phase.
+pub fn render_frame_progress(frame: &mut Frame, progress: &FrameProgress) {
+    // header
+    let header_block = Block::new().borders(section_borders());
+    let header = Paragraph::new(vec![
+        Line::raw("Oxide"),
+        Line::raw(tr("Processing...")),
+    ]).block(header_block);
+
+    // The sections of the screen.
+    let leaflets = Layout::new(Direction::Vertical, [
+        Constraint::Length(4), // header
+        Constraint::Length(3), // gauge
+        Constraint::Fill(1), // details
+    ]).split(frame.area());
+
+    // rendering the header
+    frame.render_widget(header, leaflets[0]);
+
+    // rendering the gauge, which can only fill up if the number of frames is known
+    let frame_label = match progress.frame_count {
+        Some(frame_count) => trf("Frame {}/{}", &[progress.current_frame().to_string(), frame_count.to_string()]),
+        None => trf("Frame {}", &[progress.current_frame().to_string()]),
+    };
+    if is_plain_output() {
+        let percent = progress.percent_complete().map(|percent| format!(" ({:.1}%)", percent)).unwrap_or_default();
+        frame.render_widget(Paragraph::new(frame_label + percent.as_str()), leaflets[1]);
+    }
+    else {
+        let gauge = Gauge::default()
+            .block(Block::new().borders(Borders::ALL))
+            .ratio(progress.percent_complete().unwrap_or(0.0) / 100.0)
+            .label(frame_label);
+        frame.render_widget(gauge, leaflets[1]);
+    }
+
+    // rendering the details
+    let body = Paragraph::new(vec![
+        Line::raw(trf("Phase: {}", &[tr(&progress.current_phase().name())])),
+        Line::raw(trf("Decoded: {}  Palettized: {}  Encoded: {}", &[progress.decoded_count.to_string(), progress.palettized_count.to_string(), progress.encoded_count.to_string()])),
+    ]);
+    frame.render_widget(body, leaflets[2]);
+}
+
+/// Renders the screen of a reported progress event.
+pub fn render_progress_event(frame: &mut Frame, event: &ProgressEvents) {
+    match event {
+        ProgressEvents::LoadingColors => render_loading(frame, "Loading colors...".to_string()),
+        // effects have no palette to count
+        ProgressEvents::SpectrumBuilt(0) => render_loading(frame, "Processing...".to_string()),
+        ProgressEvents::SpectrumBuilt(color_count) => render_loading(frame, trf("Mapping pixels onto {} colors...", &[color_count.to_string()])),
+        ProgressEvents::TileDone(tile, tile_count) => render_progress(frame, *tile as f64 / *tile_count as f64 * 100.0),
+        ProgressEvents::FramesDone(progress) => render_frame_progress(frame, progress),
+        ProgressEvents::Saved(path) => render_loading(frame, trf("Saved to {}", &[path.to_string_lossy().to_string()])),
+    }
+}
+
+/// Starts drawing every reported progress event to the terminal until the returned consumer is dropped.
+pub fn start_progress_screen() -> std::io::Result<ProgressConsumer> {
+    let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+    Ok(ProgressConsumer::start(move |event| {
+        let _ = terminal.draw(|frame| render_progress_event(frame, &event));
+    }))
+}
+
+
+
 /// Defines a keybind instruction for the user.
 pub struct Instruction {
     /// The keybind label.
@@ -157,7 +733,7 @@ impl Instruction {
     /// Returns a formatted string representation of the instruction.
     fn printed(&mut self) -> String {
         let mut print = "".to_string();
-        print += &format!("[{}] {}", &self.key, &self.label);
+        print += &format!("[{}] {}", &self.key, tr(&self.label));
         print
     }
 
@@ -170,6 +746,9 @@ impl Instruction {
         let mut current_group: String = "".to_string();
         let mut amount_in_group: usize = 0;
 
+        // plain output lists every instruction on its own line
+        let group_limit = if is_plain_output() { 1 } else { group_limit };
+
         // adds the current line to the list of lines and creates a new blank line in its place if it reaches the group limit
         for mut instruction in instructions {
             if amount_in_group >= group_limit {
@@ -198,6 +777,38 @@ impl Instruction {
     pub fn select_previous() -> Instruction { Instruction::new("<".to_string(), "previous page".to_string(), KeyCode::Left) }
     pub fn confirm_instruction() -> Instruction { Instruction::new("ENTER".to_string(), "confirm".to_string(), KeyCode::Enter) }
     pub fn reset_instruction() -> Instruction { Instruction::new("ESC".to_string(), "reset".to_string(), KeyCode::Esc) }
+    pub fn back_instruction() -> Instruction { Instruction::new("ESC".to_string(), "back".to_string(), KeyCode::Esc) }
+    pub fn analyze_instruction() -> Instruction { Instruction::new("A".to_string(), "analyze".to_string(), KeyCode::Char('a')) }
+    pub fn sequence_mode_instruction() -> Instruction { Instruction::new("N".to_string(), "toggle sequence mode".to_string(), KeyCode::Char('n')) }
+    pub fn search_instruction() -> Instruction { Instruction::new("/".to_string(), "search".to_string(), KeyCode::Char('/')) }
+    pub fn number_instruction() -> Instruction { Instruction::new("1-9/0".to_string(), "jump to processor".to_string(), KeyCode::Null) }
+    pub fn two_digit_number_instruction() -> Instruction { Instruction::new("#".to_string(), "two-digit number".to_string(), KeyCode::Char('#')) }
+    pub fn shuffle_instruction() -> Instruction { Instruction::new("X".to_string(), "shuffle".to_string(), KeyCode::Char('x')) }
+    pub fn favorite_instruction() -> Instruction { Instruction::new("F".to_string(), "star/unstar favorite".to_string(), KeyCode::Char('f')) }
+    pub fn dominant_colors_instruction() -> Instruction { Instruction::new("D".to_string(), "dominant colors".to_string(), KeyCode::Char('d')) }
+    pub fn export_css_instruction() -> Instruction { Instruction::new("C".to_string(), "export CSS palette".to_string(), KeyCode::Char('c')) }
+    pub fn export_scss_instruction() -> Instruction { Instruction::new("S".to_string(), "export SCSS palette".to_string(), KeyCode::Char('s')) }
+    pub fn export_gimp_palette_instruction() -> Instruction { Instruction::new("E".to_string(), "export GIMP palette".to_string(), KeyCode::Char('e')) }
+    pub fn export_palette_file_instruction() -> Instruction { Instruction::new("J".to_string(), "export JSON palette".to_string(), KeyCode::Char('j')) }
+    pub fn export_terminal_schemes_instruction() -> Instruction { Instruction::new("T".to_string(), "export terminal schemes".to_string(), KeyCode::Char('t')) }
+    pub fn export_lut_instruction() -> Instruction { Instruction::new("L".to_string(), "export LUT".to_string(), KeyCode::Char('l')) }
+    pub fn export_text_art_instruction() -> Instruction { Instruction::new("A".to_string(), "export ANSI/ASCII art".to_string(), KeyCode::Char('a')) }
+    pub fn export_heatmap_instruction() -> Instruction { Instruction::new("H".to_string(), "export difference heatmap".to_string(), KeyCode::Char('h')) }
+    pub fn dither_comparison_instruction() -> Instruction { Instruction::new("D".to_string(), "compare dithering".to_string(), KeyCode::Char('d')) }
+    pub fn color_vision_instruction() -> Instruction { Instruction::new("V".to_string(), "simulate color vision".to_string(), KeyCode::Char('v')) }
+    pub fn palette_cycle_instruction() -> Instruction { Instruction::new("G".to_string(), "export palette cycle GIF".to_string(), KeyCode::Char('g')) }
+    pub fn live_instruction() -> Instruction { Instruction::new("W".to_string(), "live webcam/stream".to_string(), KeyCode::Char('w')) }
+    pub fn morph_instruction() -> Instruction { Instruction::new("M".to_string(), "morph into another palette".to_string(), KeyCode::Char('m')) }
+    pub fn inspect_spectrum_instruction() -> Instruction { Instruction::new("I".to_string(), "inspect spectrum".to_string(), KeyCode::Char('i')) }
+    pub fn next_slice_instruction() -> Instruction { Instruction::new(">".to_string(), "next blue plane".to_string(), KeyCode::Right) }
+    pub fn previous_slice_instruction() -> Instruction { Instruction::new("<".to_string(), "previous blue plane".to_string(), KeyCode::Left) }
+    pub fn batch_instruction() -> Instruction { Instruction::new("TAB".to_string(), "apply to all images".to_string(), KeyCode::Tab) }
+    pub fn resume_batch_instruction() -> Instruction { Instruction::new("B".to_string(), "resume batch".to_string(), KeyCode::Char('b')) }
+    pub fn directories_instruction() -> Instruction { Instruction::new("P".to_string(), "choose folders".to_string(), KeyCode::Char('p')) }
+    pub fn switch_directory_instruction() -> Instruction { Instruction::new("TAB".to_string(), "source/output".to_string(), KeyCode::Tab) }
+    pub fn settings_instruction() -> Instruction { Instruction::new("O".to_string(), "settings".to_string(), KeyCode::Char('o')) }
+    pub fn force_cpu_instruction() -> Instruction { Instruction::new("C".to_string(), "force CPU on/off".to_string(), KeyCode::Char('c')) }
+    pub fn next_adapter_instruction() -> Instruction { Instruction::new("A".to_string(), "next GPU adapter".to_string(), KeyCode::Char('a')) }
     pub fn run_again_instruction() -> Instruction { Instruction::new("R".to_string(), "run again".to_string(), KeyCode::Char('r')) }
     pub fn quit_instruction() -> Instruction { Instruction::new("Q".to_string(), "quit".to_string(), KeyCode::Char('q')) }
 
@@ -212,15 +823,32 @@ impl Instruction {
                     Instruction::select_next(),
                     Instruction::select_previous(),
                     Instruction::confirm_instruction(),
+                    Instruction::analyze_instruction(),
+                    Instruction::dominant_colors_instruction(),
+                    Instruction::sequence_mode_instruction(),
+                    Instruction::resume_batch_instruction(),
+                    Instruction::directories_instruction(),
+                    Instruction::settings_instruction(),
                     Instruction::reset_instruction(),
                     Instruction::quit_instruction(),
                 ], 4)
             }
+            Pages::Analyzing | Pages::ReportingDominantColors => {
+                Instruction::in_groups(vec![
+                    Instruction::back_instruction(),
+                    Instruction::quit_instruction(),
+                ], 4)
+            }
             Pages::SelectingProcessingType => {
                 Instruction::in_groups(vec![
                     Instruction::select_next(),
                     Instruction::select_previous(),
                     Instruction::confirm_instruction(),
+                    Instruction::number_instruction(),
+                    Instruction::two_digit_number_instruction(),
+                    Instruction::search_instruction(),
+                    Instruction::shuffle_instruction(),
+                    Instruction::favorite_instruction(),
                     Instruction::reset_instruction(),
                     Instruction::quit_instruction(),
                 ], 4)
@@ -228,15 +856,81 @@ impl Instruction {
             Pages::Preprocessing => {
                 Instruction::in_groups(vec![
                     Instruction::confirm_instruction(),
+                    Instruction::batch_instruction(),
                     Instruction::reset_instruction(),
                 ], 4)
             }
             Pages::Finished => {
                 Instruction::in_groups(vec![
+                    Instruction::export_css_instruction(),
+                    Instruction::export_scss_instruction(),
+                    Instruction::export_gimp_palette_instruction(),
+                    Instruction::export_palette_file_instruction(),
+                    Instruction::export_terminal_schemes_instruction(),
+                    Instruction::export_lut_instruction(),
+                    Instruction::export_text_art_instruction(),
+                    Instruction::export_heatmap_instruction(),
+                    Instruction::dither_comparison_instruction(),
+                    Instruction::color_vision_instruction(),
+                    Instruction::palette_cycle_instruction(),
+                    Instruction::morph_instruction(),
+                    Instruction::inspect_spectrum_instruction(),
+                    Instruction::live_instruction(),
                     Instruction::run_again_instruction(),
                     Instruction::quit_instruction(),
                 ], 4)
             }
+            Pages::Live => {
+                Instruction::in_groups(vec![
+                    Instruction::color_vision_instruction(),
+                    Instruction::back_instruction(),
+                    Instruction::quit_instruction(),
+                ], 4)
+            }
+            Pages::SelectingMorphTarget => {
+                Instruction::in_groups(vec![
+                    Instruction::select_next(),
+                    Instruction::select_previous(),
+                    Instruction::confirm_instruction(),
+                    Instruction::back_instruction(),
+                    Instruction::quit_instruction(),
+                ], 4)
+            }
+            Pages::PreprocessingDither | Pages::PreprocessingMorphTarget => {
+                Instruction::in_groups(vec![
+                    Instruction::confirm_instruction(),
+                    Instruction::back_instruction(),
+                ], 4)
+            }
+            Pages::InspectingSpectrum => {
+                Instruction::in_groups(vec![
+                    Instruction::next_slice_instruction(),
+                    Instruction::previous_slice_instruction(),
+                    Instruction::back_instruction(),
+                    Instruction::quit_instruction(),
+                ], 4)
+            }
+            Pages::BatchProcessing => {
+                Instruction::in_groups(vec![
+                    Instruction::reset_instruction(),
+                    Instruction::quit_instruction(),
+                ], 4)
+            }
+            Pages::ChoosingDirectories => {
+                Instruction::in_groups(vec![
+                    Instruction::switch_directory_instruction(),
+                    Instruction::confirm_instruction(),
+                    Instruction::back_instruction(),
+                ], 4)
+            }
+            Pages::Settings => {
+                Instruction::in_groups(vec![
+                    Instruction::force_cpu_instruction(),
+                    Instruction::next_adapter_instruction(),
+                    Instruction::back_instruction(),
+                    Instruction::quit_instruction(),
+                ], 4)
+            }
         }
     }
 }
\ No newline at end of file
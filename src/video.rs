@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufWriter, Error, ErrorKind, Read, Result, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+use image::{DynamicImage, ImageBuffer, ImageError, ImageResult, Rgb};
+use crate::cache::CacheKeyHasher;
+use crate::pipeline::{run_pipeline, FrameProgress};
+use crate::processor::compute::{Gpu, PaletteMapping, ThresholdMask};
+
+/// Gets the file extensions of the videos that can be processed.
+pub fn video_extensions() -> Vec<&'static str> { vec!["mp4", "mov", "mkv", "webm", "avi"] }
+
+/// Gets the extension of processed videos.
+pub fn output_extension() -> &'static str { "mp4" }
+
+/// Gets the max number of colors an indexed png palette can hold.
+fn max_indexed_colors() -> usize { 256 }
+
+
+
+/// Returns if a given path is a video by its extension.
+pub fn is_video(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| video_extensions().contains(&extension.to_lowercase().as_str()))
+}
+
+/// The reference frames opened so far, by the path of their video, with the time the video was last modified when they were opened.
+static REFERENCE_FRAMES: OnceLock<Mutex<HashMap<PathBuf, (SystemTime, DynamicImage)>>> = OnceLock::new();
+
+
+
+/// Returns the temporary directory the reference frame of a given video is extracted to.
+/// The directory is named after the file and a hash of its full path, so videos with the same name in different folders don't share it.
+fn working_directory(source_path: &Path) -> PathBuf {
+    let full_path = std::path::absolute(source_path).unwrap_or(source_path.to_path_buf());
+    let mut hasher = CacheKeyHasher::new();
+    full_path.hash(&mut hasher);
+    let stem = source_path.file_stem().unwrap_or_default().to_string_lossy();
+    std::env::temp_dir().join("oxide").join(format!("{}-{:016x}", stem, hasher.finish()))
+}
+
+/// Returns when a file was last modified.
+fn get_modified_time(path: &Path) -> Result<SystemTime> {
+    fs::metadata(path)?.modified()
+}
+
+/// Runs ffmpeg or ffprobe with the given arguments and returns what it printed.
+fn run(program: &str, arguments: &[&str]) -> Result<String> {
+    let output = Command::new(program).args(arguments).output()
+        .map_err(|e| Error::other(format!("could not run {} (is ffmpeg installed?): {}", program, e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::other(format!("{} failed: {}", program, stderr.lines().last().unwrap_or_default())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Returns the average frame rate of a video as a fraction ffmpeg understands (e.g. "30000/1001").
+/// Videos with a variable frame rate are processed at this rate, so they keep their length and speed.
+fn get_frame_rate(source_path: &Path) -> Result<String> {
+    let source = source_path.to_string_lossy();
+    let frame_rate = |entry: &str| run("ffprobe", &["-v", "error", "-select_streams", "v:0", "-show_entries", entry, "-of", "default=noprint_wrappers=1:nokey=1", &source]);
+    // containers that don't know the average rate report it as 0/0
+    match frame_rate("stream=avg_frame_rate")? {
+        average_frame_rate if !average_frame_rate.starts_with('0') => Ok(average_frame_rate),
+        _ => frame_rate("stream=r_frame_rate"),
+    }
+}
+
+/// Returns the duration of a video in seconds.
+fn get_duration(source_path: &Path) -> Result<f64> {
+    let source = source_path.to_string_lossy();
+    let duration = run("ffprobe", &["-v", "error", "-show_entries", "format=duration", "-of", "default=noprint_wrappers=1:nokey=1", &source])?;
+    duration.parse().map_err(|_| Error::other(format!("ffprobe returned an invalid duration: {}", duration)))
+}
+
+/// Extracts a frame from the middle of a video and returns its path.
+/// The middle frame stands in for the whole video wherever a single source image is needed (e.g. for automatic palettes).
+/// A frame extracted earlier is reused as long as the video hasn't changed since.
+pub fn get_reference_frame(source_path: &Path) -> Result<PathBuf> {
+    let reference_frame_path = working_directory(source_path).join("reference.png");
+    if let (Ok(frame_time), Ok(source_time)) = (get_modified_time(&reference_frame_path), get_modified_time(source_path)) && frame_time >= source_time {
+        return Ok(reference_frame_path);
+    }
+    fs::create_dir_all(working_directory(source_path))?;
+
+    let source = source_path.to_string_lossy();
+    let timestamp = format!("{:.3}", get_duration(source_path).unwrap_or(0.0) / 2.0);
+    let reference_frame = reference_frame_path.to_string_lossy();
+    run("ffmpeg", &["-y", "-v", "error", "-ss", &timestamp, "-i", &source, "-frames:v", "1", &reference_frame])?;
+    Ok(reference_frame_path)
+}
+
+/// Opens a source image, or the reference frame if the source is a video.
+/// Reference frames are kept in memory, so previews of a video don't extract and decode its frame again.
+pub fn open_source_image(source_path: &Path) -> ImageResult<DynamicImage> {
+    if !is_video(source_path) { return image::open(source_path); }
+
+    let source_time = get_modified_time(source_path).map_err(ImageError::IoError)?;
+    let reference_frames = REFERENCE_FRAMES.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some((frame_time, frame)) = reference_frames.lock().unwrap_or_else(|e| e.into_inner()).get(source_path) && *frame_time == source_time {
+        return Ok(frame.clone());
+    }
+
+    let reference_frame_path = get_reference_frame(source_path).map_err(ImageError::IoError)?;
+    let frame = image::open(reference_frame_path)?;
+    reference_frames.lock().unwrap_or_else(|e| e.into_inner()).insert(source_path.to_path_buf(), (source_time, frame.clone()));
+    Ok(frame)
+}
+
+/// Saves an image, as a palette-indexed png if it is a png with few enough colors and in the format of its extension otherwise.
+pub fn save_image(image: &ImageBuffer<Rgb<u8>, Vec<u8>>, path: &Path) -> ImageResult<()> {
+    let is_png = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+    match get_indexed_pixels(image) {
+        Some((palette, indices)) if is_png => save_indexed_png(path, image.width(), image.height(), &palette, &indices).map_err(ImageError::IoError),
+        _ => image.save(path),
+    }
+}
+
+/// Returns the colors of an image and the index of every pixel's color, or None if it uses more colors than an indexed png can hold.
+fn get_indexed_pixels(image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Option<(Vec<Rgb<u8>>, Vec<u8>)> {
+    let mut palette = Vec::new();
+    let mut palette_indices: HashMap<Rgb<u8>, u8> = HashMap::new();
+    let mut indices = Vec::with_capacity((image.width() * image.height()) as usize);
+    for pixel in image.pixels() {
+        let index = match palette_indices.get(pixel) {
+            Some(index) => *index,
+            None => {
+                if palette.len() == max_indexed_colors() { return None; }
+                palette.push(*pixel);
+                palette_indices.insert(*pixel, (palette.len() - 1) as u8);
+                (palette.len() - 1) as u8
+            }
+        };
+        indices.push(index);
+    }
+    Some((palette, indices))
+}
+
+/// Writes palette indices as an indexed png with the smallest bit depth (1, 2, 4 or 8 bits per pixel) that fits the palette.
+fn save_indexed_png(path: &Path, width: u32, height: u32, palette: &[Rgb<u8>], indices: &[u8]) -> Result<()> {
+    let (bit_depth, bits) = match palette.len() {
+        0..=2 => (png::BitDepth::One, 1),
+        3..=4 => (png::BitDepth::Two, 2),
+        5..=16 => (png::BitDepth::Four, 4),
+        _ => (png::BitDepth::Eight, 8),
+    };
+
+    // packs the indices of every row into bytes, leftmost pixel first, with each row starting on a new byte
+    let pixels_per_byte = 8 / bits;
+    let data: Vec<u8> = indices.chunks(width.max(1) as usize).flat_map(|row| {
+        row.chunks(pixels_per_byte).map(|byte_indices| {
+            byte_indices.iter().enumerate().fold(0u8, |byte, (position, index)| byte | index << (8 - bits * (position + 1)))
+        }).collect::<Vec<u8>>()
+    }).collect();
+
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(bit_depth);
+    encoder.set_palette(palette.iter().flat_map(|color| color.0).collect::<Vec<u8>>());
+    let mut writer = encoder.write_header().map_err(Error::other)?;
+    writer.write_image_data(&data).map_err(Error::other)?;
+    writer.finish().map_err(Error::other)
+}
+
+/// Converts an animation or video into another format, picked by ffmpeg from the extension of the output path.
+pub fn convert(input_path: &Path, output_path: &Path) -> Result<()> {
+    let input = input_path.to_string_lossy();
+    let output = output_path.to_string_lossy();
+    run("ffmpeg", &["-y", "-v", "error", "-i", &input, "-loop", "0", &output])?;
+    Ok(())
+}
+
+/// Returns the width and height of a video.
+fn get_dimensions(source_path: &Path) -> Result<(u32, u32)> {
+    let source = source_path.to_string_lossy();
+    let dimensions = run("ffprobe", &["-v", "error", "-select_streams", "v:0", "-show_entries", "stream=width,height", "-of", "csv=s=x:p=0", &source])?;
+    let parsed = dimensions.split_once('x').and_then(|(width, height)| Some((width.trim().parse().ok()?, height.trim().parse().ok()?)));
+    parsed.ok_or_else(|| Error::other(format!("ffprobe returned invalid dimensions: {}", dimensions)))
+}
+
+/// Returns the number of frames in a video, if the container knows it.
+pub fn get_frame_count(source_path: &Path) -> Option<usize> {
+    let source = source_path.to_string_lossy();
+    run("ffprobe", &["-v", "error", "-select_streams", "v:0", "-show_entries", "stream=nb_frames", "-of", "default=noprint_wrappers=1:nokey=1", &source]).ok()?.parse().ok()
+}
+
+/// Maps every frame of a video, nudged by a threshold mask for ordered dithering, onto the palettes and re-encodes it with the original audio.
+/// Frames stream from one ffmpeg process through the gpu into another without touching the disk, and the progress is reported after each frame.
+pub fn process_video(source_path: &Path, output_path: &Path, palette_mapping: &PaletteMapping, mask: &ThresholdMask, on_progress: impl FnMut(&FrameProgress)) -> Result<()> {
+    let source = source_path.to_string_lossy();
+    let output = output_path.to_string_lossy();
+    let (width, height) = get_dimensions(source_path)?;
+    let frame_rate = get_frame_rate(source_path)?;
+    let spawn_error = |e: Error| Error::other(format!("could not run ffmpeg (is ffmpeg installed?): {}", e));
+
+    // decoding every frame as raw rgb at the rate the encoder assembles them at, since raw frames carry no timestamps
+    // frames of variable frame rate videos are repeated or dropped where needed, so every frame is shown at its original time
+    let mut decoder = Command::new("ffmpeg")
+        .args(["-v", "error", "-i", &source, "-fps_mode", "cfr", "-r", &frame_rate, "-f", "rawvideo", "-pix_fmt", "rgb24", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(spawn_error)?;
+
+    // encoding the raw frames with the audio of the source, padded to the even size h264 requires
+    let size = format!("{}x{}", width, height);
+    let mut encoder = Command::new("ffmpeg")
+        .args([
+            "-y", "-v", "error",
+            "-f", "rawvideo", "-pix_fmt", "rgb24", "-s", &size, "-framerate", &frame_rate, "-i", "-",
+            "-i", &source,
+            "-map", "0:v", "-map", "1:a?",
+            "-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2",
+            "-c:v", "libx264", "-pix_fmt", "yuv420p",
+            "-c:a", "aac", "-shortest",
+            &output,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_error)?;
+
+    let mut decoded_frames = decoder.stdout.take().ok_or_else(|| Error::other("could not read from ffmpeg"))?;
+    let mut encoded_frames = encoder.stdin.take().ok_or_else(|| Error::other("could not write to ffmpeg"))?;
+    let frame_size = (width * height * 3) as usize;
+    let frames = std::iter::from_fn(move || {
+        let mut bytes = vec![0u8; frame_size];
+        match decoded_frames.read_exact(&mut bytes) {
+            Ok(_) => Some(Ok(ImageBuffer::from_raw(width, height, bytes).expect("a frame always holds width * height pixels"))),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => None,
+            Err(e) => Some(Err(e)),
+        }
+    });
+
+    let gpu = Gpu::new();
+    let result = run_pipeline(
+        frames,
+        |frame: ImageBuffer<Rgb<u8>, Vec<u8>>| Ok(palette_mapping.process_dithered_on(&gpu, &frame, mask)),
+        move |frame: ImageBuffer<Rgb<u8>, Vec<u8>>| encoded_frames.write_all(frame.as_raw()),
+        get_frame_count(source_path),
+        on_progress,
+    );
+
+    // the encoder finishes the file once its input is closed by the pipeline
+    let _ = decoder.kill();
+    let _ = decoder.wait();
+    let encoded = encoder.wait_with_output()?;
+    result?;
+    if !encoded.status.success() {
+        let stderr = String::from_utf8_lossy(&encoded.stderr);
+        return Err(Error::other(format!("ffmpeg failed: {}", stderr.lines().last().unwrap_or_default())));
+    }
+    Ok(())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexed_pngs_keep_their_pixels_at_every_bit_depth() {
+        // 13 pixels per row leaves the last byte of every row partly filled at 1, 2 and 4 bits per pixel
+        let (width, height) = (13, 5);
+        for color_count in [2usize, 4, 16, 256] {
+            let palette: Vec<Rgb<u8>> = (0..color_count).map(|index| Rgb([index as u8, 255 - index as u8, (index * 7 % 256) as u8])).collect();
+            let indices: Vec<u8> = (0..width * height).map(|index| ((index % width * 7 + index / width * 3) as usize % color_count) as u8).collect();
+            let path = std::env::temp_dir().join(format!("oxide-test-indexed-{}-{}.png", color_count, std::process::id()));
+            save_indexed_png(&path, width, height, &palette, &indices).unwrap();
+            let decoded = image::open(&path).unwrap().to_rgb8();
+            fs::remove_file(&path).unwrap();
+
+            assert_eq!(decoded.dimensions(), (width, height));
+            for (pixel, index) in decoded.pixels().zip(&indices) {
+                assert_eq!(*pixel, palette[*index as usize], "{} colors", color_count);
+            }
+        }
+    }
+}